@@ -0,0 +1,1390 @@
+//! Command-line entry points.
+//!
+//! Only built with the `cli` feature, which pulls in the argument parser and
+//! terminal output crates.
+
+use std::fs::File;
+use std::io::{BufWriter, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Instant, SystemTime};
+
+use anyhow::Context;
+use clap::Parser;
+
+use crate::{bench, cli, config, detectors, docker, doctor, hook, output, plugin, policy};
+use crate::rules::ensure_rules;
+use crate::cli::{Cli, Commands, ConfigCommand, HookCommand, ReportCommand, RuleDocsFormat, RulesCommand, SeverityLevel};
+use crate::detectors::runtime::scanner::{connect_docker, daemon_info, scan_docker_stream, RuntimeScanOptions};
+use crate::detectors::runtime::hosts::load_hosts;
+use crate::detectors::runtime::ports::PortPolicy;
+use crate::detectors::runtime::yaml_rules::YamlRuleEngine;
+use crate::detectors::dockerfile::scanner::{scan_dockerfile_report, DockerfileScanOptions};
+use crate::output::printer::{container_section, print_scan_report, print_scan_summary, print_status_line, report_sections, PlainTableWriter};
+use crate::output::anonymize::Anonymizer;
+use crate::output::attestation::{AttestationSigner, ScanAttestation};
+use crate::output::exporters::{ContainerExportStream, Exporter};
+use crate::output::template::TemplateExporter;
+use crate::output::metadata::ScanMetadata;
+use crate::history::{self, HistoryStore, ScanRecord};
+use crate::output::storage::{ObjectUrl, StagedOutput};
+use crate::output::severity::SeverityScheme;
+use crate::output::syslog::SyslogSink;
+use crate::output::score::{ScoreWeights, TargetScore};
+use crate::output::summary::{ScanSummary, SeverityCounts, TargetKind};
+use crate::detectors::compose::{self, ComposeFiles};
+use crate::docker::model::{FileResult, RiskLevel};
+use crate::config::{ConfigFile, FileOutputConfig, PathOverrides, ScanConfig, SeverityMap, TagThresholds};
+use crate::report::{ScanFindings, ScanReport, EXIT_ENVIRONMENT, EXIT_SUCCESS};
+use crate::error::{Result, ValerisError};
+
+/// Rules listed in each table of `--profile-rules`
+const PROFILE_TOP: usize = 10;
+
+// ────────────────────────────────────────────────────────────────────
+// HELPER FUNCTIONS
+// ────────────────────────────────────────────────────────────────────
+
+/// Applies configuration file defaults to CLI arguments
+/// CLI arguments always take precedence
+fn apply_config_defaults(
+    cli_value: &Option<Vec<String>>,
+    config_value: &Option<Vec<String>>,
+) -> Option<Vec<String>> {
+    if cli_value.is_some() {
+        cli_value.clone()
+    } else {
+        config_value.clone()
+    }
+}
+
+/// Converts CLI SeverityLevel to RiskLevel
+fn severity_to_risk(severity: &SeverityLevel) -> RiskLevel {
+    match severity {
+        SeverityLevel::Informative => RiskLevel::Informative,
+        SeverityLevel::Low => RiskLevel::Low,
+        SeverityLevel::Medium => RiskLevel::Medium,
+        SeverityLevel::High => RiskLevel::High,
+        SeverityLevel::Critical => RiskLevel::Critical,
+    }
+}
+
+/// Filters findings by severity
+fn filter_by_severity(
+    results: &mut [docker::model::ContainerResult],
+    severity: Option<&Vec<SeverityLevel>>,
+    min_severity: Option<&SeverityLevel>,
+    tags: &TagThresholds,
+) {
+    for result in results.iter_mut() {
+        filter_findings(&mut result.findings, severity, min_severity, tags);
+    }
+}
+
+/// Filters the findings of one target by severity
+fn filter_findings(
+    findings: &mut Vec<docker::model::Finding>,
+    severity: Option<&Vec<SeverityLevel>>,
+    min_severity: Option<&SeverityLevel>,
+    tags: &TagThresholds,
+) {
+    if let Some(severities) = severity {
+        // Filter to exact severity levels
+        let risk_levels: Vec<RiskLevel> = severities.iter().map(severity_to_risk).collect();
+        findings.retain(|f| risk_levels.contains(&f.risk));
+    } else {
+        // Filter to minimum severity and above, per tag where configured
+        tags.retain(findings, min_severity.map(severity_to_risk));
+    }
+}
+
+/// Applies a resolved policy's rule subset and severity profile.
+///
+/// The policy's rules (minus `exclude`) replace `only`, its severity map is
+/// applied over the configured one, and its minimum severity is used unless
+/// `--severity` or `--min-severity` was given.
+fn apply_policy(
+    policy: Option<policy::ResolvedPolicy>,
+    only: Option<Vec<String>>,
+    exclude: Option<&Vec<String>>,
+    severity: Option<&Vec<SeverityLevel>>,
+    min_severity: Option<SeverityLevel>,
+    mut severity_map: SeverityMap,
+) -> (Option<Vec<String>>, Option<SeverityLevel>, SeverityMap) {
+    let Some(policy) = policy else {
+        return (only, min_severity, severity_map);
+    };
+    tracing::debug!("Applying policy '{}' ({} rules)", policy.name, policy.rules.len());
+
+    let only = Some(policy.only(exclude));
+    let min_severity = match severity {
+        Some(_) => min_severity,
+        None => min_severity.or(policy.min_severity),
+    };
+    severity_map.extend(policy.severity_map);
+    (only, min_severity, severity_map)
+}
+
+/// Posts Dockerfile reports as comments on a GitHub pull request
+async fn publish_github_pr(
+    api_url: &str,
+    repo: &str,
+    pr: u64,
+    reports: &[std::path::PathBuf],
+) -> Result<()> {
+    use output::github::{load_reports, publish_pr_report, GitHubClient};
+
+    let token = std::env::var(config::GITHUB_TOKEN_ENV).map_err(|_| {
+        ValerisError::Config(format!(
+            "{} must be set to comment on pull requests",
+            config::GITHUB_TOKEN_ENV
+        ))
+    })?;
+    let reports = load_reports(reports)?;
+    let client = GitHubClient::new(api_url, repo, token)?;
+
+    let outcome = publish_pr_report(&client, pr, &reports).await?;
+    println!(
+        "{} summary comment on {}#{} and posted {} inline comment(s)",
+        if outcome.summary_updated { "Updated" } else { "Created" },
+        repo,
+        pr,
+        outcome.inline_comments
+    );
+    Ok(())
+}
+
+/// Locates the rules directory, downloading rules if needed and allowed
+fn locate_rules(rules: &config::RulesConfig) -> Result<std::path::PathBuf> {
+    ensure_rules(rules).map_err(|source| ValerisError::RulesLoad {
+        path: rules.base_dir.clone(),
+        source,
+    })
+}
+
+/// Rejects commands that would reach the network under `--offline`: object
+/// storage outputs, syslog, and the GitHub reporter. Rule downloads are
+/// disabled separately, and scanned Docker daemons are still contacted.
+fn check_offline(command: &Commands) -> Result<()> {
+    let (outputs, syslog): (Vec<String>, _) = match command {
+        Commands::Scan { output, syslog, .. } | Commands::DockerFile { output, syslog, .. } => {
+            (output.iter().map(|o| o.path.clone()).collect(), syslog.is_some())
+        }
+        Commands::EnvFile { output, .. } | Commands::Network { output, .. } => {
+            (output.iter().map(|o| o.path.clone()).collect(), false)
+        }
+        Commands::Merge { output, .. } => (output.iter().cloned().collect(), false),
+        Commands::Report { command: ReportCommand::GithubPr { .. } } => {
+            return Err(ValerisError::Offline("`report github-pr`".to_string()));
+        }
+        _ => (Vec::new(), false),
+    };
+    if let Some(output) = outputs
+        .into_iter()
+        .find(|o| ObjectUrl::parse(o).is_ok_and(|url| url.is_some()))
+    {
+        return Err(ValerisError::Offline(format!("Uploading the report to {output}")));
+    }
+    if syslog {
+        return Err(ValerisError::Offline("--syslog".to_string()));
+    }
+    Ok(())
+}
+
+/// Checks if any findings meet the fail-on threshold
+fn should_fail(
+    results: &[docker::model::ContainerResult],
+    fail_on: Option<&SeverityLevel>,
+    tags: &TagThresholds,
+) -> bool {
+    tags.fails(
+        results.iter().flat_map(|result| &result.findings),
+        fail_on.map(severity_to_risk),
+    )
+}
+
+/// Dockerfiles and `.env` files scanned alongside the containers
+/// (`scan --dockerfile` / `--compose`).
+struct FileScan {
+    dockerfiles: Vec<FileResult>,
+    env_files: Vec<FileResult>,
+    rules_evaluated: usize,
+}
+
+impl FileScan {
+    fn targets(&self) -> usize {
+        self.dockerfiles.len() + self.env_files.len()
+    }
+
+    fn results(&self) -> impl Iterator<Item = &FileResult> + Clone {
+        self.dockerfiles.iter().chain(&self.env_files)
+    }
+}
+
+/// Scans the files of a combined scan with the severity remapping and
+/// filters of `template`, which only needs its `path` filled in per
+/// Dockerfile. Fail-on is left to the caller, which evaluates it once over
+/// every target.
+fn scan_files(files: &ComposeFiles, template: &DockerfileScanOptions) -> Result<FileScan> {
+    let mut scan = FileScan {
+        dockerfiles: Vec::new(),
+        env_files: Vec::new(),
+        rules_evaluated: 0,
+    };
+    for path in &files.dockerfiles {
+        let outcome = scan_dockerfile_report(&DockerfileScanOptions {
+            path: path.clone(),
+            ..template.clone()
+        })?;
+        scan.rules_evaluated += outcome.summary.rules_evaluated;
+        scan.dockerfiles.push(FileResult { path: path.clone(), findings: outcome.findings });
+    }
+    for path in &files.env_files {
+        let mut findings = detectors::env_file::scan_env_file(path)?;
+        template.severity_map.apply(&mut findings);
+        filter_findings(
+            &mut findings,
+            template.severity.as_ref(),
+            template.min_severity.as_ref(),
+            &template.tag_thresholds,
+        );
+        scan.env_files.push(FileResult { path: path.clone(), findings });
+    }
+    if !files.env_files.is_empty() {
+        scan.rules_evaluated += detectors::env_file::CHECK_COUNT;
+    }
+    Ok(scan)
+}
+
+/// Format of a scan report written to stdout without `--format`: a table on
+/// a terminal, JSON when stdout is piped or redirected.
+fn default_console_format() -> cli::OutputFormat {
+    if std::io::stdout().is_terminal() {
+        cli::OutputFormat::Table
+    } else {
+        cli::OutputFormat::Json
+    }
+}
+
+/// Name of a CLI value as written in the configuration file (`medium`, `json`).
+fn value_name(value: &impl clap::ValueEnum) -> Option<String> {
+    value.to_possible_value().map(|v| v.get_name().to_string())
+}
+
+/// One `--output` of a scan, in its own format or rendered with the
+/// `--template`.
+struct ScanOutput {
+    /// Path or URL as given, recorded in attestations
+    name: String,
+    format: cli::OutputFormat,
+    template: Option<Arc<TemplateExporter>>,
+    staged: StagedOutput,
+}
+
+impl ScanOutput {
+    /// Resolves every `--output`. Those without a `:FORMAT` suffix are
+    /// rendered with `template` if one is given, and written in `format`
+    /// otherwise.
+    fn resolve(
+        outputs: Vec<cli::OutputTarget>,
+        format: &cli::OutputFormat,
+        template: Option<&Path>,
+    ) -> Result<Vec<Self>> {
+        let template = template.map(TemplateExporter::from_file).transpose()?.map(Arc::new);
+        outputs
+            .into_iter()
+            .map(|output| {
+                Ok(Self {
+                    format: output.format_or(format).clone(),
+                    template: template.clone().filter(|_| output.format.is_none()),
+                    staged: StagedOutput::new(Some(output.path.clone()))?,
+                    name: output.path,
+                })
+            })
+            .collect()
+    }
+
+    /// Local path the report is written to
+    fn local(&self) -> &str {
+        self.staged.local().as_deref().unwrap_or_default()
+    }
+
+    /// Writes the complete report. Runtime-only scans stream their outputs
+    /// instead, except templated ones.
+    fn write(&self, report: &ScanReport) -> anyhow::Result<()> {
+        let path = self.local();
+        if let Some(template) = &self.template {
+            let file = File::create(path).with_context(|| format!("Failed to create output file {}", path))?;
+            let mut writer = BufWriter::new(file);
+            template.export(report, &mut writer)?;
+            return writer.flush().context("Failed to flush report");
+        }
+        if matches!(self.format, cli::OutputFormat::Table) {
+            let summary = report.summary.as_ref().context("Report has no scan summary to export")?;
+            let mut writer = PlainTableWriter::create(path, report.severity_scheme)?;
+            for (context, section) in report_sections(&report.findings) {
+                writer.write_report(context, section)?;
+            }
+            return writer.finish(summary);
+        }
+        output::exporters::export_report(report, &self.format, &Some(path.to_string()))
+    }
+}
+
+/// Writes a finished report to every `--output`, or to the console without
+/// any. Report files are always written; `quiet` only silences the console.
+async fn write_outputs(
+    report: &ScanReport,
+    format: &cli::OutputFormat,
+    outputs: Vec<ScanOutput>,
+    quiet: bool,
+) -> Result<()> {
+    if outputs.is_empty() && !quiet {
+        if matches!(format, cli::OutputFormat::Table) {
+            for (context, section) in report_sections(&report.findings) {
+                print_scan_report(context, section, report.severity_scheme);
+            }
+            if let Some(summary) = &report.summary {
+                print_scan_summary(summary, report.severity_scheme);
+            }
+        } else {
+            output::exporters::export_report(report, format, &None).map_err(ValerisError::Export)?;
+        }
+    }
+    for output in outputs {
+        output.write(report).map_err(ValerisError::Export)?;
+        output.staged.finish().await?;
+    }
+    if let (false, Some(summary)) = (quiet, &report.summary) {
+        print_status_line(summary);
+    }
+    Ok(())
+}
+
+// ────────────────────────────────────────────────────────────────────
+// LIST YAML DETECTORS
+// ────────────────────────────────────────────────────────────────────
+fn list_detectors(rules_dir: &Path) -> Result<()> {
+    let engine = YamlRuleEngine::from_dir(rules_dir)?;
+    println!("Available YAML detectors ({}):", rules_dir.display());
+    for r in engine.rules() {
+        let name = r.name.as_deref().unwrap_or("");
+        println!(
+            "- [{}] {} {}",
+            r.id,
+            name,
+            r.target.as_deref().unwrap_or("")
+        );
+    }
+    Ok(())
+}
+
+// ────────────────────────────────────────────────────────────────────
+// RULE DOCUMENTATION
+// ────────────────────────────────────────────────────────────────────
+fn generate_rule_docs(
+    format: &RuleDocsFormat,
+    runtime_rules: Option<std::path::PathBuf>,
+    dockerfile_rules: Option<std::path::PathBuf>,
+    output: Option<std::path::PathBuf>,
+    rules: &config::RulesConfig,
+) -> anyhow::Result<()> {
+    let runtime_dir = match runtime_rules {
+        Some(dir) => dir,
+        None => ensure_rules(rules)?,
+    };
+    let engine = YamlRuleEngine::from_dir(&runtime_dir)?;
+
+    let dockerfile_dir = dockerfile_rules.unwrap_or_else(|| config::RulesConfig::default().dockerfile_dir());
+    let dockerfile_ruleset = if dockerfile_dir.exists() {
+        detectors::dockerfile::yaml_rules::load_rules_from_dir(&dockerfile_dir)?.rules
+    } else {
+        tracing::debug!("No Dockerfile rules found in {}", dockerfile_dir.display());
+        Vec::new()
+    };
+
+    let doc = match format {
+        RuleDocsFormat::Markdown => {
+            output::rule_docs::render_markdown(engine.rules(), &dockerfile_ruleset)
+        }
+    };
+
+    match output {
+        Some(path) => {
+            std::fs::write(&path, doc)
+                .with_context(|| format!("Failed to write rule docs to {}", path.display()))?;
+            tracing::info!("Rule docs written to {}", path.display());
+        }
+        None => print!("{}", doc),
+    }
+    Ok(())
+}
+
+/// Runs the CLI and exits the process with code 1 if the fail-on threshold
+/// is met.
+///
+/// Kept for backward compatibility; embedders should use [`run`], which
+/// returns the outcome instead of exiting.
+pub async fn run_with_args<I, T>(args: I) -> Result<()>
+where
+    I: IntoIterator<Item = T>,
+    T: Into<std::ffi::OsString> + Clone,
+{
+    let report = run(args).await?;
+    if report.failed() {
+        std::process::exit(report.exit_code);
+    }
+    Ok(())
+}
+
+/// Runs a Valeris command and returns its outcome.
+///
+/// Console output is printed as usual, but the process is never exited:
+/// the caller decides what to do with [`ScanReport::exit_code`]. `--help`
+/// and `--version` print their text and return an empty report.
+///
+/// # Arguments
+///
+/// * `args` - Command-line arguments, including the binary name
+///
+/// # Errors
+///
+/// Returns a [`ValerisError`] if the command could not be completed;
+/// [`ValerisError::exit_code`] tells user errors (invalid filters, rules,
+/// configuration, or Dockerfile) apart from environment errors (Docker
+/// unreachable, unreadable files, failed exports).
+pub async fn run<I, T>(args: I) -> Result<ScanReport>
+where
+    I: IntoIterator<Item = T>,
+    T: Into<std::ffi::OsString> + Clone,
+{
+    let mut args: Vec<std::ffi::OsString> = args.into_iter().map(Into::into).collect();
+    let mut plugin_invocation = None;
+    if plugin::is_plugin(&args) {
+        if args.get(1).is_some_and(|arg| arg == plugin::METADATA_COMMAND) {
+            println!("{}", plugin::metadata());
+            return Ok(ScanReport::default());
+        }
+        let invocation = plugin::parse_args(args);
+        args = invocation.args.clone();
+        plugin_invocation = Some(invocation);
+    }
+
+    let cli = match Cli::try_parse_from(&args) {
+        Ok(cli) => cli,
+        // `--help` and `--version` are not errors: print them like clap would
+        Err(e) if !e.use_stderr() => {
+            e.print().map_err(anyhow::Error::from)?;
+            return Ok(ScanReport::default());
+        }
+        Err(e) => {
+            let message = e.render().to_string();
+            return Err(ValerisError::Usage(message.trim_start_matches("error: ").trim_end().to_string()));
+        }
+    };
+
+    // Load configuration file if it exists. Doctor reports an unreadable one
+    // and `config init --force` replaces it; every other command fails on it.
+    let config_file = match ConfigFile::load_default() {
+        Ok(config_file) => config_file,
+        Err(_) if matches!(cli.command, Commands::Doctor {} | Commands::Config { command: Some(ConfigCommand::Init { .. }) }) => None,
+        Err(e) => return Err(ValerisError::Config(format!("{:#}", e))),
+    };
+
+    if config_file.is_some() {
+        tracing::debug!("Loaded configuration from file");
+    }
+
+    // `--offline` never downloads rules, whatever the configuration says
+    let offline = cli.offline;
+    if offline {
+        check_offline(&cli.command)?;
+    }
+    let mut rules_config = config_file.as_ref().map(ConfigFile::rules_config).unwrap_or_default();
+    rules_config.auto_download &= !offline;
+
+    // Before the configuration is validated, so doctor can report a bad one
+    // and `config init --force` can replace it
+    match &cli.command {
+        Commands::Doctor {} => {
+            let docker_host = plugin_invocation.as_ref().and_then(|p| p.docker_host());
+            let report = doctor::run_checks(docker_host.as_deref(), &rules_config).await;
+            doctor::print_doctor_report(&report);
+            return Ok(ScanReport {
+                exit_code: if report.failed() { EXIT_ENVIRONMENT } else { EXIT_SUCCESS },
+                ..ScanReport::default()
+            });
+        }
+        Commands::Config {
+            command:
+                Some(ConfigCommand::Init {
+                    path,
+                    force,
+                    state,
+                    only,
+                    exclude,
+                    min_severity,
+                    fail_on,
+                    format,
+                    lang,
+                }),
+        } => {
+            let path = path.clone().or_else(config::default_config_path).ok_or_else(|| {
+                ValerisError::Config("Could not determine the configuration directory; pass --path".into())
+            })?;
+            let seed = ConfigFile {
+                scan: Some(ScanConfig {
+                    default_state: state.clone(),
+                    only: only.clone(),
+                    exclude: exclude.clone(),
+                    min_severity: min_severity.as_ref().and_then(value_name),
+                    fail_on: fail_on.as_ref().and_then(value_name),
+                    ..ScanConfig::default()
+                }),
+                output: Some(FileOutputConfig {
+                    format: format.as_ref().and_then(value_name),
+                    lang: lang.clone(),
+                    ..FileOutputConfig::default()
+                }),
+                ..ConfigFile::default()
+            };
+            config::write_config(&path, &seed, *force)?;
+            println!("Wrote configuration to {}", path.display());
+            if std::env::var_os(config::CONFIG_FILE_ENV).is_some() {
+                println!("Note: {} is set, so scans read that file instead", config::CONFIG_FILE_ENV);
+            }
+            println!("Edit it to your preferences, then check it with: valeris doctor");
+            return Ok(ScanReport::default());
+        }
+        _ => {}
+    }
+
+    let severity_map = match &config_file {
+        Some(cfg) => cfg.severity_map()?,
+        None => SeverityMap::default(),
+    };
+    let tag_thresholds = match &config_file {
+        Some(cfg) => cfg.tag_thresholds()?,
+        None => TagThresholds::default(),
+    };
+    let score_weights = match &config_file {
+        Some(cfg) => cfg.score_weights()?,
+        None => ScoreWeights::default(),
+    };
+    let severity_scheme = match &config_file {
+        Some(cfg) => cfg.severity_scheme()?,
+        None => SeverityScheme::default(),
+    };
+    let config_lang = config_file
+        .as_ref()
+        .and_then(|c| c.output.as_ref())
+        .and_then(|o| o.lang.clone());
+    // Secrets are masked unless the configuration file opts out
+    let config_redact_secrets = config_file
+        .as_ref()
+        .and_then(|c| c.output.as_ref())
+        .and_then(|o| o.redact_secrets)
+        .unwrap_or(true);
+
+    let report = match cli.command {
+        Commands::Scan {
+            target: _target,
+            only,
+            exclude,
+            policy,
+            state,
+            container,
+            since,
+            input,
+            quadlet,
+            hosts,
+            dockerfile,
+            compose,
+            dockerfile_rules,
+            probe,
+            usage,
+            drift,
+            severity,
+            min_severity,
+            fail_on,
+            fail_score,
+            quiet,
+            format,
+            output,
+            template,
+            lang,
+            anonymize,
+            no_redact_secrets,
+            explain,
+            profile_rules,
+            syslog,
+            store,
+            attest,
+            attest_key,
+            print_schema,
+        } => {
+            if print_schema {
+                print!("{}", output::exporters::REPORT_SCHEMA);
+                return Ok(ScanReport::default());
+            }
+
+            // Resolve before recording this scan, so `--since last` sees the previous one
+            let since_cutoff = since
+                .as_deref()
+                .map(detectors::runtime::incremental::resolve_since)
+                .transpose()?;
+            let scan_started_at = SystemTime::now();
+
+            // Apply configuration file defaults (CLI args override)
+            let scan_config = config_file.as_ref().and_then(|c| c.scan.as_ref());
+
+            let effective_only = apply_config_defaults(&only, &scan_config.and_then(|s| s.only.clone()));
+            let effective_exclude = apply_config_defaults(&exclude, &scan_config.and_then(|s| s.exclude.clone()));
+            let effective_state = apply_config_defaults(&state, &scan_config.and_then(|s| s.default_state.clone()));
+            // An explicit --only replaces a configured policy
+            let effective_policy = match &only {
+                Some(_) => None,
+                None => policy.or_else(|| scan_config.and_then(|s| s.policy.clone())),
+            };
+            let max_parallel = config_file
+                .as_ref()
+                .and_then(|c| c.docker.as_ref())
+                .and_then(|d| d.max_parallel)
+                .unwrap_or_else(|| config::DockerConfig::default().max_parallel_scans);
+
+            let started = Instant::now();
+            let rules_dir = tokio::task::spawn_blocking(move || locate_rules(&rules_config))
+                .await
+                .context("Failed to spawn rules download task")??;
+            let policy = effective_policy
+                .as_deref()
+                .map(|name| policy::runtime_policy(&rules_dir, name))
+                .transpose()?;
+            let (effective_only, min_severity, severity_map) =
+                apply_policy(policy, effective_only, effective_exclude.as_ref(), severity.as_ref(), min_severity, severity_map);
+
+            let scan_options = RuntimeScanOptions {
+                rules_dir,
+                only: effective_only,
+                exclude: effective_exclude,
+                state: effective_state,
+                container,
+                max_parallel,
+                since: since_cutoff,
+                observer: None,
+                // As a Docker CLI plugin, scan the daemon the Docker CLI is pointed at
+                docker_host: match (&input, &quadlet, &hosts) {
+                    (None, None, None) => plugin_invocation.as_ref().and_then(|p| p.docker_host()),
+                    _ => None,
+                },
+                input: input.clone(),
+                quadlet: quadlet.clone(),
+                hosts: hosts.as_deref().map(load_hosts).transpose()?,
+                probe,
+                usage,
+                drift,
+                ports: match &config_file {
+                    Some(cfg) => cfg.port_policy()?,
+                    None => PortPolicy::default(),
+                },
+                lang: lang.or_else(|| config_lang.clone()),
+                explain,
+                redact_secrets: config_redact_secrets && !no_redact_secrets,
+                profile: profile_rules.then(|| Arc::new(bench::RuleProfile::default())),
+            };
+            // Static targets of a combined scan; resolved first so a bad
+            // Compose file fails before the daemon is scanned
+            let combined = !dockerfile.is_empty() || !compose.is_empty();
+            let mut static_files = ComposeFiles {
+                dockerfiles: Vec::new(),
+                env_files: Vec::new(),
+            };
+            static_files.extend(ComposeFiles {
+                dockerfiles: dockerfile,
+                env_files: Vec::new(),
+            });
+            for path in &compose {
+                static_files.extend(compose::referenced_files(path)?);
+            }
+            let file_template = DockerfileScanOptions {
+                severity: severity.clone(),
+                min_severity: min_severity.clone(),
+                quiet: true,
+                format: cli::OutputFormat::Json,
+                severity_map: severity_map.clone(),
+                path_overrides: match &config_file {
+                    Some(cfg) => cfg.path_overrides()?,
+                    None => PathOverrides::default(),
+                },
+                tag_thresholds: tag_thresholds.clone(),
+                score_weights: score_weights.clone(),
+                lang: scan_options.lang.clone(),
+                explain,
+                redact_secrets: scan_options.redact_secrets,
+                profile: scan_options.profile.clone(),
+                ..DockerfileScanOptions::new(
+                    PathBuf::new(),
+                    dockerfile_rules.unwrap_or_else(|| config::RulesConfig::default().dockerfile_dir()),
+                )
+            };
+
+            let mut syslog = syslog.as_deref().map(SyslogSink::connect).transpose()?;
+            let signer = attest_key.as_deref().map(AttestationSigner::from_pem_file).transpose()?;
+            let mut attestation = attest.as_ref().map(|_| ScanAttestation::new(scan_started_at));
+            // `--output` files default to JSON; stdout to a table on a terminal
+            let outputs = ScanOutput::resolve(
+                output,
+                format.as_ref().unwrap_or(&cli::OutputFormat::Json),
+                template.as_deref(),
+            )?;
+            let console_format = format.unwrap_or_else(default_console_format);
+            let console_table = matches!(console_format, cli::OutputFormat::Table);
+            let mut history = store.map(|path| {
+                (HistoryStore::open(path), ScanRecord::new(TargetKind::Containers, scan_started_at))
+            });
+            let seen = match &history {
+                Some((store, _)) => {
+                    let renames = YamlRuleEngine::from_dir(&scan_options.rules_dir)?.renames();
+                    Some(store.seen_index(scan_started_at)?.with_renames(&renames))
+                }
+                None => None,
+            };
+            let mut scan = scan_docker_stream(&scan_options).await?;
+            let files = combined.then(|| scan_files(&static_files, &file_template)).transpose()?;
+
+            // Inspect files and units are not daemon scans; don't move `--since last`
+            if input.is_none() && quadlet.is_none() {
+                if let Err(e) = detectors::runtime::incremental::record_scan_time(scan_started_at) {
+                    tracing::warn!("Failed to record scan time: {e:#}");
+                }
+            }
+            // Remap severities before any filtering, then apply severity filtering
+            let prepare = |result: &mut docker::model::ContainerResult| {
+                severity_map.apply(&mut result.findings);
+                filter_by_severity(std::slice::from_mut(result), severity.as_ref(), min_severity.as_ref(), &tag_thresholds);
+                docker::model::sort_findings(&mut result.findings);
+                if let Some(seen) = &seen {
+                    seen.annotate_container(result);
+                }
+            };
+            let target_score = |result: &docker::model::ContainerResult| TargetScore {
+                target: match &result.host {
+                    Some(host) => format!("{}/{}", host, history::container_target(&result.container)),
+                    None => history::container_target(&result.container).to_string(),
+                },
+                score: score_weights.score(&result.findings),
+            };
+
+            // History, syslog and attestations keep the real names
+            let mut anonymizer = anonymize.then(Anonymizer::new);
+            let mut metadata = ScanMetadata::new(scan_started_at)
+                .with_rules(&scan_options.rules_dir)
+                .with_daemon(scan.daemon.clone())
+                .with_filter("policy", effective_policy.as_deref())
+                .with_filter("only", scan_options.only.as_ref())
+                .with_filter("exclude", scan_options.exclude.as_ref())
+                .with_filter("state", scan_options.state.as_ref())
+                .with_filter("container", scan_options.container.as_ref())
+                .with_filter("since", since.as_deref())
+                .with_filter("severity", severity.as_ref())
+                .with_filter("min_severity", min_severity.as_ref())
+                .with_filter("fail_on", fail_on.as_ref())
+                .with_filter("fail_score", fail_score.as_ref())
+                .with_offline(offline);
+            if let Some(anonymizer) = &mut anonymizer {
+                anonymizer.metadata(&mut metadata);
+            }
+
+            // Report files are always written; quiet only silences the console.
+            // Combined scans are not streamed: the report ends with the files.
+            let (summary, findings) = if files.is_none() {
+                // Emit each container as soon as it is evaluated: print it, or
+                // write it to every report. Results are still collected for
+                // the returned report and templates.
+                let console = outputs.is_empty();
+                let stdout_report = (console && !console_table && !quiet).then_some(&console_format);
+                let mut streams = outputs
+                    .iter()
+                    .filter(|output| output.template.is_none())
+                    .map(|output| {
+                        ContainerExportStream::create(&output.format, &Some(output.local().to_string()), severity_scheme)
+                    })
+                    .chain(stdout_report.map(|format| ContainerExportStream::create(format, &None, severity_scheme)))
+                    .collect::<anyhow::Result<Vec<_>>>()
+                    .map_err(ValerisError::Export)?;
+                let console = console && console_table;
+                let mut results = Vec::new();
+                let mut counts = SeverityCounts::default();
+                let mut scores = Vec::new();
+                let mut scanned = 0;
+                let mut should_exit_with_error = false;
+
+                while let Some(mut result) = scan.next().await {
+                    prepare(&mut result);
+                    counts.add(&result.findings);
+                    should_exit_with_error |= should_fail(std::slice::from_ref(&result), fail_on.as_ref(), &tag_thresholds)
+                        || score_weights.exceeds(&result.findings, fail_score);
+                    if let Some(sink) = &mut syslog {
+                        sink.send_container(&result)?;
+                    }
+                    if let Some((_, record)) = &mut history {
+                        record.add_container(&result);
+                    }
+                    if let Some(attestation) = &mut attestation {
+                        attestation.add_container(&result);
+                    }
+                    if let Some(anonymizer) = &mut anonymizer {
+                        anonymizer.container(&mut result);
+                    }
+                    scores.push(target_score(&result));
+                    for stream in &mut streams {
+                        stream.write_result(&result).map_err(ValerisError::Export)?;
+                    }
+                    scanned += 1;
+                    if console && !quiet {
+                        let (context, section) = container_section(&result);
+                        print_scan_report(context, section, severity_scheme);
+                    }
+                    results.push(result);
+                }
+
+                let summary = ScanSummary::from_counts(
+                    TargetKind::Containers,
+                    scanned,
+                    scan.rules_evaluated,
+                    counts,
+                    started.elapsed(),
+                    should_exit_with_error,
+                )
+                .with_scores(scores)
+                .with_metadata(metadata);
+                for stream in streams {
+                    stream.finish(&summary).map_err(ValerisError::Export)?;
+                }
+                let report = ScanReport::from_scan(summary.clone(), ScanFindings::Containers(results))
+                    .with_severity_scheme(severity_scheme);
+                for output in outputs {
+                    if output.template.is_some() {
+                        output.write(&report).map_err(ValerisError::Export)?;
+                    }
+                    let local = Path::new(output.local());
+                    if let Some(attestation) = attestation.as_mut().filter(|_| local.exists()) {
+                        attestation.add_report(&output.name, local)?;
+                    }
+                    output.staged.finish().await?;
+                }
+                if console && !quiet {
+                    print_scan_summary(&summary, severity_scheme);
+                }
+                (summary, report.findings)
+            } else {
+                let scan = scan.collect().await;
+                let mut results = scan.results;
+                results.iter_mut().for_each(prepare);
+                if let Some(sink) = &mut syslog {
+                    for result in &results {
+                        sink.send_container(result)?;
+                    }
+                }
+                if let Some((_, record)) = &mut history {
+                    results.iter().for_each(|result| record.add_container(result));
+                }
+                if let Some(attestation) = &mut attestation {
+                    results.iter().for_each(|result| attestation.add_container(result));
+                }
+                let mut files = files;
+                if let Some(anonymizer) = &mut anonymizer {
+                    results.iter_mut().for_each(|result| anonymizer.container(result));
+                    for result in files.iter_mut().flat_map(|f| f.dockerfiles.iter_mut().chain(&mut f.env_files)) {
+                        result.path = anonymizer.path(&result.path);
+                        anonymizer.findings(&mut result.findings);
+                    }
+                }
+
+                // One fail-on evaluation across containers and files
+                let file_findings = files.iter().flat_map(|f| f.results()).flat_map(|r| &r.findings);
+                let should_exit_with_error = should_fail(&results, fail_on.as_ref(), &tag_thresholds)
+                    || tag_thresholds.fails(file_findings.clone(), fail_on.as_ref().map(severity_to_risk))
+                    || results.iter().any(|r| score_weights.exceeds(&r.findings, fail_score))
+                    || files.iter().flat_map(|f| f.results()).any(|r| score_weights.exceeds(&r.findings, fail_score));
+
+                let file_scores = files.iter().flat_map(|f| f.results()).map(|result| TargetScore {
+                    target: result.path.display().to_string(),
+                    score: score_weights.score(&result.findings),
+                });
+                let summary = ScanSummary::new(
+                    if files.is_some() { TargetKind::Combined } else { TargetKind::Containers },
+                    results.len() + files.as_ref().map_or(0, FileScan::targets),
+                    scan.rules_evaluated + files.as_ref().map_or(0, |f| f.rules_evaluated),
+                    results.iter().flat_map(|r| r.findings.iter()).chain(file_findings),
+                    started.elapsed(),
+                    should_exit_with_error,
+                )
+                .with_scores(results.iter().map(target_score).chain(file_scores).collect())
+                .with_metadata(metadata);
+
+                let findings = match files {
+                    Some(files) => ScanFindings::Combined {
+                        containers: results,
+                        dockerfiles: files.dockerfiles,
+                        env_files: files.env_files,
+                    },
+                    None => ScanFindings::Containers(results),
+                };
+                if outputs.is_empty() && console_table && !quiet {
+                    for (context, section) in report_sections(&findings) {
+                        print_scan_report(context, section, severity_scheme);
+                    }
+                    print_scan_summary(&summary, severity_scheme);
+                }
+                let report = ScanReport::from_scan(summary.clone(), findings).with_severity_scheme(severity_scheme);
+                if outputs.is_empty() && !console_table && !quiet {
+                    output::exporters::export_report(&report, &console_format, &None).map_err(ValerisError::Export)?;
+                }
+                for output in outputs {
+                    output.write(&report).map_err(ValerisError::Export)?;
+                    if let Some(attestation) = &mut attestation {
+                        attestation.add_report(&output.name, Path::new(output.local()))?;
+                    }
+                    output.staged.finish().await?;
+                }
+                (summary, report.findings)
+            };
+            if let Some((store, record)) = &history {
+                store.record(record)?;
+            }
+            if let (Some(path), Some(signer), Some(attestation)) = (&attest, &signer, attestation) {
+                signer.write(&attestation.statement(&scan_options.rules_dir, &summary)?, path)?;
+            }
+            if !quiet {
+                print_status_line(&summary);
+            }
+            if let Some(profile) = &scan_options.profile {
+                bench::print_rule_profile(&profile.timings(), PROFILE_TOP);
+            }
+
+            ScanReport::from_scan(summary, findings)
+        }
+
+        Commands::DockerFile {
+            path,
+            rules,
+            only,
+            exclude,
+            policy,
+            severity,
+            min_severity,
+            fail_on,
+            fail_score,
+            quiet,
+            format,
+            output,
+            lang,
+            anonymize,
+            no_redact_secrets,
+            explain,
+            profile_rules,
+            syslog,
+            store,
+            attest,
+            attest_key,
+        } => {
+            let is_table = matches!(format, cli::OutputFormat::Table);
+            let outputs = ScanOutput::resolve(output, &format, None)?;
+            let started_at = SystemTime::now();
+            let history = store.map(HistoryStore::open);
+            let policy = policy
+                .as_deref()
+                .map(|name| policy::dockerfile_policy(&rules, name))
+                .transpose()?;
+            let (only, min_severity, severity_map) =
+                apply_policy(policy, only, exclude.as_ref(), severity.as_ref(), min_severity, severity_map);
+            let seen = match &history {
+                Some(store) => {
+                    let ruleset = detectors::dockerfile::yaml_rules::load_rules_from_dir(&rules)?;
+                    let renames = detectors::dockerfile::yaml_rules::renames(&ruleset.rules);
+                    Some(store.seen_index(started_at)?.with_renames(&renames))
+                }
+                None => None,
+            };
+            let scan_options = DockerfileScanOptions {
+                path: path.clone(),
+                rules_dir: rules,
+                only,
+                exclude,
+                severity,
+                min_severity,
+                fail_on,
+                fail_score,
+                // The scanner prints to the console only; report files are written below
+                quiet: quiet || !outputs.is_empty(),
+                format,
+                output_file: None,
+                severity_scheme,
+                severity_map,
+                path_overrides: match &config_file {
+                    Some(cfg) => cfg.path_overrides()?,
+                    None => PathOverrides::default(),
+                },
+                tag_thresholds,
+                observer: None,
+                history: seen,
+                score_weights,
+                lang: lang.or_else(|| config_lang.clone()),
+                anonymize,
+                explain,
+                redact_secrets: config_redact_secrets && !no_redact_secrets,
+                profile: profile_rules.then(|| Arc::new(bench::RuleProfile::default())),
+                offline,
+            };
+            let mut syslog = syslog.as_deref().map(SyslogSink::connect).transpose()?;
+            let signer = attest_key.as_deref().map(AttestationSigner::from_pem_file).transpose()?;
+            let outcome = scan_dockerfile_report(&scan_options)?;
+            if !outputs.is_empty() {
+                let report = ScanReport::from_scan(
+                    outcome.summary.clone(),
+                    ScanFindings::Dockerfile {
+                        path: outcome.path.clone(),
+                        findings: outcome.findings.clone(),
+                    },
+                )
+                .with_severity_scheme(severity_scheme);
+                for output in &outputs {
+                    output.write(&report).map_err(ValerisError::Export)?;
+                }
+                if !quiet {
+                    print_status_line(&outcome.summary);
+                }
+            }
+            // Built before the staged reports are uploaded and removed
+            let statement = if signer.is_some() {
+                let mut attestation = ScanAttestation::new(started_at);
+                attestation.add_dockerfile(&path)?;
+                for output in &outputs {
+                    attestation.add_report(&output.name, Path::new(output.local()))?;
+                }
+                Some(attestation.statement(&scan_options.rules_dir, &outcome.summary)?)
+            } else {
+                None
+            };
+            for output in outputs {
+                output.staged.finish().await?;
+            }
+            if let Some(sink) = &mut syslog {
+                sink.send_dockerfile(&path, &outcome.findings)?;
+            }
+            if let Some(store) = &history {
+                let mut record = ScanRecord::new(TargetKind::Dockerfiles, started_at);
+                record.add_dockerfile(&path, &outcome.findings);
+                store.record(&record)?;
+            }
+            if let (Some(path), Some(signer), Some(statement)) = (&attest, &signer, &statement) {
+                signer.write(statement, path)?;
+            }
+            if is_table && !quiet {
+                println!("Dockerfile processed successfully");
+            }
+            if let Some(profile) = &scan_options.profile {
+                bench::print_rule_profile(&profile.timings(), PROFILE_TOP);
+            }
+
+            ScanReport::from_scan(
+                outcome.summary,
+                ScanFindings::Dockerfile {
+                    path,
+                    findings: outcome.findings,
+                },
+            )
+        }
+
+        Commands::EnvFile {
+            path,
+            severity,
+            min_severity,
+            fail_on,
+            fail_score,
+            quiet,
+            format,
+            output,
+        } => {
+            let started = Instant::now();
+            let metadata = ScanMetadata::new(SystemTime::now())
+                .with_filter("severity", severity.as_ref())
+                .with_filter("min_severity", min_severity.as_ref())
+                .with_filter("fail_on", fail_on.as_ref())
+                .with_filter("fail_score", fail_score.as_ref())
+                .with_offline(offline);
+            let outputs = ScanOutput::resolve(output, &format, None)?;
+            let mut findings = detectors::env_file::scan_env_file(&path)?;
+
+            // Remap severities before any filtering
+            severity_map.apply(&mut findings);
+            filter_findings(&mut findings, severity.as_ref(), min_severity.as_ref(), &tag_thresholds);
+            let should_exit_with_error = tag_thresholds.fails(&findings, fail_on.as_ref().map(severity_to_risk))
+                || score_weights.exceeds(&findings, fail_score);
+
+            let summary = ScanSummary::new(
+                TargetKind::EnvFiles,
+                1,
+                detectors::env_file::CHECK_COUNT,
+                &findings,
+                started.elapsed(),
+                should_exit_with_error,
+            )
+            .with_scores(vec![TargetScore {
+                target: path.display().to_string(),
+                score: score_weights.score(&findings),
+            }])
+            .with_metadata(metadata);
+
+            let report = ScanReport::from_scan(summary, ScanFindings::EnvFile { path, findings })
+                .with_severity_scheme(severity_scheme);
+            write_outputs(&report, &format, outputs, quiet).await?;
+            report
+        }
+
+        Commands::Network {
+            input,
+            severity,
+            min_severity,
+            fail_on,
+            fail_score,
+            quiet,
+            format,
+            output,
+        } => {
+            let started = Instant::now();
+            let mut metadata = ScanMetadata::new(SystemTime::now())
+                .with_filter("severity", severity.as_ref())
+                .with_filter("min_severity", min_severity.as_ref())
+                .with_filter("fail_on", fail_on.as_ref())
+                .with_filter("fail_score", fail_score.as_ref())
+                .with_offline(offline);
+            let outputs = ScanOutput::resolve(output, &format, None)?;
+            let (networks, images) = match &input {
+                Some(path) => (detectors::network::load_network_file(path)?, Default::default()),
+                None => {
+                    // As a Docker CLI plugin, scan the daemon the Docker CLI is pointed at
+                    let docker_host = plugin_invocation.as_ref().and_then(|p| p.docker_host());
+                    let docker = connect_docker(docker_host.as_deref()).map_err(ValerisError::DockerConnection)?;
+                    metadata = metadata.with_daemon(daemon_info(docker_host.as_deref()).await);
+                    detectors::network::get_networks(&docker)
+                        .await
+                        .map_err(ValerisError::DockerConnection)?
+                }
+            };
+            let mut results = detectors::network::scan_networks(networks, &images);
+
+            // Remap severities before any filtering
+            for result in &mut results {
+                severity_map.apply(&mut result.findings);
+                filter_findings(&mut result.findings, severity.as_ref(), min_severity.as_ref(), &tag_thresholds);
+            }
+            let should_exit_with_error = tag_thresholds.fails(
+                results.iter().flat_map(|r| r.findings.iter()),
+                fail_on.as_ref().map(severity_to_risk),
+            ) || results.iter().any(|r| score_weights.exceeds(&r.findings, fail_score));
+
+            let summary = ScanSummary::new(
+                TargetKind::Networks,
+                results.len(),
+                detectors::network::CHECK_COUNT,
+                results.iter().flat_map(|r| r.findings.iter()),
+                started.elapsed(),
+                should_exit_with_error,
+            )
+            .with_scores(
+                results
+                    .iter()
+                    .map(|r| TargetScore {
+                        target: r.network.name.clone().unwrap_or_default(),
+                        score: score_weights.score(&r.findings),
+                    })
+                    .collect(),
+            )
+            .with_metadata(metadata);
+
+            let report = ScanReport::from_scan(summary, ScanFindings::Networks(results))
+                .with_severity_scheme(severity_scheme);
+            write_outputs(&report, &format, outputs, quiet).await?;
+            report
+        }
+
+        Commands::Bench {
+            rules,
+            fixtures,
+            iterations,
+            top,
+        } => {
+            let report = bench::run_bench(&rules, &fixtures, iterations)?;
+            bench::print_bench_report(&report, top);
+            ScanReport::default()
+        }
+
+        Commands::History {
+            store,
+            container,
+            since,
+        } => {
+            let filter = history::HistoryFilter {
+                targets: container,
+                since: since
+                    .as_deref()
+                    .map(detectors::runtime::incremental::resolve_since)
+                    .transpose()?,
+            };
+            let store = HistoryStore::open(store.unwrap_or_else(history::default_store_path));
+            let report = history::history_report(store.scans()?, &filter);
+            history::print_history_report(&report);
+            ScanReport::default()
+        }
+
+        Commands::Merge { reports, output } => {
+            let merged = output::merge::merge_reports(&reports)?;
+            let json = serde_json::to_string_pretty(&merged)
+                .context("Failed to serialize merged report")
+                .map_err(ValerisError::Export)?;
+            let staged_output = StagedOutput::new(output)?;
+            match staged_output.local() {
+                Some(path) => std::fs::write(path, json + "\n")
+                    .with_context(|| format!("Failed to write {}", path))
+                    .map_err(ValerisError::Export)?,
+                None => println!("{}", json),
+            }
+            staged_output.finish().await?;
+            ScanReport::default()
+        }
+
+        Commands::ListPlugins { .. } => {
+            let rules_dir = locate_rules(&rules_config)?;
+            list_detectors(&rules_dir)?;
+            ScanReport::default()
+        }
+
+        Commands::Rules { command } => match command {
+            RulesCommand::Docs {
+                format,
+                runtime_rules,
+                dockerfile_rules,
+                output,
+            } => {
+                generate_rule_docs(&format, runtime_rules, dockerfile_rules, output, &rules_config)?;
+                ScanReport::default()
+            }
+        },
+
+        Commands::Report { command } => match command {
+            ReportCommand::GithubPr {
+                repo,
+                pr,
+                report,
+                api_url,
+            } => {
+                publish_github_pr(&api_url, &repo, pr, &report).await?;
+                ScanReport::default()
+            }
+            ReportCommand::Compliance { framework, report } => {
+                use output::compliance::{compliance_report, load_report_findings, print_compliance_report};
+
+                let findings = load_report_findings(&report)?;
+                print_compliance_report(&compliance_report(&framework, findings));
+                ScanReport::default()
+            }
+        },
+
+        Commands::Hook { command } => match command {
+            HookCommand::Install {
+                hook_type,
+                fail_on,
+                force,
+            } => {
+                let path = hook::install(&hook_type, fail_on.as_ref(), force)?;
+                println!("Installed {} hook at {}", hook_type.file_name(), path.display());
+                ScanReport::default()
+            }
+            HookCommand::Run {
+                files,
+                all,
+                fail_on,
+                rules,
+            } => {
+                let source = if all {
+                    hook::HookSource::Tracked
+                } else if files.is_empty() {
+                    hook::HookSource::Staged
+                } else {
+                    hook::HookSource::Files(files)
+                };
+                hook::run(source, fail_on, rules, offline).await?
+            }
+        },
+
+        Commands::Doctor {} | Commands::Config { command: Some(ConfigCommand::Init { .. }) } => {
+            unreachable!("runs before the configuration is validated")
+        }
+
+        Commands::Config { command: None } => {
+            println!("Valeris Configuration");
+            println!("{}", "━".repeat(60));
+
+            // Check environment variable
+            if let Ok(path) = std::env::var(config::CONFIG_FILE_ENV) {
+                println!("\n📝 Environment variable set:");
+                println!("   {}={}", config::CONFIG_FILE_ENV, path);
+                let path_buf = std::path::PathBuf::from(&path);
+                if path_buf.exists() {
+                    println!("   Status: ✅ File exists");
+                } else {
+                    println!("   Status: ❌ File not found");
+                }
+            }
+
+            // Check XDG config directory
+            if let Some(config_dir) = dirs::config_dir() {
+                let path = config_dir.join("valeris").join("config.toml");
+                println!("\n📁 XDG config location:");
+                println!("   {}", path.display());
+                if path.exists() {
+                    println!("   Status: ✅ File exists");
+                    if let Ok(cfg) = ConfigFile::load(&path) {
+                        println!("   Parse: ✅ Valid TOML");
+                        if cfg.scan.is_some() {
+                            println!("   - Contains [scan] configuration");
+                        }
+                        if cfg.output.is_some() {
+                            println!("   - Contains [output] configuration");
+                        }
+                        if cfg.rules.is_some() {
+                            println!("   - Contains [rules] configuration");
+                        }
+                        if cfg.docker.is_some() {
+                            println!("   - Contains [docker] configuration");
+                        }
+                    } else {
+                        println!("   Parse: ❌ Invalid TOML");
+                    }
+                } else {
+                    println!("   Status: ⚠️  File not found (create to use)");
+                }
+            }
+
+            // Check home directory
+            if let Some(home_dir) = dirs::home_dir() {
+                let path = home_dir.join(".valeris.toml");
+                println!("\n🏠 Home directory location:");
+                println!("   {}", path.display());
+                if path.exists() {
+                    println!("   Status: ✅ File exists");
+                } else {
+                    println!("   Status: ⚠️  File not found");
+                }
+            }
+
+            println!("\n💡 To create a config file:");
+            println!("   valeris config init");
+            println!("   vi ~/.config/valeris/config.toml");
+            ScanReport::default()
+        }
+    };
+    Ok(report.with_severity_scheme(severity_scheme))
+}
@@ -0,0 +1,418 @@
+//! Rule benchmarking.
+//!
+//! Backs `valeris bench`: loads a rule bundle, evaluates every rule against
+//! a directory of fixtures (container inspect JSON and Dockerfiles), and
+//! reports per-rule timings so rule authors can spot pathological regexes
+//! before shipping a bundle.
+//!
+//! [`RuleProfile`] collects the same timings during a real scan
+//! (`--profile-rules`), so users can see which rules cost the most and which
+//! report the most findings on their own containers and Dockerfiles.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Context, Result};
+#[cfg(feature = "cli")]
+use comfy_table::{presets::UTF8_FULL, Cell, ContentArrangement, Table};
+use dockerfile_parser::Dockerfile;
+use serde_json::Value;
+use walkdir::WalkDir;
+
+use crate::detectors::dockerfile::scanner::evaluate_rules;
+use crate::detectors::dockerfile::yaml_rules::{load_rules_from_dir, Rule};
+use crate::detectors::runtime::yaml_rules::YamlRuleEngine;
+
+/// Which scanner a benchmarked rule belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RuleKind {
+    /// Runtime (container inspect) rule
+    Runtime,
+    /// Dockerfile rule
+    Dockerfile,
+}
+
+impl RuleKind {
+    /// Short name shown in reports
+    pub fn label(&self) -> &'static str {
+        match self {
+            RuleKind::Runtime => "runtime",
+            RuleKind::Dockerfile => "dockerfile",
+        }
+    }
+}
+
+/// Accumulated evaluation time for a single rule.
+#[derive(Debug, Clone)]
+pub struct RuleTiming {
+    pub id: String,
+    pub kind: RuleKind,
+    /// Total time spent evaluating the rule across all fixtures and iterations
+    pub total: Duration,
+    /// Number of rule evaluations performed
+    pub evaluations: usize,
+    /// Number of findings produced (in the first iteration, when benchmarking)
+    pub findings: usize,
+}
+
+impl RuleTiming {
+    /// Average time per evaluation.
+    pub fn mean(&self) -> Duration {
+        if self.evaluations == 0 {
+            return Duration::ZERO;
+        }
+        self.total / self.evaluations as u32
+    }
+}
+
+/// Results of a benchmark run.
+#[derive(Debug)]
+pub struct BenchReport {
+    pub runtime_load: Duration,
+    pub dockerfile_load: Duration,
+    pub inspect_fixtures: usize,
+    pub dockerfile_fixtures: usize,
+    pub iterations: usize,
+    /// Per-rule timings, slowest first
+    pub timings: Vec<RuleTiming>,
+}
+
+/// Per-rule timings and finding counts collected during a scan
+/// (`--profile-rules`).
+///
+/// Shared by the scan's worker threads; each rule evaluation is added with
+/// [`RuleProfile::record`].
+#[derive(Debug, Default)]
+pub struct RuleProfile {
+    timings: Mutex<HashMap<(RuleKind, String), RuleTiming>>,
+}
+
+impl RuleProfile {
+    /// Adds one evaluation of rule `id` that took `elapsed` and produced
+    /// `findings` findings.
+    pub fn record(&self, id: &str, kind: RuleKind, elapsed: Duration, findings: usize) {
+        let mut timings = self.timings.lock().unwrap_or_else(|e| e.into_inner());
+        let timing = timings
+            .entry((kind, id.to_string()))
+            .or_insert_with(|| new_timing(id, kind));
+        timing.total += elapsed;
+        timing.evaluations += 1;
+        timing.findings += findings;
+    }
+
+    /// Returns the timings recorded so far, slowest first.
+    pub fn timings(&self) -> Vec<RuleTiming> {
+        let timings = self.timings.lock().unwrap_or_else(|e| e.into_inner());
+        let mut timings: Vec<RuleTiming> = timings.values().cloned().collect();
+        timings.sort_by(|a, b| b.total.cmp(&a.total).then_with(|| a.id.cmp(&b.id)));
+        timings
+    }
+}
+
+/// A parsed Dockerfile fixture.
+struct DockerfileFixture {
+    path: PathBuf,
+    content: String,
+    dockerfile: Dockerfile,
+}
+
+/// Times rule loading and per-rule evaluation over a fixtures directory.
+///
+/// Runtime rules are read from `{rules}/docker` or `{rules}/runtime/docker`,
+/// Dockerfile rules from `{rules}/dockerfile`. Fixtures are discovered
+/// recursively: `*.json` files containing an object are treated as container
+/// inspect responses, and `Dockerfile*` / `*.Dockerfile` files as Dockerfiles.
+///
+/// # Arguments
+///
+/// * `rules` - Rule bundle directory
+/// * `fixtures` - Directory containing fixture files
+/// * `iterations` - Number of times each rule is evaluated against each fixture
+///
+/// # Errors
+///
+/// Returns an error if rules cannot be loaded or a fixture cannot be read or parsed
+pub fn run_bench(rules: &Path, fixtures: &Path, iterations: usize) -> Result<BenchReport> {
+    let iterations = iterations.max(1);
+
+    let started = Instant::now();
+    let engine = YamlRuleEngine::from_dir(&runtime_rules_base(rules))?;
+    let runtime_load = started.elapsed();
+
+    let dockerfile_dir = rules.join("dockerfile");
+    let started = Instant::now();
+    let dockerfile_rules = if dockerfile_dir.exists() {
+        load_rules_from_dir(&dockerfile_dir)?.rules
+    } else {
+        Vec::new()
+    };
+    let dockerfile_load = started.elapsed();
+
+    if engine.rules().is_empty() && dockerfile_rules.is_empty() {
+        return Err(anyhow!("No rules found in {}", rules.display()));
+    }
+
+    let (inspects, dockerfiles) = load_fixtures(fixtures)?;
+
+    let mut timings = Vec::new();
+
+    for rule in engine.rules() {
+        let mut timing = new_timing(&rule.id, RuleKind::Runtime);
+        for iteration in 0..iterations {
+            for value in &inspects {
+                let started = Instant::now();
+                let findings = engine.scan_value_with_rule(rule, value);
+                timing.total += started.elapsed();
+                timing.evaluations += 1;
+                if iteration == 0 {
+                    timing.findings += findings.len();
+                }
+            }
+        }
+        timings.push(timing);
+    }
+
+    for rule in &dockerfile_rules {
+        let mut timing = new_timing(rule.id(), RuleKind::Dockerfile);
+        for iteration in 0..iterations {
+            for fixture in &dockerfiles {
+                let started = Instant::now();
+                let findings = evaluate_rules(
+                    &fixture.dockerfile,
+                    std::slice::from_ref::<Rule>(rule),
+                    &fixture.content,
+                    &fixture.path,
+                );
+                timing.total += started.elapsed();
+                timing.evaluations += 1;
+                if iteration == 0 {
+                    timing.findings += findings.len();
+                }
+            }
+        }
+        timings.push(timing);
+    }
+
+    timings.sort_by(|a, b| b.total.cmp(&a.total).then_with(|| a.id.cmp(&b.id)));
+
+    Ok(BenchReport {
+        runtime_load,
+        dockerfile_load,
+        inspect_fixtures: inspects.len(),
+        dockerfile_fixtures: dockerfiles.len(),
+        iterations,
+        timings,
+    })
+}
+
+/// Prints the benchmark report, limited to the `top` slowest rules.
+#[cfg(feature = "cli")]
+pub fn print_bench_report(report: &BenchReport, top: usize) {
+    println!("Valeris rule benchmark");
+    println!("{}", "━".repeat(60));
+    println!(
+        "Fixtures: {} inspect JSON, {} Dockerfiles ({} iterations)",
+        report.inspect_fixtures, report.dockerfile_fixtures, report.iterations
+    );
+    println!(
+        "Rule loading: runtime {}, dockerfile {}",
+        format_duration(report.runtime_load),
+        format_duration(report.dockerfile_load)
+    );
+    println!();
+
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_header(vec!["Rule", "Scanner", "Total", "Mean", "Evaluations", "Findings"]);
+
+    for timing in report.timings.iter().take(top) {
+        table.add_row(vec![
+            Cell::new(&timing.id),
+            Cell::new(timing.kind.label()),
+            Cell::new(format_duration(timing.total)),
+            Cell::new(format_duration(timing.mean())),
+            Cell::new(timing.evaluations),
+            Cell::new(timing.findings),
+        ]);
+    }
+
+    println!("{table}");
+    if report.timings.len() > top {
+        println!("... {} more rules (use --top to show more)", report.timings.len() - top);
+    }
+}
+
+/// Prints the `top` slowest rules and the `top` rules with the most findings
+/// of a scan profile to stderr, keeping stdout for the report.
+#[cfg(feature = "cli")]
+pub fn print_rule_profile(timings: &[RuleTiming], top: usize) {
+    let total: Duration = timings.iter().map(|t| t.total).sum();
+    eprintln!();
+    eprintln!("Rule profile");
+    eprintln!("{}", "━".repeat(60));
+    eprintln!(
+        "{} rules evaluated in {}",
+        timings.len(),
+        format_duration(total)
+    );
+
+    let table = |rows: &mut dyn Iterator<Item = &RuleTiming>| {
+        let mut table = Table::new();
+        table
+            .load_preset(UTF8_FULL)
+            .set_content_arrangement(ContentArrangement::Dynamic)
+            .set_header(vec!["Rule", "Scanner", "Total", "Mean", "Evaluations", "Findings"]);
+        for timing in rows {
+            table.add_row(vec![
+                Cell::new(&timing.id),
+                Cell::new(timing.kind.label()),
+                Cell::new(format_duration(timing.total)),
+                Cell::new(format_duration(timing.mean())),
+                Cell::new(timing.evaluations),
+                Cell::new(timing.findings),
+            ]);
+        }
+        table
+    };
+
+    eprintln!();
+    eprintln!("Slowest rules");
+    eprintln!("{}", table(&mut timings.iter().take(top)));
+
+    let mut noisiest: Vec<&RuleTiming> = timings.iter().filter(|t| t.findings > 0).collect();
+    noisiest.sort_by(|a, b| b.findings.cmp(&a.findings).then_with(|| a.id.cmp(&b.id)));
+    if !noisiest.is_empty() {
+        eprintln!();
+        eprintln!("Noisiest rules");
+        eprintln!("{}", table(&mut noisiest.into_iter().take(top)));
+    }
+}
+
+fn new_timing(id: &str, kind: RuleKind) -> RuleTiming {
+    RuleTiming {
+        id: id.to_string(),
+        kind,
+        total: Duration::ZERO,
+        evaluations: 0,
+        findings: 0,
+    }
+}
+
+/// Supports both the installed layout (`{base}/docker`) and the repository
+/// layout (`{base}/runtime/docker`).
+fn runtime_rules_base(rules: &Path) -> PathBuf {
+    let nested = rules.join("runtime");
+    if nested.join("docker").exists() {
+        nested
+    } else {
+        rules.to_path_buf()
+    }
+}
+
+/// Whether `path` is named like a Dockerfile (`Dockerfile*` or `*.Dockerfile`).
+pub(crate) fn is_dockerfile(path: &Path) -> bool {
+    let name = path.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+    name.starts_with("Dockerfile") || name.to_ascii_lowercase().ends_with(".dockerfile")
+}
+
+fn load_fixtures(dir: &Path) -> Result<(Vec<Value>, Vec<DockerfileFixture>)> {
+    if !dir.is_dir() {
+        return Err(anyhow!("Fixtures directory {} does not exist", dir.display()));
+    }
+
+    let mut inspects = Vec::new();
+    let mut dockerfiles = Vec::new();
+
+    for entry in WalkDir::new(dir).into_iter().filter_map(Result::ok) {
+        let path = entry.path();
+        if !entry.file_type().is_file() {
+            continue;
+        }
+
+        if path.extension().and_then(|e| e.to_str()) == Some("json") {
+            let raw = fs::read_to_string(path)
+                .with_context(|| format!("reading {}", path.display()))?;
+            let value: Value = serde_json::from_str(&raw)
+                .with_context(|| format!("parsing {}", path.display()))?;
+            if value.is_object() {
+                inspects.push(value);
+            }
+        } else if is_dockerfile(path) {
+            let content = fs::read_to_string(path)
+                .with_context(|| format!("reading {}", path.display()))?;
+            let dockerfile = Dockerfile::parse(&content)
+                .map_err(|e| anyhow!("Error parsing {}: {:?}", path.display(), e))?;
+            dockerfiles.push(DockerfileFixture {
+                path: path.to_path_buf(),
+                content,
+                dockerfile,
+            });
+        }
+    }
+
+    Ok((inspects, dockerfiles))
+}
+
+#[cfg(feature = "cli")]
+fn format_duration(duration: Duration) -> String {
+    let micros = duration.as_micros();
+    if micros >= 1_000_000 {
+        format!("{:.2}s", duration.as_secs_f64())
+    } else if micros >= 1_000 {
+        format!("{:.2}ms", micros as f64 / 1_000.0)
+    } else {
+        format!("{}µs", micros)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn run_bench_times_repository_rules() {
+        let report = run_bench(Path::new("rules"), Path::new("tests"), 1).unwrap();
+
+        assert!(report.inspect_fixtures > 0);
+        assert!(report.dockerfile_fixtures > 0);
+        assert!(report.timings.iter().any(|t| t.kind == RuleKind::Runtime));
+        assert!(report.timings.iter().any(|t| t.kind == RuleKind::Dockerfile));
+        assert!(report.timings.windows(2).all(|w| w[0].total >= w[1].total));
+    }
+
+    #[test]
+    fn rule_profile_accumulates_per_rule() {
+        let profile = RuleProfile::default();
+        profile.record("root_user", RuleKind::Runtime, Duration::from_micros(10), 1);
+        profile.record("privileged_mode", RuleKind::Runtime, Duration::from_micros(5), 0);
+        profile.record("root_user", RuleKind::Runtime, Duration::from_micros(10), 1);
+        profile.record("root_user", RuleKind::Dockerfile, Duration::from_micros(1), 0);
+
+        let timings = profile.timings();
+        let ids: Vec<(&str, RuleKind)> = timings.iter().map(|t| (t.id.as_str(), t.kind)).collect();
+        assert_eq!(
+            ids,
+            [
+                ("root_user", RuleKind::Runtime),
+                ("privileged_mode", RuleKind::Runtime),
+                ("root_user", RuleKind::Dockerfile),
+            ]
+        );
+        assert_eq!(timings[0].evaluations, 2);
+        assert_eq!(timings[0].findings, 2);
+        assert_eq!(timings[0].mean(), Duration::from_micros(10));
+    }
+
+    #[cfg(feature = "cli")]
+    #[test]
+    fn format_duration_picks_unit() {
+        assert_eq!(format_duration(Duration::from_micros(15)), "15µs");
+        assert_eq!(format_duration(Duration::from_micros(2500)), "2.50ms");
+        assert_eq!(format_duration(Duration::from_millis(1500)), "1.50s");
+    }
+}
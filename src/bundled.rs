@@ -0,0 +1,113 @@
+//! Rule metadata compiled into the binary.
+//!
+//! Rule packs are downloaded separately (see
+//! [`DEFAULT_RULES_RELEASE_URL`](crate::config::DEFAULT_RULES_RELEASE_URL)),
+//! and packs published before policy bundles and compliance mappings existed
+//! ship neither. The policies and mappings of the repository's `rules/`
+//! directory are bundled here so `--policy` and `valeris report compliance`
+//! work with any pack: a rules directory without a `policies.yaml` uses the bundled
+//! one, and a runtime rule without a `compliance` mapping gets the mapping of
+//! the bundled rule with the same ID.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::OnceLock;
+
+use serde::Deserialize;
+
+/// `policies.yaml` of the bundled runtime rules
+pub const RUNTIME_POLICIES: &str = include_str!("../rules/runtime/policies.yaml");
+
+/// `policies.yaml` of the bundled Dockerfile rules
+pub const DOCKERFILE_POLICIES: &str = include_str!("../rules/dockerfile/policies.yaml");
+
+/// The bundled runtime rules, one YAML document each
+const RUNTIME_RULES: &[&str] = &[
+    include_str!("../rules/runtime/docker/apparmor.yaml"),
+    include_str!("../rules/runtime/docker/capabilities.yaml"),
+    include_str!("../rules/runtime/docker/cgroup_parent.yaml"),
+    include_str!("../rules/runtime/docker/device_access.yaml"),
+    include_str!("../rules/runtime/docker/dns_settings.yaml"),
+    include_str!("../rules/runtime/docker/exposed_ports.yaml"),
+    include_str!("../rules/runtime/docker/extra_hosts.yaml"),
+    include_str!("../rules/runtime/docker/healthcheck.yaml"),
+    include_str!("../rules/runtime/docker/image_no_digest.yaml"),
+    include_str!("../rules/runtime/docker/image_tag.yaml"),
+    include_str!("../rules/runtime/docker/ipc_mode.yaml"),
+    include_str!("../rules/runtime/docker/log_driver.yaml"),
+    include_str!("../rules/runtime/docker/log_size_limit.yaml"),
+    include_str!("../rules/runtime/docker/mount_propagation.yaml"),
+    include_str!("../rules/runtime/docker/network_links.yaml"),
+    include_str!("../rules/runtime/docker/network_mode.yaml"),
+    include_str!("../rules/runtime/docker/no_new_privileges.yaml"),
+    include_str!("../rules/runtime/docker/outdated_image.yaml"),
+    include_str!("../rules/runtime/docker/pid_limits.yaml"),
+    include_str!("../rules/runtime/docker/pid_mode.yaml"),
+    include_str!("../rules/runtime/docker/port_binding.yaml"),
+    include_str!("../rules/runtime/docker/privileged_mode.yaml"),
+    include_str!("../rules/runtime/docker/readonly_rootfs.yaml"),
+    include_str!("../rules/runtime/docker/resource_cpu_limit.yaml"),
+    include_str!("../rules/runtime/docker/resource_memory_limit.yaml"),
+    include_str!("../rules/runtime/docker/restart_policy.yaml"),
+    include_str!("../rules/runtime/docker/root_user.yaml"),
+    include_str!("../rules/runtime/docker/seccomp.yaml"),
+    include_str!("../rules/runtime/docker/secrets_in_env.yaml"),
+    include_str!("../rules/runtime/docker/security_options.yaml"),
+    include_str!("../rules/runtime/docker/sensitive_mounts.yaml"),
+    include_str!("../rules/runtime/docker/sysctls.yaml"),
+    include_str!("../rules/runtime/docker/tmpfs_noexec.yaml"),
+    include_str!("../rules/runtime/docker/user_namespace.yaml"),
+    include_str!("../rules/runtime/docker/uts_mode.yaml"),
+    include_str!("../rules/runtime/docker/writable_mounts.yaml"),
+];
+
+/// The parts of a bundled rule other packs may lack
+#[derive(Deserialize)]
+struct BundledRule {
+    id: String,
+    #[serde(default)]
+    compliance: BTreeMap<String, Vec<String>>,
+}
+
+/// Compliance mapping of the bundled runtime rule `id` (case-insensitive),
+/// if it has one.
+pub fn runtime_compliance(id: &str) -> Option<&'static BTreeMap<String, Vec<String>>> {
+    static MAPPINGS: OnceLock<HashMap<String, BTreeMap<String, Vec<String>>>> = OnceLock::new();
+    MAPPINGS
+        .get_or_init(|| {
+            RUNTIME_RULES
+                .iter()
+                .filter_map(|yaml| serde_yml::from_str::<BundledRule>(yaml).ok())
+                .filter(|rule| !rule.compliance.is_empty())
+                .map(|rule| (rule.id.to_lowercase(), rule.compliance))
+                .collect()
+        })
+        .get(&id.to_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundled_policies_parse() {
+        for yaml in [RUNTIME_POLICIES, DOCKERFILE_POLICIES] {
+            assert!(serde_yml::from_str::<crate::policy::PolicyFile>(yaml).is_ok());
+        }
+    }
+
+    #[test]
+    fn every_runtime_rule_is_bundled() {
+        let files = std::fs::read_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/rules/runtime/docker")).unwrap().count();
+        assert_eq!(RUNTIME_RULES.len(), files);
+        for yaml in RUNTIME_RULES {
+            serde_yml::from_str::<BundledRule>(yaml).unwrap();
+        }
+    }
+
+    #[test]
+    fn finds_mappings_by_rule_id() {
+        assert!(runtime_compliance("Privileged_Mode").unwrap().contains_key("nist-800-190"));
+        assert!(runtime_compliance("no_healthcheck").is_none());
+        assert!(runtime_compliance("not_a_rule").is_none());
+    }
+}
@@ -1,4 +1,12 @@
+//! Command-line arguments.
+//!
+//! The value enums ([`SeverityLevel`], [`OutputFormat`], ...) are part of the
+//! library API and always available; the argument parser (`Cli` and its
+//! subcommands) requires the `cli` feature.
+
+#[cfg(feature = "cli")]
 use clap::{Parser, Subcommand, ValueEnum};
+#[cfg(feature = "cli")]
 use std::path::PathBuf;
 
 /// Valeris - Security Scanner for Container Runtime and Build-time Misconfigurations
@@ -23,6 +31,7 @@ use std::path::PathBuf;
 ///
 ///   # List all available detection rules
 ///   valeris list-plugins
+#[cfg(feature = "cli")]
 #[derive(Parser)]
 #[command(
     name = "valeris",
@@ -35,10 +44,27 @@ use std::path::PathBuf;
 pub struct Cli {
     #[command(subcommand)]
     pub command: Commands,
+
+    #[arg(
+        long,
+        global = true,
+        value_enum,
+        default_value = "text",
+        help = "How operational errors are written to stderr (json: one object with a stable error code)"
+    )]
+    pub error_format: ErrorFormat,
+
+    #[arg(
+        long,
+        global = true,
+        help = "Never access the network: fail if rules are missing instead of downloading them, and reject object storage outputs, syslog, and GitHub reports"
+    )]
+    pub offline: bool,
 }
 
 /// Target platform for security scanning
-#[derive(ValueEnum, Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(ValueEnum))]
 pub enum ScanTarget {
     /// Scan Docker containers
     Docker,
@@ -49,7 +75,8 @@ pub enum ScanTarget {
 }
 
 /// Severity levels for filtering findings
-#[derive(ValueEnum, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "cli", derive(ValueEnum))]
 pub enum SeverityLevel {
     /// Informational findings
     Informative,
@@ -59,20 +86,389 @@ pub enum SeverityLevel {
     Medium,
     /// High severity issues
     High,
+    /// Critical issues, such as a direct path to host compromise
+    Critical,
 }
 /// Output format for scan results
-#[derive(ValueEnum, Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(ValueEnum))]
 pub enum OutputFormat {
     /// Human-readable table with colors (default for terminal)
     Table,
     /// JSON format for CI/CD and programmatic parsing
     Json,
+    /// Newline-delimited JSON, one finding per line as containers are scanned
+    Ndjson,
     /// CSV format for spreadsheets and data analysis
     Csv,
+    /// DefectDojo generic findings JSON for vulnerability management import
+    Defectdojo,
+    /// OCSF Security Finding events (class 2001) for SIEM pipelines
+    Ocsf,
+    /// SARIF 2.1.0 log for GitHub Code Scanning and other SARIF consumers
+    Sarif,
+    /// JUnit XML test results for CI test report views
+    Junit,
+    /// Self-contained HTML page with a sortable findings table
+    Html,
+    /// GitLab security report JSON for the security dashboard and MR widgets
+    Gitlab,
+}
+
+/// A `--output` destination, optionally with its own format
+/// (`report.sarif:sarif`).
+#[cfg(feature = "cli")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OutputTarget {
+    /// File path or object storage URL
+    pub path: String,
+    /// Format of this output; `--format` applies when `None`
+    pub format: Option<OutputFormat>,
+}
+
+#[cfg(feature = "cli")]
+impl OutputTarget {
+    /// Format of this output, falling back to `default` (the `--format` value).
+    pub fn format_or<'a>(&'a self, default: &'a OutputFormat) -> &'a OutputFormat {
+        self.format.as_ref().unwrap_or(default)
+    }
+}
+
+#[cfg(feature = "cli")]
+impl std::str::FromStr for OutputTarget {
+    type Err = String;
+
+    /// Splits `PATH:FORMAT`. The text after the last `:` is only taken as a
+    /// format if it names one, so URLs such as `s3://bucket/report.json`
+    /// stay whole.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if let Some((path, format)) = value.rsplit_once(':') {
+            if let Ok(format) = <OutputFormat as ValueEnum>::from_str(format, true) {
+                if path.is_empty() {
+                    return Err(format!("'{}' has no path before the format", value));
+                }
+                return Ok(Self {
+                    path: path.to_string(),
+                    format: Some(format),
+                });
+            }
+        }
+        Ok(Self {
+            path: value.to_string(),
+            format: None,
+        })
+    }
+}
+
+/// Output format for generated rule documentation
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(ValueEnum))]
+pub enum RuleDocsFormat {
+    /// Markdown page with one section per rule
+    Markdown,
+}
+
+/// How operational errors (Docker unreachable, rules missing, bad
+/// configuration, ...) are written to stderr
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(ValueEnum))]
+pub enum ErrorFormat {
+    /// `Error: <message>: <cause>` line
+    #[default]
+    Text,
+    /// One JSON object with a stable error code (see [`crate::error::ErrorReport`])
+    Json,
+}
+
+impl ErrorFormat {
+    /// Finds `--error-format` in raw arguments.
+    ///
+    /// The binary needs the format even when the command fails before or
+    /// while its arguments are parsed, so this does not go through clap.
+    /// Unknown values fall back to [`ErrorFormat::Text`].
+    pub fn from_args<T: AsRef<std::ffi::OsStr>>(args: &[T]) -> Self {
+        let mut format = Self::Text;
+        let mut args = args.iter().map(|a| a.as_ref().to_string_lossy());
+        while let Some(arg) = args.next() {
+            if arg == "--" {
+                break;
+            }
+            let value = match arg.strip_prefix("--error-format") {
+                Some("") => args.next(),
+                Some(rest) => rest.strip_prefix('=').map(|v| v.to_string().into()),
+                None => continue,
+            };
+            format = match value.as_deref() {
+                Some("json") => Self::Json,
+                _ => Self::Text,
+            };
+        }
+        format
+    }
+}
+
+/// Git hook installed by `valeris hook install`
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(ValueEnum))]
+pub enum HookType {
+    /// Scan staged Dockerfiles before each commit
+    PreCommit,
+    /// Scan every tracked Dockerfile before each push
+    PrePush,
+}
+
+impl HookType {
+    /// File name of the hook under `.git/hooks`
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            HookType::PreCommit => "pre-commit",
+            HookType::PrePush => "pre-push",
+        }
+    }
+}
+
+/// Subcommands for working with the rule bundle
+#[cfg(feature = "cli")]
+#[derive(Subcommand)]
+pub enum RulesCommand {
+    /// Generate documentation for every loaded rule
+    ///
+    /// Renders each rule's metadata (description, severity, remediation,
+    /// references, examples) so published docs match the rule bundle.
+    ///
+    /// Examples:
+    ///   # Print Markdown docs for the installed rules
+    ///   valeris rules docs --format markdown
+    ///
+    ///   # Document the rules in this repository
+    ///   valeris rules docs --runtime-rules ./rules/runtime \
+    ///     --dockerfile-rules ./rules/dockerfile --output docs/rules/reference.md
+    Docs {
+        #[arg(
+            long,
+            short = 'f',
+            value_enum,
+            default_value = "markdown",
+            help = "Documentation format"
+        )]
+        format: RuleDocsFormat,
+
+        #[arg(
+            long,
+            value_name = "PATH",
+            help = "Directory containing runtime rules (defaults to the installed rules)"
+        )]
+        runtime_rules: Option<PathBuf>,
+
+        #[arg(
+            long,
+            value_name = "PATH",
+            help = "Directory containing Dockerfile rules (defaults to the installed rules)"
+        )]
+        dockerfile_rules: Option<PathBuf>,
+
+        #[arg(
+            long,
+            short = 'o',
+            value_name = "FILE",
+            help = "Write documentation to file instead of stdout"
+        )]
+        output: Option<PathBuf>,
+    },
+}
+
+/// Subcommands for the configuration file
+#[cfg(feature = "cli")]
+#[derive(Subcommand)]
+pub enum ConfigCommand {
+    /// Write a commented configuration file
+    ///
+    /// Writes every setting with its documentation, commented out, to the
+    /// XDG location (~/.config/valeris/config.toml) or --path. Settings passed
+    /// as flags are written uncommented. An existing file is only replaced
+    /// with --force.
+    ///
+    /// Examples:
+    ///   valeris config init
+    ///   valeris config init --min-severity medium --fail-on high --format json
+    ///   valeris config init --path ./valeris.toml --force
+    Init {
+        #[arg(
+            long,
+            short = 'p',
+            value_name = "FILE",
+            help = "Where to write the file (default: ~/.config/valeris/config.toml)"
+        )]
+        path: Option<PathBuf>,
+
+        #[arg(long, help = "Replace an existing configuration file")]
+        force: bool,
+
+        #[arg(long, value_delimiter = ',', help = "Container states to scan by default")]
+        state: Option<Vec<String>>,
+
+        #[arg(long, value_delimiter = ',', help = "Detectors to run by default")]
+        only: Option<Vec<String>>,
+
+        #[arg(long, value_delimiter = ',', help = "Detectors to always exclude")]
+        exclude: Option<Vec<String>>,
+
+        #[arg(long, value_enum, help = "Default minimum severity to show")]
+        min_severity: Option<SeverityLevel>,
+
+        #[arg(long, value_enum, help = "Default fail-on threshold")]
+        fail_on: Option<SeverityLevel>,
+
+        #[arg(long, short = 'f', value_enum, help = "Default output format")]
+        format: Option<OutputFormat>,
+
+        #[arg(long, value_name = "LANG", help = "Language of rule messages, e.g. es")]
+        lang: Option<String>,
+    },
+}
+
+/// Subcommands for git hook integration
+#[cfg(feature = "cli")]
+#[derive(Subcommand)]
+pub enum HookCommand {
+    /// Install a git hook that scans Dockerfiles
+    ///
+    /// Writes the hook into the repository's hooks directory (honoring
+    /// core.hooksPath). Existing hooks not written by Valeris are only
+    /// replaced with --force.
+    ///
+    /// Examples:
+    ///   valeris hook install
+    ///   valeris hook install --type pre-push --fail-on medium
+    Install {
+        #[arg(
+            long = "type",
+            short = 't',
+            value_enum,
+            default_value = "pre-commit",
+            help = "Hook to install"
+        )]
+        hook_type: HookType,
+
+        #[arg(
+            long,
+            value_enum,
+            help = "Fail on findings at or above this severity (defaults to the repository config, then high)"
+        )]
+        fail_on: Option<SeverityLevel>,
+
+        #[arg(long, help = "Replace an existing hook not installed by Valeris")]
+        force: bool,
+    },
+
+    /// Scan Dockerfiles for a git hook
+    ///
+    /// Without arguments, scans the staged content of Dockerfiles in the
+    /// index. With file arguments (as passed by the pre-commit framework),
+    /// scans those files. Settings come from .valeris.toml at the repository
+    /// root, falling back to the user configuration.
+    ///
+    /// Examples:
+    ///   valeris hook run
+    ///   valeris hook run --all --fail-on medium
+    ///   valeris hook run docker/Dockerfile
+    Run {
+        #[arg(value_name = "FILE", help = "Files to scan; non-Dockerfiles are ignored")]
+        files: Vec<PathBuf>,
+
+        #[arg(long, conflicts_with = "files", help = "Scan every tracked Dockerfile")]
+        all: bool,
+
+        #[arg(
+            long,
+            value_enum,
+            help = "Fail on findings at or above this severity (defaults to the repository config, then high)"
+        )]
+        fail_on: Option<SeverityLevel>,
+
+        #[arg(
+            long,
+            short = 'r',
+            value_name = "PATH",
+            help = "Directory containing Dockerfile rules (defaults to the installed rules)"
+        )]
+        rules: Option<PathBuf>,
+    },
+}
+
+/// Subcommands for publishing scan reports
+#[cfg(feature = "cli")]
+#[derive(Subcommand)]
+pub enum ReportCommand {
+    /// Comment Dockerfile findings on a GitHub pull request
+    ///
+    /// Posts (or updates) one summary comment and adds inline review
+    /// comments on Dockerfile lines the pull request adds. Reads the token
+    /// from the GITHUB_TOKEN environment variable.
+    ///
+    /// Examples:
+    ///   valeris docker-file --path Dockerfile --format json --output valeris.json
+    ///   valeris report github-pr --repo org/name --pr 123 --report valeris.json
+    #[command(name = "github-pr")]
+    GithubPr {
+        #[arg(long, value_name = "OWNER/NAME", help = "Repository the pull request belongs to")]
+        repo: String,
+
+        #[arg(long, value_name = "NUMBER", help = "Pull request number")]
+        pr: u64,
+
+        #[arg(
+            long,
+            short = 'r',
+            value_name = "FILE",
+            value_delimiter = ',',
+            required = true,
+            help = "Dockerfile JSON report(s) from `valeris docker-file --format json` (comma-separated)"
+        )]
+        report: Vec<PathBuf>,
+
+        #[arg(
+            long,
+            value_name = "URL",
+            default_value = crate::output::github::DEFAULT_API_URL,
+            help = "GitHub API URL (for GitHub Enterprise Server)"
+        )]
+        api_url: String,
+    },
+
+    /// Group report findings by compliance control
+    ///
+    /// Reads JSON reports from `scan` or `docker-file` and lists their
+    /// findings under the framework controls their rules map to.
+    ///
+    /// Examples:
+    ///   valeris scan --format json --output scan.json
+    ///   valeris report compliance --framework nist-800-190 --report scan.json
+    Compliance {
+        #[arg(
+            long,
+            value_name = "FRAMEWORK",
+            default_value = crate::output::compliance::NIST_800_190,
+            help = "Framework to group by (e.g. nist-800-190)"
+        )]
+        framework: String,
+
+        #[arg(
+            long,
+            short = 'r',
+            value_name = "FILE",
+            value_delimiter = ',',
+            required = true,
+            help = "JSON report(s) from `valeris scan` or `valeris docker-file` (comma-separated)"
+        )]
+        report: Vec<PathBuf>,
+    },
 }
 
+#[cfg(feature = "cli")]
 #[derive(Subcommand)]
 pub enum Commands {
+
     /// Scan running containers for security misconfigurations
     ///
     /// Analyzes running Docker containers against YAML-defined security rules.
@@ -89,6 +485,9 @@ pub enum Commands {
     ///   # Exclude certain detectors
     ///   valeris scan --exclude readonly_rootfs
     ///
+    ///   # Run a predefined policy bundle
+    ///   valeris scan --policy cis-docker
+    ///
     ///   # Filter by container state
     ///   valeris scan --state running
     ///
@@ -113,6 +512,7 @@ pub enum Commands {
     ///   # Export to CSV for analysis
     ///   valeris scan --format csv --output report.csv
     #[command(visible_alias = "s")]
+    #[command(group(clap::ArgGroup::new("fail_gate").args(["fail_on", "fail_score"]).multiple(true)))]
     Scan {
         // Target Selection
         #[arg(
@@ -150,6 +550,19 @@ pub enum Commands {
         )]
         exclude: Option<Vec<String>>,
 
+        #[arg(
+            long,
+            value_name = "NAME",
+            help = "Run a policy bundle's rules and severity profile",
+            long_help = "Run the rule subset and severity profile of a policy defined in the \
+                        rule bundle's policies.yaml. --exclude still applies, and --severity or \
+                        --min-severity override the policy's minimum severity.\n\n\
+                        Bundled policies: baseline, strict, cis-docker, nist-800-190\n\n\
+                        Example: --policy baseline",
+            conflicts_with = "only"
+        )]
+        policy: Option<String>,
+
         // Container Filtering
         #[arg(
             long,
@@ -183,6 +596,146 @@ pub enum Commands {
         )]
         container: Option<Vec<String>>,
 
+        #[arg(
+            long,
+            value_name = "SINCE",
+            help = "Only scan containers created or restarted since a time (e.g. 2h, 1d, last)",
+            long_help = "Only scan containers that were created or (re)started since the given time.\n\
+                        Accepts:\n\
+                        - A duration relative to now: 30m, 2h, 1d\n\
+                        - An RFC 3339 timestamp: 2024-05-01T12:00:00Z\n\
+                        - 'last': the start time of the previous scan\n\n\
+                        Examples:\n  \
+                        --since 1h                      # Containers changed in the last hour\n  \
+                        --since last                    # Containers changed since the last scan"
+        )]
+        since: Option<String>,
+
+        #[arg(
+            long,
+            short = 'i',
+            value_name = "FILE",
+            help = "Scan pre-collected `docker inspect` JSON instead of the Docker daemon",
+            long_help = "Scan containers from a file of `docker inspect` output instead of \
+                        querying the Docker daemon, e.g. forensic captures or output collected \
+                        on another host. The file may contain a single inspect object or the \
+                        array printed by `docker inspect`. State, container, and --since filters \
+                        still apply.\n\n\
+                        Example:\n  \
+                        docker inspect $(docker ps -aq) > inspect.json\n  \
+                        valeris scan --input inspect.json"
+        )]
+        input: Option<PathBuf>,
+
+        #[arg(
+            long,
+            value_name = "PATH",
+            conflicts_with_all = ["input", "hosts"],
+            help = "Scan Podman Quadlet or systemd units instead of the Docker daemon",
+            long_help = "Scan the containers defined by Podman units instead of querying the \
+                        Docker daemon: Quadlet .container and .kube files, and .service units \
+                        from `podman generate systemd`. PATH is a unit file or a directory \
+                        searched recursively. Unit settings are mapped onto `docker inspect` \
+                        fields, so the runtime rules and filters apply unchanged.\n\n\
+                        Example:\n  \
+                        valeris scan --quadlet /etc/containers/systemd"
+        )]
+        quadlet: Option<PathBuf>,
+
+        #[arg(
+            long,
+            value_name = "FILE",
+            conflicts_with = "input",
+            help = "Scan every Docker host listed in a TOML hosts file",
+            long_help = "Scan the Docker daemons listed in a TOML hosts file concurrently and \
+                        merge their containers into one report, each tagged with its host. \
+                        Each [[hosts]] entry has a `url` (ssh://[user@]host[:port], \
+                        tcp://host:port or unix://path) and an optional `name` used as the \
+                        label. ssh:// hosts are reached through the system `ssh` client. \
+                        Unreachable hosts are skipped with a warning.\n\n\
+                        Example:\n  \
+                        valeris scan --hosts hosts.toml --format json -o fleet.json"
+        )]
+        hosts: Option<PathBuf>,
+
+        #[arg(
+            long,
+            value_name = "PATH",
+            help = "Also scan a Dockerfile in the same run (repeatable)",
+            long_help = "Scan a Dockerfile with the Dockerfile rules in the same run as the \
+                        containers. Its findings go into the same report, and severity filters \
+                        and --fail-on are evaluated once across every target. --only, --exclude \
+                        and --policy select runtime rules only. Can be given several times.\n\n\
+                        Example:\n  \
+                        valeris scan --dockerfile ./Dockerfile --fail-on high"
+        )]
+        dockerfile: Vec<PathBuf>,
+
+        #[arg(
+            long,
+            value_name = "FILE",
+            help = "Also scan the Dockerfiles and env files a Compose file references (repeatable)",
+            long_help = "Scan, in the same run as the containers, the Dockerfiles the services \
+                        of a Compose file build (`build:`) and the env files they load \
+                        (`env_file:` and the project .env). Remote build contexts and missing \
+                        files are skipped. Can be given several times.\n\n\
+                        Example:\n  \
+                        valeris scan --compose docker-compose.yml --format json -o report.json"
+        )]
+        compose: Vec<PathBuf>,
+
+        #[arg(
+            long,
+            value_name = "DIR",
+            help = "Dockerfile rules for --dockerfile and --compose (default: installed rule bundle)"
+        )]
+        dockerfile_rules: Option<PathBuf>,
+
+        #[arg(
+            long,
+            conflicts_with_all = ["input", "quadlet", "hosts"],
+            help = "Probe running containers with `docker exec` to verify their privileges",
+            long_help = "Run a small read-only set of commands inside each running container \
+                        with `docker exec` (id -u, capsh --print or /proc/self/status, and \
+                        /proc/self/mounts) and report where the effective user, capabilities \
+                        or mount flags grant more than `docker inspect` shows. Images without \
+                        these commands are skipped. Probe findings are probe_user_mismatch, \
+                        probe_capability_mismatch, probe_rootfs_writable and \
+                        probe_mount_writable, and work with --only/--exclude.\n\n\
+                        Example:\n  \
+                        valeris scan --probe --state running"
+        )]
+        probe: bool,
+
+        #[arg(
+            long,
+            conflicts_with_all = ["input", "quadlet", "hosts"],
+            help = "Compare memory limits with actual usage from the Docker stats API",
+            long_help = "Read one Docker stats sample for each running container and report \
+                        memory limits far out of line with usage: no limit while 8 GiB or \
+                        more is resident (usage_memory_unbounded), or a limit of 1 GiB or \
+                        more that is at least 100 times the resident memory \
+                        (usage_memory_limit_oversized). Findings work with --only/--exclude.\n\n\
+                        Example:\n  \
+                        valeris scan --usage --state running"
+        )]
+        usage: bool,
+
+        #[arg(
+            long,
+            conflicts_with_all = ["input", "quadlet", "hosts"],
+            help = "Check container filesystems against their images with docker diff",
+            long_help = "Read `docker diff` for each container and report changes that point to \
+                        tampering or mutable-infrastructure drift: account, sudo, cron, and \
+                        loader files changed (drift_sensitive_file_changed), binaries and \
+                        libraries changed in system directories (drift_system_path_changed), \
+                        and new setuid/setgid files (drift_setuid_file_added). Findings work \
+                        with --only/--exclude.\n\n\
+                        Example:\n  \
+                        valeris scan --drift"
+        )]
+        drift: bool,
+
         // Severity Filtering
         #[arg(
             long,
@@ -191,7 +744,7 @@ pub enum Commands {
             help = "Filter findings by severity (comma-separated)",
             long_help = "Show only findings with specified severity levels. Multiple levels \
                         can be specified as a comma-separated list.\n\n\
-                        Available levels (in order): informative, low, medium, high\n\n\
+                        Available levels (in order): informative, low, medium, high, critical\n\n\
                         Examples:\n  \
                         --severity high                    # Only high severity\n  \
                         --severity medium,high             # Medium and high\n  \
@@ -205,7 +758,7 @@ pub enum Commands {
             help = "Show only findings at or above this severity",
             long_help = "Filter findings to show only those at or above the specified minimum \
                         severity level. This is a shorthand for specifying multiple severities.\n\n\
-                        Available levels: informative, low, medium, high\n\n\
+                        Available levels: informative, low, medium, high, critical\n\n\
                         Examples:\n  \
                         --min-severity medium    # Show medium and high\n  \
                         --min-severity high      # Show only high",
@@ -220,7 +773,7 @@ pub enum Commands {
             help = "Exit with error code 1 if findings at or above this level exist",
             long_help = "Causes valeris to exit with code 1 if any findings at or above the \
                         specified severity level are found. Useful for CI/CD pipelines.\n\n\
-                        Available levels: informative, low, medium, high\n\n\
+                        Available levels: informative, low, medium, high, critical\n\n\
                         Examples:\n  \
                         --fail-on high       # Fail on high severity findings\n  \
                         --fail-on medium     # Fail on medium or high findings\n  \
@@ -230,11 +783,26 @@ pub enum Commands {
 
         #[arg(
             long,
-            help = "Suppress all output, only set exit code (implies --fail-on)",
-            long_help = "Run in quiet mode with no output. Useful for CI/CD where you only \
-                        care about the exit code. This flag requires --fail-on to be set.\n\n\
-                        Example: valeris scan --quiet --fail-on high",
-            requires = "fail_on"
+            value_name = "POINTS",
+            help = "Exit with error code 1 if any target's risk points exceed this value",
+            long_help = "Causes valeris to exit with code 1 if any target's risk points (the summed \
+                        finding weights from [output.score_weights] and [output.tag_weights]) \
+                        exceed the given value. Combines with --fail-on: either one fails the scan.\n\n\
+                        With the default weights (high 20, medium 10, low 3), --fail-score 25 fails \
+                        on three medium findings as it does on a high and a low one."
+        )]
+        fail_score: Option<u32>,
+
+        #[arg(
+            long,
+            help = "Suppress console output, only set exit code (requires --fail-on or --fail-score)",
+            long_help = "Run in quiet mode with no console output. Useful for CI/CD where you only \
+                        care about the exit code. This flag requires --fail-on or --fail-score to be set.\n\
+                        Files requested with --output are still written.\n\n\
+                        Examples:\n  \
+                        valeris scan --quiet --fail-on high\n  \
+                        valeris scan --quiet --fail-on high --format json --output report.json",
+            requires = "fail_gate"
         )]
         quiet: bool,
 
@@ -243,24 +811,154 @@ pub enum Commands {
             long,
             short = 'f',
             value_enum,
-            default_value = "json",
-            requires = "output",
-            help = "Output format (requires --output)"
+            help = "Output format (default: table on a terminal, json otherwise)",
+            long_help = "Format of the report. Without --output the report is written to stdout, \
+                        as a table when stdout is a terminal and as JSON when it is piped or \
+                        redirected. --output files default to JSON.\n\n\
+                        Example: valeris scan --format json | jq '.containers[].findings'"
         )]
-        format: OutputFormat,
+        format: Option<OutputFormat>,
 
         #[arg(
             long,
             short = 'o',
-            value_name = "FILE",
-            help = "Write results to file instead of stdout",
+            value_name = "FILE[:FORMAT]",
+            help = "Write results to file instead of stdout (repeatable, FILE:FORMAT to set the format per file)",
             long_help = "Write scan results to the specified file instead of stdout.\n\
-                        The format is determined by the --format flag.\n\n\
+                        The format is determined by the --format flag, or by a `:FORMAT` suffix \
+                        for that file. Repeat the option to write several reports from one scan. \
+                        Object storage URLs (s3://, gcs://, az://) upload the report using the \
+                        provider's standard credential environment variables.\n\n\
                         Examples:\n  \
                         --output findings.json\n  \
-                        --output report.csv"
+                        --output report.csv\n  \
+                        --output report.json:json --output report.sarif:sarif --output report.html:html\n  \
+                        --output s3://security-reports/host-1/report.json"
         )]
-        output: Option<String>,
+        output: Vec<OutputTarget>,
+
+        #[arg(
+            long,
+            value_name = "FILE",
+            requires = "output",
+            conflicts_with = "format",
+            help = "Render --output files with a Handlebars report template instead of --format",
+            long_help = "Render the report with a Handlebars template, for branded or tool-specific \
+                        layouts. Every --output without a :FORMAT suffix is rendered with the \
+                        template. Templates see `metadata`, `summary`, `failed`, `targets` (each \
+                        with its `findings`) and a flat `findings` list; values are HTML-escaped \
+                        for .html/.htm templates.\n\n\
+                        Example: valeris scan --template audit.md.hbs --output audit.md"
+        )]
+        template: Option<PathBuf>,
+
+        #[arg(
+            long,
+            value_name = "LANG",
+            help = "Report rule messages in this language (e.g. es)",
+            long_help = "Report rule names, messages and remediation from the LANG message \
+                        catalog of the rules directory (i18n/LANG.yaml). Rule IDs are unchanged; \
+                        rules the catalog does not cover keep their English text. Overrides \
+                        [output] lang in the configuration file.\n\n\
+                        Example: --lang es"
+        )]
+        lang: Option<String>,
+
+        #[arg(
+            long,
+            help = "Replace container names and IDs, images and paths with pseudonyms",
+            long_help = "Replace container names and IDs, image names, host labels and file paths \
+                        in the console output and report with salted hashes, consistent within \
+                        the report, so it can be shared without exposing internal naming. \
+                        Findings history, syslog and attestations keep the real values."
+        )]
+        anonymize: bool,
+
+        #[arg(
+            long,
+            help = "Show secret values in findings instead of masking them",
+            long_help = "Secret findings mask the values they quote by default, e.g. \
+                        DB_PASSWORD=hunter2 becomes DB_PASSWORD=****, in descriptions, tables, \
+                        matched values and every report, syslog message and history record. \
+                        This flag shows the values for one run, like redact_secrets = false \
+                        under [output] in the configuration file."
+        )]
+        no_redact_secrets: bool,
+
+        #[arg(
+            long,
+            help = "Show the fields and values each finding matched",
+            long_help = "Record, for each finding, the JSONPath (container rules) or instruction \
+                        field (Dockerfile rules) the rule matched and the value found there. \
+                        Printed under each finding in table output and written as a `debug` \
+                        list in JSON reports, to speed up false-positive triage."
+        )]
+        explain: bool,
+
+        #[arg(
+            long,
+            help = "Print the slowest and noisiest rules after the scan",
+            long_help = "Record each rule's evaluation time and findings during the scan and \
+                        print the 10 slowest rules and the 10 rules with the most findings to \
+                        stderr when it ends, so stdout still holds only the report. Use it to \
+                        pick rules to exclude or to find rules worth optimizing."
+        )]
+        profile_rules: bool,
+
+        #[arg(
+            long,
+            value_name = "URL",
+            help = "Also send each finding to a syslog endpoint (RFC 5424)",
+            long_help = "Send one RFC 5424 message per finding to a syslog endpoint, in addition \
+                        to the console or --output report. Finding details are sent as \
+                        structured data.\n\n\
+                        Endpoints:\n  \
+                        udp://host[:port]     # Remote syslog over UDP (default port 514)\n  \
+                        tcp://host[:port]     # Remote syslog over TCP (default port 601)\n  \
+                        unix:///dev/log       # Local syslog daemon"
+        )]
+        syslog: Option<String>,
+
+        #[arg(
+            long,
+            value_name = "PATH",
+            help = "Record this scan's findings in a local history file",
+            long_help = "Append this scan's targets and finding fingerprints to a local findings \
+                        history (JSON Lines, one scan per line), for comparing scans over time. \
+                        The file is created if it does not exist.\n\n\
+                        Example: --store ~/.local/share/valeris/history.jsonl"
+        )]
+        store: Option<PathBuf>,
+
+        #[arg(
+            long,
+            value_name = "PATH",
+            requires = "attest_key",
+            help = "Write a signed in-toto attestation of this scan",
+            long_help = "Write an in-toto statement of this scan (scanned images, rule bundle \
+                        digest, summary and report digest) in a DSSE envelope signed with \
+                        --attest-key, for deployment gates to verify.\n\n\
+                        Example: --attest scan.intoto.json --attest-key valeris-attest.pem"
+        )]
+        attest: Option<PathBuf>,
+
+        #[arg(
+            long,
+            value_name = "PATH",
+            requires = "attest",
+            help = "PKCS#8 PEM private key (ECDSA P-256 or Ed25519) to sign --attest with"
+        )]
+        attest_key: Option<PathBuf>,
+
+        #[arg(
+            long,
+            help = "Print the JSON Schema of JSON reports and exit",
+            long_help = "Print the JSON Schema describing the reports written with --format json \
+                        (by scan, docker-file and merge) and exit without scanning. Reports \
+                        carry the schema_version they follow.\n\n\
+                        Example: valeris scan --print-schema > valeris-report.schema.json"
+        )]
+        print_schema: bool,
     },
 
     /// Scan Dockerfiles for build-time security issues
@@ -282,6 +980,9 @@ pub enum Commands {
     ///   # Run only specific detectors
     ///   valeris docker-file -p ./Dockerfile -r ./rules/dockerfile --only DF001,DF006
     ///
+    ///   # Run a predefined policy bundle
+    ///   valeris docker-file -p ./Dockerfile -r ./rules/dockerfile --policy baseline
+    ///
     ///   # Export findings as JSON
     ///   valeris docker-file --path ./Dockerfile --rules ./rules/dockerfile \
     ///     --format json --output dockerfile-findings.json
@@ -289,6 +990,7 @@ pub enum Commands {
     ///   # Human-readable table output
     ///   valeris docker-file --path ./Dockerfile --rules ./rules/dockerfile --format table
     #[command(visible_alias = "df")]
+    #[command(group(clap::ArgGroup::new("fail_gate").args(["fail_on", "fail_score"]).multiple(true)))]
     DockerFile {
         #[arg(
             long,
@@ -337,6 +1039,19 @@ pub enum Commands {
         )]
         exclude: Option<Vec<String>>,
 
+        #[arg(
+            long,
+            value_name = "NAME",
+            help = "Run a policy bundle's rules and severity profile",
+            long_help = "Run the rule subset and severity profile of a policy defined in \
+                        policies.yaml in the --rules directory. --exclude still applies, and \
+                        --severity or --min-severity override the policy's minimum severity.\n\n\
+                        Bundled policies: baseline, strict, cis-docker, nist-800-190\n\n\
+                        Example: --policy cis-docker",
+            conflicts_with = "only"
+        )]
+        policy: Option<String>,
+
         // Severity Filtering
         #[arg(
             long,
@@ -345,7 +1060,7 @@ pub enum Commands {
             help = "Filter findings by severity (comma-separated)",
             long_help = "Show only findings with specified severity levels. Multiple levels \
                         can be specified as a comma-separated list.\n\n\
-                        Available levels (in order): informative, low, medium, high\n\n\
+                        Available levels (in order): informative, low, medium, high, critical\n\n\
                         Examples:\n  \
                         --severity high                    # Only high severity\n  \
                         --severity medium,high             # Medium and high",
@@ -359,7 +1074,7 @@ pub enum Commands {
             help = "Show only findings at or above this severity",
             long_help = "Filter findings to show only those at or above the specified minimum \
                         severity level.\n\n\
-                        Available levels: informative, low, medium, high\n\n\
+                        Available levels: informative, low, medium, high, critical\n\n\
                         Examples:\n  \
                         --min-severity medium    # Show medium and high\n  \
                         --min-severity high      # Show only high"
@@ -373,7 +1088,7 @@ pub enum Commands {
             help = "Exit with error code 1 if findings at or above this level exist",
             long_help = "Causes valeris to exit with code 1 if any findings at or above the \
                         specified severity level are found. Useful for CI/CD pipelines.\n\n\
-                        Available levels: informative, low, medium, high\n\n\
+                        Available levels: informative, low, medium, high, critical\n\n\
                         Examples:\n  \
                         --fail-on high       # Fail on high severity findings\n  \
                         --fail-on medium     # Fail on medium or high findings"
@@ -382,11 +1097,24 @@ pub enum Commands {
 
         #[arg(
             long,
-            help = "Suppress all output, only set exit code (implies --fail-on)",
-            long_help = "Run in quiet mode with no output. Useful for CI/CD where you only \
-                        care about the exit code. This flag requires --fail-on to be set.\n\n\
+            value_name = "POINTS",
+            help = "Exit with error code 1 if any target's risk points exceed this value",
+            long_help = "Causes valeris to exit with code 1 if any target's risk points (the summed \
+                        finding weights from [output.score_weights] and [output.tag_weights]) \
+                        exceed the given value. Combines with --fail-on: either one fails the scan.\n\n\
+                        With the default weights (high 20, medium 10, low 3), --fail-score 25 fails \
+                        on three medium findings as it does on a high and a low one."
+        )]
+        fail_score: Option<u32>,
+
+        #[arg(
+            long,
+            help = "Suppress console output, only set exit code (requires --fail-on or --fail-score)",
+            long_help = "Run in quiet mode with no console output. Useful for CI/CD where you only \
+                        care about the exit code. This flag requires --fail-on or --fail-score to be set.\n\
+                        Files requested with --output are still written.\n\n\
                         Example: valeris docker-file -p ./Dockerfile -r ./rules/dockerfile --quiet --fail-on high",
-            requires = "fail_on"
+            requires = "fail_gate"
         )]
         quiet: bool,
 
@@ -403,14 +1131,276 @@ pub enum Commands {
         #[arg(
             long,
             short = 'o',
-            value_name = "FILE",
-            help = "Write results to file instead of stdout",
-            long_help = "Write scan results to the specified file.\n\n\
+            value_name = "FILE[:FORMAT]",
+            help = "Write results to file instead of stdout (repeatable, FILE:FORMAT to set the format per file)",
+            long_help = "Write scan results to the specified file. The format is determined by \
+                        the --format flag, or by a `:FORMAT` suffix for that file. Repeat the \
+                        option to write several reports from one scan.\n\n\
                         Examples:\n  \
                         --output dockerfile-report.json\n  \
-                        --output findings.csv"
+                        --output findings.csv\n  \
+                        --output report.json:json --output report.sarif:sarif"
         )]
-        output: Option<PathBuf>,
+        output: Vec<OutputTarget>,
+
+        #[arg(
+            long,
+            value_name = "LANG",
+            help = "Report rule messages in this language (e.g. es)",
+            long_help = "Report rule names, messages and remediation from the LANG message \
+                        catalog of the rules directory (i18n/LANG.yaml). Overrides [output] lang \
+                        in the configuration file.\n\n\
+                        Example: --lang es"
+        )]
+        lang: Option<String>,
+
+        #[arg(
+            long,
+            help = "Replace container names and IDs, images and paths with pseudonyms",
+            long_help = "Replace container names and IDs, image names, host labels and file paths \
+                        in the console output and report with salted hashes, consistent within \
+                        the report, so it can be shared without exposing internal naming. \
+                        Findings history, syslog and attestations keep the real values."
+        )]
+        anonymize: bool,
+
+        #[arg(
+            long,
+            help = "Show secret values in findings instead of masking them",
+            long_help = "Secret findings mask the values they quote by default, e.g. \
+                        DB_PASSWORD=hunter2 becomes DB_PASSWORD=****, in descriptions, tables, \
+                        matched values and every report, syslog message and history record. \
+                        This flag shows the values for one run, like redact_secrets = false \
+                        under [output] in the configuration file."
+        )]
+        no_redact_secrets: bool,
+
+        #[arg(
+            long,
+            help = "Show the fields and values each finding matched",
+            long_help = "Record, for each finding, the JSONPath (container rules) or instruction \
+                        field (Dockerfile rules) the rule matched and the value found there. \
+                        Printed under each finding in table output and written as a `debug` \
+                        list in JSON reports, to speed up false-positive triage."
+        )]
+        explain: bool,
+
+        #[arg(
+            long,
+            help = "Print the slowest and noisiest rules after the scan",
+            long_help = "Record each rule's evaluation time and findings during the scan and \
+                        print the 10 slowest rules and the 10 rules with the most findings to \
+                        stderr when it ends, so stdout still holds only the report. Use it to \
+                        pick rules to exclude or to find rules worth optimizing."
+        )]
+        profile_rules: bool,
+
+        #[arg(
+            long,
+            value_name = "URL",
+            help = "Also send each finding to a syslog endpoint (RFC 5424)",
+            long_help = "Send one RFC 5424 message per finding to a syslog endpoint \
+                        (udp://host[:port], tcp://host[:port] or unix:///dev/log).\n\n\
+                        Example: --syslog udp://logs.example.com"
+        )]
+        syslog: Option<String>,
+
+        #[arg(
+            long,
+            value_name = "PATH",
+            help = "Record this scan's findings in a local history file"
+        )]
+        store: Option<PathBuf>,
+
+        #[arg(
+            long,
+            value_name = "PATH",
+            requires = "attest_key",
+            help = "Write a signed in-toto attestation of this scan"
+        )]
+        attest: Option<PathBuf>,
+
+        #[arg(
+            long,
+            value_name = "PATH",
+            requires = "attest",
+            help = "PKCS#8 PEM private key (ECDSA P-256 or Ed25519) to sign --attest with"
+        )]
+        attest_key: Option<PathBuf>,
+    },
+
+    /// Scan a .env file for exposed credentials
+    ///
+    /// Flags credential-looking entries, files readable by every user, and
+    /// credentials that Dockerfiles or Compose files in the same directory
+    /// bake into images or inject as environment variables. Values are never
+    /// printed, only their keys.
+    ///
+    /// Examples:
+    ///   # Scan the .env file of a Compose project
+    ///   valeris env-file --path ./.env
+    ///
+    ///   # CI/CD integration - fail on high findings
+    ///   valeris env-file -p ./.env --fail-on high
+    ///
+    ///   # Export findings as JSON
+    ///   valeris env-file -p ./.env --format json --output env-findings.json
+    #[command(group(clap::ArgGroup::new("fail_gate").args(["fail_on", "fail_score"]).multiple(true)))]
+    EnvFile {
+        #[arg(
+            long,
+            short = 'p',
+            value_name = "PATH",
+            help = "Path to the .env file to scan"
+        )]
+        path: PathBuf,
+
+        // Severity Filtering
+        #[arg(
+            long,
+            value_name = "SEVERITIES",
+            value_delimiter = ',',
+            help = "Filter findings by severity (comma-separated)",
+            conflicts_with = "min_severity"
+        )]
+        severity: Option<Vec<SeverityLevel>>,
+
+        #[arg(
+            long,
+            value_name = "LEVEL",
+            help = "Show only findings at or above this severity"
+        )]
+        min_severity: Option<SeverityLevel>,
+
+        // CI/CD Integration
+        #[arg(
+            long,
+            value_name = "LEVEL",
+            help = "Exit with error code 1 if findings at or above this level exist"
+        )]
+        fail_on: Option<SeverityLevel>,
+
+        #[arg(
+            long,
+            value_name = "POINTS",
+            help = "Exit with error code 1 if any target's risk points exceed this value"
+        )]
+        fail_score: Option<u32>,
+
+        #[arg(
+            long,
+            help = "Suppress console output, only set exit code (requires --fail-on or --fail-score)",
+            requires = "fail_gate"
+        )]
+        quiet: bool,
+
+        // Output Options
+        #[arg(
+            long,
+            short = 'f',
+            value_enum,
+            default_value = "table",
+            help = "Output format"
+        )]
+        format: OutputFormat,
+
+        #[arg(
+            long,
+            short = 'o',
+            value_name = "FILE[:FORMAT]",
+            help = "Write results to file instead of stdout (repeatable, FILE:FORMAT to set the format per file)"
+        )]
+        output: Vec<OutputTarget>,
+    },
+
+    /// Scan Docker networks for risky configurations
+    ///
+    /// Checks how containers are connected: containers on host-driver
+    /// networks, databases and other sensitive services on networks that are
+    /// not --internal, bridge networks with inter-container communication
+    /// enabled, and user-defined networks no container uses.
+    ///
+    /// Examples:
+    ///   # Scan the networks of the local Docker daemon
+    ///   valeris network
+    ///
+    ///   # Scan pre-collected `docker network inspect` output
+    ///   docker network inspect $(docker network ls -q) > networks.json
+    ///   valeris network --input networks.json
+    ///
+    ///   # CI/CD integration - fail on medium or higher findings
+    ///   valeris network --fail-on medium --format json --output networks-report.json
+    #[command(group(clap::ArgGroup::new("fail_gate").args(["fail_on", "fail_score"]).multiple(true)))]
+    Network {
+        #[arg(
+            long,
+            short = 'i',
+            value_name = "FILE",
+            help = "Scan pre-collected `docker network inspect` JSON instead of the Docker daemon",
+            long_help = "Scan networks from a file of `docker network inspect` output instead of \
+                        querying the Docker daemon. The file may contain a single network object \
+                        or the array printed by `docker network inspect`. Container images are \
+                        not part of this output, so sensitive services are recognized by \
+                        container name only."
+        )]
+        input: Option<PathBuf>,
+
+        // Severity Filtering
+        #[arg(
+            long,
+            value_name = "SEVERITIES",
+            value_delimiter = ',',
+            help = "Filter findings by severity (comma-separated)",
+            conflicts_with = "min_severity"
+        )]
+        severity: Option<Vec<SeverityLevel>>,
+
+        #[arg(
+            long,
+            value_name = "LEVEL",
+            help = "Show only findings at or above this severity"
+        )]
+        min_severity: Option<SeverityLevel>,
+
+        // CI/CD Integration
+        #[arg(
+            long,
+            value_name = "LEVEL",
+            help = "Exit with error code 1 if findings at or above this level exist"
+        )]
+        fail_on: Option<SeverityLevel>,
+
+        #[arg(
+            long,
+            value_name = "POINTS",
+            help = "Exit with error code 1 if any target's risk points exceed this value"
+        )]
+        fail_score: Option<u32>,
+
+        #[arg(
+            long,
+            help = "Suppress console output, only set exit code (requires --fail-on or --fail-score)",
+            requires = "fail_gate"
+        )]
+        quiet: bool,
+
+        // Output Options
+        #[arg(
+            long,
+            short = 'f',
+            value_enum,
+            default_value = "table",
+            help = "Output format"
+        )]
+        format: OutputFormat,
+
+        #[arg(
+            long,
+            short = 'o',
+            value_name = "FILE[:FORMAT]",
+            help = "Write results to file instead of stdout (repeatable, FILE:FORMAT to set the format per file)"
+        )]
+        output: Vec<OutputTarget>,
     },
 
     /// List all available security detection rules
@@ -446,6 +1436,163 @@ pub enum Commands {
     /// Examples:
     ///   # Show config file status
     ///   valeris config
+    ///
+    ///   # Write a commented config file
+    ///   valeris config init
     #[command(visible_alias = "cfg")]
-    Config {},
+    Config {
+        #[command(subcommand)]
+        command: Option<ConfigCommand>,
+    },
+
+    /// Diagnose the installation
+    ///
+    /// Checks Docker socket connectivity and the daemon version, the rules
+    /// directory and its version, the configuration file, and the terminal,
+    /// and prints how to fix each problem found. Exits with code 3 if a
+    /// check fails.
+    ///
+    /// Examples:
+    ///   # Check everything a scan needs
+    ///   valeris doctor
+    Doctor {},
+
+    /// Benchmark rule loading and evaluation against fixtures
+    ///
+    /// Times every rule against a directory of container inspect JSON files
+    /// and Dockerfiles, and reports the slowest rules first. Useful for
+    /// spotting pathological regexes before shipping a rule bundle.
+    ///
+    /// Examples:
+    ///   # Benchmark the repository rules against the test fixtures
+    ///   valeris bench --rules ./rules --fixtures ./tests
+    ///
+    ///   # More iterations for stable numbers, show every rule
+    ///   valeris bench --rules ./rules --fixtures ./fixtures --iterations 100 --top 1000
+    Bench {
+        #[arg(
+            long,
+            value_name = "DIR",
+            help = "Rule bundle directory (with docker/ or runtime/docker/, and/or dockerfile/)"
+        )]
+        rules: PathBuf,
+
+        #[arg(
+            long,
+            value_name = "DIR",
+            help = "Directory of fixtures (*.json inspect output, Dockerfile*, *.Dockerfile)"
+        )]
+        fixtures: PathBuf,
+
+        #[arg(
+            long,
+            short = 'n',
+            value_name = "N",
+            default_value_t = 10,
+            help = "Number of times each rule is evaluated against each fixture"
+        )]
+        iterations: usize,
+
+        #[arg(
+            long,
+            value_name = "N",
+            default_value_t = 20,
+            help = "Number of slowest rules to show"
+        )]
+        top: usize,
+    },
+
+    /// Report findings over time from the findings history
+    ///
+    /// Reads the history written by `scan --store` / `docker-file --store` and
+    /// shows finding counts per scan, plus the findings introduced and resolved
+    /// between the first and latest matching scan.
+    ///
+    /// Examples:
+    ///   # Posture of the nginx containers over the last 30 days
+    ///   valeris history --container nginx --since 30d
+    ///
+    ///   # Read a specific history file
+    ///   valeris history --store ./history.jsonl
+    History {
+        #[arg(
+            long,
+            value_name = "PATH",
+            help = "History file to read (default: ~/.local/share/valeris/history.jsonl)"
+        )]
+        store: Option<PathBuf>,
+
+        #[arg(
+            long,
+            short = 'c',
+            value_name = "PATTERN",
+            value_delimiter = ',',
+            help = "Only include targets whose name contains a pattern (comma-separated)"
+        )]
+        container: Option<Vec<String>>,
+
+        #[arg(
+            long,
+            value_name = "SINCE",
+            help = "Only include scans since a time (e.g. 30d, 2024-05-01T00:00:00Z)"
+        )]
+        since: Option<String>,
+    },
+
+    /// Merge JSON reports into one
+    ///
+    /// Combines the JSON reports of separate `scan` or `docker-file` runs
+    /// (per host, per repository, ...). Targets present in several reports
+    /// are merged, findings are deduplicated by fingerprint, and the
+    /// summaries are aggregated.
+    ///
+    /// Examples:
+    ///   # Combine the reports of two hosts
+    ///   valeris merge web-1.json web-2.json -o combined.json
+    Merge {
+        #[arg(
+            value_name = "REPORT",
+            required = true,
+            help = "JSON reports from `valeris scan`, `valeris docker-file`, or `valeris merge`"
+        )]
+        reports: Vec<PathBuf>,
+
+        #[arg(
+            long,
+            short = 'o',
+            value_name = "FILE",
+            help = "Write the merged report to a file or object storage URL (stdout if omitted)"
+        )]
+        output: Option<String>,
+    },
+
+    /// Work with the loaded rule bundle
+    ///
+    /// Examples:
+    ///   # Generate Markdown documentation for all rules
+    ///   valeris rules docs --format markdown
+    Rules {
+        #[command(subcommand)]
+        command: RulesCommand,
+    },
+
+    /// Publish scan reports to code review tools
+    ///
+    /// Examples:
+    ///   # Comment findings on a GitHub pull request
+    ///   GITHUB_TOKEN=... valeris report github-pr --repo org/name --pr 123 --report valeris.json
+    Report {
+        #[command(subcommand)]
+        command: ReportCommand,
+    },
+
+    /// Scan Dockerfiles from git hooks
+    ///
+    /// Examples:
+    ///   # Fail commits that stage Dockerfiles with high-severity findings
+    ///   valeris hook install
+    Hook {
+        #[command(subcommand)]
+        command: HookCommand,
+    },
 }
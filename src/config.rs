@@ -3,11 +3,18 @@
 //! This module provides configuration settings and constants used throughout
 //! the application, including rules management, Docker settings, and output preferences.
 
-use std::path::PathBuf;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
 use std::io::IsTerminal;
 use serde::{Deserialize, Serialize};
 use anyhow::{Context, Result};
 
+use crate::detectors::runtime::ports::PortPolicy;
+use crate::docker::model::{Finding, RiskLevel};
+use crate::error::ValerisError;
+use crate::output::score::ScoreWeights;
+use crate::output::severity::SeverityScheme;
+
 /// Default URL for downloading rule releases from GitHub
 pub const DEFAULT_RULES_RELEASE_URL: &str =
     "https://github.com/rsgbengi/valeris-rules/releases/download/v0.1.0/detectors.tar.gz";
@@ -18,6 +25,9 @@ pub const RULES_DIR_ENV: &str = "VALERIS_RULES_DIR";
 /// Environment variable for config file path override
 pub const CONFIG_FILE_ENV: &str = "VALERIS_CONFIG_FILE";
 
+/// Environment variable holding the token for `valeris report github-pr`
+pub const GITHUB_TOKEN_ENV: &str = "GITHUB_TOKEN";
+
 /// Rules directory configuration
 pub struct RulesConfig {
     /// Base directory for rules
@@ -142,8 +152,22 @@ pub struct ScanConfig {
     pub min_severity: Option<String>,
     /// Default fail-on threshold
     pub fail_on: Option<String>,
+    /// Default policy bundle (--policy equivalent)
+    pub policy: Option<String>,
     /// Always run in quiet mode
     pub quiet: Option<bool>,
+    /// Per-tag thresholds (`[scan.tags.<tag>]`)
+    pub tags: Option<BTreeMap<String, TagThresholdConfig>>,
+}
+
+/// Thresholds for findings carrying one rule tag (`[scan.tags.secrets]`)
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct TagThresholdConfig {
+    /// Minimum severity to keep, replacing `--min-severity` for tagged findings
+    pub min_severity: Option<String>,
+    /// Fail-on threshold, replacing `--fail-on` for tagged findings
+    pub fail_on: Option<String>,
 }
 
 /// Output configuration from file
@@ -156,6 +180,20 @@ pub struct FileOutputConfig {
     pub colors: Option<bool>,
     /// Table width
     pub table_width: Option<usize>,
+    /// Severity remapping (`[output.severity_map]`), e.g. `low = "informative"`
+    /// or `informative = "ignore"`
+    pub severity_map: Option<HashMap<String, String>>,
+    /// Score penalty per finding (`[output.score_weights]`), e.g. `high = 25`
+    pub score_weights: Option<HashMap<String, u32>>,
+    /// Score penalty per finding with a rule tag (`[output.tag_weights]`),
+    /// replacing its severity's weight, e.g. `secrets = 50`
+    pub tag_weights: Option<HashMap<String, u32>>,
+    /// Language of the rule message catalog, e.g. `es`
+    pub lang: Option<String>,
+    /// How severities are named in output: `standard`, `legacy`, or `cli`
+    pub severity_scheme: Option<String>,
+    /// Mask secret values in findings (default: true)
+    pub redact_secrets: Option<bool>,
 }
 
 /// Rules configuration from file
@@ -180,6 +218,46 @@ pub struct FileDockerConfig {
     pub host: Option<String>,
 }
 
+/// Settings of the built-in detectors (`[detectors.*]`)
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct DetectorsConfig {
+    /// Host port binding checks (`[detectors.ports]`)
+    pub ports: Option<PortsConfig>,
+    /// Port policy of the `exposed_ports` rule (`[detectors.exposed_ports]`)
+    pub exposed_ports: Option<ExposedPortsConfig>,
+}
+
+/// Host port binding checks (`[detectors.ports]`)
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct PortsConfig {
+    /// Host ports never reported, as `PORT` or `PORT/PROTOCOL` (e.g. `443/tcp`)
+    pub allow: Option<Vec<String>>,
+}
+
+/// Port policy of the `exposed_ports` rule (`[detectors.exposed_ports]`)
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct ExposedPortsConfig {
+    /// Ports whose exposure is expected; their findings are dropped
+    pub allow: Option<Vec<String>>,
+    /// Ports that must not be exposed; their findings are raised to high
+    pub deny: Option<Vec<String>>,
+}
+
+/// Rule adjustments for Dockerfiles matching a path glob
+/// (`[override."docker/legacy/**"]`)
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct PathOverrideConfig {
+    /// Rule IDs to skip for matching Dockerfiles
+    pub exclude: Option<Vec<String>>,
+    /// Severity remapping for matching Dockerfiles, replacing
+    /// `[output.severity_map]` entries for the same severity
+    pub severity_map: Option<HashMap<String, String>>,
+}
+
 /// Complete configuration file structure
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(default)]
@@ -188,6 +266,10 @@ pub struct ConfigFile {
     pub output: Option<FileOutputConfig>,
     pub rules: Option<FileRulesConfig>,
     pub docker: Option<FileDockerConfig>,
+    pub detectors: Option<DetectorsConfig>,
+    /// Per-path rule adjustments, keyed by path glob
+    #[serde(rename = "override")]
+    pub overrides: Option<BTreeMap<String, PathOverrideConfig>>,
 }
 
 impl ConfigFile {
@@ -204,32 +286,496 @@ impl ConfigFile {
 
     /// Tries to load configuration from default locations
     pub fn load_default() -> Result<Option<Self>> {
+        Self::locate().map(|path| Self::load(&path)).transpose()
+    }
+
+    /// Returns the configuration file [`ConfigFile::load_default`] reads:
+    /// the file in `VALERIS_CONFIG_FILE`, `~/.config/valeris/config.toml`,
+    /// or `~/.valeris.toml`, whichever exists first.
+    pub fn locate() -> Option<PathBuf> {
         // Try environment variable first
-        if let Ok(path_str) = std::env::var(CONFIG_FILE_ENV) {
-            let path = PathBuf::from(path_str);
-            if path.exists() {
-                return Ok(Some(Self::load(&path)?));
+        let from_env = std::env::var(CONFIG_FILE_ENV).ok().map(PathBuf::from);
+        // Then the XDG config directory, then the home directory
+        let xdg = dirs::config_dir().map(|dir| dir.join("valeris").join("config.toml"));
+        let home = dirs::home_dir().map(|dir| dir.join(".valeris.toml"));
+        [from_env, xdg, home].into_iter().flatten().find(|path| path.exists())
+    }
+
+    /// Checks every value that is only parsed when a scan uses it: severity
+    /// names, thresholds, ports, overrides, and weights.
+    ///
+    /// # Errors
+    ///
+    /// Returns the [`ValerisError::Config`] of the first invalid value
+    pub fn validate(&self) -> Result<(), ValerisError> {
+        self.severity_map()?;
+        self.severity_scheme()?;
+        self.score_weights()?;
+        self.tag_thresholds()?;
+        self.port_policy()?;
+        self.path_overrides()?;
+        Ok(())
+    }
+}
+
+// ────────────────────────────────────────────────────────────────────
+// CONFIG INIT
+// ────────────────────────────────────────────────────────────────────
+
+/// Commented configuration file `valeris config init` writes
+pub const CONFIG_TEMPLATE: &str = include_str!("../valeris.toml.example");
+
+/// Returns where `valeris config init` writes by default:
+/// `~/.config/valeris/config.toml`.
+pub fn default_config_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("valeris").join("config.toml"))
+}
+
+/// Renders [`CONFIG_TEMPLATE`] with the values set in `seed`.
+///
+/// Each seeded value replaces the commented-out example of its key in the
+/// template; values the template has no example for go right below their
+/// section header. Everything else stays commented out.
+///
+/// # Errors
+///
+/// Returns an error if `seed` cannot be written as TOML
+pub fn render_config(seed: &ConfigFile) -> Result<String> {
+    let seed = toml::Value::try_from(seed).context("Failed to serialize configuration")?;
+    let mut values: Vec<(String, String, String)> = Vec::new();
+    for (section, table) in seed.as_table().into_iter().flatten() {
+        for (key, value) in table.as_table().into_iter().flatten() {
+            if !value.is_table() {
+                values.push((section.clone(), key.clone(), value.to_string()));
             }
         }
+    }
 
-        // Try XDG config directory
-        if let Some(config_dir) = dirs::config_dir() {
-            let path = config_dir.join("valeris").join("config.toml");
-            if path.exists() {
-                return Ok(Some(Self::load(&path)?));
+    let lines: Vec<(String, &str)> = template_lines().collect();
+    let has_example = |section: &str, key: &str| {
+        lines
+            .iter()
+            .any(|(s, line)| s == section && template_example(line) == Some(key))
+    };
+    let mut out = String::new();
+    for (section, line) in &lines {
+        let seeded = template_example(line)
+            .and_then(|key| values.iter().position(|(s, k, _)| s == section && k == key));
+        if let Some(index) = seeded {
+            let (_, key, value) = values.remove(index);
+            out.push_str(&format!("{key} = {value}\n"));
+            continue;
+        }
+        out.push_str(line);
+        out.push('\n');
+        if line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) == Some(section.as_str()) {
+            for (_, key, value) in values.iter().filter(|(s, k, _)| s == section && !has_example(s, k)) {
+                out.push_str(&format!("{key} = {value}\n"));
             }
+            values.retain(|(s, k, _)| s != section || has_example(s, k));
         }
+    }
+    // Sections the template does not have
+    let mut current = None;
+    for (section, key, value) in values {
+        if current.as_ref() != Some(&section) {
+            out.push_str(&format!("\n[{section}]\n"));
+            current = Some(section);
+        }
+        out.push_str(&format!("{key} = {value}\n"));
+    }
+    Ok(out)
+}
+
+/// Lines of [`CONFIG_TEMPLATE`] with the section each one is in.
+fn template_lines() -> impl Iterator<Item = (String, &'static str)> {
+    let mut section = String::new();
+    CONFIG_TEMPLATE.lines().map(move |line| {
+        if let Some(header) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            section = header.to_string();
+        }
+        (section.clone(), line)
+    })
+}
+
+/// Key of a commented-out example line such as `# min_severity = "medium"`.
+fn template_example(line: &str) -> Option<&str> {
+    let (key, _) = line.strip_prefix("# ")?.split_once(" = ")?;
+    (!key.contains(' ')).then_some(key)
+}
+
+/// Writes the configuration [`render_config`] renders for `seed` to `path`,
+/// creating its parent directories.
+///
+/// # Errors
+///
+/// Returns an error if `path` exists and `force` is not set, or if the file
+/// cannot be written
+pub fn write_config(path: &Path, seed: &ConfigFile, force: bool) -> Result<()> {
+    if path.exists() && !force {
+        anyhow::bail!("{} already exists; pass --force to replace it", path.display());
+    }
+    let contents = render_config(seed)?;
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+    }
+    std::fs::write(path, contents).with_context(|| format!("Failed to write config file: {}", path.display()))
+}
+
+// ────────────────────────────────────────────────────────────────────
+// SEVERITY REMAPPING
+// ────────────────────────────────────────────────────────────────────
+
+/// Value in `[output.severity_map]` that drops findings entirely
+pub const SEVERITY_IGNORE: &str = "ignore";
+
+/// Parses a severity name as written in configuration files.
+///
+/// Accepts the CLI names (`informative`, `low`, `medium`, `high`) plus the
+/// `info` and `critical` aliases used by rule files. Case-insensitive.
+pub fn parse_risk_level(name: &str) -> Option<RiskLevel> {
+    match name.trim().to_ascii_lowercase().as_str() {
+        "informative" | "info" => Some(RiskLevel::Informative),
+        "low" => Some(RiskLevel::Low),
+        "medium" => Some(RiskLevel::Medium),
+        "high" => Some(RiskLevel::High),
+        "critical" => Some(RiskLevel::Critical),
+        _ => None,
+    }
+}
+
+/// User-defined severity remapping applied to findings before filtering,
+/// printing, and fail-on evaluation.
+///
+/// Each source severity maps either to a new severity or to `None`, meaning
+/// findings of that severity are dropped.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SeverityMap {
+    mapping: HashMap<RiskLevel, Option<RiskLevel>>,
+}
+
+impl SeverityMap {
+    /// Builds a severity map from the raw `[output.severity_map]` table.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ValerisError::Config`] if a key or value is not a known
+    /// severity (or `ignore`).
+    pub fn from_config(raw: &HashMap<String, String>) -> Result<Self, ValerisError> {
+        Self::from_table(raw, "[output.severity_map]")
+    }
+
+    /// Builds a severity map from a raw table, naming `section` in errors.
+    pub(crate) fn from_table(raw: &HashMap<String, String>, section: &str) -> Result<Self, ValerisError> {
+        let mut mapping = HashMap::new();
+
+        for (from, to) in raw {
+            let Some(source) = parse_risk_level(from) else {
+                return Err(ValerisError::Config(format!(
+                    "Unknown severity '{}' in {}",
+                    from, section
+                )));
+            };
+
+            let target = if to.trim().eq_ignore_ascii_case(SEVERITY_IGNORE) {
+                None
+            } else {
+                match parse_risk_level(to) {
+                    Some(level) => Some(level),
+                    None => {
+                        return Err(ValerisError::Config(format!(
+                            "Unknown severity '{}' for '{}' in {} \
+                             (expected informative, low, medium, high, critical, or ignore)",
+                            to, from, section
+                        )))
+                    }
+                }
+            };
+
+            mapping.insert(source, target);
+        }
+
+        Ok(Self { mapping })
+    }
+
+    /// Adds the remappings of `other`, replacing any for the same severity.
+    pub fn extend(&mut self, other: SeverityMap) {
+        self.mapping.extend(other.mapping);
+    }
+
+    /// Returns `true` if no remapping is configured.
+    pub fn is_empty(&self) -> bool {
+        self.mapping.is_empty()
+    }
+
+    /// Remaps finding severities in place, dropping ignored findings.
+    pub fn apply(&self, findings: &mut Vec<Finding>) {
+        if self.is_empty() {
+            return;
+        }
+
+        findings.retain_mut(|finding| match self.mapping.get(&finding.risk) {
+            Some(Some(level)) => {
+                finding.risk = level.clone();
+                true
+            }
+            Some(None) => false,
+            None => true,
+        });
+    }
+}
+
+// ────────────────────────────────────────────────────────────────────
+// PER-TAG THRESHOLDS
+// ────────────────────────────────────────────────────────────────────
+
+/// Compiled `[scan.tags.<tag>]` thresholds.
+///
+/// A finding carrying configured tags uses the lowest threshold among
+/// them instead of the global `--min-severity` / `--fail-on`; findings
+/// without configured tags keep the global thresholds.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TagThresholds {
+    min_severity: HashMap<String, RiskLevel>,
+    fail_on: HashMap<String, RiskLevel>,
+}
 
-        // Try home directory
-        if let Some(home_dir) = dirs::home_dir() {
-            let path = home_dir.join(".valeris.toml");
-            if path.exists() {
-                return Ok(Some(Self::load(&path)?));
+impl TagThresholds {
+    /// Compiles the raw `[scan.tags]` tables.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ValerisError::Config`] if a severity is unknown.
+    pub fn from_config(raw: &BTreeMap<String, TagThresholdConfig>) -> Result<Self, ValerisError> {
+        let parse = |tag: &str, key: &str, value: &str| {
+            parse_risk_level(value).ok_or_else(|| {
+                ValerisError::Config(format!(
+                    "Unknown severity '{}' for {} in [scan.tags.{}]",
+                    value, key, tag
+                ))
+            })
+        };
+
+        let mut thresholds = Self::default();
+        for (tag, table) in raw {
+            if let Some(value) = &table.min_severity {
+                thresholds.min_severity.insert(tag.clone(), parse(tag, "min_severity", value)?);
+            }
+            if let Some(value) = &table.fail_on {
+                thresholds.fail_on.insert(tag.clone(), parse(tag, "fail_on", value)?);
             }
         }
+        Ok(thresholds)
+    }
 
-        // No config file found
-        Ok(None)
+    /// Returns `true` if no tag threshold is configured.
+    pub fn is_empty(&self) -> bool {
+        self.min_severity.is_empty() && self.fail_on.is_empty()
+    }
+
+    /// Keeps findings at or above their minimum severity.
+    ///
+    /// # Arguments
+    ///
+    /// * `findings` - Findings to filter in place
+    /// * `default` - Minimum severity for findings without configured tags
+    pub fn retain(&self, findings: &mut Vec<Finding>, default: Option<RiskLevel>) {
+        findings.retain(|f| {
+            Self::threshold(&self.min_severity, f, default.as_ref()).is_none_or(|min| f.risk >= *min)
+        });
+    }
+
+    /// Returns whether any finding is at or above its fail-on threshold.
+    ///
+    /// # Arguments
+    ///
+    /// * `findings` - Findings to check
+    /// * `default` - Fail-on threshold for findings without configured tags
+    pub fn fails<'a>(
+        &self,
+        findings: impl IntoIterator<Item = &'a Finding>,
+        default: Option<RiskLevel>,
+    ) -> bool {
+        findings.into_iter().any(|f| {
+            Self::threshold(&self.fail_on, f, default.as_ref()).is_some_and(|min| f.risk >= *min)
+        })
+    }
+
+    /// Lowest threshold among the finding's tags, or `default` if none is set.
+    fn threshold<'a>(
+        by_tag: &'a HashMap<String, RiskLevel>,
+        finding: &Finding,
+        default: Option<&'a RiskLevel>,
+    ) -> Option<&'a RiskLevel> {
+        finding
+            .tags
+            .iter()
+            .filter_map(|tag| by_tag.get(tag))
+            .min()
+            .or(default)
+    }
+}
+
+// ────────────────────────────────────────────────────────────────────
+// PER-PATH OVERRIDES
+// ────────────────────────────────────────────────────────────────────
+
+/// Compiled `[override."<glob>"]` tables.
+///
+/// Globs are matched against the Dockerfile path as given on the command
+/// line (relative to the repository root in git hooks), with any leading
+/// `./` removed; `*` also matches `/`. Every matching table applies, in
+/// glob order.
+#[derive(Debug, Clone, Default)]
+pub struct PathOverrides {
+    entries: Vec<PathOverride>,
+}
+
+#[derive(Debug, Clone)]
+struct PathOverride {
+    glob: globset::GlobMatcher,
+    exclude: Vec<String>,
+    severity_map: SeverityMap,
+}
+
+impl PathOverrides {
+    /// Compiles the raw `[override]` tables.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ValerisError::Config`] if a glob or severity is invalid.
+    pub fn from_config(raw: &BTreeMap<String, PathOverrideConfig>) -> Result<Self, ValerisError> {
+        let entries = raw
+            .iter()
+            .map(|(pattern, table)| {
+                let section = format!("[override.\"{}\"]", pattern);
+                let glob = globset::Glob::new(pattern)
+                    .map_err(|e| ValerisError::Config(format!("Invalid glob in {}: {}", section, e)))?
+                    .compile_matcher();
+                let severity_map = match &table.severity_map {
+                    Some(map) => SeverityMap::from_table(map, &format!("{}.severity_map", section))?,
+                    None => SeverityMap::default(),
+                };
+                Ok(PathOverride {
+                    glob,
+                    exclude: table.exclude.clone().unwrap_or_default(),
+                    severity_map,
+                })
+            })
+            .collect::<Result<_, ValerisError>>()?;
+        Ok(Self { entries })
+    }
+
+    /// Returns `true` if no override is configured.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Drops findings excluded for `path` and remaps severities with `base`
+    /// extended by the severity maps of the overrides matching `path`.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Dockerfile the findings belong to
+    /// * `base` - Severity map that applies to every file
+    /// * `findings` - Findings to adjust in place
+    pub fn apply(&self, path: &Path, base: &SeverityMap, findings: &mut Vec<Finding>) {
+        let path = path.strip_prefix(".").unwrap_or(path);
+        let mut severity_map = base.clone();
+        for entry in self.entries.iter().filter(|e| e.glob.is_match(path)) {
+            findings.retain(|f| !entry.exclude.contains(&f.kind));
+            severity_map.extend(entry.severity_map.clone());
+        }
+        severity_map.apply(findings);
+    }
+}
+
+impl ConfigFile {
+    /// Returns the configured per-tag thresholds, or none if unset.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ValerisError::Config`] if a `[scan.tags]` table is invalid.
+    pub fn tag_thresholds(&self) -> Result<TagThresholds, ValerisError> {
+        match self.scan.as_ref().and_then(|s| s.tags.as_ref()) {
+            Some(raw) => TagThresholds::from_config(raw),
+            None => Ok(TagThresholds::default()),
+        }
+    }
+
+    /// Returns the configured port policy, or the default if unset.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ValerisError::Config`] if `[detectors.ports]` or
+    /// `[detectors.exposed_ports]` lists an invalid port.
+    pub fn port_policy(&self) -> Result<PortPolicy, ValerisError> {
+        match &self.detectors {
+            Some(raw) => PortPolicy::from_config(raw),
+            None => Ok(PortPolicy::default()),
+        }
+    }
+
+    /// Returns the configured per-path overrides, or none if unset.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ValerisError::Config`] if an `[override]` table is invalid.
+    pub fn path_overrides(&self) -> Result<PathOverrides, ValerisError> {
+        match &self.overrides {
+            Some(raw) => PathOverrides::from_config(raw),
+            None => Ok(PathOverrides::default()),
+        }
+    }
+
+    /// Returns the configured severity map, or an empty map if none is set.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ValerisError::Config`] if the `[output.severity_map]` table
+    /// is invalid.
+    pub fn severity_map(&self) -> Result<SeverityMap, ValerisError> {
+        match self.output.as_ref().and_then(|o| o.severity_map.as_ref()) {
+            Some(raw) => SeverityMap::from_config(raw),
+            None => Ok(SeverityMap::default()),
+        }
+    }
+
+    /// Returns the configured severity naming scheme, or the default.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ValerisError::Config`] if the scheme is unknown.
+    pub fn severity_scheme(&self) -> Result<SeverityScheme, ValerisError> {
+        match self.output.as_ref().and_then(|o| o.severity_scheme.as_deref()) {
+            Some(name) => SeverityScheme::from_config(name),
+            None => Ok(SeverityScheme::default()),
+        }
+    }
+
+    /// Returns the configured score weights, or the defaults if none are set,
+    /// including the tag weights from `[output.tag_weights]`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ValerisError::Config`] if the table names an unknown severity.
+    pub fn score_weights(&self) -> Result<ScoreWeights, ValerisError> {
+        let weights = match self.output.as_ref().and_then(|o| o.score_weights.as_ref()) {
+            Some(raw) => ScoreWeights::from_config(raw)?,
+            None => ScoreWeights::default(),
+        };
+        let tags = self.output.as_ref().and_then(|o| o.tag_weights.clone());
+        Ok(weights.with_tag_weights(tags.unwrap_or_default()))
+    }
+
+    /// Returns the rules configuration, honoring `[rules].auto_download`.
+    pub fn rules_config(&self) -> RulesConfig {
+        let mut config = RulesConfig::default();
+        if let Some(auto_download) = self.rules.as_ref().and_then(|r| r.auto_download) {
+            config.auto_download = auto_download;
+        }
+        config
     }
 }
 
@@ -244,6 +790,64 @@ mod tests {
         assert!(config.base_dir.to_string_lossy().contains("valeris"));
     }
 
+    #[test]
+    fn rules_config_honors_auto_download() {
+        let config: ConfigFile = toml::from_str("[rules]\nauto_download = false").unwrap();
+        assert!(!config.rules_config().auto_download);
+        assert!(ConfigFile::default().rules_config().auto_download);
+    }
+
+    #[test]
+    fn config_template_parses_to_an_empty_configuration() {
+        let config: ConfigFile = toml::from_str(&render_config(&ConfigFile::default()).unwrap()).unwrap();
+        assert!(config.scan.is_some_and(|scan| scan.min_severity.is_none() && scan.only.is_none()));
+        assert!(config.output.is_some_and(|output| output.format.is_none()));
+    }
+
+    #[test]
+    fn seeded_values_replace_their_examples() {
+        let seed = ConfigFile {
+            scan: Some(ScanConfig {
+                min_severity: Some("medium".into()),
+                exclude: Some(vec!["log_driver".into()]),
+                policy: Some("cis".into()),
+                ..Default::default()
+            }),
+            output: Some(FileOutputConfig {
+                format: Some("json".into()),
+                ..Default::default()
+            }),
+            detectors: Some(DetectorsConfig::default()),
+            ..Default::default()
+        };
+        let rendered = render_config(&seed).unwrap();
+
+        assert!(rendered.contains("[scan]\npolicy = \"cis\"\n"));
+        assert!(rendered.contains("\nmin_severity = \"medium\"\n"));
+        assert!(!rendered.contains("# min_severity = \"medium\""));
+        assert!(rendered.contains("\nexclude = [\"log_driver\"]\n"));
+        assert!(rendered.contains("# Default output format (table, json, csv, sarif, ...)\nformat = \"json\"\n"));
+        assert!(rendered.contains("# only = "));
+
+        let config: ConfigFile = toml::from_str(&rendered).unwrap();
+        let scan = config.scan.unwrap();
+        assert_eq!(scan.min_severity.as_deref(), Some("medium"));
+        assert_eq!(scan.policy.as_deref(), Some("cis"));
+        assert_eq!(config.output.unwrap().format.as_deref(), Some("json"));
+    }
+
+    #[test]
+    fn write_config_refuses_to_replace_a_file_without_force() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("valeris").join("config.toml");
+        write_config(&path, &ConfigFile::default(), false).unwrap();
+        assert!(std::fs::read_to_string(&path).unwrap().starts_with("# Valeris Configuration File"));
+
+        let err = write_config(&path, &ConfigFile::default(), false).unwrap_err();
+        assert!(err.to_string().contains("pass --force"));
+        write_config(&path, &ConfigFile::default(), true).unwrap();
+    }
+
     #[test]
     fn test_docker_config_defaults() {
         let config = DockerConfig::default();
@@ -270,4 +874,126 @@ mod tests {
         assert!(runtime_dir.to_string_lossy().contains("runtime"));
         assert!(dockerfile_dir.to_string_lossy().contains("dockerfile"));
     }
+
+    fn finding(risk: RiskLevel) -> Finding {
+        Finding::new("test", "test", risk)
+    }
+
+    #[test]
+    fn test_severity_map_remaps_and_drops() {
+        let raw: HashMap<String, String> = [
+            ("informative", "ignore"),
+            ("low", "informative"),
+        ]
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+        let map = SeverityMap::from_config(&raw).unwrap();
+
+        let mut findings = vec![
+            finding(RiskLevel::Informative),
+            finding(RiskLevel::Low),
+            finding(RiskLevel::High),
+        ];
+        map.apply(&mut findings);
+
+        assert_eq!(findings.len(), 2);
+        assert_eq!(findings[0].risk, RiskLevel::Informative);
+        assert_eq!(findings[1].risk, RiskLevel::High);
+    }
+
+    #[test]
+    fn test_severity_map_rejects_unknown_levels() {
+        let raw: HashMap<String, String> =
+            [("low".to_string(), "severe".to_string())].into_iter().collect();
+        assert!(SeverityMap::from_config(&raw).is_err());
+
+        let raw: HashMap<String, String> =
+            [("urgent".to_string(), "low".to_string())].into_iter().collect();
+        assert!(SeverityMap::from_config(&raw).is_err());
+    }
+
+    #[test]
+    fn test_severity_map_parses_from_toml() {
+        let cfg: ConfigFile = toml::from_str(
+            "[output.severity_map]\ninformative = \"ignore\"\nlow = \"info\"\n",
+        )
+        .unwrap();
+        let map = cfg.severity_map().unwrap();
+        assert!(!map.is_empty());
+    }
+
+    #[test]
+    fn test_path_overrides_exclude_and_remap_matching_paths() {
+        let cfg: ConfigFile = toml::from_str(
+            "[output.severity_map]\nmedium = \"high\"\n\n\
+             [override.\"docker/legacy/**\"]\nexclude = [\"DF001\"]\n\n\
+             [override.\"docker/legacy/**\".severity_map]\nmedium = \"low\"\n",
+        )
+        .unwrap();
+        let overrides = cfg.path_overrides().unwrap();
+        let base = cfg.severity_map().unwrap();
+        let findings = || {
+            vec![
+                Finding::new("DF001", "latest tag", RiskLevel::Medium),
+                Finding::new("DF005", "ADD used", RiskLevel::Medium),
+            ]
+        };
+
+        let mut legacy = findings();
+        overrides.apply(Path::new("./docker/legacy/api/Dockerfile"), &base, &mut legacy);
+        assert_eq!(legacy.len(), 1);
+        assert_eq!(legacy[0].kind, "DF005");
+        assert_eq!(legacy[0].risk, RiskLevel::Low);
+
+        let mut service = findings();
+        overrides.apply(Path::new("docker/api/Dockerfile"), &base, &mut service);
+        assert_eq!(service.len(), 2);
+        assert!(service.iter().all(|f| f.risk == RiskLevel::High));
+    }
+
+    #[test]
+    fn test_path_overrides_reject_invalid_severity() {
+        let cfg: ConfigFile =
+            toml::from_str("[override.\"legacy/**\".severity_map]\nmedium = \"lowest\"\n").unwrap();
+        let err = cfg.path_overrides().unwrap_err();
+        assert!(err.to_string().contains("[override.\"legacy/**\"].severity_map"));
+    }
+
+    #[test]
+    fn test_tag_thresholds_replace_global_thresholds_for_tagged_findings() {
+        let cfg: ConfigFile = toml::from_str(
+            "[scan.tags.secrets]\nfail_on = \"informative\"\n\n\
+             [scan.tags.best-practice]\nfail_on = \"high\"\nmin_severity = \"medium\"\n",
+        )
+        .unwrap();
+        let thresholds = cfg.tag_thresholds().unwrap();
+        let tagged = |tag: &str, risk| Finding {
+            tags: vec![tag.to_string()],
+            ..Finding::new("test", "test", risk)
+        };
+
+        let secret = tagged("secrets", RiskLevel::Informative);
+        let practice = tagged("best-practice", RiskLevel::Medium);
+        let untagged = Finding::new("test", "test", RiskLevel::Low);
+        assert!(thresholds.fails([&secret], Some(RiskLevel::High)));
+        assert!(!thresholds.fails([&practice], Some(RiskLevel::Low)));
+        assert!(thresholds.fails([&untagged], Some(RiskLevel::Low)));
+        assert!(!thresholds.fails([&untagged], None));
+
+        let mut findings = vec![
+            tagged("best-practice", RiskLevel::Low),
+            practice.clone(),
+            secret.clone(),
+        ];
+        thresholds.retain(&mut findings, Some(RiskLevel::Medium));
+        assert_eq!(findings, vec![practice]);
+    }
+
+    #[test]
+    fn test_tag_thresholds_reject_unknown_severity() {
+        let cfg: ConfigFile = toml::from_str("[scan.tags.secrets]\nfail_on = \"urgent\"\n").unwrap();
+        let err = cfg.tag_thresholds().unwrap_err();
+        assert!(err.to_string().contains("[scan.tags.secrets]"));
+    }
 }
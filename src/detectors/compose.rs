@@ -0,0 +1,181 @@
+//! Files a Compose project builds from and injects.
+//!
+//! `scan --compose <FILE>` does not evaluate the Compose file itself: it
+//! resolves the Dockerfiles its services build (`build:`) and the env files
+//! they load (`env_file:`, plus the project `.env` Compose interpolates), so
+//! the Dockerfile and `.env` scanners can check them in the same run as the
+//! containers. Remote build contexts and inline Dockerfiles are skipped, and
+//! so are referenced files that do not exist.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde_yml::Value;
+
+use crate::error::{Result, ValerisError};
+
+/// Dockerfile a service builds from when `build.dockerfile` is not set
+const DEFAULT_DOCKERFILE: &str = "Dockerfile";
+
+/// Local files referenced by a Compose file, sorted and deduplicated.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ComposeFiles {
+    pub dockerfiles: Vec<PathBuf>,
+    pub env_files: Vec<PathBuf>,
+}
+
+impl ComposeFiles {
+    /// Adds the files referenced by another Compose file.
+    pub fn extend(&mut self, other: ComposeFiles) {
+        self.dockerfiles.extend(other.dockerfiles);
+        self.env_files.extend(other.env_files);
+        for files in [&mut self.dockerfiles, &mut self.env_files] {
+            files.sort();
+            files.dedup();
+        }
+    }
+}
+
+/// Resolves the Dockerfiles and env files of every service in a Compose file.
+///
+/// # Arguments
+///
+/// * `path` - The Compose file; relative paths in it are resolved against its directory
+///
+/// # Errors
+///
+/// * [`ValerisError::InputRead`] if the file cannot be read
+/// * [`ValerisError::InputParse`] if it is not valid YAML
+pub fn referenced_files(path: &Path) -> Result<ComposeFiles> {
+    let content = fs::read_to_string(path).map_err(|source| ValerisError::InputRead {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let document: Value = serde_yml::from_str(&content).map_err(|e| ValerisError::InputParse {
+        path: path.to_path_buf(),
+        message: e.to_string(),
+    })?;
+    let project_dir = path.parent().unwrap_or(Path::new(""));
+
+    let mut dockerfiles = Vec::new();
+    let mut env_files = Vec::new();
+    let services = document.get("services").and_then(Value::as_mapping);
+    for service in services.into_iter().flat_map(|s| s.values()) {
+        dockerfiles.extend(build_dockerfile(service, project_dir));
+        env_files.extend(service_env_files(service, project_dir));
+    }
+    env_files.push(project_dir.join(".env"));
+
+    let mut files = ComposeFiles::default();
+    files.extend(ComposeFiles {
+        dockerfiles: existing(dockerfiles, path),
+        env_files: existing(env_files, path),
+    });
+    Ok(files)
+}
+
+/// Dockerfile of a service's `build:` (a context path or a mapping).
+fn build_dockerfile(service: &Value, project_dir: &Path) -> Option<PathBuf> {
+    let (context, dockerfile) = match service.get("build")? {
+        Value::String(context) => (context.as_str(), DEFAULT_DOCKERFILE),
+        build @ Value::Mapping(_) => {
+            if build.get("dockerfile_inline").is_some() {
+                return None;
+            }
+            (
+                build.get("context").and_then(Value::as_str).unwrap_or("."),
+                build.get("dockerfile").and_then(Value::as_str).unwrap_or(DEFAULT_DOCKERFILE),
+            )
+        }
+        _ => return None,
+    };
+    if context.contains("://") || context.starts_with("git@") {
+        tracing::debug!("Skipping remote build context {}", context);
+        return None;
+    }
+    Some(project_dir.join(context).join(dockerfile))
+}
+
+/// Files of a service's `env_file:` (a path, a list of paths, or a list of
+/// `{ path, required }` mappings).
+fn service_env_files(service: &Value, project_dir: &Path) -> Vec<PathBuf> {
+    let entries = match service.get("env_file") {
+        Some(Value::Sequence(entries)) => entries.iter().collect(),
+        Some(entry) => vec![entry],
+        None => Vec::new(),
+    };
+    entries
+        .into_iter()
+        .filter_map(|entry| match entry {
+            Value::String(path) => Some(path.as_str()),
+            entry => entry.get("path").and_then(Value::as_str),
+        })
+        .map(|path| project_dir.join(path))
+        .collect()
+}
+
+fn existing(paths: Vec<PathBuf>, compose_file: &Path) -> Vec<PathBuf> {
+    paths
+        .into_iter()
+        .filter(|path| {
+            let exists = path.is_file();
+            if !exists {
+                tracing::debug!("{} references missing file {}", compose_file.display(), path.display());
+            }
+            exists
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_build_dockerfiles_and_env_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("api")).unwrap();
+        fs::write(root.join("api/Dockerfile"), "FROM alpine:3.20\n").unwrap();
+        fs::write(root.join("worker.Dockerfile"), "FROM alpine:3.20\n").unwrap();
+        fs::write(root.join(".env"), "TAG=1\n").unwrap();
+        fs::write(root.join("api.env"), "DB_PASSWORD=x\n").unwrap();
+        fs::write(
+            root.join("compose.yaml"),
+            r#"
+services:
+  api:
+    build: ./api
+    env_file: api.env
+  worker:
+    build:
+      context: .
+      dockerfile: worker.Dockerfile
+    env_file:
+      - path: api.env
+      - path: optional.env
+        required: false
+  remote:
+    build: https://github.com/acme/app.git
+  db:
+    image: postgres:16
+"#,
+        )
+        .unwrap();
+
+        let files = referenced_files(&root.join("compose.yaml")).unwrap();
+        assert_eq!(
+            files.dockerfiles,
+            vec![root.join("./api").join("Dockerfile"), root.join(".").join("worker.Dockerfile")]
+        );
+        assert_eq!(files.env_files, vec![root.join(".env"), root.join("api.env")]);
+    }
+
+    #[test]
+    fn rejects_invalid_yaml() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("compose.yaml");
+        fs::write(&path, "services: [").unwrap();
+        assert!(matches!(referenced_files(&path), Err(ValerisError::InputParse { .. })));
+    }
+}
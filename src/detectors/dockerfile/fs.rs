@@ -0,0 +1,64 @@
+//! Filesystem access for Dockerfile scans.
+//!
+//! File-level rules look at files next to the Dockerfile (currently only
+//! `.dockerignore`). [`scan_dockerfile_content`] reaches them through the
+//! [`FileSystem`] trait, so callers holding a Dockerfile in memory (web
+//! services, editor integrations) can answer from their own buffers instead
+//! of the local disk.
+//!
+//! [`scan_dockerfile_content`]: crate::detectors::dockerfile::scanner::scan_dockerfile_content
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use futures::future::BoxFuture;
+
+/// Read-only view of the files around a Dockerfile.
+pub trait FileSystem: Send + Sync {
+    /// Resolves to `true` if a file exists at `path`.
+    fn exists<'a>(&'a self, path: &'a Path) -> BoxFuture<'a, bool>;
+}
+
+/// The local disk, accessed through `tokio::fs`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StdFileSystem;
+
+impl FileSystem for StdFileSystem {
+    fn exists<'a>(&'a self, path: &'a Path) -> BoxFuture<'a, bool> {
+        Box::pin(async move { tokio::fs::try_exists(path).await.unwrap_or(false) })
+    }
+}
+
+/// A fixed set of paths, for scanning buffers that never touch disk.
+///
+/// # Example
+///
+/// ```
+/// use valeris::detectors::dockerfile::fs::MemoryFileSystem;
+///
+/// let fs = MemoryFileSystem::new().with_file("project/.dockerignore");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MemoryFileSystem {
+    files: HashSet<PathBuf>,
+}
+
+impl MemoryFileSystem {
+    /// Creates an empty filesystem.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a file at `path`
+    pub fn with_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.files.insert(path.into());
+        self
+    }
+}
+
+impl FileSystem for MemoryFileSystem {
+    fn exists<'a>(&'a self, path: &'a Path) -> BoxFuture<'a, bool> {
+        let exists = self.files.contains(path);
+        Box::pin(async move { exists })
+    }
+}
@@ -2,12 +2,14 @@
 //!
 //! This module provides helper functions for extracting information from
 //! Dockerfile instructions, converting them to searchable formats, and
-//! calculating line numbers.
+//! calculating line numbers and columns.
 
 use dockerfile_parser::{Instruction, Stage};
 use serde_yml::Value;
 use std::collections::HashMap;
 
+use crate::docker::model::SourceSpan;
+
 /// Extracts the instruction type as a string (e.g., "FROM", "RUN", "USER").
 ///
 /// # Arguments
@@ -39,6 +41,9 @@ pub fn get_instruction_kind(ins: &Instruction) -> String {
 /// - ENV: `env.key`, `env.value`
 /// - USER/EXPOSE/etc: instruction-specific fields
 ///
+/// The original source text (`raw`) is added separately by
+/// [`get_raw_text`], since it needs the file content.
+///
 /// # Arguments
 ///
 /// * `ins` - The instruction to convert
@@ -113,7 +118,63 @@ pub fn instruction_to_map(ins: &Instruction) -> HashMap<String, Value> {
 ///
 /// The 1-based line number, or None if it cannot be determined
 pub fn get_line_number(ins: &Instruction, content: &str) -> Option<usize> {
-    let span = match ins {
+    let span = instruction_span(ins);
+
+    // Convert byte offset to line number by counting newlines
+    let line_num = content[..span.start]
+        .chars()
+        .filter(|&c| c == '\n')
+        .count() + 1;
+
+    Some(line_num)
+}
+
+/// Calculates the columns and end line of an instruction in the source file.
+///
+/// # Arguments
+///
+/// * `ins` - The instruction
+/// * `content` - The full Dockerfile content
+///
+/// # Returns
+///
+/// The span of the instruction without trailing whitespace, or None if it
+/// does not fall within `content`
+pub fn get_span(ins: &Instruction, content: &str) -> Option<SourceSpan> {
+    let start = instruction_span(ins).start;
+    let end = start + get_raw_text(ins, content)?.len();
+    let column = |offset: usize| {
+        let line_start = content[..offset].rfind('\n').map_or(0, |i| i + 1);
+        content[line_start..offset].chars().count() + 1
+    };
+
+    Some(SourceSpan {
+        column: column(start),
+        end_line: content[..end].matches('\n').count() + 1,
+        end_column: column(end),
+    })
+}
+
+/// Returns the instruction exactly as written in the source, including
+/// flags, comments between continuation lines, and `\` line continuations.
+///
+/// # Arguments
+///
+/// * `ins` - The instruction
+/// * `content` - The full Dockerfile content
+///
+/// # Returns
+///
+/// The source text with trailing whitespace removed, or None if the
+/// instruction's span does not fall within `content`
+pub fn get_raw_text<'a>(ins: &Instruction, content: &'a str) -> Option<&'a str> {
+    let span = instruction_span(ins);
+    content.get(span.start..span.end).map(str::trim_end)
+}
+
+/// Returns the source span of any instruction variant.
+fn instruction_span(ins: &Instruction) -> &dockerfile_parser::Span {
+    match ins {
         Instruction::From(f) => &f.span,
         Instruction::Run(r) => &r.span,
         Instruction::Cmd(c) => &c.span,
@@ -123,15 +184,7 @@ pub fn get_line_number(ins: &Instruction, content: &str) -> Option<usize> {
         Instruction::Entrypoint(e) => &e.span,
         Instruction::Arg(a) => &a.span,
         Instruction::Misc(m) => &m.span,
-    };
-
-    // Convert byte offset to line number by counting newlines
-    let line_num = content[..span.start]
-        .chars()
-        .filter(|&c| c == '\n')
-        .count() + 1;
-
-    Some(line_num)
+    }
 }
 
 /// Finds the last USER instruction in a stage.
@@ -321,4 +374,29 @@ mod tests {
         // Should return the LAST user instruction
         assert_eq!(user, Some("nobody".to_string()));
     }
+
+    #[test]
+    fn test_get_raw_text_keeps_flags_and_continuations() {
+        let dockerfile = "FROM nginx\nRUN --mount=type=cache,target=/var/cache \\\n    apt-get update\nUSER nobody\n";
+        let parsed = Dockerfile::parse(dockerfile).unwrap();
+        let stage = parsed.iter_stages().next().unwrap();
+
+        assert_eq!(
+            get_raw_text(stage.instructions[1], dockerfile),
+            Some("RUN --mount=type=cache,target=/var/cache \\\n    apt-get update")
+        );
+        assert_eq!(get_raw_text(stage.instructions[2], dockerfile), Some("USER nobody"));
+    }
+
+    #[test]
+    fn test_get_span_covers_continuations() {
+        let dockerfile = "FROM nginx\n  RUN apt-get update \\\n    && apt-get install -y curl  \nUSER nobody";
+        let parsed = Dockerfile::parse(dockerfile).unwrap();
+        let stage = parsed.iter_stages().next().unwrap();
+
+        let span = |i: usize| get_span(stage.instructions[i], dockerfile).unwrap();
+        assert_eq!(span(0), SourceSpan { column: 1, end_line: 1, end_column: 11 });
+        assert_eq!(span(1), SourceSpan { column: 3, end_line: 3, end_column: 31 });
+        assert_eq!(span(2), SourceSpan { column: 1, end_line: 4, end_column: 12 });
+    }
 }
@@ -4,6 +4,8 @@
 //! supporting various matching strategies including equals, regex, glob, and composite matches.
 
 use crate::detectors::dockerfile::yaml_rules::{Matcher, Predicate};
+use crate::docker::model::MatchedField;
+use globset::GlobMatcher;
 use serde_yml::Value;
 use std::collections::HashMap;
 
@@ -89,7 +91,7 @@ fn matches_predicate(pred: &Predicate, context: &HashMap<String, Value>) -> bool
 /// * `context` - The instruction's data
 /// * `equals` - Expected exact value (optional)
 /// * `regex` - Compiled regex pattern (optional)
-/// * `glob` - Glob pattern (optional)
+/// * `glob` - Compiled glob pattern (optional)
 /// * `missing` - Whether field should be absent (optional)
 ///
 /// # Returns
@@ -100,7 +102,7 @@ fn matches_field_conditions(
     context: &HashMap<String, Value>,
     equals: Option<&Value>,
     regex: Option<&regex::Regex>,
-    glob: Option<&String>,
+    glob: Option<&GlobMatcher>,
     missing: Option<bool>,
 ) -> bool {
     let value = context.get(field);
@@ -128,10 +130,10 @@ fn matches_field_conditions(
         return false;
     }
 
-    // Check glob
-    if let Some(pattern) = glob {
+    // Check glob (compiled once when the rule was loaded)
+    if let Some(glob) = glob {
         if let Value::String(s) = value {
-            return glob_match(pattern, s);
+            return glob.is_match(s);
         }
         return false;
     }
@@ -139,35 +141,59 @@ fn matches_field_conditions(
     true
 }
 
-/// Performs glob-style pattern matching.
+/// Lists the fields a matching `matcher` tested, with their values in
+/// `context`, for explaining a finding.
 ///
-/// Converts glob patterns to regex:
-/// - `*` matches any sequence of characters
-/// - `?` matches a single character
-/// - `.` is treated as a literal dot
+/// Includes the direct field and every `all` predicate, but only the `any`
+/// predicates that matched.
 ///
 /// # Arguments
 ///
-/// * `pattern` - Glob pattern (e.g., "*.txt", "file??.log")
-/// * `text` - Text to match against
+/// * `matcher` - A matcher that matched `context`
+/// * `context` - Key-value map extracted from the instruction
 ///
 /// # Returns
 ///
-/// `true` if the text matches the glob pattern
-fn glob_match(pattern: &str, text: &str) -> bool {
-    let regex_pattern = pattern
-        .replace(".", "\\.")
-        .replace("*", ".*")
-        .replace("?", ".");
-
-    regex::Regex::new(&format!("^{}$", regex_pattern))
-        .map(|re| re.is_match(text))
-        .unwrap_or(false)
+/// One entry per distinct field, in rule order; missing fields have no value
+pub fn matched_fields(matcher: &Matcher, context: &HashMap<String, Value>) -> Vec<MatchedField> {
+    let all = matcher.all.iter().flatten();
+    let any = matcher
+        .any
+        .iter()
+        .flatten()
+        .filter(|p| matches_predicate(p, context));
+    let fields = all
+        .chain(any)
+        .filter_map(|p| p.field.as_deref())
+        .chain(matcher.field.as_deref());
+
+    let mut out: Vec<MatchedField> = Vec::new();
+    for field in fields {
+        if out.iter().all(|f| f.path != field) {
+            out.push(MatchedField::new(field, context.get(field).map(display_value)));
+        }
+    }
+    out
+}
+
+/// Renders a context value the way it reads in a Dockerfile.
+fn display_value(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => serde_yml::to_string(other)
+            .map(|s| s.trim_end().to_string())
+            .unwrap_or_default(),
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::detectors::dockerfile::yaml_rules::compile_glob;
+
+    fn glob_match(pattern: &str, text: &str) -> bool {
+        compile_glob(pattern).unwrap().is_match(text)
+    }
 
     #[test]
     fn test_glob_match_wildcard() {
@@ -189,6 +215,24 @@ mod tests {
         assert!(!glob_match("file.txt", "fileXtxt"));
     }
 
+    #[test]
+    fn test_glob_match_star_crosses_slashes() {
+        assert!(glob_match("*.sh", "/usr/local/bin/entrypoint.sh"));
+        assert!(glob_match("http*", "https://example.com/install.sh"));
+    }
+
+    #[test]
+    fn test_matches_field_conditions_glob() {
+        let mut context = HashMap::new();
+        context.insert("src".to_string(), Value::String("app/.env".to_string()));
+
+        let glob = compile_glob("*.env").unwrap();
+        assert!(matches_field_conditions("src", &context, None, None, Some(&glob), None));
+
+        let glob = compile_glob("*.key").unwrap();
+        assert!(!matches_field_conditions("src", &context, None, None, Some(&glob), None));
+    }
+
     #[test]
     fn test_matches_field_conditions_equals() {
         let mut context = HashMap::new();
@@ -382,4 +426,37 @@ mod tests {
 
         assert!(matches_matcher(&matcher, &context));
     }
+
+    #[test]
+    fn test_matched_fields_lists_tested_fields_and_values() {
+        let matcher: Matcher = serde_yml::from_str(
+            r#"
+all:
+  - field: user
+    equals: root
+any:
+  - field: port
+    equals: "22"
+  - field: proto
+    equals: udp
+field: healthcheck
+missing: true
+"#,
+        )
+        .unwrap();
+        let mut context = HashMap::new();
+        context.insert("user".to_string(), Value::String("root".to_string()));
+        context.insert("port".to_string(), Value::String("22".to_string()));
+        context.insert("proto".to_string(), Value::String("tcp".to_string()));
+
+        assert!(matches_matcher(&matcher, &context));
+        assert_eq!(
+            matched_fields(&matcher, &context),
+            vec![
+                MatchedField::new("user", Some("root".to_string())),
+                MatchedField::new("port", Some("22".to_string())),
+                MatchedField::new("healthcheck", None),
+            ]
+        );
+    }
 }
@@ -11,6 +11,7 @@
 //! * [`yaml_rules`] - YAML rule definitions and loading
 //! * [`matcher`] - Rule matching logic (regex, glob, predicates)
 //! * [`instruction_utils`] - Utilities for working with Dockerfile instructions
+//! * [`fs`] - Filesystem abstraction for scanning in-memory Dockerfiles
 //!
 //! For output formatting, see the unified [`crate::output`] module:
 //! - [`crate::output::printer`] - Visual console output
@@ -42,3 +43,4 @@ pub mod scanner;
 pub mod yaml_rules;
 pub mod matcher;
 pub mod instruction_utils;
+pub mod fs;
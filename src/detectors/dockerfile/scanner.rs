@@ -3,30 +3,285 @@
 //! This module orchestrates the scanning of Dockerfiles for security issues
 //! and misconfigurations using YAML-defined rules.
 
-use anyhow::{Context, anyhow};
+use std::collections::HashMap;
 use std::fs::read_to_string;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Instant, SystemTime};
 use dockerfile_parser::{Dockerfile, Instruction};
 
-use crate::detectors::dockerfile::yaml_rules::{self, Rule, Severity};
-use crate::docker::model::{Finding, RiskLevel};
-use crate::output::printer::{print_scan_report, ScanContext};
+use crate::detectors::dockerfile::fs::FileSystem;
+use crate::detectors::dockerfile::yaml_rules::{
+    self, InstructionKinds, InstructionPattern, Rule, Severity,
+};
+use crate::docker::model::{Finding, FindingTarget, MatchedField, RiskLevel};
+#[cfg(feature = "cli")]
+use crate::output::printer::{print_scan_report, print_scan_summary, print_status_line, PlainTableWriter, ScanContext};
+use crate::output::anonymize::Anonymizer;
+use crate::output::redact;
+use crate::output::severity::SeverityScheme;
+use crate::output::metadata::ScanMetadata;
 use crate::output::exporters::{export_scan_results, ScanSource};
-use crate::detectors::dockerfile::matcher::matches_matcher;
+use crate::output::score::{ScoreWeights, TargetScore};
+use crate::output::summary::{ScanSummary, TargetKind};
+use crate::detectors::dockerfile::matcher::{matched_fields, matches_matcher};
 use crate::detectors::dockerfile::instruction_utils::{
     get_instruction_kind,
     instruction_to_map,
     get_line_number,
+    get_raw_text,
+    get_span,
     find_last_user_instruction,
 };
-use crate::cli::OutputFormat;
+use crate::cli::{OutputFormat, SeverityLevel};
+use crate::config::{PathOverrides, SeverityMap, TagThresholds};
+use crate::error::{Result, ValerisError};
+use crate::bench::{RuleKind, RuleProfile};
+use crate::history::SeenIndex;
+use crate::i18n::MessageCatalog;
+use crate::observer::{ScanObserver, ScanTarget};
+
+/// Options for a Dockerfile scan.
+///
+/// Built with [`DockerfileScanOptions::new`] and the `with_*` methods; every
+/// option not set keeps its default (no filters, table output to stdout).
+///
+/// # Example
+///
+/// ```no_run
+/// use valeris::cli::{OutputFormat, SeverityLevel};
+/// use valeris::detectors::dockerfile::scanner::{scan_dockerfile_with_options, DockerfileScanOptions};
+///
+/// let options = DockerfileScanOptions::new("./Dockerfile", "./rules/dockerfile")
+///     .with_min_severity(SeverityLevel::Medium)
+///     .with_fail_on(SeverityLevel::High)
+///     .with_format(OutputFormat::Json)
+///     .with_output("report.json");
+///
+/// let should_fail = scan_dockerfile_with_options(&options)?;
+/// # Ok::<(), valeris::error::ValerisError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct DockerfileScanOptions {
+    /// Path to the Dockerfile to scan
+    pub path: PathBuf,
+    /// Directory containing YAML rule definitions
+    pub rules_dir: PathBuf,
+    /// Rule IDs to run exclusively
+    pub only: Option<Vec<String>>,
+    /// Rule IDs to skip
+    pub exclude: Option<Vec<String>>,
+    /// Exact severity levels to keep
+    pub severity: Option<Vec<SeverityLevel>>,
+    /// Minimum severity to keep
+    pub min_severity: Option<SeverityLevel>,
+    /// Severity at or above which the scan fails
+    pub fail_on: Option<SeverityLevel>,
+    /// Risk points (summed [`ScoreWeights`]) above which the scan fails
+    pub fail_score: Option<u32>,
+    /// Suppress console output (report files are still written)
+    pub quiet: bool,
+    /// Output format
+    pub format: OutputFormat,
+    /// File to write the report to (stdout if `None`)
+    pub output_file: Option<PathBuf>,
+    /// How severities are named in the table and report
+    pub severity_scheme: SeverityScheme,
+    /// Severity remapping applied before filtering
+    pub severity_map: SeverityMap,
+    /// Exclusions and severity remapping for matching Dockerfile paths
+    pub path_overrides: PathOverrides,
+    /// Minimum severity and fail-on thresholds per rule tag
+    pub tag_thresholds: TagThresholds,
+    /// Receives findings and completion events
+    pub observer: Option<Arc<dyn ScanObserver>>,
+    /// Annotates findings with their first and last sighting
+    pub history: Option<SeenIndex>,
+    /// Weights of the Dockerfile's security score in the summary
+    pub score_weights: ScoreWeights,
+    /// Language of the rule message catalog to use (e.g. `es`)
+    pub lang: Option<String>,
+    /// Replace the Dockerfile path in the output with a pseudonym
+    pub anonymize: bool,
+    /// Keep each finding's matched fields and values ([`Finding::debug`])
+    pub explain: bool,
+    /// Mask the values of secret findings ([`crate::output::redact`])
+    pub redact_secrets: bool,
+    /// Records each rule's evaluation time and findings (`--profile-rules`)
+    pub profile: Option<Arc<RuleProfile>>,
+    /// Record in the report metadata that the scan ran with `--offline`
+    pub offline: bool,
+}
+
+impl DockerfileScanOptions {
+    /// Creates options for scanning `path` with the rules in `rules_dir`.
+    pub fn new(path: impl Into<PathBuf>, rules_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            rules_dir: rules_dir.into(),
+            only: None,
+            exclude: None,
+            severity: None,
+            min_severity: None,
+            fail_on: None,
+            fail_score: None,
+            quiet: false,
+            format: OutputFormat::Table,
+            output_file: None,
+            severity_scheme: SeverityScheme::default(),
+            severity_map: SeverityMap::default(),
+            path_overrides: PathOverrides::default(),
+            tag_thresholds: TagThresholds::default(),
+            observer: None,
+            history: None,
+            score_weights: ScoreWeights::default(),
+            lang: None,
+            anonymize: false,
+            explain: false,
+            redact_secrets: false,
+            profile: None,
+            offline: false,
+        }
+    }
+
+    /// Runs only the given rule IDs
+    pub fn with_only(mut self, ids: Vec<String>) -> Self {
+        self.only = Some(ids);
+        self
+    }
+
+    /// Skips the given rule IDs
+    pub fn with_exclude(mut self, ids: Vec<String>) -> Self {
+        self.exclude = Some(ids);
+        self
+    }
+
+    /// Keeps only findings with exactly these severities
+    pub fn with_severity(mut self, levels: Vec<SeverityLevel>) -> Self {
+        self.severity = Some(levels);
+        self
+    }
+
+    /// Keeps only findings at or above `level`
+    pub fn with_min_severity(mut self, level: SeverityLevel) -> Self {
+        self.min_severity = Some(level);
+        self
+    }
+
+    /// Fails the scan if any finding is at or above `level`
+    pub fn with_fail_on(mut self, level: SeverityLevel) -> Self {
+        self.fail_on = Some(level);
+        self
+    }
+
+    /// Fails the scan if the findings' risk points exceed `points`
+    pub fn with_fail_score(mut self, points: u32) -> Self {
+        self.fail_score = Some(points);
+        self
+    }
+
+    /// Suppresses console output
+    pub fn with_quiet(mut self) -> Self {
+        self.quiet = true;
+        self
+    }
+
+    /// Sets the output format
+    pub fn with_format(mut self, format: OutputFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Writes the report to `path` instead of stdout
+    pub fn with_output(mut self, path: impl Into<PathBuf>) -> Self {
+        self.output_file = Some(path.into());
+        self
+    }
+
+    /// Names severities in the table and report with `scheme`
+    pub fn with_severity_scheme(mut self, scheme: SeverityScheme) -> Self {
+        self.severity_scheme = scheme;
+        self
+    }
+
+    /// Remaps finding severities before filtering
+    pub fn with_severity_map(mut self, severity_map: SeverityMap) -> Self {
+        self.severity_map = severity_map;
+        self
+    }
+
+    /// Applies `[override."<glob>"]` adjustments when the path matches
+    pub fn with_path_overrides(mut self, overrides: PathOverrides) -> Self {
+        self.path_overrides = overrides;
+        self
+    }
+
+    /// Uses per-tag minimum severity and fail-on thresholds
+    pub fn with_tag_thresholds(mut self, thresholds: TagThresholds) -> Self {
+        self.tag_thresholds = thresholds;
+        self
+    }
+
+    /// Notifies `observer` of findings and scan completion
+    pub fn with_observer(mut self, observer: Arc<dyn ScanObserver>) -> Self {
+        self.observer = Some(observer);
+        self
+    }
+
+    /// Annotates findings with `first_seen`, `last_seen`, and `is_new` from
+    /// the findings history
+    pub fn with_history(mut self, index: SeenIndex) -> Self {
+        self.history = Some(index);
+        self
+    }
+
+    /// Sets the weights of the security score
+    pub fn with_score_weights(mut self, weights: ScoreWeights) -> Self {
+        self.score_weights = weights;
+        self
+    }
+
+    /// Reports rule messages from the `lang` catalog of the rules directory
+    pub fn with_lang(mut self, lang: impl Into<String>) -> Self {
+        self.lang = Some(lang.into());
+        self
+    }
+
+    /// Reports the Dockerfile under a pseudonym (see [`Anonymizer`])
+    pub fn with_anonymize(mut self) -> Self {
+        self.anonymize = true;
+        self
+    }
+
+    /// Reports which fields each finding matched and their values
+    pub fn with_explain(mut self) -> Self {
+        self.explain = true;
+        self
+    }
+
+    /// Masks secret values in findings, keeping the key names
+    pub fn with_redact_secrets(mut self) -> Self {
+        self.redact_secrets = true;
+        self
+    }
+
+    /// Records each rule's evaluation time and findings in `profile`
+    pub fn with_rule_profile(mut self, profile: Arc<RuleProfile>) -> Self {
+        self.profile = Some(profile);
+        self
+    }
+
+    /// Records in the report metadata that the scan ran without network access
+    pub fn with_offline(mut self) -> Self {
+        self.offline = true;
+        self
+    }
+}
 
 /// Scans a Dockerfile for security issues and misconfigurations.
 ///
-/// This function performs a three-level analysis:
-/// 1. Instruction-level checks (individual FROM, RUN, ENV, etc.)
-/// 2. Stage-level checks (entire build stage properties)
-/// 3. File-level checks (global properties like .dockerignore)
+/// Thin wrapper around [`scan_dockerfile_with_options`], kept for backward
+/// compatibility.
 ///
 /// # Arguments
 ///
@@ -37,7 +292,7 @@ use crate::cli::OutputFormat;
 /// * `severity` - Optional exact severity levels to filter
 /// * `min_severity` - Optional minimum severity threshold
 /// * `fail_on` - Optional severity level to trigger exit code 1
-/// * `quiet` - Suppress all output
+/// * `quiet` - Suppress console output (report files are still written)
 /// * `format` - Output format (Table, JSON, or CSV)
 /// * `output_file` - Optional file path to write output to
 ///
@@ -46,88 +301,389 @@ use crate::cli::OutputFormat;
 /// Returns `Ok(bool)` where the boolean indicates whether the scan should fail
 /// (true if fail_on threshold was met), or an error if the file couldn't be
 /// read or parsed
-///
-/// # Example
-///
-/// ```no_run
-/// use std::path::PathBuf;
-/// use valeris::detectors::dockerfile::scanner::scan_dockerfile;
-/// use valeris::cli::OutputFormat;
-///
-/// let result = scan_dockerfile(
-///     PathBuf::from("./Dockerfile"),
-///     PathBuf::from("./rules/dockerfile"),
-///     None,
-///     None,
-///     None,
-///     None,
-///     None,
-///     false,
-///     OutputFormat::Table,
-///     None
-/// );
-/// ```
 #[allow(clippy::too_many_arguments)]
 pub fn scan_dockerfile(
     path: PathBuf,
     rules_dir: PathBuf,
     only: Option<Vec<String>>,
     exclude: Option<Vec<String>>,
-    severity: Option<Vec<crate::cli::SeverityLevel>>,
-    min_severity: Option<crate::cli::SeverityLevel>,
-    fail_on: Option<crate::cli::SeverityLevel>,
+    severity: Option<Vec<SeverityLevel>>,
+    min_severity: Option<SeverityLevel>,
+    fail_on: Option<SeverityLevel>,
     quiet: bool,
     format: OutputFormat,
     output_file: Option<PathBuf>,
-) -> anyhow::Result<bool> {
-    let content = read_to_string(&path)
-        .with_context(|| format!("reading {}", path.display()))?;
+) -> Result<bool> {
+    scan_dockerfile_with_severity_map(
+        path,
+        rules_dir,
+        only,
+        exclude,
+        severity,
+        min_severity,
+        fail_on,
+        quiet,
+        format,
+        output_file,
+        &SeverityMap::default(),
+    )
+}
 
-    let dockerfile = Dockerfile::parse(&content)
-        .map_err(|e| anyhow!("Error parsing Dockerfile: {:?}", e))?;
+/// Scans a Dockerfile like [`scan_dockerfile`], remapping finding severities
+/// with `severity_map` before filtering, output, and fail-on evaluation.
+///
+/// Thin wrapper around [`scan_dockerfile_with_options`], kept for backward
+/// compatibility.
+#[allow(clippy::too_many_arguments)]
+pub fn scan_dockerfile_with_severity_map(
+    path: PathBuf,
+    rules_dir: PathBuf,
+    only: Option<Vec<String>>,
+    exclude: Option<Vec<String>>,
+    severity: Option<Vec<SeverityLevel>>,
+    min_severity: Option<SeverityLevel>,
+    fail_on: Option<SeverityLevel>,
+    quiet: bool,
+    format: OutputFormat,
+    output_file: Option<PathBuf>,
+    severity_map: &SeverityMap,
+) -> Result<bool> {
+    scan_dockerfile_with_options(&DockerfileScanOptions {
+        path,
+        rules_dir,
+        only,
+        exclude,
+        severity,
+        min_severity,
+        fail_on,
+        fail_score: None,
+        quiet,
+        format,
+        output_file,
+        severity_scheme: SeverityScheme::default(),
+        severity_map: severity_map.clone(),
+        path_overrides: PathOverrides::default(),
+        tag_thresholds: TagThresholds::default(),
+        observer: None,
+        history: None,
+        score_weights: ScoreWeights::default(),
+        lang: None,
+        anonymize: false,
+        explain: false,
+        redact_secrets: false,
+        profile: None,
+        offline: false,
+    })
+}
+
+/// Findings and statistics from a completed Dockerfile scan.
+#[derive(Debug)]
+pub struct DockerfileScanOutcome {
+    /// Dockerfile path as reported, anonymized with `anonymize`
+    pub path: PathBuf,
+    /// Findings after severity remapping and filtering
+    pub findings: Vec<Finding>,
+    /// Scan statistics, including whether fail-on was triggered
+    pub summary: ScanSummary,
+}
 
-    let mut ruleset = yaml_rules::load_rules_from_dir(rules_dir.as_path())?;
+/// Scans a Dockerfile like [`scan_dockerfile_report`], returning only
+/// whether the fail-on threshold was met.
+pub fn scan_dockerfile_with_options(options: &DockerfileScanOptions) -> Result<bool> {
+    Ok(scan_dockerfile_report(options)?.summary.fail_on_triggered)
+}
+
+/// Scans a Dockerfile for security issues and misconfigurations.
+///
+/// This function performs a three-level analysis:
+/// 1. Instruction-level checks (individual FROM, RUN, ENV, etc.)
+/// 2. Stage-level checks (entire build stage properties)
+/// 3. File-level checks (global properties like .dockerignore)
+///
+/// Findings excluded by a matching path override are dropped and the rest
+/// remapped with the options' severity map (extended by those overrides),
+/// then filtered, reported, and checked against the fail-on threshold.
+///
+/// # Arguments
+///
+/// * `options` - What to scan and how to filter and report it
+///
+/// # Returns
+///
+/// The remaining findings and scan summary, or an error if the file couldn't
+/// be read or parsed
+pub fn scan_dockerfile_report(options: &DockerfileScanOptions) -> Result<DockerfileScanOutcome> {
+    let started = Instant::now();
+    let mut metadata = ScanMetadata::new(SystemTime::now())
+        .with_rules(&options.rules_dir)
+        .with_filter("only", options.only.as_ref())
+        .with_filter("exclude", options.exclude.as_ref())
+        .with_filter("severity", options.severity.as_ref())
+        .with_filter("min_severity", options.min_severity.as_ref())
+        .with_filter("fail_on", options.fail_on.as_ref())
+        .with_filter("fail_score", options.fail_score.as_ref())
+        .with_offline(options.offline);
+    let path = &options.path;
+    let content = read_to_string(path).map_err(|source| ValerisError::DockerfileRead {
+        path: path.clone(),
+        source,
+    })?;
+
+    let dockerfile = Dockerfile::parse(&content).map_err(|e| ValerisError::DockerfileParse {
+        path: path.clone(),
+        message: format!("{:?}", e),
+    })?;
+
+    let mut ruleset = yaml_rules::load_rules_from_dir(options.rules_dir.as_path())?;
+    if let Some(lang) = &options.lang {
+        yaml_rules::localize(&mut ruleset.rules, &MessageCatalog::load(&options.rules_dir, lang)?);
+    }
 
     // Apply rule filtering (only/exclude)
-    filter_rules(&mut ruleset.rules, only.as_ref(), exclude.as_ref());
+    filter_rules(&mut ruleset.rules, options.only.as_ref(), options.exclude.as_ref());
+
+    let mut all_findings = evaluate_rules(&dockerfile, &ruleset.rules, &content, path);
+    if let Some(profile) = &options.profile {
+        profile_rules(profile, &dockerfile, &ruleset.rules, &content, path);
+    }
+    // Redaction learns the secret values from the matched fields
+    if options.redact_secrets {
+        redact::redact_findings(&mut all_findings);
+    }
+    if !options.explain {
+        all_findings.iter_mut().for_each(|f| f.debug.clear());
+    }
+
+    // Apply per-path exclusions and remap severities before any filtering
+    options.path_overrides.apply(path, &options.severity_map, &mut all_findings);
+
+    // Apply severity filtering
+    filter_findings_by_severity(
+        &mut all_findings,
+        options.severity.as_ref(),
+        options.min_severity.as_ref(),
+        &options.tag_thresholds,
+    );
+
+    if let Some(history) = &options.history {
+        history.annotate_dockerfile(path, &mut all_findings);
+    }
+
+    // Check if we should fail based on fail_on threshold or risk points
+    let should_fail = should_fail_scan(&all_findings, options.fail_on.as_ref(), &options.tag_thresholds)
+        || options.score_weights.exceeds(&all_findings, options.fail_score);
+
+    let shown_path = if options.anonymize {
+        let mut anonymizer = Anonymizer::new();
+        anonymizer.findings(&mut all_findings);
+        anonymizer.metadata(&mut metadata);
+        anonymizer.path(path)
+    } else {
+        path.clone()
+    };
+    let path = &shown_path;
+
+    let summary = ScanSummary::new(
+        TargetKind::Dockerfiles,
+        1,
+        ruleset.rules.len(),
+        &all_findings,
+        started.elapsed(),
+        should_fail,
+    )
+    .with_scores(vec![TargetScore {
+        target: path.display().to_string(),
+        score: options.score_weights.score(&all_findings),
+    }])
+    .with_metadata(metadata);
+
+    if let Some(observer) = &options.observer {
+        for finding in &all_findings {
+            observer.on_finding(ScanTarget::Dockerfile(path), finding);
+        }
+        observer.on_complete(&summary);
+    }
+
+    // Quiet mode silences the console but still writes report files
+    let writes_file = options.output_file.is_some();
+    if !options.quiet || writes_file {
+        output_results(
+            path,
+            &all_findings,
+            options.format.clone(),
+            options.output_file.clone(),
+            &summary,
+            options.severity_scheme,
+        )?;
+    }
+    #[cfg(feature = "cli")]
+    if !options.quiet {
+        print_status_line(&summary);
+    }
 
-    let mut all_findings = Vec::new();
+    Ok(DockerfileScanOutcome {
+        path: path.clone(),
+        findings: all_findings,
+        summary,
+    })
+}
+
+/// Evaluates rules against a parsed Dockerfile at every level.
+///
+/// # Arguments
+///
+/// * `dockerfile` - Parsed Dockerfile
+/// * `rules` - Rules to evaluate
+/// * `content` - Raw Dockerfile content (for line number calculation)
+/// * `path` - Path to the Dockerfile (for file-level checks)
+///
+/// # Returns
+///
+/// Findings from instruction-, stage-, correlation-, and file-level rules,
+/// in that order, each targeting `path`
+pub fn evaluate_rules(
+    dockerfile: &Dockerfile,
+    rules: &[Rule],
+    content: &str,
+    path: &Path,
+) -> Vec<Finding> {
+    let has_dockerignore = needs_dockerignore(rules, dockerfile)
+        && dockerignore_path(path).is_some_and(|p| p.exists());
+    evaluate_rules_with(dockerfile, rules, content, path, has_dockerignore)
+}
+
+/// Times each rule on its own against `dockerfile`, as `valeris bench` does,
+/// and records it in `profile`.
+///
+/// The scan evaluates rules level by level (instructions, stages, ...)
+/// sharing indexes between them, so rules are timed in a separate pass.
+fn profile_rules(profile: &RuleProfile, dockerfile: &Dockerfile, rules: &[Rule], content: &str, path: &Path) {
+    for rule in rules {
+        let started = Instant::now();
+        let findings = evaluate_rules(dockerfile, std::slice::from_ref(rule), content, path);
+        profile.record(rule.id(), RuleKind::Dockerfile, started.elapsed(), findings.len());
+    }
+}
+
+/// Scans Dockerfile content held in memory.
+///
+/// Unlike [`scan_dockerfile_report`], nothing is read from or written to
+/// disk by the scanner itself: the `.dockerignore` check goes through `fs`,
+/// and the findings are returned unfiltered instead of being reported.
+///
+/// # Arguments
+///
+/// * `content` - Dockerfile source
+/// * `path` - Where the Dockerfile lives; sets the findings' target and
+///   locates `.dockerignore` next to it
+/// * `rules` - Rules to evaluate, e.g. from [`yaml_rules::load_rules_from_dir`]
+/// * `fs` - Filesystem used to look up `.dockerignore`
+///
+/// # Errors
+///
+/// * [`ValerisError::DockerfileParse`] if `content` is not a valid Dockerfile
+///
+/// # Example
+///
+/// ```no_run
+/// use std::path::Path;
+/// use valeris::detectors::dockerfile::fs::MemoryFileSystem;
+/// use valeris::detectors::dockerfile::scanner::scan_dockerfile_content;
+/// use valeris::detectors::dockerfile::yaml_rules::load_rules_from_dir;
+///
+/// # async fn example() -> valeris::error::Result<()> {
+/// let ruleset = load_rules_from_dir(Path::new("./rules/dockerfile"))?;
+/// let fs = MemoryFileSystem::new().with_file("app/.dockerignore");
+///
+/// let findings = scan_dockerfile_content(
+///     "FROM ubuntu:latest\nCOPY . /app\n",
+///     Path::new("app/Dockerfile"),
+///     &ruleset.rules,
+///     &fs,
+/// )
+/// .await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn scan_dockerfile_content(
+    content: &str,
+    path: &Path,
+    rules: &[Rule],
+    fs: &dyn FileSystem,
+) -> Result<Vec<Finding>> {
+    let dockerfile = Dockerfile::parse(content).map_err(|e| ValerisError::DockerfileParse {
+        path: path.to_path_buf(),
+        message: format!("{:?}", e),
+    })?;
+
+    let has_dockerignore = match dockerignore_path(path) {
+        Some(ignore) if needs_dockerignore(rules, &dockerfile) => fs.exists(&ignore).await,
+        _ => false,
+    };
+
+    Ok(evaluate_rules_with(&dockerfile, rules, content, path, has_dockerignore))
+}
+
+/// Evaluates rules at every level, with the `.dockerignore` lookup
+/// already resolved.
+fn evaluate_rules_with(
+    dockerfile: &Dockerfile,
+    rules: &[Rule],
+    content: &str,
+    path: &Path,
+    has_dockerignore: bool,
+) -> Vec<Finding> {
+    let mut findings = Vec::new();
 
     // Scan at instruction level
-    all_findings.extend(scan_instructions(&dockerfile, &ruleset.rules, &content));
+    findings.extend(scan_instructions(dockerfile, rules, content));
 
     // Scan at stage level
-    all_findings.extend(scan_stages(&dockerfile, &ruleset.rules, &content));
+    findings.extend(scan_stages(dockerfile, rules, content));
 
-    // Scan at file level
-    all_findings.extend(scan_file(&dockerfile, &ruleset.rules, &path));
+    // Scan relationships between instructions in each stage
+    findings.extend(scan_correlations(dockerfile, rules, content));
 
-    // Apply severity filtering
-    filter_findings_by_severity(&mut all_findings, severity.as_ref(), min_severity.as_ref());
-
-    // Check if we should fail based on fail_on threshold
-    let should_fail = should_fail_scan(&all_findings, fail_on.as_ref());
+    // Scan at file level
+    findings.extend(scan_file(dockerfile, rules, has_dockerignore));
 
-    // Output results based on format (unless quiet mode)
-    if !quiet {
-        output_results(&path, &all_findings, format, output_file)?;
+    let target = FindingTarget::File { path: path.to_path_buf() };
+    for finding in &mut findings {
+        finding.target = Some(target.clone());
     }
 
-    Ok(should_fail)
+    findings
 }
 
 /// Outputs scan results in the specified format.
+///
+/// Table output needs the `cli` feature; without it, nothing is printed or
+/// written.
 fn output_results(
     path: &PathBuf,
     findings: &[Finding],
     format: OutputFormat,
     output_file: Option<PathBuf>,
-) -> anyhow::Result<()> {
+    summary: &ScanSummary,
+    scheme: SeverityScheme,
+) -> Result<()> {
     match format {
-        OutputFormat::Table => {
-            // Table format goes to stdout
-            print_scan_report(ScanContext::Dockerfile(path), findings);
-        }
+        #[cfg(feature = "cli")]
+        OutputFormat::Table => match output_file {
+            Some(output_file) => {
+                let mut writer = PlainTableWriter::create(output_file, scheme).map_err(ValerisError::Export)?;
+                writer
+                    .write_report(ScanContext::Dockerfile(path), findings)
+                    .and_then(|_| writer.finish(summary))
+                    .map_err(ValerisError::Export)?;
+            }
+            None => {
+                print_scan_report(ScanContext::Dockerfile(path), findings, scheme);
+                print_scan_summary(summary, scheme);
+            }
+        },
+        #[cfg(not(feature = "cli"))]
+        OutputFormat::Table => {}
         _ => {
             // Use unified exporter for JSON and CSV
             export_scan_results(
@@ -137,7 +693,10 @@ fn output_results(
                 },
                 &format,
                 &output_file.as_ref().map(|p| p.display().to_string()),
-            )?;
+                summary,
+                scheme,
+            )
+            .map_err(ValerisError::Export)?;
         }
     }
 
@@ -146,6 +705,9 @@ fn output_results(
 
 /// Scans all instructions in all stages for rule violations.
 ///
+/// Instruction rules are indexed by kind up front, so each instruction is
+/// only checked against the rules that target its kind (or every kind).
+///
 /// # Arguments
 ///
 /// * `dockerfile` - Parsed Dockerfile
@@ -161,11 +723,16 @@ fn scan_instructions(
     content: &str,
 ) -> Vec<Finding> {
     let mut findings = Vec::new();
+    let index = index_instruction_rules(rules);
 
     for stage in dockerfile.iter_stages() {
         for instruction in &stage.instructions {
+            let kind_rules = index.candidates(&get_instruction_kind(instruction));
+            if kind_rules.is_empty() {
+                continue;
+            }
             findings.extend(check_instruction_rules(
-                rules,
+                kind_rules,
                 instruction,
                 stage.index,
                 content,
@@ -201,13 +768,41 @@ fn scan_stages(
     findings
 }
 
+/// Scans all build stages for correlation rule violations.
+///
+/// # Arguments
+///
+/// * `dockerfile` - Parsed Dockerfile
+/// * `rules` - List of rule definitions
+/// * `content` - Raw Dockerfile content (for `raw` fields and line numbers)
+///
+/// # Returns
+///
+/// Vector of findings from correlation rules
+fn scan_correlations(
+    dockerfile: &Dockerfile,
+    rules: &[Rule],
+    content: &str,
+) -> Vec<Finding> {
+    if !rules.iter().any(|rule| matches!(rule, Rule::Correlation { .. })) {
+        return Vec::new();
+    }
+
+    let mut findings = Vec::new();
+    for stage in dockerfile.iter_stages() {
+        findings.extend(check_correlation_rules(rules, &stage, content));
+    }
+
+    findings
+}
+
 /// Scans the entire Dockerfile for file-level rule violations.
 ///
 /// # Arguments
 ///
 /// * `dockerfile` - Parsed Dockerfile
 /// * `rules` - List of rule definitions
-/// * `path` - Path to the Dockerfile
+/// * `has_dockerignore` - Whether a `.dockerignore` exists next to the Dockerfile
 ///
 /// # Returns
 ///
@@ -215,38 +810,83 @@ fn scan_stages(
 fn scan_file(
     dockerfile: &Dockerfile,
     rules: &[Rule],
-    path: &Path,
+    has_dockerignore: bool,
 ) -> Vec<Finding> {
-    check_file_rules(rules, dockerfile, path)
+    check_file_rules(rules, dockerfile, has_dockerignore)
+}
+
+/// Instruction-scoped rules grouped by the instruction kind they target.
+struct InstructionRuleIndex<'a> {
+    rules: &'a [Rule],
+    /// Instruction kind -> indices of rules listing it
+    by_kind: HashMap<&'a str, Vec<usize>>,
+    /// Rules with `kind: "*"`
+    any: Vec<usize>,
+}
+
+impl<'a> InstructionRuleIndex<'a> {
+    /// Returns the rules that apply to an instruction of `kind`, in load order.
+    fn candidates(&self, kind: &str) -> Vec<&'a Rule> {
+        let mut indices: Vec<usize> = self
+            .by_kind
+            .get(kind)
+            .into_iter()
+            .flatten()
+            .chain(&self.any)
+            .copied()
+            .collect();
+        indices.sort_unstable();
+        indices.dedup();
+        indices.into_iter().map(|i| &self.rules[i]).collect()
+    }
+}
+
+/// Groups instruction-scoped rules by the instruction kinds they target.
+fn index_instruction_rules(rules: &[Rule]) -> InstructionRuleIndex<'_> {
+    let mut index = InstructionRuleIndex {
+        rules,
+        by_kind: HashMap::new(),
+        any: Vec::new(),
+    };
+    for (i, rule) in rules.iter().enumerate() {
+        match rule {
+            Rule::Instruction { kind: InstructionKinds::Any, .. } => index.any.push(i),
+            Rule::Instruction { kind: InstructionKinds::Only(kinds), .. } => {
+                for kind in kinds {
+                    index.by_kind.entry(kind.as_str()).or_default().push(i);
+                }
+            }
+            _ => {}
+        }
+    }
+    index
 }
 
 /// Checks a single instruction against all instruction-scoped rules.
-fn check_instruction_rules(
-    rules: &[Rule],
+fn check_instruction_rules<'a>(
+    rules: impl IntoIterator<Item = &'a Rule>,
     ins: &Instruction,
     stage_index: usize,
     content: &str,
 ) -> Vec<Finding> {
     let mut findings = Vec::new();
+    let ins_kind = get_instruction_kind(ins);
+    let mut context = None;
 
     for rule in rules {
-        if let Rule::Instruction { id, kind, matcher, severity, message, .. } = rule {
-            let ins_kind = get_instruction_kind(ins);
-
-            if &ins_kind != kind {
+        if let Rule::Instruction { kind, matcher, message, .. } = rule {
+            if !kind.matches(&ins_kind) {
                 continue;
             }
 
-            let context = instruction_to_map(ins);
-
-            if matches_matcher(matcher, &context) {
-                let line = get_line_number(ins, content);
+            let context = context.get_or_insert_with(|| instruction_context(ins, content));
 
+            if matches_matcher(matcher, context) {
                 findings.push(Finding {
-                    kind: id.clone(),
-                    description: format!("Stage {}: {}", stage_index, message),
-                    risk: severity_to_risk(severity),
-                    line,
+                    line: get_line_number(ins, content),
+                    span: get_span(ins, content),
+                    debug: matched_fields(matcher, context),
+                    ..rule_finding(rule, format!("Stage {}: {}", stage_index, message))
                 });
             }
         }
@@ -264,19 +904,18 @@ fn check_stage_rules(
     let mut findings = Vec::new();
 
     for rule in rules {
-        if let Rule::Stage { id, when, severity, message, .. } = rule {
+        if let Rule::Stage { when, message, .. } = rule {
             if when.must_end_non_root {
                 let last_user = find_last_user_instruction(stage);
 
                 if last_user.is_none() || last_user == Some("root".to_string()) {
-                    let line = stage.instructions.first()
-                        .and_then(|ins| get_line_number(ins, content));
+                    let first = stage.instructions.first();
 
                     findings.push(Finding {
-                        kind: id.clone(),
-                        description: format!("Stage {}: {}", stage.index, message),
-                        risk: severity_to_risk(severity),
-                        line,
+                        line: first.and_then(|ins| get_line_number(ins, content)),
+                        span: first.and_then(|ins| get_span(ins, content)),
+                        debug: vec![MatchedField::new("USER", last_user)],
+                        ..rule_finding(rule, format!("Stage {}: {}", stage.index, message))
                     });
                 }
             }
@@ -286,37 +925,93 @@ fn check_stage_rules(
     findings
 }
 
+/// Checks a build stage against all correlation rules.
+///
+/// A rule fires when every `present` pattern matches some instruction and
+/// no `absent` pattern matches any. The finding points at the instruction
+/// matching the first `present` pattern, or at the stage's first
+/// instruction when the rule only lists `absent` patterns. Rules without
+/// any pattern never fire.
+fn check_correlation_rules(
+    rules: &[Rule],
+    stage: &dockerfile_parser::Stage,
+    content: &str,
+) -> Vec<Finding> {
+    let instructions: Vec<_> = stage
+        .instructions
+        .iter()
+        .map(|ins| (*ins, get_instruction_kind(ins), instruction_context(ins, content)))
+        .collect();
+    let find = |pattern: &InstructionPattern| {
+        instructions.iter().find(|(_, kind, context)| {
+            pattern.kind.matches(kind)
+                && pattern.matcher.as_ref().is_none_or(|m| matches_matcher(m, context))
+        })
+    };
+
+    let mut findings = Vec::new();
+    for rule in rules {
+        let Rule::Correlation { present, absent, message, .. } = rule else {
+            continue;
+        };
+        if present.is_empty() && absent.is_empty() {
+            continue;
+        }
+
+        let Some(matched) = present.iter().map(find).collect::<Option<Vec<_>>>() else {
+            continue;
+        };
+        if absent.iter().any(|pattern| find(pattern).is_some()) {
+            continue;
+        }
+
+        let anchor = matched
+            .first()
+            .map(|(ins, _, _)| *ins)
+            .or_else(|| stage.instructions.first().copied());
+        let debug = present
+            .iter()
+            .zip(&matched)
+            .flat_map(|(pattern, (ins, kind, context))| match &pattern.matcher {
+                Some(matcher) => matched_fields(matcher, context),
+                None => vec![MatchedField::new(kind.clone(), get_raw_text(ins, content).map(str::to_string))],
+            })
+            .collect();
+        findings.push(Finding {
+            line: anchor.and_then(|ins| get_line_number(ins, content)),
+            span: anchor.and_then(|ins| get_span(ins, content)),
+            debug,
+            ..rule_finding(rule, format!("Stage {}: {}", stage.index, message))
+        });
+    }
+
+    findings
+}
+
+/// Builds the field map instruction matchers see, including `raw`.
+fn instruction_context(ins: &Instruction, content: &str) -> HashMap<String, serde_yml::Value> {
+    let mut map = instruction_to_map(ins);
+    if let Some(raw) = get_raw_text(ins, content) {
+        map.insert("raw".to_string(), serde_yml::Value::String(raw.to_string()));
+    }
+    map
+}
+
 /// Checks the entire Dockerfile against file-scoped rules.
 fn check_file_rules(
     rules: &[Rule],
     df: &Dockerfile,
-    path: &Path,
+    has_dockerignore: bool,
 ) -> Vec<Finding> {
     let mut findings = Vec::new();
 
     for rule in rules {
-        if let Rule::File { id, when, severity, message, .. } = rule {
-            if when.requires_dockerignore_if_copy_dot {
-                let has_copy_dot = df.iter_stages().any(|stage| {
-                    stage.instructions.iter().any(|ins| {
-                        matches!(ins, Instruction::Copy(c) if c.sources.iter().any(|s| s.content == "."))
-                    })
+        if let Rule::File { when, message, .. } = rule {
+            if when.requires_dockerignore_if_copy_dot && has_copy_dot(df) && !has_dockerignore {
+                findings.push(Finding {
+                    debug: vec![MatchedField::new(".dockerignore", None)],
+                    ..rule_finding(rule, message.clone())
                 });
-
-                if has_copy_dot {
-                    let dockerignore = path.parent()
-                        .map(|p| p.join(".dockerignore"))
-                        .filter(|p| p.exists());
-
-                    if dockerignore.is_none() {
-                        findings.push(Finding {
-                            kind: id.clone(),
-                            description: message.clone(),
-                            risk: severity_to_risk(severity),
-                            line: None,
-                        });
-                    }
-                }
             }
         }
     }
@@ -324,18 +1019,55 @@ fn check_file_rules(
     findings
 }
 
+/// Whether any file rule depends on `.dockerignore` existing for this Dockerfile.
+fn needs_dockerignore(rules: &[Rule], df: &Dockerfile) -> bool {
+    rules.iter().any(|rule| {
+        matches!(rule, Rule::File { when, .. } if when.requires_dockerignore_if_copy_dot)
+    }) && has_copy_dot(df)
+}
+
+/// Whether any stage copies the whole build context (`COPY . ...`).
+fn has_copy_dot(df: &Dockerfile) -> bool {
+    df.iter_stages().any(|stage| {
+        stage.instructions.iter().any(|ins| {
+            matches!(ins, Instruction::Copy(c) if c.sources.iter().any(|s| s.content == "."))
+        })
+    })
+}
+
+/// The `.dockerignore` next to the Dockerfile at `path`.
+fn dockerignore_path(path: &Path) -> Option<PathBuf> {
+    path.parent().map(|p| p.join(".dockerignore"))
+}
+
+/// Builds a finding carrying the rule's ID, severity, and metadata.
+fn rule_finding(rule: &Rule, description: String) -> Finding {
+    Finding {
+        rule_name: rule.name().map(str::to_string),
+        remediation: Some(rule.remediation().trim().to_string()).filter(|r| !r.is_empty()),
+        tags: rule.tags().to_vec(),
+        category: rule.category().map(str::to_string),
+        compliance: rule.compliance().clone(),
+        ..Finding::new(rule.id(), description, severity_to_risk(rule.severity()))
+    }
+}
+
 /// Converts a rule severity to a risk level.
 fn severity_to_risk(severity: &Severity) -> RiskLevel {
     match severity {
         Severity::Info => RiskLevel::Informative,
         Severity::Low => RiskLevel::Low,
         Severity::Medium => RiskLevel::Medium,
-        Severity::High | Severity::Critical => RiskLevel::High,
+        Severity::High => RiskLevel::High,
+        Severity::Critical => RiskLevel::Critical,
     }
 }
 
 /// Filters rules based on only/exclude sets.
 ///
+/// Deprecated rules are dropped unless `only` selects them, which logs a
+/// warning. Excluding a deprecated ID also excludes its `replaced_by` rule.
+///
 /// # Arguments
 ///
 /// * `rules` - Mutable reference to rules vector
@@ -354,22 +1086,28 @@ fn filter_rules(
             let rule_id = get_rule_id(rule);
             only_ids.contains(rule_id)
         });
-    } else if let Some(exclude_set) = exclude {
-        let exclude_ids: HashSet<&str> = exclude_set.iter().map(|s| s.as_str()).collect();
-        rules.retain(|rule| {
-            let rule_id = get_rule_id(rule);
-            !exclude_ids.contains(rule_id)
-        });
+        for rule in rules.iter().filter(|rule| rule.deprecated()) {
+            match rule.replaced_by() {
+                Some(new) => tracing::warn!("Rule '{}' is deprecated; use '{}' instead", rule.id(), new),
+                None => tracing::warn!("Rule '{}' is deprecated", rule.id()),
+            }
+        }
+    } else {
+        let mut exclude_ids: HashSet<String> = exclude.into_iter().flatten().cloned().collect();
+        // An exclusion written against the old ID still applies after a rename
+        let renamed: Vec<String> = rules
+            .iter()
+            .filter(|rule| rule.deprecated() && exclude_ids.contains(rule.id()))
+            .filter_map(|rule| rule.replaced_by().map(str::to_string))
+            .collect();
+        exclude_ids.extend(renamed);
+        rules.retain(|rule| !rule.deprecated() && !exclude_ids.contains(get_rule_id(rule)));
     }
 }
 
 /// Gets the rule ID from a Rule enum.
 fn get_rule_id(rule: &Rule) -> &str {
-    match rule {
-        Rule::Instruction { id, .. } => id,
-        Rule::Stage { id, .. } => id,
-        Rule::File { id, .. } => id,
-    }
+    rule.id()
 }
 
 /// Filters findings by severity level(s).
@@ -379,10 +1117,12 @@ fn get_rule_id(rule: &Rule) -> &str {
 /// * `findings` - Mutable reference to findings vector
 /// * `severity` - Optional exact severity levels to match
 /// * `min_severity` - Optional minimum severity threshold
+/// * `tags` - Per-tag minimum severities replacing `min_severity`
 fn filter_findings_by_severity(
     findings: &mut Vec<Finding>,
     severity: Option<&Vec<crate::cli::SeverityLevel>>,
     min_severity: Option<&crate::cli::SeverityLevel>,
+    tags: &TagThresholds,
 ) {
     if let Some(severity_levels) = severity {
         // Filter by exact severity match
@@ -392,10 +1132,9 @@ fn filter_findings_by_severity(
             .collect();
 
         findings.retain(|f| target_risks.contains(&f.risk));
-    } else if let Some(min_sev) = min_severity {
-        // Filter by minimum severity
-        let min_risk = severity_level_to_risk(min_sev);
-        findings.retain(|f| f.risk >= min_risk);
+    } else {
+        // Filter by minimum severity, per tag where configured
+        tags.retain(findings, min_severity.map(severity_level_to_risk));
     }
 }
 
@@ -408,6 +1147,7 @@ fn severity_level_to_risk(level: &crate::cli::SeverityLevel) -> RiskLevel {
         SeverityLevel::Low => RiskLevel::Low,
         SeverityLevel::Medium => RiskLevel::Medium,
         SeverityLevel::High => RiskLevel::High,
+        SeverityLevel::Critical => RiskLevel::Critical,
     }
 }
 
@@ -417,20 +1157,17 @@ fn severity_level_to_risk(level: &crate::cli::SeverityLevel) -> RiskLevel {
 ///
 /// * `findings` - Findings from the scan
 /// * `fail_on` - Optional minimum severity level to trigger failure
+/// * `tags` - Per-tag fail-on thresholds replacing `fail_on`
 ///
 /// # Returns
 ///
-/// `true` if any finding meets or exceeds the fail_on threshold
+/// `true` if any finding meets or exceeds its fail-on threshold
 fn should_fail_scan(
     findings: &[Finding],
     fail_on: Option<&crate::cli::SeverityLevel>,
+    tags: &TagThresholds,
 ) -> bool {
-    if let Some(threshold) = fail_on {
-        let threshold_risk = severity_level_to_risk(threshold);
-        findings.iter().any(|f| f.risk >= threshold_risk)
-    } else {
-        false
-    }
+    tags.fails(findings, fail_on.map(severity_level_to_risk))
 }
 
 #[cfg(test)]
@@ -461,7 +1198,7 @@ mod tests {
 
     #[test]
     fn test_severity_to_risk_critical() {
-        assert_eq!(severity_to_risk(&Severity::Critical), RiskLevel::High);
+        assert_eq!(severity_to_risk(&Severity::Critical), RiskLevel::Critical);
     }
 
     #[test]
@@ -475,7 +1212,7 @@ mod tests {
             Rule::Instruction {
                 id: "DF001".to_string(),
                 name: Some("No latest tag".to_string()),
-                kind: "FROM".to_string(),
+                kind: "FROM".into(),
                 matcher: Matcher {
                     all: None,
                     any: None,
@@ -489,6 +1226,10 @@ mod tests {
                 message: "Base image uses latest tag".to_string(),
                 remediation: "Pin to specific version".to_string(),
                 tags: vec![],
+                category: None,
+                compliance: Default::default(),
+                deprecated: false,
+                replaced_by: None,
             }
         ];
 
@@ -511,7 +1252,7 @@ mod tests {
             Rule::Instruction {
                 id: "DF001".to_string(),
                 name: Some("No latest tag".to_string()),
-                kind: "FROM".to_string(),
+                kind: "FROM".into(),
                 matcher: Matcher {
                     all: None,
                     any: None,
@@ -525,6 +1266,10 @@ mod tests {
                 message: "Base image uses latest tag".to_string(),
                 remediation: "Pin to specific version".to_string(),
                 tags: vec![],
+                category: None,
+                compliance: Default::default(),
+                deprecated: false,
+                replaced_by: None,
             }
         ];
 
@@ -545,7 +1290,7 @@ mod tests {
             Rule::Instruction {
                 id: "DF002".to_string(),
                 name: Some("No root user".to_string()),
-                kind: "USER".to_string(),
+                kind: "USER".into(),
                 matcher: Matcher {
                     all: None,
                     any: None,
@@ -559,6 +1304,10 @@ mod tests {
                 message: "Container runs as root".to_string(),
                 remediation: "Use a non-root user".to_string(),
                 tags: vec![],
+                category: None,
+                compliance: Default::default(),
+                deprecated: false,
+                replaced_by: None,
             }
         ];
 
@@ -581,7 +1330,7 @@ mod tests {
             Rule::Instruction {
                 id: "DF001".to_string(),
                 name: Some("Test".to_string()),
-                kind: "FROM".to_string(), // Rule targets FROM, not RUN
+                kind: "FROM".into(), // Rule targets FROM, not RUN
                 matcher: Matcher {
                     all: None,
                     any: None,
@@ -595,6 +1344,10 @@ mod tests {
                 message: "Test".to_string(),
                 remediation: "Test".to_string(),
                 tags: vec![],
+                category: None,
+                compliance: Default::default(),
+                deprecated: false,
+                replaced_by: None,
             }
         ];
 
@@ -604,6 +1357,216 @@ mod tests {
         assert_eq!(findings.len(), 0);
     }
 
+    #[test]
+    fn test_localize_translates_dockerfile_rule_text() {
+        let yaml = r#"
+version: 1
+rules:
+  - id: DF001
+    name: "Latest tag"
+    scope: instruction
+    kind: FROM
+    match:
+      field: from.tag
+      equals: "latest"
+    severity: high
+    message: "Uses latest"
+    remediation: "Pin a version"
+"#;
+        let mut rules: yaml_rules::RuleSet = serde_yml::from_str(yaml).unwrap();
+        let catalog: MessageCatalog =
+            serde_yml::from_str("DF001:\n  message: \"Usa latest\"\n  remediation: \"Fije una versión\"\n").unwrap();
+        yaml_rules::localize(&mut rules.rules, &catalog);
+
+        let dockerfile = "FROM nginx:latest";
+        let parsed = Dockerfile::parse(dockerfile).unwrap();
+        let findings = scan_instructions(&parsed, &rules.rules, dockerfile);
+        assert_eq!(findings[0].kind, "DF001");
+        assert_eq!(findings[0].description, "Stage 0: Usa latest");
+        assert_eq!(findings[0].remediation.as_deref(), Some("Fije una versión"));
+        assert_eq!(findings[0].rule_name.as_deref(), Some("Latest tag"));
+    }
+
+    #[test]
+    fn test_filter_rules_handles_deprecated_rules() {
+        let yaml = r#"
+version: 1
+rules:
+  - id: DF300
+    scope: instruction
+    kind: FROM
+    deprecated: true
+    replaced_by: DF301
+    match:
+      field: from.tag
+      equals: "latest"
+    severity: high
+    message: "latest"
+    remediation: "Pin"
+  - id: DF301
+    scope: instruction
+    kind: FROM
+    match:
+      field: from.tag
+      equals: "latest"
+    severity: high
+    message: "latest"
+    remediation: "Pin"
+"#;
+        let rules: yaml_rules::RuleSet = serde_yml::from_str(yaml).unwrap();
+        assert_eq!(yaml_rules::renames(&rules.rules)["DF300"], "DF301");
+        let ids = |only: Option<Vec<String>>, exclude: Option<Vec<String>>| {
+            let mut filtered = serde_yml::from_str::<yaml_rules::RuleSet>(yaml).unwrap().rules;
+            filter_rules(&mut filtered, only.as_ref(), exclude.as_ref());
+            filtered.iter().map(|r| r.id().to_string()).collect::<Vec<_>>()
+        };
+
+        assert_eq!(ids(None, None), vec!["DF301"]);
+        assert_eq!(ids(Some(vec!["DF300".into()]), None), vec!["DF300"]);
+        assert!(ids(None, Some(vec!["DF300".into()])).is_empty());
+    }
+
+    #[test]
+    fn test_scan_instructions_list_and_wildcard_kinds() {
+        let yaml = r#"
+version: 1
+rules:
+  - id: DF200
+    scope: instruction
+    kind: [RUN, CMD, ENTRYPOINT]
+    match:
+      field: command
+      regex: "sudo"
+    severity: medium
+    message: "sudo used"
+    remediation: "Drop sudo"
+  - id: DF201
+    scope: instruction
+    kind: "*"
+    match:
+      field: from.image
+      missing: true
+    severity: info
+    message: "not a FROM"
+    remediation: "None"
+"#;
+        let rules: yaml_rules::RuleSet = serde_yml::from_str(yaml).unwrap();
+        assert!(matches!(&rules.rules[1], Rule::Instruction { kind: InstructionKinds::Any, .. }));
+
+        let dockerfile = "FROM nginx\nRUN sudo apt-get update\nCMD sudo nginx\nLABEL a=sudo";
+        let parsed = Dockerfile::parse(dockerfile).unwrap();
+        let findings = scan_instructions(&parsed, &rules.rules, dockerfile);
+
+        let ids: Vec<(&str, Option<usize>)> =
+            findings.iter().map(|f| (f.kind.as_str(), f.line)).collect();
+        assert_eq!(
+            ids,
+            vec![
+                ("DF200", Some(2)),
+                ("DF201", Some(2)),
+                ("DF200", Some(3)),
+                ("DF201", Some(3)),
+                ("DF201", Some(4)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_check_instruction_rules_matches_raw_text() {
+        let dockerfile = "FROM nginx\nRUN --network=host \\\n    curl example.com";
+        let parsed = Dockerfile::parse(dockerfile).unwrap();
+        let stage = parsed.iter_stages().next().unwrap();
+
+        let rules = vec![Rule::Instruction {
+            id: "DF210".to_string(),
+            name: None,
+            kind: "RUN".into(),
+            matcher: Matcher {
+                all: None,
+                any: None,
+                field: Some("raw".to_string()),
+                equals: None,
+                regex: Some(regex::Regex::new(r"--network=host\s+\\").unwrap()),
+                glob: None,
+                missing: None,
+            },
+            severity: Severity::Medium,
+            message: "RUN uses the host network".to_string(),
+            remediation: "Drop --network=host".to_string(),
+            tags: vec![],
+            category: None,
+            compliance: Default::default(),
+            deprecated: false,
+            replaced_by: None,
+        }];
+
+        let findings = check_instruction_rules(&rules, stage.instructions[1], 0, dockerfile);
+
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].line, Some(2));
+    }
+
+    #[test]
+    fn test_scan_correlations_present_and_absent() {
+        let yaml = r#"
+version: 1
+rules:
+  - id: DF010
+    scope: correlation
+    present:
+      - kind: EXPOSE
+        match: { field: port, equals: "22" }
+    absent:
+      - kind: USER
+    severity: high
+    message: "SSH exposed while running as root"
+    remediation: "Switch to a non-root USER"
+"#;
+        let rules: yaml_rules::RuleSet = serde_yml::from_str(yaml).unwrap();
+
+        let dockerfile = "FROM alpine AS build\nEXPOSE 22\n\nFROM alpine\nEXPOSE 22\nUSER app\n\nFROM alpine\nEXPOSE 80";
+        let parsed = Dockerfile::parse(dockerfile).unwrap();
+        let findings = scan_correlations(&parsed, &rules.rules, dockerfile);
+
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].kind, "DF010");
+        assert_eq!(findings[0].line, Some(2));
+        assert_eq!(findings[0].description, "Stage 0: SSH exposed while running as root");
+    }
+
+    #[test]
+    fn test_scan_correlations_absent_only_points_at_stage_start() {
+        let yaml = r#"
+version: 1
+rules:
+  - id: DF011
+    scope: correlation
+    absent:
+      - kind: "*"
+        match: { field: raw, regex: "HEALTHCHECK" }
+    severity: low
+    message: "No HEALTHCHECK"
+    remediation: "Add a HEALTHCHECK"
+"#;
+        let rules: yaml_rules::RuleSet = serde_yml::from_str(yaml).unwrap();
+
+        let dockerfile = "FROM alpine\nRUN true\n\nFROM alpine\nHEALTHCHECK CMD true";
+        let parsed = Dockerfile::parse(dockerfile).unwrap();
+        let findings = scan_correlations(&parsed, &rules.rules, dockerfile);
+
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].line, Some(1));
+    }
+
+    #[test]
+    fn test_instruction_kinds_rejects_empty_list() {
+        let yaml = "version: 1\nrules:\n  - id: X\n    scope: instruction\n    kind: []\n    \
+                    match: {field: command, equals: x}\n    severity: low\n    message: m\n    \
+                    remediation: r\n";
+        let err = serde_yml::from_str::<yaml_rules::RuleSet>(yaml).unwrap_err();
+        assert!(err.to_string().contains("kind list must not be empty"));
+    }
+
     #[test]
     fn test_check_stage_rules_must_end_non_root_no_user() {
         let dockerfile = "FROM nginx\nRUN apt-get update";
@@ -621,6 +1584,10 @@ mod tests {
                 message: "Stage does not end with non-root user".to_string(),
                 remediation: "Add USER directive".to_string(),
                 tags: vec![],
+                category: None,
+                compliance: Default::default(),
+                deprecated: false,
+                replaced_by: None,
             }
         ];
 
@@ -648,6 +1615,10 @@ mod tests {
                 message: "Stage ends with root user".to_string(),
                 remediation: "Use non-root user".to_string(),
                 tags: vec![],
+                category: None,
+                compliance: Default::default(),
+                deprecated: false,
+                replaced_by: None,
             }
         ];
 
@@ -674,6 +1645,10 @@ mod tests {
                 message: "Stage does not end with non-root user".to_string(),
                 remediation: "Add USER directive".to_string(),
                 tags: vec![],
+                category: None,
+                compliance: Default::default(),
+                deprecated: false,
+                replaced_by: None,
             }
         ];
 
@@ -692,7 +1667,7 @@ mod tests {
             Rule::Instruction {
                 id: "DF001".to_string(),
                 name: Some("No latest tag".to_string()),
-                kind: "FROM".to_string(),
+                kind: "FROM".into(),
                 matcher: Matcher {
                     all: None,
                     any: None,
@@ -706,11 +1681,15 @@ mod tests {
                 message: "Base image uses latest tag".to_string(),
                 remediation: "Pin to specific version".to_string(),
                 tags: vec![],
+                category: None,
+                compliance: Default::default(),
+                deprecated: false,
+                replaced_by: None,
             },
             Rule::Instruction {
                 id: "DF002".to_string(),
                 name: Some("No root user".to_string()),
-                kind: "USER".to_string(),
+                kind: "USER".into(),
                 matcher: Matcher {
                     all: None,
                     any: None,
@@ -724,6 +1703,10 @@ mod tests {
                 message: "Container runs as root".to_string(),
                 remediation: "Use a non-root user".to_string(),
                 tags: vec![],
+                category: None,
+                compliance: Default::default(),
+                deprecated: false,
+                replaced_by: None,
             }
         ];
 
@@ -749,6 +1732,10 @@ mod tests {
                 message: "Stage does not end with non-root user".to_string(),
                 remediation: "Add USER directive".to_string(),
                 tags: vec![],
+                category: None,
+                compliance: Default::default(),
+                deprecated: false,
+                replaced_by: None,
             }
         ];
 
@@ -5,10 +5,11 @@
 //!
 //! # Rule Types
 //!
-//! Rules can operate at three different scopes:
+//! Rules can operate at four different scopes:
 //!
 //! * **Instruction** - Checks individual Dockerfile instructions (FROM, RUN, USER, etc.)
 //! * **Stage** - Checks entire build stages (multi-stage builds)
+//! * **Correlation** - Checks which instructions a build stage does and does not contain
 //! * **File** - Checks file-level properties (e.g., .dockerignore existence)
 //!
 //! # Example Rule
@@ -27,11 +28,40 @@
 //!     message: "Base image uses mutable latest tag"
 //!     remediation: "Pin to specific version"
 //!     tags: [reproducibility]
+//!     compliance:
+//!       nist-800-190: ["4.2.2"]
+//! ```
+//!
+//! `kind` also accepts a list (`kind: [RUN, CMD, ENTRYPOINT]`) or `"*"` to
+//! check every instruction.
+//!
+//! # Correlation Rules
+//!
+//! A correlation rule fires for a build stage when every `present` pattern
+//! matches at least one of its instructions and no `absent` pattern matches
+//! any of them:
+//!
+//! ```yaml
+//!   - id: DF010
+//!     scope: correlation
+//!     present:
+//!       - kind: EXPOSE
+//!         match: { field: port, equals: "22" }
+//!     absent:
+//!       - kind: USER
+//!     severity: high
+//!     message: "SSH is exposed and the stage runs as root"
+//!     remediation: "Drop sshd or switch to a non-root USER"
+//!     tags: [security]
 //! ```
 
+use globset::GlobMatcher;
 use regex::Regex;
 use serde::Deserialize;
-use anyhow::Context;
+use crate::error::ValerisError;
+use crate::i18n::{MessageCatalog, RuleText};
+use crate::policy::POLICY_FILE;
+use std::collections::BTreeMap;
 use std::path::Path;
 
 #[derive(Debug, Deserialize)]
@@ -51,13 +81,16 @@ pub enum Severity {
     Critical
 }
 
+// Rules are loaded once per scan, so the precompiled matcher inflating
+// the Instruction variant is not worth boxing.
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug, Deserialize)]
 #[serde(tag = "scope", rename_all="lowercase", deny_unknown_fields)]
 pub enum Rule {
     Instruction {
         id: String,
         name: Option<String>,
-        kind: String,
+        kind: InstructionKinds,
         #[serde(rename = "match")]
         matcher: Matcher,
         severity: Severity,
@@ -65,6 +98,14 @@ pub enum Rule {
         remediation: String,
         #[serde(default)]
         tags:Vec<String>,
+        #[serde(default)]
+        category: Option<String>,
+        #[serde(default)]
+        compliance: BTreeMap<String, Vec<String>>,
+        #[serde(default)]
+        deprecated: bool,
+        #[serde(default)]
+        replaced_by: Option<String>,
     },
 
     Stage {
@@ -75,6 +116,37 @@ pub enum Rule {
         message: String,
         remediation: String,
         tags: Vec<String>,
+        #[serde(default)]
+        category: Option<String>,
+        #[serde(default)]
+        compliance: BTreeMap<String, Vec<String>>,
+        #[serde(default)]
+        deprecated: bool,
+        #[serde(default)]
+        replaced_by: Option<String>,
+    },
+    Correlation {
+        id: String,
+        name: Option<String>,
+        /// Patterns that must each match an instruction in the stage
+        #[serde(default)]
+        present: Vec<InstructionPattern>,
+        /// Patterns that must not match any instruction in the stage
+        #[serde(default)]
+        absent: Vec<InstructionPattern>,
+        severity: Severity,
+        message: String,
+        remediation: String,
+        #[serde(default)]
+        tags: Vec<String>,
+        #[serde(default)]
+        category: Option<String>,
+        #[serde(default)]
+        compliance: BTreeMap<String, Vec<String>>,
+        #[serde(default)]
+        deprecated: bool,
+        #[serde(default)]
+        replaced_by: Option<String>,
     },
     File {
         id: String,
@@ -84,6 +156,226 @@ pub enum Rule {
         message:String,
         remediation: String,
         tags: Vec<String>,
+        #[serde(default)]
+        category: Option<String>,
+        #[serde(default)]
+        compliance: BTreeMap<String, Vec<String>>,
+        #[serde(default)]
+        deprecated: bool,
+        #[serde(default)]
+        replaced_by: Option<String>,
+    }
+}
+
+impl Rule {
+    /// Returns the rule ID.
+    pub fn id(&self) -> &str {
+        match self {
+            Rule::Instruction { id, .. }
+            | Rule::Stage { id, .. }
+            | Rule::Correlation { id, .. }
+            | Rule::File { id, .. } => id,
+        }
+    }
+
+    /// Returns the human-readable rule name, if any.
+    pub fn name(&self) -> Option<&str> {
+        match self {
+            Rule::Instruction { name, .. }
+            | Rule::Stage { name, .. }
+            | Rule::Correlation { name, .. }
+            | Rule::File { name, .. } => name.as_deref(),
+        }
+    }
+
+    /// Returns the scope name as written in YAML (`instruction`, `stage`,
+    /// `correlation`, `file`).
+    pub fn scope(&self) -> &'static str {
+        match self {
+            Rule::Instruction { .. } => "instruction",
+            Rule::Stage { .. } => "stage",
+            Rule::Correlation { .. } => "correlation",
+            Rule::File { .. } => "file",
+        }
+    }
+
+    /// Returns the rule severity.
+    pub fn severity(&self) -> &Severity {
+        match self {
+            Rule::Instruction { severity, .. }
+            | Rule::Stage { severity, .. }
+            | Rule::Correlation { severity, .. }
+            | Rule::File { severity, .. } => severity,
+        }
+    }
+
+    /// Returns the finding message.
+    pub fn message(&self) -> &str {
+        match self {
+            Rule::Instruction { message, .. }
+            | Rule::Stage { message, .. }
+            | Rule::Correlation { message, .. }
+            | Rule::File { message, .. } => message,
+        }
+    }
+
+    /// Returns the remediation advice.
+    pub fn remediation(&self) -> &str {
+        match self {
+            Rule::Instruction { remediation, .. }
+            | Rule::Stage { remediation, .. }
+            | Rule::Correlation { remediation, .. }
+            | Rule::File { remediation, .. } => remediation,
+        }
+    }
+
+    /// Returns the rule tags.
+    pub fn tags(&self) -> &[String] {
+        match self {
+            Rule::Instruction { tags, .. }
+            | Rule::Stage { tags, .. }
+            | Rule::Correlation { tags, .. }
+            | Rule::File { tags, .. } => tags,
+        }
+    }
+
+    /// Returns the rule category, if any.
+    pub fn category(&self) -> Option<&str> {
+        match self {
+            Rule::Instruction { category, .. }
+            | Rule::Stage { category, .. }
+            | Rule::Correlation { category, .. }
+            | Rule::File { category, .. } => category.as_deref(),
+        }
+    }
+
+    /// Returns whether the rule is deprecated (runs only when selected with
+    /// `--only`).
+    pub fn deprecated(&self) -> bool {
+        match self {
+            Rule::Instruction { deprecated, .. }
+            | Rule::Stage { deprecated, .. }
+            | Rule::Correlation { deprecated, .. }
+            | Rule::File { deprecated, .. } => *deprecated,
+        }
+    }
+
+    /// Returns the ID of the rule that supersedes this one, if any.
+    pub fn replaced_by(&self) -> Option<&str> {
+        match self {
+            Rule::Instruction { replaced_by, .. }
+            | Rule::Stage { replaced_by, .. }
+            | Rule::Correlation { replaced_by, .. }
+            | Rule::File { replaced_by, .. } => replaced_by.as_deref(),
+        }
+    }
+
+    /// Replaces the rule's name, message, and remediation with the fields
+    /// `text` sets.
+    pub fn localize(&mut self, text: &RuleText) {
+        match self {
+            Rule::Instruction { name, message, remediation, .. }
+            | Rule::Stage { name, message, remediation, .. }
+            | Rule::Correlation { name, message, remediation, .. }
+            | Rule::File { name, message, remediation, .. } => {
+                if let Some(translated) = &text.name {
+                    *name = Some(translated.clone());
+                }
+                if let Some(translated) = &text.message {
+                    *message = translated.clone();
+                }
+                if let Some(translated) = &text.remediation {
+                    *remediation = translated.clone();
+                }
+            }
+        }
+    }
+
+    /// Returns the compliance controls the rule maps to, keyed by framework.
+    pub fn compliance(&self) -> &BTreeMap<String, Vec<String>> {
+        match self {
+            Rule::Instruction { compliance, .. }
+            | Rule::Stage { compliance, .. }
+            | Rule::Correlation { compliance, .. }
+            | Rule::File { compliance, .. } => compliance,
+        }
+    }
+}
+
+/// Instruction kinds an instruction-scoped rule applies to.
+///
+/// Written in YAML as a single kind (`RUN`), a list (`[RUN, CMD]`), or `"*"`
+/// for every instruction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InstructionKinds {
+    /// Every instruction
+    Any,
+    /// Only the listed instructions
+    Only(Vec<String>),
+}
+
+impl InstructionKinds {
+    /// Returns whether an instruction of `kind` (e.g. `RUN`) is covered.
+    pub fn matches(&self, kind: &str) -> bool {
+        match self {
+            InstructionKinds::Any => true,
+            InstructionKinds::Only(kinds) => kinds.iter().any(|k| k == kind),
+        }
+    }
+}
+
+impl From<&str> for InstructionKinds {
+    fn from(kind: &str) -> Self {
+        if kind == "*" {
+            InstructionKinds::Any
+        } else {
+            InstructionKinds::Only(vec![kind.to_string()])
+        }
+    }
+}
+
+impl std::fmt::Display for InstructionKinds {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InstructionKinds::Any => f.write_str("*"),
+            InstructionKinds::Only(kinds) => f.write_str(&kinds.join(", ")),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for InstructionKinds {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            One(String),
+            Many(Vec<String>),
+        }
+
+        match Raw::deserialize(deserializer)? {
+            Raw::One(kind) => Ok(InstructionKinds::from(kind.as_str())),
+            Raw::Many(kinds) if kinds.is_empty() => {
+                Err(serde::de::Error::custom("kind list must not be empty"))
+            }
+            Raw::Many(kinds) if kinds.iter().any(|k| k == "*") => Ok(InstructionKinds::Any),
+            Raw::Many(kinds) => Ok(InstructionKinds::Only(kinds)),
+        }
+    }
+}
+
+impl Severity {
+    /// Returns the lowercase name used in YAML rule files.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Severity::Info => "info",
+            Severity::Low => "low",
+            Severity::Medium => "medium",
+            Severity::High => "high",
+            Severity::Critical => "critical",
+        }
     }
 }
 
@@ -102,6 +394,28 @@ mod optional_regex {
     }
 }
 
+/// Compiles a glob pattern once, at rule load time.
+///
+/// `*` matches any sequence of characters (including `/`), `?` matches a
+/// single character, and `.` is a literal dot.
+pub fn compile_glob(pattern: &str) -> Result<GlobMatcher, globset::Error> {
+    Ok(globset::Glob::new(pattern)?.compile_matcher())
+}
+
+mod optional_glob {
+    use globset::GlobMatcher;
+    use serde::{self, Deserialize, Deserializer};
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<GlobMatcher>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s: Option<String> = Option::deserialize(deserializer)?;
+        s.map(|pattern| super::compile_glob(&pattern).map_err(serde::de::Error::custom))
+            .transpose()
+    }
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct Matcher {
@@ -115,8 +429,8 @@ pub struct Matcher {
     pub equals: Option<String>,
     #[serde(default, deserialize_with = "optional_regex::deserialize")]
     pub regex: Option<Regex>,
-    #[serde(default)]
-    pub glob: Option<String>,
+    #[serde(default, deserialize_with = "optional_glob::deserialize")]
+    pub glob: Option<GlobMatcher>,
     #[serde(default)]
     pub missing: Option<bool>,
 }
@@ -130,12 +444,22 @@ pub struct Predicate {
     pub equals: Option<serde_yml::Value>,
     #[serde(default, deserialize_with = "optional_regex::deserialize")]
     pub regex: Option<Regex>,
-    #[serde(default)]
-    pub glob: Option<String>,
+    #[serde(default, deserialize_with = "optional_glob::deserialize")]
+    pub glob: Option<GlobMatcher>,
     #[serde(default)]
     pub missing: Option<bool>,
 }
 
+/// An instruction a correlation rule looks for: its kind and, optionally,
+/// a matcher over the same fields instruction rules see.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct InstructionPattern {
+    pub kind: InstructionKinds,
+    #[serde(default, rename = "match")]
+    pub matcher: Option<Matcher>,
+}
+
 #[derive(Debug, Deserialize, Default)]
 #[serde(deny_unknown_fields)]
 pub struct StageWhen {
@@ -151,19 +475,52 @@ pub struct FileWhen {
 }
 
 
-pub fn load_rules_from_dir(dir: &Path) -> anyhow::Result<RuleSet> {
+/// Maps the ID of each deprecated rule with a `replaced_by` to the ID of its
+/// replacement.
+pub fn renames(rules: &[Rule]) -> std::collections::HashMap<String, String> {
+    rules
+        .iter()
+        .filter(|rule| rule.deprecated())
+        .filter_map(|rule| Some((rule.id().to_string(), rule.replaced_by()?.to_string())))
+        .collect()
+}
+
+/// Applies the translations in `catalog` to the rules it covers.
+pub fn localize(rules: &mut [Rule], catalog: &MessageCatalog) {
+    for rule in rules {
+        if let Some(text) = catalog.get(rule.id()) {
+            rule.localize(text);
+        }
+    }
+}
+
+/// Loads every `*.yml` / `*.yaml` rule file in `dir` into one rule set,
+/// skipping the [`POLICY_FILE`].
+///
+/// # Errors
+///
+/// * [`ValerisError::RulesLoad`] if the directory or a rule file cannot be read
+/// * [`ValerisError::RuleParse`] if a rule file is invalid
+pub fn load_rules_from_dir(dir: &Path) -> Result<RuleSet, ValerisError> {
+    let load_error = |path: &Path, source: std::io::Error| ValerisError::RulesLoad {
+        path: path.to_path_buf(),
+        source: source.into(),
+    };
+
     let mut out = RuleSet {
         version: 1,
         rules: Vec::new(),
     };
-    for entry in std::fs::read_dir(dir)
-        .with_context(|| format!("Failed to read rules directory {}", dir.display()))? {
-        let path = entry
-            .with_context(|| format!("Failed to read directory entry in {}", dir.display()))?
-            .path();
+    for entry in std::fs::read_dir(dir).map_err(|e| load_error(dir, e))? {
+        let path = entry.map_err(|e| load_error(dir, e))?.path();
+        // Policy definitions share the directory but are not rules
+        if path.file_name().is_some_and(|n| n == POLICY_FILE) {
+            continue;
+        }
         if path.extension().map(|e| e == "yml" || e == "yaml").unwrap_or(false){
-            let content = std::fs::read_to_string(&path).with_context(|| format!("Reading {}", path.display()))?;
-            let parsed: RuleSet = serde_yml::from_str(&content).with_context(|| format!("Parsing yaml in {}", path.display()))?;
+            let content = std::fs::read_to_string(&path).map_err(|e| load_error(&path, e))?;
+            let parsed: RuleSet = serde_yml::from_str(&content)
+                .map_err(|e| ValerisError::rule_parse(&path, e))?;
             out.rules.extend(parsed.rules);
         }
     }
@@ -1,2 +1,5 @@
 pub mod runtime;
 pub mod dockerfile;
+pub mod env_file;
+pub mod network;
+pub mod compose;
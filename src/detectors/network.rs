@@ -0,0 +1,376 @@
+//! Docker network scanning.
+//!
+//! `valeris network` reads `docker network inspect` data, from the daemon or
+//! a file, and checks how containers are connected. The checks are built in
+//! rather than loaded from a rule bundle:
+//!
+//! | ID     | Severity      | Check                                                      |
+//! |--------|---------------|------------------------------------------------------------|
+//! | NET001 | High          | Containers attached to a `host`-driver network             |
+//! | NET002 | Medium        | Sensitive service (database, cache, secret store) on a network that is not `--internal` |
+//! | NET003 | Low           | Bridge network with inter-container communication enabled  |
+//! | NET004 | Informative   | User-defined network with no containers attached           |
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use bollard::{
+    container::ListContainersOptions,
+    models::Network,
+    network::{InspectNetworkOptions, ListNetworksOptions},
+    Docker,
+};
+use serde_json::Value;
+
+use crate::docker::model::{Finding, FindingTarget, NetworkResult, RiskLevel};
+use crate::error::{Result, ValerisError};
+
+/// Rule ID of containers sharing the host's network stack
+pub const HOST_DRIVER: &str = "NET001";
+/// Rule ID of sensitive services on networks with external access
+pub const SENSITIVE_NOT_INTERNAL: &str = "NET002";
+/// Rule ID of bridge networks without ICC disabled
+pub const ICC_ENABLED: &str = "NET003";
+/// Rule ID of networks no container uses
+pub const UNATTACHED: &str = "NET004";
+
+/// Number of built-in checks, reported as the rules evaluated
+pub const CHECK_COUNT: usize = 4;
+
+/// Networks Docker creates itself
+const PREDEFINED_NETWORKS: [&str; 3] = ["bridge", "host", "none"];
+
+/// Bridge driver option that turns inter-container communication off
+const ICC_OPTION: &str = "com.docker.network.bridge.enable_icc";
+
+/// Name and image fragments of services that should not be reachable from
+/// outside their network
+const SENSITIVE_SERVICES: [&str; 20] = [
+    "postgres",
+    "mysql",
+    "mariadb",
+    "mongo",
+    "mssql",
+    "oracle",
+    "redis",
+    "valkey",
+    "memcached",
+    "elasticsearch",
+    "opensearch",
+    "cassandra",
+    "couchdb",
+    "influxdb",
+    "rabbitmq",
+    "kafka",
+    "zookeeper",
+    "etcd",
+    "consul",
+    "vault",
+];
+
+/// Reads and inspects the networks of a Docker daemon.
+///
+/// # Arguments
+///
+/// * `docker` - Connected Docker client
+///
+/// # Returns
+///
+/// The inspected networks and the image of every container, keyed by
+/// container ID, used to recognize sensitive services
+///
+/// # Errors
+///
+/// Returns an error if the networks or containers cannot be listed.
+/// Networks removed between listing and inspection are skipped.
+pub async fn get_networks(docker: &Docker) -> Result<(Vec<Network>, HashMap<String, String>), bollard::errors::Error> {
+    let listed = docker.list_networks(None::<ListNetworksOptions<String>>).await?;
+    let mut networks = Vec::with_capacity(listed.len());
+    for network in listed {
+        let Some(id) = network.id.as_deref() else { continue };
+        match docker.inspect_network(id, None::<InspectNetworkOptions<String>>).await {
+            Ok(inspected) => networks.push(inspected),
+            Err(e) => tracing::warn!("Failed to inspect network {}: {}", id, e),
+        }
+    }
+
+    let images = docker
+        .list_containers(Some(ListContainersOptions::<String> {
+            all: true,
+            ..Default::default()
+        }))
+        .await?
+        .into_iter()
+        .filter_map(|c| Some((c.id?, c.image?)))
+        .collect();
+    Ok((networks, images))
+}
+
+/// Reads networks from a file of `docker network inspect` output.
+///
+/// Accepts a single network object or the array printed by
+/// `docker network inspect`.
+///
+/// # Errors
+///
+/// * [`ValerisError::InputRead`] if the file cannot be read
+/// * [`ValerisError::InputParse`] if it is not network inspect JSON
+pub fn load_network_file(path: &Path) -> Result<Vec<Network>> {
+    let raw = fs::read_to_string(path).map_err(|source| ValerisError::InputRead {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let parse_error = |message: String| ValerisError::InputParse {
+        path: path.to_path_buf(),
+        message,
+    };
+
+    let items = match serde_json::from_str(&raw).map_err(|e| parse_error(e.to_string()))? {
+        Value::Array(items) => items,
+        object @ Value::Object(_) => vec![object],
+        _ => {
+            return Err(parse_error(
+                "expected a network inspect object or an array of them".to_string(),
+            ))
+        }
+    };
+
+    items
+        .into_iter()
+        .enumerate()
+        .map(|(i, item)| {
+            let network: Network =
+                serde_json::from_value(item).map_err(|e| parse_error(format!("entry {}: {}", i, e)))?;
+            match network.id.is_some() && network.scope.is_some() {
+                true => Ok(network),
+                false => Err(parse_error(format!("entry {}: not a network (no Id or Scope)", i))),
+            }
+        })
+        .collect()
+}
+
+/// Runs the network checks on every network.
+///
+/// # Arguments
+///
+/// * `networks` - Inspected networks
+/// * `images` - Image of each container by ID; container names are matched
+///   when an image is unknown (e.g. for `--input` files)
+///
+/// # Returns
+///
+/// One result per network, in input order
+pub fn scan_networks(networks: Vec<Network>, images: &HashMap<String, String>) -> Vec<NetworkResult> {
+    networks
+        .into_iter()
+        .map(|network| {
+            let findings = check_network(&network, images);
+            NetworkResult { network, findings }
+        })
+        .collect()
+}
+
+/// Runs the network checks on one network.
+pub fn check_network(network: &Network, images: &HashMap<String, String>) -> Vec<Finding> {
+    let name = network.name.as_deref().unwrap_or_default();
+    let driver = network.driver.as_deref().unwrap_or_default();
+    let internal = network.internal.unwrap_or(false);
+    // Container names with their image, when known
+    let mut attached: Vec<(String, Option<&str>)> = network
+        .containers
+        .iter()
+        .flatten()
+        .map(|(id, c)| (c.name.clone().unwrap_or_else(|| id.clone()), images.get(id).map(String::as_str)))
+        .collect();
+    attached.sort();
+    let names = |list: &[(String, Option<&str>)]| {
+        list.iter().map(|(name, _)| name.as_str()).collect::<Vec<_>>().join(", ")
+    };
+
+    let mut findings = Vec::new();
+    if driver == "host" && !attached.is_empty() {
+        findings.push(finding(
+            network,
+            HOST_DRIVER,
+            "Containers on the host network",
+            format!(
+                "Network '{}' uses the host driver; attached containers share the host's network stack: {}",
+                name,
+                names(&attached)
+            ),
+            RiskLevel::High,
+            "Attach the containers to a user-defined bridge network and publish only the ports they need",
+        ));
+    }
+
+    let sensitive: Vec<(String, Option<&str>)> = attached
+        .iter()
+        .filter(|(name, image)| is_sensitive(name, *image))
+        .cloned()
+        .collect();
+    if !internal && !matches!(driver, "host" | "null") && !sensitive.is_empty() {
+        findings.push(finding(
+            network,
+            SENSITIVE_NOT_INTERNAL,
+            "Sensitive service on an external network",
+            format!(
+                "Network '{}' is not internal but carries sensitive services: {}",
+                name,
+                names(&sensitive)
+            ),
+            RiskLevel::Medium,
+            "Move databases, caches and secret stores to a network created with --internal, \
+             and connect only the services that use them",
+        ));
+    }
+
+    let icc_disabled = network
+        .options
+        .as_ref()
+        .and_then(|options| options.get(ICC_OPTION))
+        .is_some_and(|value| value == "false");
+    if driver == "bridge" && !icc_disabled && attached.len() > 1 {
+        findings.push(finding(
+            network,
+            ICC_ENABLED,
+            "Inter-container communication enabled",
+            format!(
+                "Network '{}' allows inter-container communication; its {} containers can reach each other on every port",
+                name,
+                attached.len()
+            ),
+            RiskLevel::Low,
+            "Create the network with -o com.docker.network.bridge.enable_icc=false, or split \
+             containers that do not talk to each other onto separate networks",
+        ));
+    }
+
+    let user_defined = !PREDEFINED_NETWORKS.contains(&name) && !network.ingress.unwrap_or(false);
+    if user_defined && attached.is_empty() {
+        findings.push(finding(
+            network,
+            UNATTACHED,
+            "Unused network",
+            format!("Network '{}' has no containers attached", name),
+            RiskLevel::Informative,
+            "Remove stale networks with docker network rm or docker network prune",
+        ));
+    }
+    findings
+}
+
+/// Whether a container looks like a database, cache, broker, or secret store.
+fn is_sensitive(name: &str, image: Option<&str>) -> bool {
+    // Repository name without registry, namespace, tag or digest
+    let repository = image.map(|image| {
+        let image = image.split('@').next().unwrap_or(image);
+        let last = image.rsplit('/').next().unwrap_or(image);
+        last.split(':').next().unwrap_or(last).to_lowercase()
+    });
+    let name = name.to_lowercase();
+    SENSITIVE_SERVICES
+        .iter()
+        .any(|service| name.contains(service) || repository.as_deref().is_some_and(|r| r.contains(service)))
+}
+
+fn finding(
+    network: &Network,
+    id: &str,
+    name: &str,
+    description: String,
+    risk: RiskLevel,
+    remediation: &str,
+) -> Finding {
+    let mut finding = Finding::new(id, description, risk);
+    finding.rule_name = Some(name.to_string());
+    finding.remediation = Some(remediation.to_string());
+    finding.tags = vec!["security".to_string(), "network".to_string()];
+    finding.category = Some("network".to_string());
+    finding
+        .compliance
+        .insert(crate::output::compliance::NIST_800_190.to_string(), vec!["4.4.2".to_string()]);
+    finding.target = Some(FindingTarget::network(network));
+    finding
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bollard::models::NetworkContainer;
+
+    fn network(name: &str, driver: &str, containers: &[(&str, &str)]) -> Network {
+        Network {
+            id: Some(format!("{}-id", name)),
+            name: Some(name.to_string()),
+            driver: Some(driver.to_string()),
+            internal: Some(false),
+            containers: Some(
+                containers
+                    .iter()
+                    .map(|(id, name)| {
+                        (id.to_string(), NetworkContainer {
+                            name: Some(name.to_string()),
+                            ..Default::default()
+                        })
+                    })
+                    .collect(),
+            ),
+            ..Default::default()
+        }
+    }
+
+    fn ids(findings: &[Finding]) -> Vec<&str> {
+        findings.iter().map(|f| f.kind.as_str()).collect()
+    }
+
+    #[test]
+    fn flags_sensitive_services_and_icc() {
+        let backend = network("backend", "bridge", &[("a1", "app"), ("d1", "db")]);
+        let images = HashMap::from([("d1".to_string(), "docker.io/library/postgres:16".to_string())]);
+
+        let findings = check_network(&backend, &images);
+
+        assert_eq!(ids(&findings), [SENSITIVE_NOT_INTERNAL, ICC_ENABLED]);
+        assert!(findings[0].description.ends_with(": db"));
+        assert!(matches!(&findings[0].target, Some(FindingTarget::Network { name, .. }) if name == "backend"));
+    }
+
+    #[test]
+    fn internal_networks_without_icc_pass() {
+        let mut backend = network("backend", "bridge", &[("a1", "app"), ("d1", "redis-cache")]);
+        backend.internal = Some(true);
+        backend.options = Some(HashMap::from([(ICC_OPTION.to_string(), "false".to_string())]));
+
+        assert!(check_network(&backend, &HashMap::new()).is_empty());
+    }
+
+    #[test]
+    fn flags_host_driver_and_unattached_networks() {
+        let host = network("host", "host", &[("w1", "web")]);
+        assert_eq!(ids(&check_network(&host, &HashMap::new())), [HOST_DRIVER]);
+
+        // The predefined networks are not stale when empty
+        assert!(check_network(&network("host", "host", &[]), &HashMap::new()).is_empty());
+        assert!(check_network(&network("none", "null", &[]), &HashMap::new()).is_empty());
+
+        let stale = network("old-project_default", "bridge", &[]);
+        assert_eq!(ids(&check_network(&stale, &HashMap::new())), [UNATTACHED]);
+    }
+
+    #[test]
+    fn loads_network_inspect_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("networks.json");
+        fs::write(
+            &path,
+            r#"[{"Name": "backend", "Id": "abc", "Scope": "local", "Driver": "bridge", "Containers": {}}]"#,
+        )
+        .unwrap();
+        assert_eq!(load_network_file(&path).unwrap()[0].name.as_deref(), Some("backend"));
+
+        // `docker inspect` of a container
+        fs::write(&path, r#"[{"Id": "abc", "Driver": "overlay2", "Config": {}}]"#).unwrap();
+        assert!(matches!(load_network_file(&path), Err(ValerisError::InputParse { .. })));
+    }
+}
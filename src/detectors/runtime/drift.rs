@@ -0,0 +1,424 @@
+//! Container filesystem drift.
+//!
+//! `valeris scan --drift` reads `docker diff` for each container and flags
+//! changes to its writable layer that a container built for immutable
+//! deployment should never make:
+//!
+//! * account, sudo, cron, and loader files changed, added, or deleted
+//!   (`/etc/passwd`, `/etc/sudoers.d/*`, `/etc/ld.so.preload`, ...)
+//! * binaries and libraries changed in system directories (`/usr/bin`, ...)
+//! * new files with the setuid or setgid bit, a classic persistence backdoor
+//!
+//! `docker diff` does not report file modes, so added paths are downloaded
+//! from the container as tar archives and their entry headers checked for the
+//! setuid/setgid bits. Archives above [`MAX_ARCHIVE_BYTES`] are skipped.
+
+use bollard::{
+    container::DownloadFromContainerOptions,
+    models::{ChangeType, ContainerInspectResponse, FilesystemChange},
+    Docker,
+};
+use futures::stream::StreamExt;
+use std::collections::HashSet;
+use std::fmt;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::docker::model::{Finding, FindingTarget, RiskLevel};
+
+/// Finding kinds reported from `docker diff`, usable with `--only`/`--exclude`
+pub const CHECK_IDS: [&str; 3] = [
+    "drift_sensitive_file_changed",
+    "drift_system_path_changed",
+    "drift_setuid_file_added",
+];
+
+/// Files whose change is a sign of tampering
+const SENSITIVE_FILES: [&str; 9] = [
+    "/etc/passwd",
+    "/etc/shadow",
+    "/etc/group",
+    "/etc/gshadow",
+    "/etc/sudoers",
+    "/etc/ld.so.preload",
+    "/etc/crontab",
+    "/etc/profile",
+    "/etc/environment",
+];
+
+/// Directories whose entries are as sensitive as [`SENSITIVE_FILES`]
+const SENSITIVE_DIRS: [&str; 6] = [
+    "/etc/sudoers.d",
+    "/etc/cron.d",
+    "/etc/profile.d",
+    "/etc/ssh",
+    "/root/.ssh",
+    "/var/spool/cron",
+];
+
+/// Directories holding system binaries and libraries
+const SYSTEM_DIRS: [&str; 10] = [
+    "/bin",
+    "/sbin",
+    "/usr/bin",
+    "/usr/sbin",
+    "/usr/local/bin",
+    "/usr/local/sbin",
+    "/lib",
+    "/lib64",
+    "/usr/lib",
+    "/usr/lib64",
+];
+
+/// Setuid and setgid permission bits
+const SETID_BITS: u32 = 0o6000;
+
+/// Largest archive of an added path read for setuid/setgid files (16 MiB)
+pub const MAX_ARCHIVE_BYTES: usize = 16 << 20;
+
+/// Added paths downloaded per container when looking for setuid/setgid files
+const MAX_ARCHIVES: usize = 32;
+
+/// Changed system paths listed in a finding before the rest are counted
+const MAX_LISTED_PATHS: usize = 5;
+
+/// Maximum time to wait for a container's diff or one of its archives
+const DIFF_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How a path differs from the container's image.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    Modified,
+    Added,
+    Deleted,
+}
+
+impl fmt::Display for ChangeKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ChangeKind::Modified => "modified",
+            ChangeKind::Added => "added",
+            ChangeKind::Deleted => "deleted",
+        })
+    }
+}
+
+/// One entry of `docker diff`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change {
+    pub path: String,
+    pub kind: ChangeKind,
+}
+
+impl From<FilesystemChange> for Change {
+    fn from(change: FilesystemChange) -> Self {
+        let kind = match change.kind {
+            ChangeType::_0 => ChangeKind::Modified,
+            ChangeType::_1 => ChangeKind::Added,
+            ChangeType::_2 => ChangeKind::Deleted,
+        };
+        Self { path: change.path, kind }
+    }
+}
+
+/// A container's filesystem changes relative to its image.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Drift {
+    /// Entries of `docker diff`
+    pub changes: Vec<Change>,
+    /// Added files with the setuid or setgid bit
+    pub setuid_files: Vec<String>,
+}
+
+/// Reads the drift of a container, returning its findings.
+///
+/// Stopped containers keep their writable layer, so they are checked too.
+/// Containers whose diff cannot be read get no findings; failures are logged
+/// rather than aborting the scan.
+pub(crate) async fn check_container(docker: &Docker, container: &ContainerInspectResponse) -> Vec<Finding> {
+    let Some(id) = container.id.as_deref() else {
+        return Vec::new();
+    };
+    match diff(docker, id).await {
+        Some(drift) => check(container, &drift),
+        None => Vec::new(),
+    }
+}
+
+/// Reads the diff of container `id` and the modes of its added files.
+async fn diff(docker: &Docker, id: &str) -> Option<Drift> {
+    let changes = match tokio::time::timeout(DIFF_TIMEOUT, docker.container_changes(id)).await {
+        Ok(Ok(changes)) => changes.unwrap_or_default(),
+        Ok(Err(e)) => {
+            tracing::warn!("Failed to read filesystem changes of container {}: {}", id, e);
+            return None;
+        }
+        Err(_) => {
+            tracing::warn!("Timed out reading filesystem changes of container {}", id);
+            return None;
+        }
+    };
+    let changes: Vec<Change> = changes.into_iter().map(Change::from).collect();
+
+    let mut setuid_files = Vec::new();
+    for path in added_roots(&changes).take(MAX_ARCHIVES) {
+        if let Some(archive) = download(docker, id, path).await {
+            setuid_files.extend(setuid_entries(path, &archive));
+        }
+    }
+    Some(Drift { changes, setuid_files })
+}
+
+/// Added paths whose parent directory was not itself added, so each added
+/// tree is downloaded once.
+fn added_roots(changes: &[Change]) -> impl Iterator<Item = &str> {
+    let added: HashSet<&str> = changes
+        .iter()
+        .filter(|c| c.kind == ChangeKind::Added)
+        .map(|c| c.path.as_str())
+        .collect();
+    changes
+        .iter()
+        .filter(|c| c.kind == ChangeKind::Added)
+        .map(|c| c.path.as_str())
+        .filter(move |path| {
+            Path::new(path)
+                .parent()
+                .and_then(Path::to_str)
+                .is_none_or(|parent| !added.contains(parent))
+        })
+}
+
+/// Downloads `path` from container `id` as a tar archive, or `None` if it
+/// cannot be read or exceeds [`MAX_ARCHIVE_BYTES`].
+async fn download(docker: &Docker, id: &str, path: &str) -> Option<Vec<u8>> {
+    let mut chunks = docker.download_from_container(id, Some(DownloadFromContainerOptions { path }));
+    let mut archive = Vec::new();
+    let read = async {
+        while let Some(chunk) = chunks.next().await {
+            archive.extend_from_slice(&chunk?);
+            if archive.len() > MAX_ARCHIVE_BYTES {
+                return Ok(false);
+            }
+        }
+        Ok::<_, bollard::errors::Error>(true)
+    };
+    match tokio::time::timeout(DIFF_TIMEOUT, read).await {
+        Ok(Ok(true)) => Some(archive),
+        Ok(Ok(false)) => {
+            tracing::debug!("Skipping {} in container {}: archive too large", path, id);
+            None
+        }
+        Ok(Err(e)) => {
+            tracing::debug!("Failed to download {} from container {}: {}", path, id, e);
+            None
+        }
+        Err(_) => {
+            tracing::debug!("Timed out downloading {} from container {}", path, id);
+            None
+        }
+    }
+}
+
+/// Lists the regular files with the setuid or setgid bit in the archive of
+/// `path`, as absolute container paths.
+///
+/// Archive entries are named relative to the parent of `path` (downloading
+/// `/tmp/x` yields `x`, `x/sh`, ...), as the Docker archive API returns them.
+pub fn setuid_entries(path: &str, archive: &[u8]) -> Vec<String> {
+    let base = Path::new(path).parent().unwrap_or(Path::new("/"));
+    let mut archive = tar::Archive::new(archive);
+    let Ok(entries) = archive.entries() else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .filter(|entry| {
+            let header = entry.header();
+            header.entry_type().is_file() && header.mode().is_ok_and(|mode| mode & SETID_BITS != 0)
+        })
+        .filter_map(|entry| entry.path().ok().map(|p| base.join(p).display().to_string()))
+        .collect()
+}
+
+/// Checks a container's drift.
+///
+/// # Returns
+///
+/// One finding per changed sensitive file and per added setuid/setgid file,
+/// and one finding listing the changes in system directories, targeting
+/// `container`
+pub fn check(container: &ContainerInspectResponse, drift: &Drift) -> Vec<Finding> {
+    let mut findings: Vec<Finding> = drift
+        .changes
+        .iter()
+        .filter(|c| is_sensitive(&c.path))
+        .map(|c| {
+            drift_finding(
+                "drift_sensitive_file_changed",
+                "Sensitive file changed in container",
+                format!("{} was {} relative to the image", c.path, c.kind),
+                RiskLevel::High,
+                "Investigate the change as possible tampering; bake intended changes into the image and recreate the container from it",
+            )
+        })
+        .collect();
+
+    let system: Vec<&Change> = drift
+        .changes
+        .iter()
+        .filter(|c| !is_sensitive(&c.path) && is_in_system_dir(&c.path))
+        .collect();
+    if !system.is_empty() {
+        let mut listed: Vec<String> = system
+            .iter()
+            .take(MAX_LISTED_PATHS)
+            .map(|c| format!("{} ({})", c.path, c.kind))
+            .collect();
+        if system.len() > MAX_LISTED_PATHS {
+            listed.push(format!("and {} more", system.len() - MAX_LISTED_PATHS));
+        }
+        findings.push(drift_finding(
+            "drift_system_path_changed",
+            "System binaries changed in container",
+            format!(
+                "{} path(s) in system directories differ from the image: {}",
+                system.len(),
+                listed.join(", ")
+            ),
+            RiskLevel::Medium,
+            "Install packages at build time and run the container with --read-only, so binaries and libraries cannot drift from the image",
+        ));
+    }
+
+    findings.extend(drift.setuid_files.iter().map(|path| {
+        drift_finding(
+            "drift_setuid_file_added",
+            "Setuid file added in container",
+            format!("{} was added with the setuid or setgid bit", path),
+            RiskLevel::High,
+            "Investigate the file as a privilege escalation backdoor, recreate the container from its image, and run it with --security-opt no-new-privileges",
+        )
+    }));
+
+    let target = FindingTarget::container(container);
+    findings
+        .into_iter()
+        .map(|finding| Finding {
+            target: Some(target.clone()),
+            ..finding
+        })
+        .collect()
+}
+
+fn is_sensitive(path: &str) -> bool {
+    SENSITIVE_FILES.contains(&path) || SENSITIVE_DIRS.iter().any(|dir| is_under(path, dir))
+}
+
+fn is_in_system_dir(path: &str) -> bool {
+    SYSTEM_DIRS.iter().any(|dir| is_under(path, dir))
+}
+
+/// Returns whether `path` is strictly inside directory `dir`.
+fn is_under(path: &str, dir: &str) -> bool {
+    path.strip_prefix(dir).is_some_and(|rest| rest.starts_with('/'))
+}
+
+fn drift_finding(kind: &str, name: &str, description: String, risk: RiskLevel, fix: &str) -> Finding {
+    Finding {
+        rule_name: Some(name.to_string()),
+        remediation: Some(fix.to_string()),
+        tags: vec!["drift".to_string(), "integrity".to_string()],
+        category: Some("runtime".to_string()),
+        ..Finding::new(kind, description, risk)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn container() -> ContainerInspectResponse {
+        ContainerInspectResponse {
+            id: Some("abc123".to_string()),
+            name: Some("/web".to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn change(path: &str, kind: ChangeKind) -> Change {
+        Change {
+            path: path.to_string(),
+            kind,
+        }
+    }
+
+    fn kinds(findings: &[Finding]) -> Vec<&str> {
+        findings.iter().map(|f| f.kind.as_str()).collect()
+    }
+
+    #[test]
+    fn reports_sensitive_files_system_paths_and_setuid_files() {
+        let drift = Drift {
+            changes: vec![
+                change("/etc", ChangeKind::Modified),
+                change("/etc/passwd", ChangeKind::Modified),
+                change("/root/.ssh/authorized_keys", ChangeKind::Added),
+                change("/usr/bin", ChangeKind::Modified),
+                change("/usr/bin/curl", ChangeKind::Added),
+                change("/var/log/app.log", ChangeKind::Added),
+            ],
+            setuid_files: vec!["/tmp/sh".to_string()],
+        };
+        let findings = check(&container(), &drift);
+
+        assert_eq!(
+            kinds(&findings),
+            [
+                "drift_sensitive_file_changed",
+                "drift_sensitive_file_changed",
+                "drift_system_path_changed",
+                "drift_setuid_file_added",
+            ]
+        );
+        assert_eq!(findings[0].description, "/etc/passwd was modified relative to the image");
+        assert_eq!(
+            findings[2].description,
+            "1 path(s) in system directories differ from the image: /usr/bin/curl (added)"
+        );
+        assert!(findings.iter().all(|f| f.target.is_some()));
+
+        // Application data and the directories themselves are not drift
+        let drift = Drift {
+            changes: vec![change("/usr/bin", ChangeKind::Modified), change("/app/cache", ChangeKind::Added)],
+            setuid_files: Vec::new(),
+        };
+        assert!(check(&container(), &drift).is_empty());
+    }
+
+    #[test]
+    fn lists_only_top_level_added_paths() {
+        let changes = [
+            change("/tmp", ChangeKind::Modified),
+            change("/tmp/x", ChangeKind::Added),
+            change("/tmp/x/sh", ChangeKind::Added),
+            change("/usr/bin/nc", ChangeKind::Added),
+        ];
+        assert_eq!(added_roots(&changes).collect::<Vec<_>>(), ["/tmp/x", "/usr/bin/nc"]);
+    }
+
+    #[test]
+    fn finds_setuid_entries_in_archive() {
+        let mut builder = tar::Builder::new(Vec::new());
+        for (name, mode) in [("x/sh", 0o4755), ("x/run.sh", 0o755), ("x/wall", 0o2755)] {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(0);
+            header.set_mode(mode);
+            header.set_cksum();
+            builder.append_data(&mut header, name, std::io::empty()).unwrap();
+        }
+        let archive = builder.into_inner().unwrap();
+
+        assert_eq!(setuid_entries("/tmp/x", &archive), ["/tmp/x/sh", "/tmp/x/wall"]);
+    }
+}
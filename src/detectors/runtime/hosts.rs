@@ -0,0 +1,285 @@
+//! Multi-host runtime scanning.
+//!
+//! `valeris scan --hosts hosts.toml` scans several Docker endpoints in one
+//! run and merges them into one report, each container tagged with the host
+//! it runs on:
+//!
+//! ```toml
+//! [[hosts]]
+//! name = "web-1"
+//! url = "ssh://deploy@web-1.example.com"
+//!
+//! [[hosts]]
+//! url = "tcp://10.0.0.5:2375"
+//! ```
+//!
+//! `ssh://` endpoints are reached by forwarding the remote Docker socket to
+//! a local one with the system `ssh` client, so keys, agents and
+//! `~/.ssh/config` apply as they do for `docker -H ssh://...`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use serde::Deserialize;
+
+use crate::error::{Result, ValerisError};
+
+/// Remote socket forwarded when an `ssh://` URL has no path
+pub const DEFAULT_REMOTE_SOCKET: &str = "/var/run/docker.sock";
+
+/// A Docker endpoint listed in a hosts file.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct HostEntry {
+    /// Label used in reports; the URL's host name if unset
+    pub name: Option<String>,
+    /// `ssh://[user@]host[:port][/socket]`, `tcp://host:port` or `unix://path`
+    pub url: String,
+}
+
+impl HostEntry {
+    /// Label the host's containers are tagged with.
+    pub fn label(&self) -> String {
+        if let Some(name) = &self.name {
+            return name.clone();
+        }
+        let rest = self.url.split_once("://").map_or(self.url.as_str(), |(_, rest)| rest);
+        let authority = rest.split('/').next().unwrap_or(rest);
+        let host = authority.rsplit_once('@').map_or(authority, |(_, host)| host);
+        match host.rsplit_once(':') {
+            Some((name, port)) if port.chars().all(|c| c.is_ascii_digit()) => name.to_string(),
+            _ if host.is_empty() => self.url.clone(),
+            _ => host.to_string(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct HostsFile {
+    #[serde(default)]
+    hosts: Vec<HostEntry>,
+}
+
+/// Reads the Docker endpoints of a hosts file.
+///
+/// # Errors
+///
+/// * [`ValerisError::InputRead`] if the file cannot be read
+/// * [`ValerisError::InputParse`] if it is not valid TOML, lists no hosts,
+///   or uses an unsupported URL scheme
+pub fn load_hosts(path: &Path) -> Result<Vec<HostEntry>> {
+    let raw = fs::read_to_string(path).map_err(|source| ValerisError::InputRead {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let parse_error = |message: String| ValerisError::InputParse {
+        path: path.to_path_buf(),
+        message,
+    };
+
+    let file: HostsFile = toml::from_str(&raw).map_err(|e| parse_error(e.to_string()))?;
+    if file.hosts.is_empty() {
+        return Err(parse_error("no [[hosts]] entries".to_string()));
+    }
+    for host in &file.hosts {
+        let supported = ["ssh://", "tcp://", "http://", "unix://"]
+            .iter()
+            .any(|scheme| host.url.starts_with(scheme));
+        if !supported {
+            return Err(parse_error(format!(
+                "unsupported URL '{}' (expected ssh://, tcp:// or unix://)",
+                host.url
+            )));
+        }
+    }
+    Ok(file.hosts)
+}
+
+// ─────────────────────────────────────────────────────────────────
+// SSH Tunnels
+// ─────────────────────────────────────────────────────────────────
+
+/// Distinguishes the local sockets of concurrent tunnels
+static TUNNEL_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// A remote Docker socket forwarded to a local Unix socket over `ssh`.
+///
+/// The `ssh` process is stopped and the local socket removed on drop.
+pub(crate) struct SshTunnel {
+    child: Child,
+    socket: PathBuf,
+}
+
+impl SshTunnel {
+    /// Starts `ssh` for `url` and waits up to `timeout` for the local
+    /// socket to appear.
+    pub(crate) async fn open(url: &str, timeout: Duration) -> std::io::Result<Self> {
+        let socket = std::env::temp_dir().join(format!(
+            "valeris-ssh-{}-{}.sock",
+            std::process::id(),
+            TUNNEL_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        let child = Command::new("ssh")
+            .args(ssh_args(url, &socket)?)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        let mut tunnel = Self { child, socket };
+
+        let started = Instant::now();
+        while !tunnel.socket.exists() {
+            if let Some(status) = tunnel.child.try_wait()? {
+                let mut stderr = String::new();
+                if let Some(mut pipe) = tunnel.child.stderr.take() {
+                    use std::io::Read;
+                    let _ = pipe.read_to_string(&mut stderr);
+                }
+                return Err(std::io::Error::other(format!(
+                    "ssh to {} exited with {}: {}",
+                    url,
+                    status,
+                    stderr.trim()
+                )));
+            }
+            if started.elapsed() > timeout {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::TimedOut,
+                    format!("timed out forwarding the Docker socket of {}", url),
+                ));
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        Ok(tunnel)
+    }
+
+    /// Docker endpoint of the local end of the tunnel.
+    pub(crate) fn docker_host(&self) -> String {
+        format!("unix://{}", self.socket.display())
+    }
+}
+
+impl Drop for SshTunnel {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+        let _ = fs::remove_file(&self.socket);
+    }
+}
+
+/// Arguments for an `ssh` process forwarding the Docker socket of `url` to
+/// `local`.
+fn ssh_args(url: &str, local: &Path) -> std::io::Result<Vec<String>> {
+    let invalid = || {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("invalid ssh URL '{}' (expected ssh://[user@]host[:port][/socket])", url),
+        )
+    };
+    let rest = url.strip_prefix("ssh://").ok_or_else(invalid)?;
+    let (authority, remote) = match rest.find('/') {
+        Some(i) => (&rest[..i], &rest[i..]),
+        None => (rest, DEFAULT_REMOTE_SOCKET),
+    };
+    let (user, host_port) = match authority.rsplit_once('@') {
+        Some((user, host)) => (Some(user), host),
+        None => (None, authority),
+    };
+    let (host, port) = match host_port.rsplit_once(':') {
+        Some((host, port)) if port.parse::<u16>().is_ok() => (host, Some(port)),
+        _ => (host_port, None),
+    };
+    if host.is_empty() {
+        return Err(invalid());
+    }
+
+    let mut args: Vec<String> = [
+        "-nNT",
+        "-o",
+        "ExitOnForwardFailure=yes",
+        "-o",
+        "BatchMode=yes",
+        "-o",
+        "StreamLocalBindUnlink=yes",
+        "-L",
+    ]
+    .iter()
+    .map(|s| s.to_string())
+    .collect();
+    args.push(format!("{}:{}", local.display(), remote));
+    if let Some(port) = port {
+        args.extend(["-p".to_string(), port.to_string()]);
+    }
+    args.push(match user {
+        Some(user) => format!("{}@{}", user, host),
+        None => host.to_string(),
+    });
+    Ok(args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: Option<&str>, url: &str) -> HostEntry {
+        HostEntry {
+            name: name.map(str::to_string),
+            url: url.to_string(),
+        }
+    }
+
+    #[test]
+    fn labels_default_to_the_url_host() {
+        assert_eq!(entry(Some("web-1"), "tcp://10.0.0.5:2375").label(), "web-1");
+        assert_eq!(entry(None, "tcp://10.0.0.5:2375").label(), "10.0.0.5");
+        assert_eq!(entry(None, "ssh://deploy@db.example.com:2222").label(), "db.example.com");
+        assert_eq!(entry(None, "unix:///var/run/docker.sock").label(), "unix:///var/run/docker.sock");
+    }
+
+    #[test]
+    fn builds_ssh_forwarding_arguments() {
+        let local = Path::new("/tmp/valeris.sock");
+
+        let args = ssh_args("ssh://deploy@web-1:2222", local).unwrap();
+        assert_eq!(args[args.len() - 4..], [
+            "/tmp/valeris.sock:/var/run/docker.sock",
+            "-p",
+            "2222",
+            "deploy@web-1"
+        ]);
+
+        let args = ssh_args("ssh://web-1/run/user/1000/docker.sock", local).unwrap();
+        assert_eq!(args[args.len() - 2..], ["/tmp/valeris.sock:/run/user/1000/docker.sock", "web-1"]);
+
+        assert!(ssh_args("ssh://", local).is_err());
+    }
+
+    #[test]
+    fn loads_hosts_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("hosts.toml");
+        fs::write(
+            &path,
+            "[[hosts]]\nname = \"web-1\"\nurl = \"ssh://deploy@web-1\"\n\n[[hosts]]\nurl = \"tcp://10.0.0.5:2375\"\n",
+        )
+        .unwrap();
+
+        let hosts = load_hosts(&path).unwrap();
+
+        assert_eq!(hosts, [entry(Some("web-1"), "ssh://deploy@web-1"), entry(None, "tcp://10.0.0.5:2375")]);
+    }
+
+    #[test]
+    fn rejects_unsupported_schemes_and_empty_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("hosts.toml");
+
+        fs::write(&path, "[[hosts]]\nurl = \"ftp://web-1\"\n").unwrap();
+        assert!(matches!(load_hosts(&path), Err(ValerisError::InputParse { .. })));
+
+        fs::write(&path, "").unwrap();
+        assert!(matches!(load_hosts(&path), Err(ValerisError::InputParse { .. })));
+    }
+}
@@ -0,0 +1,150 @@
+//! Incremental runtime scanning.
+//!
+//! Supports `valeris scan --since <SINCE>`, which restricts a scan to
+//! containers created or (re)started after a cutoff. The cutoff is either a
+//! relative duration, an absolute RFC 3339 timestamp, or the start time of
+//! the previous scan recorded on disk.
+
+use std::fs;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use anyhow::{Context, Result};
+use bollard::models::ContainerInspectResponse;
+
+use crate::error::ValerisError;
+
+/// Keyword for `--since` that refers to the previously recorded scan.
+pub const SINCE_LAST_SCAN: &str = "last";
+
+/// Returns the file used to record the start time of the last scan.
+///
+/// Lives next to the downloaded rules in the local data directory.
+pub fn last_scan_file() -> PathBuf {
+    dirs::data_local_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("valeris")
+        .join("last_scan")
+}
+
+/// Resolves a `--since` argument into an absolute cutoff time.
+///
+/// # Arguments
+///
+/// * `spec` - A duration (`30m`, `2h`, `1d`), an RFC 3339 timestamp
+///   (`2024-05-01T12:00:00Z`), or `last`
+///
+/// # Errors
+///
+/// Returns [`ValerisError::InvalidFilter`] if the value cannot be parsed, or
+/// if `last` is used before any scan has been recorded.
+pub fn resolve_since(spec: &str) -> crate::error::Result<SystemTime> {
+    let spec = spec.trim();
+
+    if spec.eq_ignore_ascii_case(SINCE_LAST_SCAN) {
+        return load_last_scan()?.ok_or_else(|| {
+            ValerisError::InvalidFilter(format!(
+                "--since last: no previous scan recorded in {}",
+                last_scan_file().display()
+            ))
+        });
+    }
+
+    if let Ok(timestamp) = humantime::parse_rfc3339_weak(spec) {
+        return Ok(timestamp);
+    }
+
+    match humantime::parse_duration(spec) {
+        Ok(duration) => SystemTime::now().checked_sub(duration).ok_or_else(|| {
+            ValerisError::InvalidFilter(format!("--since duration '{}' is too large", spec))
+        }),
+        Err(_) => Err(ValerisError::InvalidFilter(format!(
+            "Invalid --since value '{}': expected a duration (e.g. 30m, 2h, 1d), \
+             an RFC 3339 timestamp, or '{}'",
+            spec, SINCE_LAST_SCAN
+        ))),
+    }
+}
+
+/// Checks whether a container was created or (re)started at or after `cutoff`.
+///
+/// Containers whose timestamps are missing or unparsable are treated as
+/// unchanged.
+pub fn changed_since(container: &ContainerInspectResponse, cutoff: SystemTime) -> bool {
+    let started_at = container.state.as_ref().and_then(|s| s.started_at.as_deref());
+
+    [container.created.as_deref(), started_at]
+        .into_iter()
+        .flatten()
+        .filter_map(|ts| humantime::parse_rfc3339_weak(ts).ok())
+        .any(|ts| ts >= cutoff)
+}
+
+/// Reads the start time of the last recorded scan, if any.
+pub fn load_last_scan() -> Result<Option<SystemTime>> {
+    let path = last_scan_file();
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let raw = fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let timestamp = humantime::parse_rfc3339_weak(raw.trim())
+        .with_context(|| format!("Invalid timestamp in {}", path.display()))?;
+    Ok(Some(timestamp))
+}
+
+/// Records `started` as the start time of the most recent scan.
+pub fn record_scan_time(started: SystemTime) -> Result<()> {
+    let path = last_scan_file();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory {}", parent.display()))?;
+    }
+    fs::write(&path, humantime::format_rfc3339_seconds(started).to_string())
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bollard::models::ContainerState;
+    use std::time::Duration;
+
+    fn container(created: &str, started_at: &str) -> ContainerInspectResponse {
+        ContainerInspectResponse {
+            created: Some(created.to_string()),
+            state: Some(ContainerState {
+                started_at: Some(started_at.to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn resolve_since_accepts_durations_and_timestamps() {
+        let cutoff = resolve_since("2h").unwrap();
+        let elapsed = SystemTime::now().duration_since(cutoff).unwrap();
+        assert!(elapsed >= Duration::from_secs(7200));
+
+        let cutoff = resolve_since("2024-05-01T12:00:00Z").unwrap();
+        assert_eq!(cutoff, humantime::parse_rfc3339("2024-05-01T12:00:00Z").unwrap());
+
+        assert!(resolve_since("yesterday-ish").is_err());
+    }
+
+    #[test]
+    fn changed_since_checks_created_and_started_at() {
+        let cutoff = humantime::parse_rfc3339("2024-05-01T12:00:00Z").unwrap();
+
+        let old = container("2024-01-01T00:00:00Z", "2024-01-01T00:00:01.123456789Z");
+        let restarted = container("2024-01-01T00:00:00Z", "2024-05-02T08:30:00.5Z");
+        let created = container("2024-05-03T00:00:00Z", "0001-01-01T00:00:00Z");
+
+        assert!(!changed_since(&old, cutoff));
+        assert!(changed_since(&restarted, cutoff));
+        assert!(changed_since(&created, cutoff));
+        assert!(!changed_since(&ContainerInspectResponse::default(), cutoff));
+    }
+}
@@ -1,2 +1,9 @@
+pub mod drift;
+pub mod hosts;
+pub mod incremental;
+pub mod ports;
+pub mod probe;
+pub mod quadlet;
 pub mod scanner;
+pub mod usage;
 pub mod yaml_rules;
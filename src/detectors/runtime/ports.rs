@@ -0,0 +1,391 @@
+//! Host port binding checks.
+//!
+//! Built-in checks on `HostConfig.PortBindings`, run with the YAML rules on
+//! every container (including `--input` and `--quadlet` scans):
+//!
+//! * `port_sensitive_binding` — a well-known database or admin port (SSH,
+//!   the Docker API, PostgreSQL, Redis, ...) published beyond loopback
+//! * `port_privileged_binding` — any other host port below 1024
+//!
+//! Ports expected on some hosts, like 80 and 443 on an edge proxy, are
+//! listed in `[detectors.ports] allow` and never reported.
+//!
+//! The same module applies the organization's port policy to the
+//! `exposed_ports` rule: `[detectors.exposed_ports]` (or the rule's own
+//! `params`) lists ports whose exposure is expected (`allow`, finding
+//! dropped) or forbidden (`deny`, finding raised to high), so the severity
+//! reflects policy rather than only the bind address.
+
+use bollard::models::ContainerInspectResponse;
+use serde_json::Value;
+
+use crate::config::DetectorsConfig;
+use crate::detectors::runtime::yaml_rules::YamlRuleEngine;
+use crate::docker::model::{Finding, FindingTarget, RiskLevel};
+use crate::error::ValerisError;
+
+/// Rule the `[detectors.exposed_ports]` policy applies to
+pub const EXPOSED_PORTS_RULE: &str = "exposed_ports";
+
+/// Finding kinds reported by the port checks, usable with `--only`/`--exclude`
+pub const CHECK_IDS: [&str; 2] = ["port_sensitive_binding", "port_privileged_binding"];
+
+/// Host ports below this number need privileges to bind
+pub const PRIVILEGED_PORT_LIMIT: u16 = 1024;
+
+/// Well-known database and admin ports, with the service usually behind them
+pub const SENSITIVE_PORTS: [(u16, &str); 20] = [
+    (22, "SSH"),
+    (23, "Telnet"),
+    (445, "SMB"),
+    (1433, "SQL Server"),
+    (1521, "Oracle"),
+    (2375, "Docker API"),
+    (2376, "Docker API (TLS)"),
+    (2379, "etcd"),
+    (3306, "MySQL"),
+    (3389, "RDP"),
+    (5432, "PostgreSQL"),
+    (5900, "VNC"),
+    (5984, "CouchDB"),
+    (6379, "Redis"),
+    (8500, "Consul"),
+    (9200, "Elasticsearch"),
+    (10250, "Kubelet API"),
+    (11211, "Memcached"),
+    (15672, "RabbitMQ management"),
+    (27017, "MongoDB"),
+];
+
+/// A port, optionally restricted to one protocol (`443/tcp`, `53`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PortSpec {
+    pub port: u16,
+    /// Lowercase protocol (`tcp`, `udp`, `sctp`); any protocol if `None`
+    pub protocol: Option<String>,
+}
+
+impl PortSpec {
+    /// Parses `PORT` or `PORT/PROTOCOL`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ValerisError::Config`] if the port is not a number from 1
+    /// to 65535 or the protocol is empty. `section` names the
+    /// configuration table in the message.
+    pub fn parse(spec: &str, section: &str) -> Result<Self, ValerisError> {
+        let invalid = || {
+            ValerisError::Config(format!(
+                "Invalid port '{}' in {} (expected PORT or PORT/PROTOCOL, e.g. 443/tcp)",
+                spec, section
+            ))
+        };
+        let (port, protocol) = match spec.trim().split_once('/') {
+            Some((port, protocol)) => (port, Some(protocol.trim().to_ascii_lowercase())),
+            None => (spec.trim(), None),
+        };
+        let port = port.trim().parse::<u16>().ok().filter(|p| *p > 0).ok_or_else(invalid)?;
+        if protocol.as_deref() == Some("") {
+            return Err(invalid());
+        }
+        Ok(Self { port, protocol })
+    }
+
+    /// Returns whether this spec covers `port` over `protocol`.
+    pub fn matches(&self, port: u16, protocol: &str) -> bool {
+        self.port == port && self.protocol.as_deref().is_none_or(|p| p.eq_ignore_ascii_case(protocol))
+    }
+}
+
+/// Compiled `[detectors.ports]` and `[detectors.exposed_ports]` settings.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PortPolicy {
+    /// Host ports the port binding checks never report
+    allow: Vec<PortSpec>,
+    /// Ports whose `exposed_ports` findings are dropped
+    exposed_allow: Vec<PortSpec>,
+    /// Ports whose `exposed_ports` findings are raised to high
+    exposed_deny: Vec<PortSpec>,
+}
+
+impl PortPolicy {
+    /// Compiles the raw `[detectors.ports]` and `[detectors.exposed_ports]`
+    /// tables.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ValerisError::Config`] if an entry is not a valid port.
+    pub fn from_config(raw: &DetectorsConfig) -> Result<Self, ValerisError> {
+        let parse = |specs: Option<&Vec<String>>, section: &str| {
+            specs
+                .into_iter()
+                .flatten()
+                .map(|spec| PortSpec::parse(spec, section))
+                .collect::<Result<Vec<_>, _>>()
+        };
+        let exposed = raw.exposed_ports.as_ref();
+        Ok(Self {
+            allow: parse(raw.ports.as_ref().and_then(|p| p.allow.as_ref()), "[detectors.ports] allow")?,
+            exposed_allow: parse(exposed.and_then(|e| e.allow.as_ref()), "[detectors.exposed_ports] allow")?,
+            exposed_deny: parse(exposed.and_then(|e| e.deny.as_ref()), "[detectors.exposed_ports] deny")?,
+        })
+    }
+
+    /// Adds the `allow` and `deny` params of the `exposed_ports` rule, if
+    /// loaded, to the configured lists. Invalid entries are logged and
+    /// skipped.
+    pub fn with_rule_params(mut self, engine: &YamlRuleEngine) -> Self {
+        let Some(rule) = engine.rules().iter().find(|r| r.id.eq_ignore_ascii_case(EXPOSED_PORTS_RULE)) else {
+            return self;
+        };
+        for (name, list) in [("allow", &mut self.exposed_allow), ("deny", &mut self.exposed_deny)] {
+            let specs = rule.params.get(name).and_then(Value::as_array).into_iter().flatten();
+            for spec in specs {
+                let text = spec.as_str().map(str::to_string).unwrap_or_else(|| spec.to_string());
+                match PortSpec::parse(&text, &format!("the {} params of {}", name, EXPOSED_PORTS_RULE)) {
+                    Ok(spec) => list.push(spec),
+                    Err(e) => tracing::warn!("{}", e),
+                }
+            }
+        }
+        self
+    }
+
+    /// Returns whether host `port` over `protocol` is allowlisted.
+    pub fn allows(&self, port: u16, protocol: &str) -> bool {
+        self.allow.iter().any(|spec| spec.matches(port, protocol))
+    }
+
+    /// Applies the exposure policy to a container's `exposed_ports`
+    /// findings: a finding for a denied port becomes high, and one whose
+    /// port is only allowed is dropped. Deny wins over allow.
+    ///
+    /// The exposed port is read from the finding's matched `HostPort`
+    /// field, and its protocols from the container's published ports;
+    /// findings without a matched host port are left unchanged.
+    pub fn apply_exposure(&self, container: &ContainerInspectResponse, findings: &mut Vec<Finding>) {
+        if self.exposed_allow.is_empty() && self.exposed_deny.is_empty() {
+            return;
+        }
+        let published = container.network_settings.as_ref().and_then(|n| n.ports.as_ref());
+        findings.retain_mut(|finding| {
+            if !finding.kind.eq_ignore_ascii_case(EXPOSED_PORTS_RULE) {
+                return true;
+            }
+            let Some(port) = finding
+                .debug
+                .iter()
+                .find(|field| field.path.ends_with("HostPort"))
+                .and_then(|field| field.value.as_deref()?.trim().parse::<u16>().ok())
+            else {
+                return true;
+            };
+            let protocols: Vec<String> = published
+                .into_iter()
+                .flatten()
+                .filter(|(_, bindings)| {
+                    bindings.iter().flatten().any(|b| b.host_port.as_deref().map(str::trim) == Some(&*port.to_string()))
+                })
+                .map(|(key, _)| key.split_once('/').map_or("tcp", |(_, p)| p).to_ascii_lowercase())
+                .collect();
+            let matches = |specs: &[PortSpec]| protocols.iter().any(|p| specs.iter().any(|s| s.matches(port, p)));
+            if matches(&self.exposed_deny) {
+                finding.risk = RiskLevel::High;
+                true
+            } else {
+                !matches(&self.exposed_allow)
+            }
+        });
+    }
+}
+
+/// Checks a container's host port bindings.
+///
+/// # Returns
+///
+/// A finding for each binding of a sensitive port beyond loopback, and for
+/// each other binding of a privileged port, skipping ports `policy` allows;
+/// each targets `container`
+pub fn check(container: &ContainerInspectResponse, policy: &PortPolicy) -> Vec<Finding> {
+    let Some(bindings) = container.host_config.as_ref().and_then(|h| h.port_bindings.as_ref()) else {
+        return Vec::new();
+    };
+    let mut published: Vec<(u16, String, String)> = bindings
+        .iter()
+        .flat_map(|(container_port, bindings)| {
+            let protocol = container_port.split_once('/').map_or("tcp", |(_, p)| p).to_ascii_lowercase();
+            bindings.iter().flatten().filter_map(move |binding| {
+                let port = binding.host_port.as_deref()?.trim().parse::<u16>().ok()?;
+                Some((port, protocol.clone(), binding.host_ip.clone().unwrap_or_default()))
+            })
+        })
+        .filter(|(port, protocol, _)| !policy.allows(*port, protocol))
+        .collect();
+    published.sort();
+    published.dedup();
+
+    let target = FindingTarget::container(container);
+    published
+        .into_iter()
+        .filter_map(|(port, protocol, host_ip)| {
+            let service = SENSITIVE_PORTS.iter().find(|(p, _)| *p == port).map(|(_, name)| *name);
+            let finding = match service {
+                Some(service) if !is_loopback(&host_ip) => port_finding(
+                    "port_sensitive_binding",
+                    "Database or admin port published",
+                    format!("Host port {}/{} ({}) is published on {}", port, protocol, service, interface(&host_ip)),
+                    RiskLevel::High,
+                    "Keep the service on an internal network, or bind it to 127.0.0.1 and reach it through an SSH tunnel or VPN",
+                ),
+                _ if port < PRIVILEGED_PORT_LIMIT => port_finding(
+                    "port_privileged_binding",
+                    "Privileged host port bound",
+                    format!("Host port {}/{} is a privileged port (<{}) bound on {}", port, protocol, PRIVILEGED_PORT_LIMIT, interface(&host_ip)),
+                    RiskLevel::Low,
+                    "Publish the service on a port of 1024 or above, or allow the port in [detectors.ports] if the host is meant to serve it (e.g. 80/443 on an edge proxy)",
+                ),
+                _ => return None,
+            };
+            Some(Finding {
+                target: Some(target.clone()),
+                ..finding
+            })
+        })
+        .collect()
+}
+
+fn is_loopback(host_ip: &str) -> bool {
+    host_ip == "::1" || host_ip == "localhost" || host_ip.starts_with("127.")
+}
+
+fn interface(host_ip: &str) -> &str {
+    match host_ip {
+        "" | "0.0.0.0" | "::" => "all interfaces",
+        ip => ip,
+    }
+}
+
+fn port_finding(kind: &str, name: &str, description: String, risk: RiskLevel, fix: &str) -> Finding {
+    Finding {
+        rule_name: Some(name.to_string()),
+        remediation: Some(fix.to_string()),
+        tags: vec!["network".to_string(), "ports".to_string()],
+        category: Some("network".to_string()),
+        ..Finding::new(kind, description, risk)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{ExposedPortsConfig, PortsConfig};
+
+    fn container(bindings: &str) -> ContainerInspectResponse {
+        serde_json::from_str(&format!(
+            r#"{{"Id": "abc123", "Name": "/edge", "HostConfig": {{"PortBindings": {}}}}}"#,
+            bindings
+        ))
+        .unwrap()
+    }
+
+    fn descriptions(findings: &[Finding]) -> Vec<(&str, &str)> {
+        findings.iter().map(|f| (f.kind.as_str(), f.description.as_str())).collect()
+    }
+
+    #[test]
+    fn reports_sensitive_and_privileged_bindings() {
+        let container = container(
+            r#"{
+                "443/tcp": [{"HostIp": "", "HostPort": "443"}],
+                "5432/tcp": [{"HostIp": "0.0.0.0", "HostPort": "5432"}],
+                "6379/tcp": [{"HostIp": "127.0.0.1", "HostPort": "6379"}],
+                "22/tcp": [{"HostIp": "127.0.0.1", "HostPort": "22"}],
+                "8080/tcp": [{"HostIp": "", "HostPort": "8080"}],
+                "9000/tcp": [{"HostIp": "", "HostPort": ""}]
+            }"#,
+        );
+
+        let findings = check(&container, &PortPolicy::default());
+        assert_eq!(descriptions(&findings), [
+            ("port_privileged_binding", "Host port 22/tcp is a privileged port (<1024) bound on 127.0.0.1"),
+            ("port_privileged_binding", "Host port 443/tcp is a privileged port (<1024) bound on all interfaces"),
+            ("port_sensitive_binding", "Host port 5432/tcp (PostgreSQL) is published on all interfaces"),
+        ]);
+        assert!(findings.iter().all(|f| f.target.is_some()));
+    }
+
+    #[test]
+    fn allowlisted_ports_are_not_reported() {
+        let container = container(
+            r#"{
+                "80/tcp": [{"HostIp": "", "HostPort": "80"}],
+                "443/tcp": [{"HostIp": "", "HostPort": "443"}],
+                "53/udp": [{"HostIp": "", "HostPort": "53"}]
+            }"#,
+        );
+        let policy = PortPolicy::from_config(&DetectorsConfig {
+            ports: Some(PortsConfig {
+                allow: Some(vec!["80".to_string(), "443/tcp".to_string(), "53/tcp".to_string()]),
+            }),
+            ..Default::default()
+        })
+        .unwrap();
+
+        let findings = check(&container, &policy);
+        assert_eq!(descriptions(&findings), [(
+            "port_privileged_binding",
+            "Host port 53/udp is a privileged port (<1024) bound on all interfaces"
+        )]);
+    }
+
+    #[test]
+    fn rejects_invalid_port_specs() {
+        for spec in ["http", "0", "70000/tcp", "443/"] {
+            let raw = DetectorsConfig {
+                exposed_ports: Some(ExposedPortsConfig {
+                    deny: Some(vec![spec.to_string()]),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            };
+            assert!(matches!(PortPolicy::from_config(&raw), Err(ValerisError::Config(_))), "{spec}");
+        }
+    }
+
+    #[test]
+    fn exposure_policy_sets_exposed_ports_severity() {
+        let container: ContainerInspectResponse = serde_json::from_str(
+            r#"{"Id": "abc123", "Name": "/db", "NetworkSettings": {"Ports": {
+                "443/tcp": [{"HostIp": "0.0.0.0", "HostPort": "443"}],
+                "5432/tcp": [{"HostIp": "0.0.0.0", "HostPort": "5432"}],
+                "8080/tcp": [{"HostIp": "0.0.0.0", "HostPort": "8080"}]
+            }}}"#,
+        )
+        .unwrap();
+        let engine = YamlRuleEngine::from_dir(std::path::Path::new("rules/runtime")).unwrap();
+        let mut findings: Vec<Finding> = engine
+            .scan_container_json(&serde_json::to_value(&container).unwrap())
+            .into_iter()
+            .filter(|f| f.kind == EXPOSED_PORTS_RULE)
+            .collect();
+        assert_eq!(findings.len(), 3);
+
+        let policy = PortPolicy::from_config(&DetectorsConfig {
+            exposed_ports: Some(ExposedPortsConfig {
+                allow: Some(vec!["443/tcp".to_string(), "5432".to_string()]),
+                deny: Some(vec!["5432/tcp".to_string()]),
+            }),
+            ..Default::default()
+        })
+        .unwrap();
+        policy.apply_exposure(&container, &mut findings);
+
+        let mut risks: Vec<(String, RiskLevel)> =
+            findings.iter().map(|f| (f.description.clone(), f.risk.clone())).collect();
+        risks.sort();
+        assert_eq!(risks, [
+            ("Exposed port found: 0.0.0.0:5432".to_string(), RiskLevel::High),
+            ("Exposed port found: 0.0.0.0:8080".to_string(), RiskLevel::Informative),
+        ]);
+    }
+}
@@ -0,0 +1,451 @@
+//! In-container runtime probes.
+//!
+//! `valeris scan --probe` runs a few read-only commands inside each running
+//! container with `docker exec` and checks that the privileges the container
+//! really has match what `docker inspect` reports:
+//!
+//! * `id -u` — the user processes run as
+//! * `capsh --print` (or `/proc/self/status`) — the capability bounding set
+//! * `/proc/self/mounts` — which mounts are writable
+//!
+//! A mismatch means the inspect data understates the container's privileges
+//! (e.g. an entrypoint that switches back to root, or a runtime that ignores
+//! `--cap-drop`), so rules evaluated on inspect alone would miss it. Images
+//! without the probe commands (e.g. distroless) are skipped.
+
+use std::collections::BTreeSet;
+use std::time::Duration;
+
+use bollard::{
+    exec::{CreateExecOptions, StartExecResults},
+    models::ContainerInspectResponse,
+    Docker,
+};
+use futures::stream::StreamExt;
+
+use crate::docker::model::{Finding, FindingTarget, RiskLevel};
+
+/// Finding kinds reported by the probes, usable with `--only`/`--exclude`
+pub const CHECK_IDS: [&str; 4] = [
+    "probe_user_mismatch",
+    "probe_capability_mismatch",
+    "probe_rootfs_writable",
+    "probe_mount_writable",
+];
+
+/// Maximum time a single probe command may run
+const PROBE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Capabilities Docker grants a container that has no `--cap-add`/`--cap-drop`
+const DEFAULT_CAPABILITIES: [&str; 14] = [
+    "AUDIT_WRITE",
+    "CHOWN",
+    "DAC_OVERRIDE",
+    "FOWNER",
+    "FSETID",
+    "KILL",
+    "MKNOD",
+    "NET_BIND_SERVICE",
+    "NET_RAW",
+    "SETFCAP",
+    "SETGID",
+    "SETPCAP",
+    "SETUID",
+    "SYS_CHROOT",
+];
+
+/// Capability names by bit number, as in `linux/capability.h`
+const CAPABILITY_BITS: [&str; 41] = [
+    "CHOWN",
+    "DAC_OVERRIDE",
+    "DAC_READ_SEARCH",
+    "FOWNER",
+    "FSETID",
+    "KILL",
+    "SETGID",
+    "SETUID",
+    "SETPCAP",
+    "LINUX_IMMUTABLE",
+    "NET_BIND_SERVICE",
+    "NET_BROADCAST",
+    "NET_ADMIN",
+    "NET_RAW",
+    "IPC_LOCK",
+    "IPC_OWNER",
+    "SYS_MODULE",
+    "SYS_RAWIO",
+    "SYS_CHROOT",
+    "SYS_PTRACE",
+    "SYS_PACCT",
+    "SYS_ADMIN",
+    "SYS_BOOT",
+    "SYS_NICE",
+    "SYS_RESOURCE",
+    "SYS_TIME",
+    "SYS_TTY_CONFIG",
+    "MKNOD",
+    "LEASE",
+    "AUDIT_WRITE",
+    "AUDIT_CONTROL",
+    "SETFCAP",
+    "MAC_OVERRIDE",
+    "MAC_ADMIN",
+    "SYSLOG",
+    "WAKE_ALARM",
+    "BLOCK_SUSPEND",
+    "AUDIT_READ",
+    "PERFMON",
+    "BPF",
+    "CHECKPOINT_RESTORE",
+];
+
+/// What the probe commands observed inside a container.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Observed {
+    /// Effective user ID of a process started in the container
+    pub uid: Option<u32>,
+    /// Capability bounding set, as upper-case names without `CAP_`
+    pub capabilities: Option<BTreeSet<String>>,
+    /// Mount points and whether each is mounted read-write, in mount order
+    pub mounts: Vec<(String, bool)>,
+}
+
+/// Probes a running container, returning its findings.
+///
+/// Containers that are not running, or where no probe command could run,
+/// get no findings; failures are logged rather than aborting the scan.
+pub(crate) async fn probe_container(docker: &Docker, container: &ContainerInspectResponse) -> Vec<Finding> {
+    let running = container
+        .state
+        .as_ref()
+        .and_then(|s| s.running)
+        .unwrap_or(false);
+    let Some(id) = container.id.as_deref().filter(|_| running) else {
+        return Vec::new();
+    };
+    let observed = observe(docker, id).await;
+    if observed == Observed::default() {
+        tracing::warn!("No probe command could run in container {}; skipping probes", id);
+        return Vec::new();
+    }
+    compare(container, &observed)
+}
+
+/// Runs the probe commands in container `id`.
+async fn observe(docker: &Docker, id: &str) -> Observed {
+    let uid = exec(docker, id, &["id", "-u"])
+        .await
+        .and_then(|out| out.trim().parse().ok());
+    let capabilities = match exec(docker, id, &["capsh", "--print"]).await {
+        Some(out) => parse_capsh_bounding(&out),
+        None => exec(docker, id, &["cat", "/proc/self/status"])
+            .await
+            .and_then(|out| parse_status_bounding(&out)),
+    };
+    let mounts = exec(docker, id, &["cat", "/proc/self/mounts"])
+        .await
+        .map(|out| parse_mounts(&out))
+        .unwrap_or_default();
+    Observed { uid, capabilities, mounts }
+}
+
+/// Runs `cmd` in container `id` and returns its stdout, or `None` if it
+/// could not run, exited non-zero, or timed out.
+async fn exec(docker: &Docker, id: &str, cmd: &[&str]) -> Option<String> {
+    let run = async {
+        let created = docker
+            .create_exec(
+                id,
+                CreateExecOptions {
+                    cmd: Some(cmd.to_vec()),
+                    attach_stdout: Some(true),
+                    attach_stderr: Some(true),
+                    ..Default::default()
+                },
+            )
+            .await?;
+        let mut stdout = String::new();
+        if let StartExecResults::Attached { mut output, .. } = docker.start_exec(&created.id, None).await? {
+            while let Some(chunk) = output.next().await {
+                if let bollard::container::LogOutput::StdOut { message } = chunk? {
+                    stdout.push_str(&String::from_utf8_lossy(&message));
+                }
+            }
+        }
+        let exit_code = docker.inspect_exec(&created.id).await?.exit_code;
+        Ok::<_, bollard::errors::Error>((exit_code == Some(0)).then_some(stdout))
+    };
+    match tokio::time::timeout(PROBE_TIMEOUT, run).await {
+        Ok(Ok(stdout)) => stdout,
+        Ok(Err(e)) => {
+            tracing::debug!("Probe `{}` failed in container {}: {}", cmd.join(" "), id, e);
+            None
+        }
+        Err(_) => {
+            tracing::debug!("Probe `{}` timed out in container {}", cmd.join(" "), id);
+            None
+        }
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────
+// Parsing
+// ─────────────────────────────────────────────────────────────────
+
+/// Normalizes a capability name to upper case without the `CAP_` prefix.
+fn capability_name(name: &str) -> String {
+    let name = name.trim().to_uppercase();
+    name.strip_prefix("CAP_").map(str::to_string).unwrap_or(name)
+}
+
+/// Reads the bounding set from `capsh --print` output.
+fn parse_capsh_bounding(output: &str) -> Option<BTreeSet<String>> {
+    let line = output
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("Bounding set ="))?;
+    Some(
+        line.split(',')
+            .filter(|cap| !cap.trim().is_empty())
+            .map(capability_name)
+            .collect(),
+    )
+}
+
+/// Reads the bounding set from the `CapBnd` mask of `/proc/self/status`.
+fn parse_status_bounding(output: &str) -> Option<BTreeSet<String>> {
+    let mask = output
+        .lines()
+        .find_map(|line| line.strip_prefix("CapBnd:"))
+        .and_then(|hex| u64::from_str_radix(hex.trim(), 16).ok())?;
+    Some(
+        CAPABILITY_BITS
+            .iter()
+            .enumerate()
+            .filter(|(bit, _)| mask & (1 << bit) != 0)
+            .map(|(_, name)| name.to_string())
+            .collect(),
+    )
+}
+
+/// Reads mount points and their read-write flag from `/proc/self/mounts`.
+fn parse_mounts(output: &str) -> Vec<(String, bool)> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let mount_point = fields.nth(1)?.replace("\\040", " ");
+            let options = fields.nth(1)?;
+            Some((mount_point, options.split(',').any(|o| o == "rw")))
+        })
+        .collect()
+}
+
+// ─────────────────────────────────────────────────────────────────
+// Comparison
+// ─────────────────────────────────────────────────────────────────
+
+/// Compares what the probes observed with the container's inspect data.
+///
+/// # Returns
+///
+/// A finding for each discrepancy where the container has more privilege
+/// than its configuration declares, each targeting `container`
+pub fn compare(container: &ContainerInspectResponse, observed: &Observed) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    let config = container.config.as_ref();
+    let host_config = container.host_config.as_ref();
+
+    // User
+    let declared = config
+        .and_then(|c| c.user.as_deref())
+        .map(|user| user.split(':').next().unwrap_or_default())
+        .unwrap_or_default();
+    if let Some(uid) = observed.uid {
+        let declared_root = matches!(declared, "" | "root" | "0");
+        let mismatch = match declared.parse::<u32>() {
+            Ok(declared_uid) => declared_uid != uid,
+            Err(_) => !declared_root && uid == 0,
+        };
+        if mismatch {
+            findings.push(probe_finding(
+                "probe_user_mismatch",
+                "Effective user differs from the configured user",
+                format!("Configured user is '{}' but processes run as uid {}", declared, uid),
+                if uid == 0 { RiskLevel::High } else { RiskLevel::Medium },
+                "Check the entrypoint for steps that switch user (su, gosu, setuid binaries) and run the container with a fixed non-root --user",
+            ));
+        }
+    }
+
+    // Capabilities
+    let privileged = host_config.and_then(|h| h.privileged).unwrap_or(false);
+    let names = |caps: Option<&Vec<String>>| -> BTreeSet<String> {
+        caps.into_iter().flatten().map(|c| capability_name(c)).collect()
+    };
+    let added = names(host_config.and_then(|h| h.cap_add.as_ref()));
+    let dropped = names(host_config.and_then(|h| h.cap_drop.as_ref()));
+    if let (Some(actual), false, false) = (&observed.capabilities, privileged, added.contains("ALL")) {
+        let mut expected: BTreeSet<String> = match dropped.contains("ALL") {
+            true => BTreeSet::new(),
+            false => DEFAULT_CAPABILITIES.iter().map(|c| c.to_string()).collect(),
+        };
+        expected.retain(|c| !dropped.contains(c));
+        expected.extend(added);
+        let extra: Vec<&str> = actual.difference(&expected).map(String::as_str).collect();
+        if !extra.is_empty() {
+            findings.push(probe_finding(
+                "probe_capability_mismatch",
+                "Capabilities beyond the configured set",
+                format!("Bounding set includes capabilities not granted by the configuration: {}", extra.join(", ")),
+                RiskLevel::High,
+                "Check the container runtime and any wrapper scripts for capability changes, and drop unneeded capabilities with --cap-drop",
+            ));
+        }
+    }
+
+    // Mounts; the last entry for a mount point is the one in effect
+    let writable = |destination: &str| {
+        observed
+            .mounts
+            .iter()
+            .rev()
+            .find(|(mount_point, _)| mount_point == destination)
+            .map(|(_, rw)| *rw)
+    };
+    let readonly_rootfs = host_config.and_then(|h| h.readonly_rootfs).unwrap_or(false);
+    if readonly_rootfs && writable("/") == Some(true) {
+        findings.push(probe_finding(
+            "probe_rootfs_writable",
+            "Root filesystem writable despite read-only configuration",
+            "Root filesystem is configured read-only but is mounted read-write inside the container".to_string(),
+            RiskLevel::High,
+            "Check the container runtime and entrypoint for remounts of / and recreate the container with --read-only",
+        ));
+    }
+    for mount in container.mounts.iter().flatten() {
+        let Some(destination) = mount.destination.as_deref() else { continue };
+        if mount.rw == Some(false) && writable(destination) == Some(true) {
+            findings.push(probe_finding(
+                "probe_mount_writable",
+                "Mount writable despite read-only configuration",
+                format!("Mount {} is configured read-only but is mounted read-write inside the container", destination),
+                RiskLevel::High,
+                "Check the entrypoint for remounts and recreate the container with the mount marked :ro",
+            ));
+        }
+    }
+
+    let target = FindingTarget::container(container);
+    findings
+        .into_iter()
+        .map(|finding| Finding {
+            target: Some(target.clone()),
+            ..finding
+        })
+        .collect()
+}
+
+fn probe_finding(kind: &str, name: &str, description: String, risk: RiskLevel, fix: &str) -> Finding {
+    Finding {
+        rule_name: Some(name.to_string()),
+        remediation: Some(fix.to_string()),
+        tags: vec!["probe".to_string()],
+        category: Some("runtime".to_string()),
+        ..Finding::new(kind, description, risk)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bollard::models::{ContainerConfig, HostConfig, MountPoint};
+
+    fn container(user: &str, host_config: HostConfig) -> ContainerInspectResponse {
+        ContainerInspectResponse {
+            id: Some("abc123".to_string()),
+            name: Some("/web".to_string()),
+            config: Some(ContainerConfig {
+                user: Some(user.to_string()),
+                ..Default::default()
+            }),
+            host_config: Some(host_config),
+            ..Default::default()
+        }
+    }
+
+    fn kinds(findings: &[Finding]) -> Vec<&str> {
+        findings.iter().map(|f| f.kind.as_str()).collect()
+    }
+
+    #[test]
+    fn parses_bounding_sets() {
+        let capsh = "Current: =\nBounding set =cap_chown,cap_net_raw,cap_sys_admin\nSecurebits: 00/0x0/1'b0\n";
+        assert_eq!(
+            parse_capsh_bounding(capsh).unwrap(),
+            BTreeSet::from(["CHOWN".to_string(), "NET_RAW".to_string(), "SYS_ADMIN".to_string()])
+        );
+
+        let status = "Name:\tcat\nCapBnd:\t0000000000200001\nNoNewPrivs:\t0\n";
+        assert_eq!(
+            parse_status_bounding(status).unwrap(),
+            BTreeSet::from(["CHOWN".to_string(), "SYS_ADMIN".to_string()])
+        );
+    }
+
+    #[test]
+    fn parses_mount_flags() {
+        let mounts = "overlay / overlay rw,relatime 0 0\n/dev/sda1 /data ext4 ro,relatime 0 0\n/dev/sda1 /my\\040dir ext4 rw 0 0\n";
+        assert_eq!(parse_mounts(mounts), [
+            ("/".to_string(), true),
+            ("/data".to_string(), false),
+            ("/my dir".to_string(), true),
+        ]);
+    }
+
+    #[test]
+    fn reports_privileges_beyond_inspect() {
+        let mut container = container("1000", HostConfig {
+            cap_drop: Some(vec!["NET_RAW".to_string()]),
+            readonly_rootfs: Some(true),
+            ..Default::default()
+        });
+        container.mounts = Some(vec![MountPoint {
+            destination: Some("/data".to_string()),
+            rw: Some(false),
+            ..Default::default()
+        }]);
+        let observed = Observed {
+            uid: Some(0),
+            capabilities: Some(BTreeSet::from(["CHOWN".to_string(), "NET_RAW".to_string()])),
+            mounts: vec![("/".to_string(), true), ("/data".to_string(), true)],
+        };
+
+        let findings = compare(&container, &observed);
+
+        assert_eq!(kinds(&findings), [
+            "probe_user_mismatch",
+            "probe_capability_mismatch",
+            "probe_rootfs_writable",
+            "probe_mount_writable",
+        ]);
+        assert!(findings[1].description.ends_with(": NET_RAW"));
+        assert!(findings.iter().all(|f| f.target.is_some()));
+    }
+
+    #[test]
+    fn matching_privileges_report_nothing() {
+        let container = container("", HostConfig {
+            cap_add: Some(vec!["CAP_NET_ADMIN".to_string()]),
+            ..Default::default()
+        });
+        let mut capabilities: BTreeSet<String> = DEFAULT_CAPABILITIES.iter().map(|c| c.to_string()).collect();
+        capabilities.insert("NET_ADMIN".to_string());
+        let observed = Observed {
+            uid: Some(0),
+            capabilities: Some(capabilities),
+            mounts: vec![("/".to_string(), true)],
+        };
+
+        assert!(compare(&container, &observed).is_empty());
+    }
+}
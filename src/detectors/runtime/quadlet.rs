@@ -0,0 +1,1055 @@
+//! Podman Quadlet and systemd unit scanning.
+//!
+//! `valeris scan --quadlet <PATH>` reads the units Podman runs containers
+//! from and evaluates the runtime rules against them before anything is
+//! started. Each container becomes a synthetic `docker inspect` object, so
+//! the same rules, filters, and exporters apply as for a daemon scan:
+//!
+//! * `.container` Quadlet units: `[Container]` keys such as `Volume=`,
+//!   `AddCapability=`, `User=`, and `PodmanArgs=`
+//! * `.kube` Quadlet units: the pods of the Kubernetes YAML named by `Yaml=`
+//! * `.service` units from `podman generate systemd`: the `podman run`
+//!   command line of `ExecStart=`
+//!
+//! Settings a unit leaves unset get Podman's defaults (bridge network,
+//! journald logging, a PID limit of 2048). The container ID is the unit
+//! path, so findings point back to the file.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use bollard::models::ContainerInspectResponse;
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+
+use crate::error::{Result, ValerisError};
+
+/// PID limit Podman applies when none is configured (`containers.conf`)
+pub const DEFAULT_PIDS_LIMIT: i64 = 2048;
+
+/// Unit file extensions read from a directory
+pub const UNIT_EXTENSIONS: &[&str] = &["container", "kube", "service"];
+
+/// `podman run` flags that take no value
+const BOOLEAN_FLAGS: &[&str] = &[
+    "privileged",
+    "read-only",
+    "rm",
+    "detach",
+    "replace",
+    "init",
+    "interactive",
+    "tty",
+    "no-healthcheck",
+    "read-only-tmpfs",
+    "rmi",
+    "no-hosts",
+    "d",
+    "i",
+    "t",
+];
+
+/// Loads the containers defined by a unit file, or by every unit in a
+/// directory tree.
+///
+/// `.service` units that do not run `podman run` are skipped.
+///
+/// # Arguments
+///
+/// * `path` - A `.container`, `.kube`, or `.service` file, or a directory
+///   of them (e.g. `/etc/containers/systemd`)
+///
+/// # Errors
+///
+/// * [`ValerisError::InputRead`] if a unit or the YAML of a `.kube` unit
+///   cannot be read
+/// * [`ValerisError::InputParse`] if a unit is malformed (no `Image=`, no
+///   `Yaml=`, invalid Kubernetes YAML) or a directory holds no units
+pub fn load_units(path: &Path) -> Result<Vec<ContainerInspectResponse>> {
+    if !path.is_dir() {
+        return load_unit(path);
+    }
+
+    let mut units: Vec<PathBuf> = walkdir::WalkDir::new(path)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.into_path())
+        .filter(|p| p.is_file() && unit_extension(p).is_some())
+        .collect();
+    units.sort();
+
+    let mut containers = Vec::new();
+    for unit in units {
+        containers.extend(load_unit(&unit)?);
+    }
+    if containers.is_empty() {
+        return Err(ValerisError::InputParse {
+            path: path.to_path_buf(),
+            message: "no Quadlet or Podman systemd units found".to_string(),
+        });
+    }
+    Ok(containers)
+}
+
+fn unit_extension(path: &Path) -> Option<&str> {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .filter(|e| UNIT_EXTENSIONS.contains(e))
+}
+
+fn load_unit(path: &Path) -> Result<Vec<ContainerInspectResponse>> {
+    let raw = read(path)?;
+    let parse_error = |message: String| ValerisError::InputParse {
+        path: path.to_path_buf(),
+        message,
+    };
+    let unit = parse_unit(&raw);
+    let stem = path.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default();
+
+    let specs = match unit_extension(path) {
+        Some("container") => vec![container_unit(&unit, &stem).map_err(parse_error)?],
+        Some("kube") => {
+            let yaml = unit
+                .get("Kube", "Yaml")
+                .ok_or_else(|| parse_error("[Kube] has no Yaml= key".to_string()))?;
+            let yaml_path = path.parent().unwrap_or(Path::new(".")).join(yaml);
+            kube_specs(&read(&yaml_path)?).map_err(|message| ValerisError::InputParse {
+                path: yaml_path,
+                message,
+            })?
+        }
+        _ => match unit.get("Service", "ExecStart").and_then(podman_run_args) {
+            Some(args) => {
+                let mut spec = ContainerSpec::new(format!("systemd-{}", stem));
+                spec.apply_args(&args).map_err(parse_error)?;
+                vec![spec]
+            }
+            None => Vec::new(),
+        },
+    };
+
+    specs
+        .into_iter()
+        .map(|mut spec| {
+            spec.restart = unit.get("Service", "Restart").map(restart_policy);
+            spec.into_inspect(path).map_err(parse_error)
+        })
+        .collect()
+}
+
+fn read(path: &Path) -> Result<String> {
+    fs::read_to_string(path).map_err(|source| ValerisError::InputRead {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+// ─────────────────────────────────────────────────────────────────
+// Unit Parsing
+// ─────────────────────────────────────────────────────────────────
+
+/// `(section, key, value)` entries of a systemd unit, in file order.
+#[derive(Debug, Default)]
+struct Unit {
+    entries: Vec<(String, String, String)>,
+}
+
+impl Unit {
+    /// Last value of a key, as systemd resolves single-valued keys.
+    fn get(&self, section: &str, key: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .rev()
+            .find(|(s, k, _)| s == section && k == key)
+            .map(|(_, _, v)| v.as_str())
+    }
+}
+
+/// Parses systemd unit syntax: `[Section]` headers, `Key=value` lines,
+/// `#`/`;` comments, and trailing-backslash line continuations.
+fn parse_unit(raw: &str) -> Unit {
+    let mut unit = Unit::default();
+    let mut section = String::new();
+    let mut pending = String::new();
+
+    for line in raw.lines() {
+        let line = line.trim();
+        if pending.is_empty() && (line.starts_with('#') || line.starts_with(';')) {
+            continue;
+        }
+        if let Some(continued) = line.strip_suffix('\\') {
+            pending.push_str(continued);
+            pending.push(' ');
+            continue;
+        }
+        pending.push_str(line);
+        let line = std::mem::take(&mut pending);
+        let line = line.trim();
+
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            section = name.to_string();
+        } else if let Some((key, value)) = line.split_once('=') {
+            unit.entries
+                .push((section.clone(), key.trim().to_string(), value.trim().to_string()));
+        }
+    }
+    unit
+}
+
+/// Splits a command line or space-separated value into words, honoring
+/// single and double quotes and backslash escapes.
+fn split_words(value: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut quote: Option<char> = None;
+    let mut chars = value.chars();
+
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (None, '"' | '\'') => {
+                quote = Some(c);
+                in_word = true;
+            }
+            (_, '\\') => {
+                if let Some(next) = chars.next() {
+                    word.push(next);
+                    in_word = true;
+                }
+            }
+            (None, c) if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+            (_, c) => {
+                word.push(c);
+                in_word = true;
+            }
+        }
+    }
+    if in_word {
+        words.push(word);
+    }
+    words
+}
+
+/// Arguments after `podman run` (or `podman container run`) in an
+/// `ExecStart=` command line, or `None` if it runs something else.
+fn podman_run_args(exec_start: &str) -> Option<Vec<String>> {
+    let words = split_words(exec_start.trim_start_matches(['-', '@', '+', '!']));
+    let podman = words.iter().position(|w| w.rsplit('/').next() == Some("podman"))?;
+    let rest = &words[podman + 1..];
+    let run = rest
+        .iter()
+        .position(|w| w == "run")
+        .filter(|&i| rest[..i].iter().all(|w| w.starts_with('-') || w == "container"))?;
+    Some(rest[run + 1..].to_vec())
+}
+
+fn restart_policy(value: &str) -> String {
+    match value {
+        "always" => "always",
+        "no" => "no",
+        _ => "on-failure",
+    }
+    .to_string()
+}
+
+// ─────────────────────────────────────────────────────────────────
+// Container Settings
+// ─────────────────────────────────────────────────────────────────
+
+struct PortSpec {
+    host_ip: String,
+    host_port: String,
+    container_port: String,
+}
+
+struct MountSpec {
+    kind: &'static str,
+    source: String,
+    destination: String,
+    rw: bool,
+    propagation: String,
+}
+
+/// Container settings collected from a unit, in `podman run` terms.
+struct ContainerSpec {
+    name: String,
+    image: String,
+    user: String,
+    group: Option<String>,
+    env: Vec<String>,
+    privileged: bool,
+    read_only: bool,
+    cap_add: Vec<String>,
+    cap_drop: Vec<String>,
+    security_opt: Vec<String>,
+    network_mode: String,
+    pid_mode: String,
+    ipc_mode: String,
+    uts_mode: String,
+    userns_mode: String,
+    devices: Vec<String>,
+    ports: Vec<PortSpec>,
+    mounts: Vec<MountSpec>,
+    tmpfs: BTreeMap<String, String>,
+    sysctls: BTreeMap<String, String>,
+    healthcheck: Option<String>,
+    memory: i64,
+    nano_cpus: i64,
+    pids_limit: i64,
+    dns: Vec<String>,
+    extra_hosts: Vec<String>,
+    log_driver: String,
+    restart: Option<String>,
+}
+
+impl ContainerSpec {
+    fn new(name: String) -> Self {
+        Self {
+            name,
+            image: String::new(),
+            user: String::new(),
+            group: None,
+            env: Vec::new(),
+            privileged: false,
+            read_only: false,
+            cap_add: Vec::new(),
+            cap_drop: Vec::new(),
+            security_opt: Vec::new(),
+            network_mode: "bridge".to_string(),
+            pid_mode: String::new(),
+            ipc_mode: String::new(),
+            uts_mode: String::new(),
+            userns_mode: String::new(),
+            devices: Vec::new(),
+            ports: Vec::new(),
+            mounts: Vec::new(),
+            tmpfs: BTreeMap::new(),
+            sysctls: BTreeMap::new(),
+            healthcheck: None,
+            memory: 0,
+            nano_cpus: 0,
+            pids_limit: DEFAULT_PIDS_LIMIT,
+            dns: Vec::new(),
+            extra_hosts: Vec::new(),
+            log_driver: "journald".to_string(),
+            restart: None,
+        }
+    }
+
+    /// Applies `podman run` arguments; the first positional argument is
+    /// the image and the rest is its command.
+    fn apply_args(&mut self, args: &[String]) -> std::result::Result<(), String> {
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let Some(flag) = arg.strip_prefix('-') else {
+                if self.image.is_empty() {
+                    self.image = arg.clone();
+                }
+                break;
+            };
+            let flag = flag.strip_prefix('-').unwrap_or(flag);
+            let (name, inline) = match flag.split_once('=') {
+                Some((name, value)) => (name, Some(value.to_string())),
+                None => (flag, None),
+            };
+            // Combined short booleans such as `-dt`
+            let combined = !arg.starts_with("--") && name.len() > 1 && name.chars().all(|c| "dit".contains(c));
+            if combined || (inline.is_none() && BOOLEAN_FLAGS.contains(&name)) {
+                self.apply_option(name, "true")?;
+                continue;
+            }
+            let value = match inline {
+                Some(value) => value,
+                None => args
+                    .next()
+                    .cloned()
+                    .ok_or_else(|| format!("missing value for {}", arg))?,
+            };
+            self.apply_option(name, &value)?;
+        }
+        Ok(())
+    }
+
+    /// Applies one `podman run` option; unknown options are ignored.
+    fn apply_option(&mut self, name: &str, value: &str) -> std::result::Result<(), String> {
+        let enabled = value != "false";
+        match name {
+            "privileged" => self.privileged = enabled,
+            "read-only" => self.read_only = enabled,
+            "name" => self.name = value.to_string(),
+            "user" | "u" => self.user = value.to_string(),
+            "env" | "e" => self.env.push(value.to_string()),
+            "cap-add" => self.cap_add.extend(split_list(value).map(normalize_capability)),
+            "cap-drop" => self.cap_drop.extend(split_list(value).map(normalize_capability)),
+            "security-opt" => self.security_opt.push(value.to_string()),
+            "network" | "net" => self.network_mode = value.to_string(),
+            "pid" => self.pid_mode = value.to_string(),
+            "ipc" => self.ipc_mode = value.to_string(),
+            "uts" => self.uts_mode = value.to_string(),
+            "userns" => self.userns_mode = value.to_string(),
+            "device" => self.devices.push(value.to_string()),
+            "publish" | "p" => self.ports.push(parse_port(value)),
+            "volume" | "v" => self.mounts.push(parse_volume(value)),
+            "mount" => self.mounts.extend(parse_mount(value)),
+            "tmpfs" => {
+                let (path, options) = value.split_once(':').unwrap_or((value, ""));
+                self.tmpfs.insert(path.to_string(), options.to_string());
+            }
+            "sysctl" => {
+                if let Some((key, val)) = value.split_once('=') {
+                    self.sysctls.insert(key.to_string(), val.to_string());
+                }
+            }
+            "health-cmd" => self.healthcheck = Some(value.to_string()),
+            "memory" | "m" => {
+                self.memory = parse_bytes(value).ok_or_else(|| format!("invalid memory '{}'", value))?;
+            }
+            "cpus" => {
+                let cpus: f64 = value.parse().map_err(|_| format!("invalid cpus '{}'", value))?;
+                self.nano_cpus = (cpus * 1e9) as i64;
+            }
+            "pids-limit" => {
+                self.pids_limit = value.parse().map_err(|_| format!("invalid pids-limit '{}'", value))?;
+            }
+            "dns" => self.dns.push(value.to_string()),
+            "add-host" => self.extra_hosts.push(value.to_string()),
+            "log-driver" => self.log_driver = value.to_string(),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn into_inspect(self, unit: &Path) -> std::result::Result<ContainerInspectResponse, String> {
+        if self.image.is_empty() {
+            return Err(format!("container '{}' has no image", self.name));
+        }
+        let user = match &self.group {
+            Some(group) if !self.user.is_empty() => format!("{}:{}", self.user, group),
+            _ => self.user.clone(),
+        };
+
+        let mut ports = Map::new();
+        for port in &self.ports {
+            let key = if port.container_port.contains('/') {
+                port.container_port.clone()
+            } else {
+                format!("{}/tcp", port.container_port)
+            };
+            if let Value::Array(bindings) = ports.entry(key).or_insert_with(|| json!([])) {
+                bindings.push(json!({"HostIp": port.host_ip, "HostPort": port.host_port}));
+            }
+        }
+        let devices: Vec<Value> = self
+            .devices
+            .iter()
+            .map(|device| {
+                let mut parts = device.splitn(3, ':');
+                let host = parts.next().unwrap_or_default();
+                let container = parts.next().unwrap_or(host);
+                json!({
+                    "PathOnHost": host,
+                    "PathInContainer": container,
+                    "CgroupPermissions": parts.next().unwrap_or("rwm"),
+                })
+            })
+            .collect();
+        let mounts: Vec<Value> = self
+            .mounts
+            .iter()
+            .map(|m| {
+                json!({
+                    "Type": m.kind,
+                    "Source": m.source,
+                    "Destination": m.destination,
+                    "RW": m.rw,
+                    "Propagation": m.propagation,
+                })
+            })
+            .collect();
+
+        let mut host_config = json!({
+            "Privileged": self.privileged,
+            "ReadonlyRootfs": self.read_only,
+            "CapAdd": self.cap_add,
+            "CapDrop": self.cap_drop,
+            "SecurityOpt": self.security_opt,
+            "NetworkMode": self.network_mode,
+            "PidMode": self.pid_mode,
+            "IpcMode": self.ipc_mode,
+            "UTSMode": self.uts_mode,
+            "UsernsMode": self.userns_mode,
+            "Devices": devices,
+            "PortBindings": ports,
+            "Tmpfs": self.tmpfs,
+            "Sysctls": self.sysctls,
+            "Memory": self.memory,
+            "NanoCpus": self.nano_cpus,
+            "PidsLimit": self.pids_limit,
+            "Dns": self.dns,
+            "ExtraHosts": self.extra_hosts,
+            "LogConfig": {"Type": self.log_driver},
+        });
+        if let Some(restart) = &self.restart {
+            host_config["RestartPolicy"] = json!({"Name": restart});
+        }
+        let mut inspect = json!({
+            "Id": unit.display().to_string(),
+            "Name": format!("/{}", self.name),
+            "Config": {
+                "Image": self.image,
+                "User": user,
+                "Env": self.env,
+            },
+            "HostConfig": host_config,
+            "Mounts": mounts,
+            "NetworkSettings": {"Ports": ports},
+        });
+        if let Some((_, digest)) = self.image.split_once('@') {
+            inspect["Image"] = json!(digest);
+        }
+        if let Some(test) = &self.healthcheck {
+            inspect["Config"]["Healthcheck"] = json!({"Test": ["CMD-SHELL", test]});
+        }
+        serde_json::from_value(inspect).map_err(|e| e.to_string())
+    }
+}
+
+fn split_list(value: &str) -> impl Iterator<Item = &str> {
+    value.split([',', ' ']).map(str::trim).filter(|v| !v.is_empty())
+}
+
+/// `CAP_SYS_ADMIN` and `sys_admin` are both `SYS_ADMIN`, as Docker reports them.
+fn normalize_capability(cap: &str) -> String {
+    let cap = cap.to_ascii_uppercase();
+    cap.strip_prefix("CAP_").map(str::to_string).unwrap_or(cap)
+}
+
+/// Parses `[[ip:][host_port]:]container_port[/proto]`.
+fn parse_port(value: &str) -> PortSpec {
+    let parts: Vec<&str> = match value.rsplit_once("]:") {
+        // Bracketed IPv6 host address
+        Some((ip, rest)) => std::iter::once(ip.trim_start_matches('[')).chain(rest.split(':')).collect(),
+        None => value.split(':').collect(),
+    };
+    let (host_ip, host_port, container_port) = match parts.as_slice() {
+        [container] => ("", "", *container),
+        [host, container] => ("", *host, *container),
+        [ip, host, container, ..] => (*ip, *host, *container),
+        [] => ("", "", ""),
+    };
+    PortSpec {
+        host_ip: host_ip.to_string(),
+        host_port: host_port.to_string(),
+        container_port: container_port.to_string(),
+    }
+}
+
+/// Parses `-v`/`Volume=` syntax: `[source:]destination[:options]`.
+fn parse_volume(value: &str) -> MountSpec {
+    let parts: Vec<&str> = value.splitn(3, ':').collect();
+    let (source, destination, options) = match parts.as_slice() {
+        [destination] => ("", *destination, ""),
+        [source, destination] => (*source, *destination, ""),
+        [source, destination, options, ..] => (*source, *destination, *options),
+        [] => ("", "", ""),
+    };
+    let options: Vec<&str> = options.split(',').collect();
+    MountSpec {
+        kind: if source.starts_with('/') || source.starts_with('.') { "bind" } else { "volume" },
+        source: source.to_string(),
+        destination: destination.to_string(),
+        rw: !options.contains(&"ro"),
+        propagation: options
+            .iter()
+            .find(|o| o.ends_with("shared") || o.ends_with("slave") || o.ends_with("private"))
+            .map(|o| o.to_string())
+            .unwrap_or_default(),
+    }
+}
+
+/// Parses `--mount`/`Mount=` syntax: `type=bind,source=...,destination=...`.
+fn parse_mount(value: &str) -> Option<MountSpec> {
+    let mut mount = MountSpec {
+        kind: "volume",
+        source: String::new(),
+        destination: String::new(),
+        rw: true,
+        propagation: String::new(),
+    };
+    for option in value.split(',') {
+        let (key, val) = option.split_once('=').unwrap_or((option, "true"));
+        match key {
+            "type" => {
+                mount.kind = match val {
+                    "bind" => "bind",
+                    "tmpfs" => "tmpfs",
+                    _ => "volume",
+                }
+            }
+            "source" | "src" => mount.source = val.to_string(),
+            "destination" | "dst" | "target" => mount.destination = val.to_string(),
+            "ro" | "readonly" => mount.rw = val == "false",
+            "bind-propagation" => mount.propagation = val.to_string(),
+            _ => {}
+        }
+    }
+    (!mount.destination.is_empty()).then_some(mount)
+}
+
+/// Parses a byte size: plain bytes, `b`/`k`/`m`/`g` suffixes (Podman) or
+/// `Ki`/`Mi`/`Gi`/`K`/`M`/`G` suffixes (Kubernetes).
+fn parse_bytes(value: &str) -> Option<i64> {
+    let value = value.trim();
+    let split = value.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(value.len());
+    let (number, unit) = value.split_at(split);
+    let number: f64 = number.parse().ok()?;
+    let multiplier: f64 = match unit.to_ascii_lowercase().as_str() {
+        "" | "b" => 1.0,
+        "k" | "kb" | "ki" => 1024.0,
+        "m" | "mb" | "mi" => 1024.0 * 1024.0,
+        "g" | "gb" | "gi" => 1024.0 * 1024.0 * 1024.0,
+        _ => return None,
+    };
+    Some((number * multiplier) as i64)
+}
+
+// ─────────────────────────────────────────────────────────────────
+// Quadlet .container Units
+// ─────────────────────────────────────────────────────────────────
+
+/// `[Container]` keys that map directly onto a `podman run` option.
+const CONTAINER_KEYS: &[(&str, &str)] = &[
+    ("ContainerName", "name"),
+    ("User", "user"),
+    ("Volume", "volume"),
+    ("Mount", "mount"),
+    ("PublishPort", "publish"),
+    ("Network", "network"),
+    ("AddDevice", "device"),
+    ("ReadOnly", "read-only"),
+    ("UserNS", "userns"),
+    ("Tmpfs", "tmpfs"),
+    ("HealthCmd", "health-cmd"),
+    ("DNS", "dns"),
+    ("AddHost", "add-host"),
+    ("PidsLimit", "pids-limit"),
+    ("LogDriver", "log-driver"),
+];
+
+fn container_unit(unit: &Unit, stem: &str) -> std::result::Result<ContainerSpec, String> {
+    const SECTION: &str = "Container";
+    let mut spec = ContainerSpec::new(format!("systemd-{}", stem));
+    spec.image = unit
+        .get(SECTION, "Image")
+        .ok_or_else(|| "[Container] has no Image= key".to_string())?
+        .to_string();
+
+    for (section, key, value) in &unit.entries {
+        if section != SECTION {
+            continue;
+        }
+        if let Some((_, option)) = CONTAINER_KEYS.iter().find(|(k, _)| k == key) {
+            spec.apply_option(option, value)?;
+            continue;
+        }
+        match key.as_str() {
+            "Group" => spec.group = Some(value.clone()),
+            "Environment" => spec.env.extend(split_words(value)),
+            "AddCapability" => spec.cap_add.extend(split_list(value).map(normalize_capability)),
+            "DropCapability" => spec.cap_drop.extend(split_list(value).map(normalize_capability)),
+            "Sysctl" => {
+                for sysctl in split_words(value) {
+                    spec.apply_option("sysctl", &sysctl)?;
+                }
+            }
+            "NoNewPrivileges" if value == "true" => spec.security_opt.push("no-new-privileges".to_string()),
+            "SecurityLabelDisable" if value == "true" => spec.security_opt.push("label=disable".to_string()),
+            "SeccompProfile" => spec.security_opt.push(format!("seccomp={}", value)),
+            "PodmanArgs" => spec.apply_args(&split_words(value))?,
+            _ => {}
+        }
+    }
+    Ok(spec)
+}
+
+// ─────────────────────────────────────────────────────────────────
+// Quadlet .kube Units
+// ─────────────────────────────────────────────────────────────────
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase", default)]
+struct KubeObject {
+    kind: String,
+    metadata: KubeMetadata,
+    spec: Value,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct KubeMetadata {
+    name: String,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase", default)]
+struct PodSpec {
+    host_network: bool,
+    #[serde(rename = "hostPID")]
+    host_pid: bool,
+    #[serde(rename = "hostIPC")]
+    host_ipc: bool,
+    host_users: Option<bool>,
+    security_context: SecurityContext,
+    containers: Vec<KubeContainer>,
+    volumes: Vec<KubeVolume>,
+}
+
+#[derive(Deserialize, Default, Clone)]
+#[serde(rename_all = "camelCase", default)]
+struct SecurityContext {
+    privileged: Option<bool>,
+    run_as_user: Option<i64>,
+    run_as_group: Option<i64>,
+    read_only_root_filesystem: Option<bool>,
+    allow_privilege_escalation: Option<bool>,
+    capabilities: Option<Capabilities>,
+    seccomp_profile: Option<SeccompProfile>,
+}
+
+#[derive(Deserialize, Default, Clone)]
+#[serde(default)]
+struct Capabilities {
+    add: Vec<String>,
+    drop: Vec<String>,
+}
+
+#[derive(Deserialize, Default, Clone)]
+#[serde(rename_all = "camelCase", default)]
+struct SeccompProfile {
+    #[serde(rename = "type")]
+    kind: String,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase", default)]
+struct KubeContainer {
+    name: String,
+    image: String,
+    security_context: SecurityContext,
+    ports: Vec<KubePort>,
+    env: Vec<KubeEnv>,
+    volume_mounts: Vec<KubeVolumeMount>,
+    resources: KubeResources,
+    liveness_probe: Option<Value>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase", default)]
+struct KubePort {
+    container_port: u16,
+    host_port: Option<u16>,
+    #[serde(rename = "hostIP")]
+    host_ip: Option<String>,
+    protocol: Option<String>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct KubeEnv {
+    name: String,
+    value: Option<String>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase", default)]
+struct KubeVolumeMount {
+    name: String,
+    mount_path: String,
+    read_only: bool,
+    mount_propagation: Option<String>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase", default)]
+struct KubeVolume {
+    name: String,
+    host_path: Option<HostPath>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct HostPath {
+    path: String,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct KubeResources {
+    limits: BTreeMap<String, Value>,
+}
+
+/// Containers of every pod in Kubernetes YAML, named `<pod>-<container>`
+/// as `podman kube play` names them.
+fn kube_specs(yaml: &str) -> std::result::Result<Vec<ContainerSpec>, String> {
+    let mut specs = Vec::new();
+    for document in serde_yml::Deserializer::from_str(yaml) {
+        let document = Value::deserialize(document).map_err(|e| e.to_string())?;
+        if document.is_null() {
+            continue;
+        }
+        let object: KubeObject = serde_json::from_value(document).map_err(|e| e.to_string())?;
+        let pod = match object.kind.as_str() {
+            "Pod" => object.spec,
+            "Deployment" | "DaemonSet" | "StatefulSet" | "ReplicaSet" | "Job" => {
+                object.spec.get("template").and_then(|t| t.get("spec")).cloned().unwrap_or_default()
+            }
+            _ => continue,
+        };
+        let pod: PodSpec = serde_json::from_value(pod).map_err(|e| e.to_string())?;
+        for container in &pod.containers {
+            specs.push(kube_container(&object.metadata.name, &pod, container)?);
+        }
+    }
+    Ok(specs)
+}
+
+fn kube_container(
+    pod_name: &str,
+    pod: &PodSpec,
+    container: &KubeContainer,
+) -> std::result::Result<ContainerSpec, String> {
+    let mut spec = ContainerSpec::new(format!("{}-{}", pod_name, container.name));
+    spec.image = container.image.clone();
+    if pod.host_network {
+        spec.network_mode = "host".to_string();
+    }
+    if pod.host_pid {
+        spec.pid_mode = "host".to_string();
+    }
+    if pod.host_ipc {
+        spec.ipc_mode = "host".to_string();
+    }
+    if pod.host_users == Some(false) {
+        spec.userns_mode = "auto".to_string();
+    }
+
+    // Container settings override the pod's
+    let pod_ctx = &pod.security_context;
+    let ctx = &container.security_context;
+    spec.privileged = ctx.privileged.or(pod_ctx.privileged).unwrap_or(false);
+    spec.read_only = ctx.read_only_root_filesystem.unwrap_or(false);
+    if let Some(uid) = ctx.run_as_user.or(pod_ctx.run_as_user) {
+        spec.user = uid.to_string();
+    }
+    spec.group = ctx.run_as_group.or(pod_ctx.run_as_group).map(|gid| gid.to_string());
+    if ctx.allow_privilege_escalation == Some(false) {
+        spec.security_opt.push("no-new-privileges".to_string());
+    }
+    if let Some(caps) = &ctx.capabilities {
+        spec.cap_add.extend(caps.add.iter().map(|c| normalize_capability(c)));
+        spec.cap_drop.extend(caps.drop.iter().map(|c| normalize_capability(c)));
+    }
+    let seccomp = ctx.seccomp_profile.as_ref().or(pod_ctx.seccomp_profile.as_ref());
+    if seccomp.is_some_and(|s| s.kind == "Unconfined") {
+        spec.security_opt.push("seccomp=unconfined".to_string());
+    }
+
+    spec.env = container
+        .env
+        .iter()
+        .filter_map(|e| e.value.as_ref().map(|v| format!("{}={}", e.name, v)))
+        .collect();
+    for port in &container.ports {
+        let Some(host_port) = port.host_port else {
+            continue;
+        };
+        spec.ports.push(PortSpec {
+            host_ip: port.host_ip.clone().unwrap_or_default(),
+            host_port: host_port.to_string(),
+            container_port: format!(
+                "{}/{}",
+                port.container_port,
+                port.protocol.as_deref().unwrap_or("TCP").to_ascii_lowercase()
+            ),
+        });
+    }
+    for mount in &container.volume_mounts {
+        let Some(host_path) = pod
+            .volumes
+            .iter()
+            .find(|v| v.name == mount.name)
+            .and_then(|v| v.host_path.as_ref())
+        else {
+            continue;
+        };
+        spec.mounts.push(MountSpec {
+            kind: "bind",
+            source: host_path.path.clone(),
+            destination: mount.mount_path.clone(),
+            rw: !mount.read_only,
+            propagation: match mount.mount_propagation.as_deref() {
+                Some("Bidirectional") => "rshared".to_string(),
+                Some("HostToContainer") => "rslave".to_string(),
+                _ => String::new(),
+            },
+        });
+    }
+
+    let limit = |name: &str| {
+        container.resources.limits.get(name).map(|v| match v {
+            Value::String(s) => s.clone(),
+            other => other.to_string(),
+        })
+    };
+    if let Some(memory) = limit("memory") {
+        spec.memory = parse_bytes(&memory).ok_or_else(|| format!("invalid memory limit '{}'", memory))?;
+    }
+    if let Some(cpu) = limit("cpu") {
+        let cores = match cpu.strip_suffix('m') {
+            Some(millis) => millis.parse::<f64>().map(|m| m / 1000.0),
+            None => cpu.parse::<f64>(),
+        }
+        .map_err(|_| format!("invalid cpu limit '{}'", cpu))?;
+        spec.nano_cpus = (cores * 1e9) as i64;
+    }
+    if container.liveness_probe.is_some() {
+        spec.healthcheck = Some("livenessProbe".to_string());
+    }
+    Ok(spec)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inspect(container: &ContainerInspectResponse) -> Value {
+        serde_json::to_value(container).unwrap()
+    }
+
+    #[test]
+    fn maps_container_units_onto_inspect_data() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("web.container");
+        fs::write(
+            &path,
+            "[Unit]\nDescription=Web\n\n[Container]\nImage=docker.io/library/nginx:1.27\n\
+             Volume=/etc:/host/etc\nVolume=data:/data:ro\nPublishPort=8080:80\n\
+             AddCapability=CAP_SYS_ADMIN NET_RAW\nUser=1000\nGroup=1000\n\
+             PodmanArgs=--privileged \\\n  --memory 512m\n\n[Service]\nRestart=always\n",
+        )
+        .unwrap();
+
+        let containers = load_units(&path).unwrap();
+
+        assert_eq!(containers.len(), 1);
+        let json = inspect(&containers[0]);
+        assert_eq!(json["Name"], "/systemd-web");
+        assert_eq!(json["Config"]["User"], "1000:1000");
+        assert_eq!(json["HostConfig"]["Privileged"], true);
+        assert_eq!(json["HostConfig"]["CapAdd"], json!(["SYS_ADMIN", "NET_RAW"]));
+        assert_eq!(json["HostConfig"]["Memory"], 512 * 1024 * 1024);
+        assert_eq!(json["HostConfig"]["RestartPolicy"]["Name"], "always");
+        assert_eq!(json["HostConfig"]["PortBindings"]["80/tcp"][0]["HostPort"], "8080");
+        assert_eq!(json["Mounts"][0]["Source"], "/etc");
+        assert_eq!(json["Mounts"][0]["RW"], true);
+        assert_eq!(json["Mounts"][1]["Type"], "volume");
+        assert_eq!(json["Mounts"][1]["RW"], false);
+    }
+
+    #[test]
+    fn reads_podman_run_from_generated_services() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("container-db.service");
+        fs::write(
+            &path,
+            "[Service]\nExecStart=/usr/bin/podman run \\\n\t--cidfile=%t/%n.ctr-id \\\n\t--rm -d --replace \\\n\
+             \t--name db --network host -v /var/run/docker.sock:/var/run/docker.sock \\\n\
+             \t-e \"POSTGRES_PASSWORD=s3cret\" postgres:16 postgres -c fsync=off\n",
+        )
+        .unwrap();
+        fs::write(dir.path().join("other.service"), "[Service]\nExecStart=/usr/bin/sleep 1\n").unwrap();
+
+        let containers = load_units(dir.path()).unwrap();
+
+        assert_eq!(containers.len(), 1);
+        let json = inspect(&containers[0]);
+        assert_eq!(json["Name"], "/db");
+        assert_eq!(json["Config"]["Image"], "postgres:16");
+        assert_eq!(json["Config"]["Env"], json!(["POSTGRES_PASSWORD=s3cret"]));
+        assert_eq!(json["HostConfig"]["NetworkMode"], "host");
+        assert_eq!(json["Mounts"][0]["Source"], "/var/run/docker.sock");
+    }
+
+    #[test]
+    fn maps_kube_units_to_pod_containers() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("app.yaml"),
+            r#"apiVersion: v1
+kind: Pod
+metadata:
+  name: app
+spec:
+  hostNetwork: true
+  containers:
+  - name: api
+    image: example/api@sha256:abc
+    securityContext:
+      privileged: true
+      runAsUser: 1000
+    ports:
+    - containerPort: 80
+      hostPort: 8080
+    volumeMounts:
+    - name: host-etc
+      mountPath: /host
+    resources:
+      limits:
+        memory: 256Mi
+        cpu: 500m
+  volumes:
+  - name: host-etc
+    hostPath:
+      path: /etc
+---
+kind: ConfigMap
+metadata:
+  name: settings
+"#,
+        )
+        .unwrap();
+        let path = dir.path().join("app.kube");
+        fs::write(&path, "[Kube]\nYaml=app.yaml\n").unwrap();
+
+        let containers = load_units(&path).unwrap();
+
+        assert_eq!(containers.len(), 1);
+        let json = inspect(&containers[0]);
+        assert_eq!(json["Name"], "/app-api");
+        assert_eq!(json["Image"], "sha256:abc");
+        assert_eq!(json["Config"]["User"], "1000");
+        assert_eq!(json["HostConfig"]["Privileged"], true);
+        assert_eq!(json["HostConfig"]["NetworkMode"], "host");
+        assert_eq!(json["HostConfig"]["Memory"], 256 * 1024 * 1024);
+        assert_eq!(json["HostConfig"]["NanoCpus"], 500_000_000);
+        assert_eq!(json["Mounts"][0]["Source"], "/etc");
+    }
+
+    #[test]
+    fn rejects_units_without_an_image() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("broken.container");
+        fs::write(&path, "[Container]\nUser=1000\n").unwrap();
+
+        assert!(matches!(load_units(&path), Err(ValerisError::InputParse { .. })));
+        fs::remove_file(&path).unwrap();
+        assert!(matches!(load_units(dir.path()), Err(ValerisError::InputParse { .. })));
+    }
+}
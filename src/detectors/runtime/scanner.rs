@@ -1,25 +1,343 @@
-use std::{collections::HashSet, path::PathBuf};
+use std::{
+    collections::HashSet,
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, Instant, SystemTime},
+};
 
-use anyhow::{bail, Context, Result};
 use bollard::{
     container::{InspectContainerOptions, ListContainersOptions},
-    models::ContainerInspectResponse,
+    models::{ContainerInspectResponse, ContainerSummary},
     Docker,
 };
-use serde_json::to_value;
+use futures::stream::{self, BoxStream, StreamExt};
+use serde_json::{to_value, Value};
 
 use crate::{
+    bench::RuleProfile,
+    config::DockerConfig,
+    error::{Result, ValerisError},
+    detectors::runtime::hosts::{HostEntry, SshTunnel},
+    detectors::runtime::quadlet,
+    detectors::runtime::incremental::changed_since,
+    detectors::runtime::drift,
+    detectors::runtime::ports::{self, PortPolicy},
+    detectors::runtime::probe,
+    detectors::runtime::usage,
     docker::model::{ContainerResult, Finding},
+    i18n::MessageCatalog,
+    observer::{ScanObserver, ScanTarget},
+    output::metadata::DaemonInfo,
+    output::redact,
+    output::summary::{ScanSummary, SeverityCounts, TargetKind},
 };
 
 use crate::YamlRuleEngine;
 
+/// Results of a runtime scan.
+pub struct RuntimeScanResult {
+    /// Findings for each scanned container
+    pub results: Vec<ContainerResult>,
+    /// Number of rules left after applying `only`/`exclude` filters
+    pub rules_evaluated: usize,
+    /// Version of the scanned Docker daemon; `None` for inspect files,
+    /// Quadlet units, multi-host scans, or when the daemon does not answer
+    pub daemon: Option<DaemonInfo>,
+}
+
+/// Results of a runtime scan, yielded as each container is done.
+///
+/// Containers flow through a pipeline (list → inspect → live checks →
+/// evaluate) in which each stage works on up to `max_parallel` containers at
+/// a time. A result is yielded as soon as its container is evaluated, in
+/// listing order, and only the containers in flight are held in memory, so a
+/// caller that writes each result out and drops it scans very large hosts
+/// with flat memory. Returned by [`scan_docker_stream`].
+pub struct RuntimeScanStream {
+    /// Number of rules left after applying `only`/`exclude` filters
+    pub rules_evaluated: usize,
+    /// Version of the scanned Docker daemon (see [`RuntimeScanResult::daemon`])
+    pub daemon: Option<DaemonInfo>,
+    results: BoxStream<'static, ContainerResult>,
+    observer: Option<Arc<dyn ScanObserver>>,
+    started: Instant,
+    scanned: usize,
+    counts: SeverityCounts,
+}
+
+impl RuntimeScanStream {
+    /// Waits for the next container's result.
+    ///
+    /// # Returns
+    ///
+    /// The next result in listing order, or `None` once every container is
+    /// done; the observer's [`ScanObserver::on_complete`] is called then
+    pub async fn next(&mut self) -> Option<ContainerResult> {
+        let result = self.results.next().await;
+        match &result {
+            Some(result) => {
+                self.scanned += 1;
+                self.counts.add(&result.findings);
+            }
+            None => {
+                if let Some(observer) = self.observer.take() {
+                    observer.on_complete(&ScanSummary::from_counts(
+                        TargetKind::Containers,
+                        self.scanned,
+                        self.rules_evaluated,
+                        self.counts.clone(),
+                        self.started.elapsed(),
+                        false,
+                    ));
+                }
+            }
+        }
+        result
+    }
+
+    /// Waits for every container and collects their results.
+    pub async fn collect(mut self) -> RuntimeScanResult {
+        let mut results = Vec::new();
+        while let Some(result) = self.next().await {
+            results.push(result);
+        }
+        RuntimeScanResult {
+            results,
+            rules_evaluated: self.rules_evaluated,
+            daemon: self.daemon,
+        }
+    }
+}
 
+/// Options for a runtime container scan.
+///
+/// Built with [`RuntimeScanOptions::new`] and the `with_*` methods; every
+/// option not set keeps its default (all rules, all containers).
+///
+/// # Example
+///
+/// ```no_run
+/// use valeris::detectors::runtime::scanner::{scan_docker_with_options, RuntimeScanOptions};
+///
+/// # async fn run() -> valeris::error::Result<()> {
+/// let options = RuntimeScanOptions::new("/path/to/detectors")
+///     .with_state(vec!["running".to_string()])
+///     .with_exclude(vec!["readonly_rootfs".to_string()]);
+///
+/// let scan = scan_docker_with_options(&options).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct RuntimeScanOptions {
+    /// Directory containing YAML rule files
+    pub rules_dir: PathBuf,
+    /// Rule IDs to run exclusively
+    pub only: Option<Vec<String>>,
+    /// Rule IDs to skip
+    pub exclude: Option<Vec<String>>,
+    /// Container states to scan (e.g. "running", "paused")
+    pub state: Option<Vec<String>>,
+    /// Container name/ID patterns to scan
+    pub container: Option<Vec<String>>,
+    /// Maximum number of concurrent `inspect_container` calls
+    pub max_parallel: usize,
+    /// Only scan containers created or (re)started after this time
+    pub since: Option<SystemTime>,
+    /// Receives per-container progress, findings, and completion events
+    pub observer: Option<Arc<dyn ScanObserver>>,
+    /// Read containers from this `docker inspect` JSON file instead of the Docker daemon
+    pub input: Option<PathBuf>,
+    /// Read containers from Podman Quadlet or systemd units at this path instead of the Docker daemon
+    pub quadlet: Option<PathBuf>,
+    /// Docker endpoint (`unix://...`, `tcp://...`); the local socket if `None`
+    pub docker_host: Option<String>,
+    /// Scan these Docker endpoints concurrently instead of `docker_host`
+    pub hosts: Option<Vec<HostEntry>>,
+    /// Run read-only probes in running containers to check inspect data
+    pub probe: bool,
+    /// Compare memory limits with one Docker stats sample per running container
+    pub usage: bool,
+    /// Check each container's `docker diff` for tampering and drift
+    pub drift: bool,
+    /// Allowed host ports of the port binding checks, and the port policy
+    /// of the `exposed_ports` rule
+    pub ports: PortPolicy,
+    /// Language of the rule message catalog to use (e.g. `es`)
+    pub lang: Option<String>,
+    /// Keep each finding's matched JSONPaths and values ([`Finding::debug`])
+    pub explain: bool,
+    /// Mask the values of secret findings ([`crate::output::redact`])
+    pub redact_secrets: bool,
+    /// Records each rule's evaluation time and findings (`--profile-rules`)
+    pub profile: Option<Arc<RuleProfile>>,
+}
+
+impl RuntimeScanOptions {
+    /// Creates options for scanning with the rules in `rules_dir`.
+    pub fn new(rules_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            rules_dir: rules_dir.into(),
+            only: None,
+            exclude: None,
+            state: None,
+            container: None,
+            max_parallel: DockerConfig::default().max_parallel_scans,
+            since: None,
+            observer: None,
+            input: None,
+            quadlet: None,
+            docker_host: None,
+            hosts: None,
+            probe: false,
+            usage: false,
+            drift: false,
+            ports: PortPolicy::default(),
+            lang: None,
+            explain: false,
+            redact_secrets: false,
+            profile: None,
+        }
+    }
+
+    /// Runs only the given rule IDs
+    pub fn with_only(mut self, ids: Vec<String>) -> Self {
+        self.only = Some(ids);
+        self
+    }
+
+    /// Skips the given rule IDs
+    pub fn with_exclude(mut self, ids: Vec<String>) -> Self {
+        self.exclude = Some(ids);
+        self
+    }
+
+    /// Scans only containers in these states
+    pub fn with_state(mut self, states: Vec<String>) -> Self {
+        self.state = Some(states);
+        self
+    }
+
+    /// Scans only containers matching these name/ID patterns
+    pub fn with_container(mut self, patterns: Vec<String>) -> Self {
+        self.container = Some(patterns);
+        self
+    }
+
+    /// Sets the maximum number of concurrent inspections
+    pub fn with_max_parallel(mut self, max_parallel: usize) -> Self {
+        self.max_parallel = max_parallel;
+        self
+    }
+
+    /// Scans only containers created or (re)started after `cutoff`
+    pub fn with_since(mut self, cutoff: SystemTime) -> Self {
+        self.since = Some(cutoff);
+        self
+    }
+
+    /// Notifies `observer` as containers are scanned
+    pub fn with_observer(mut self, observer: Arc<dyn ScanObserver>) -> Self {
+        self.observer = Some(observer);
+        self
+    }
+
+    /// Scans the containers in a `docker inspect` JSON file instead of
+    /// querying the Docker daemon
+    pub fn with_input(mut self, path: impl Into<PathBuf>) -> Self {
+        self.input = Some(path.into());
+        self
+    }
+
+    /// Scans the containers defined by Podman Quadlet or `podman generate
+    /// systemd` units (a file or directory) instead of querying the Docker
+    /// daemon
+    pub fn with_quadlet(mut self, path: impl Into<PathBuf>) -> Self {
+        self.quadlet = Some(path.into());
+        self
+    }
+
+    /// Connects to the Docker daemon at `host` (`unix://...`, `tcp://...`)
+    /// instead of the local socket
+    pub fn with_docker_host(mut self, host: impl Into<String>) -> Self {
+        self.docker_host = Some(host.into());
+        self
+    }
+
+    /// Scans every host concurrently and tags each container with its host
+    pub fn with_hosts(mut self, hosts: Vec<HostEntry>) -> Self {
+        self.hosts = Some(hosts);
+        self
+    }
+
+    /// Runs read-only probes (`id`, `capsh`, mount flags) in running
+    /// containers with `docker exec` and reports where their effective
+    /// privileges exceed the inspect data.
+    ///
+    /// Only applies to scans of a single Docker daemon; ignored with
+    /// `input`, `quadlet` or `hosts`.
+    pub fn with_probe(mut self) -> Self {
+        self.probe = true;
+        self
+    }
+
+    /// Reads one Docker stats sample per running container and reports
+    /// memory limits far out of line with usage (see [`usage`]).
+    ///
+    /// Only applies to scans of a single Docker daemon; ignored with
+    /// `input`, `quadlet` or `hosts`.
+    pub fn with_usage(mut self) -> Self {
+        self.usage = true;
+        self
+    }
+
+    /// Reads `docker diff` per container and reports changed sensitive
+    /// files, system binaries, and new setuid files (see [`drift`]).
+    ///
+    /// Only applies to scans of a single Docker daemon; ignored with
+    /// `input`, `quadlet` or `hosts`.
+    pub fn with_drift(mut self) -> Self {
+        self.drift = true;
+        self
+    }
+
+    /// Skips host ports `policy` allows in the port binding checks, and
+    /// applies its exposure policy to `exposed_ports` findings (see [`ports`])
+    pub fn with_port_policy(mut self, policy: PortPolicy) -> Self {
+        self.ports = policy;
+        self
+    }
+
+    /// Reports rule messages from the `lang` catalog of the rules directory
+    pub fn with_lang(mut self, lang: impl Into<String>) -> Self {
+        self.lang = Some(lang.into());
+        self
+    }
+
+    /// Reports which JSONPaths each finding matched and their values
+    pub fn with_explain(mut self) -> Self {
+        self.explain = true;
+        self
+    }
+
+    /// Masks secret values in findings, keeping the key names
+    pub fn with_redact_secrets(mut self) -> Self {
+        self.redact_secrets = true;
+        self
+    }
+
+    /// Records each rule's evaluation time and findings in `profile`
+    pub fn with_rule_profile(mut self, profile: Arc<RuleProfile>) -> Self {
+        self.profile = Some(profile);
+        self
+    }
+}
 
 /// Scans running Docker containers using YAML-based detection rules.
 ///
-/// This function connects to the Docker daemon, lists containers (filtered by state
-/// if specified), and applies YAML rules to detect security misconfigurations.
+/// Thin wrapper around [`scan_docker_with_options`], kept for backward
+/// compatibility.
 ///
 /// # Arguments
 ///
@@ -28,55 +346,364 @@ use crate::YamlRuleEngine;
 /// * `exclude` - Optional vector of rule IDs to skip
 /// * `state` - Optional vector of container states to scan (e.g., ["running", "paused"])
 /// * `container` - Optional vector of container name/ID patterns to filter
-///
-/// # Returns
-///
-/// `Result<Vec<ContainerResult>>` containing findings for each scanned container
-///
-/// # Errors
-///
-/// Returns an error if:
-/// * Rules cannot be loaded from the specified directory
-/// * Docker daemon is unreachable or returns an error
-/// * Invalid rule IDs are specified in `only` or `exclude`
+/// * `max_parallel` - Maximum number of concurrent `inspect_container` calls
+/// * `since` - Optional cutoff; only containers created or (re)started after it are scanned
 pub async fn scan_docker_with_yaml_detectors(
     rules_dir: PathBuf,
     only: Option<Vec<String>>,
     exclude: Option<Vec<String>>,
     state: Option<Vec<String>>,
     container: Option<Vec<String>>,
-) -> Result<Vec<ContainerResult>> {
-    let engine = YamlRuleEngine::from_dir(&rules_dir)
-        .with_context(|| format!("loading YAML detectors from {}", rules_dir.display()))?;
+    max_parallel: usize,
+    since: Option<SystemTime>,
+) -> Result<RuntimeScanResult> {
+    scan_docker_with_options(&RuntimeScanOptions {
+        rules_dir,
+        only,
+        exclude,
+        state,
+        container,
+        max_parallel,
+        since,
+        observer: None,
+        input: None,
+        quadlet: None,
+        docker_host: None,
+        hosts: None,
+        probe: false,
+        usage: false,
+        drift: false,
+        ports: PortPolicy::default(),
+        lang: None,
+        explain: false,
+        redact_secrets: false,
+        profile: None,
+    })
+    .await
+}
 
-    let state_set = parse_state_set(&state);
-    let container_patterns = parse_container_patterns(&container);
-    let containers = get_containers(state_set.as_ref(), container_patterns.as_ref())
-        .await
-        .context("Failed to connect to Docker daemon or list containers")?;
+/// Scans Docker containers using YAML-based detection rules.
+///
+/// This function connects to the Docker daemon, lists containers (filtered by state
+/// and name/ID if specified), and applies YAML rules to detect security misconfigurations.
+/// When `options.input` is set, containers are read from that `docker inspect` file
+/// instead, and when `options.quadlet` is set, from Podman units; the daemon is then
+/// never contacted. With `options.probe`, running containers are also probed with
+/// `docker exec` (see [`probe`]), with `options.usage` their memory limits are
+/// compared with a stats sample (see [`usage`]), and with `options.drift` their
+/// filesystems are compared with their images (see [`drift`]).
+///
+/// Collects the results of [`scan_docker_stream`]; use that function to handle
+/// each container as soon as it is done.
+///
+/// # Arguments
+///
+/// * `options` - Rules, filters, and concurrency settings for the scan
+///
+/// # Returns
+///
+/// `Result<RuntimeScanResult>` containing findings for each scanned container
+/// and the number of rules that were evaluated
+///
+/// # Errors
+///
+/// * [`ValerisError::RulesLoad`] / [`ValerisError::RuleParse`] if rules cannot be loaded
+/// * [`ValerisError::DockerConnection`] if the Docker daemon is unreachable or returns an error
+/// * [`ValerisError::InputRead`] / [`ValerisError::InputParse`] if the input file or units cannot be read or parsed
+/// * [`ValerisError::InvalidFilter`] if unknown rule IDs are specified in `only` or `exclude`
+pub async fn scan_docker_with_options(options: &RuntimeScanOptions) -> Result<RuntimeScanResult> {
+    Ok(scan_docker_stream(options).await?.collect().await)
+}
+
+/// Starts a streaming scan of Docker containers.
+///
+/// Rules are loaded, filters validated, and containers listed before this
+/// returns, so configuration and connection errors surface here; the returned
+/// [`RuntimeScanStream`] then inspects, checks, and evaluates containers as
+/// its results are read. Scans the same containers with the same options as
+/// [`scan_docker_with_options`].
+///
+/// # Errors
+///
+/// Same as [`scan_docker_with_options`]; a container that fails to inspect
+/// while streaming is logged and skipped.
+///
+/// # Example
+///
+/// ```no_run
+/// use valeris::detectors::runtime::scanner::{scan_docker_stream, RuntimeScanOptions};
+///
+/// # async fn run() -> valeris::error::Result<()> {
+/// let mut scan = scan_docker_stream(&RuntimeScanOptions::new("/path/to/detectors")).await?;
+/// while let Some(result) = scan.next().await {
+///     println!("{:?}: {} findings", result.container.name, result.findings.len());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub async fn scan_docker_stream(options: &RuntimeScanOptions) -> Result<RuntimeScanStream> {
+    let started = Instant::now();
+    let rules_dir = &options.rules_dir;
+    let mut engine = YamlRuleEngine::from_dir(rules_dir)?;
+    if let Some(lang) = &options.lang {
+        engine.localize(&MessageCatalog::load(rules_dir, lang)?);
+    }
+    if let Some(profile) = &options.profile {
+        engine = engine.with_profile(profile.clone());
+    }
+
+    let live = options.input.is_none() && options.quadlet.is_none() && options.hosts.is_none();
+    let probing = options.probe && live;
+    let sampling = options.usage && live;
+    let drifting = options.drift && live;
 
-    let rule_ids = collect_rule_ids(&engine);
-    let only_set = parse_id_set(&only);
-    let exclude_set = parse_id_set(&exclude);
+    let port_policy = options.ports.clone().with_rule_params(&engine);
+    let mut rule_ids = collect_rule_ids(&engine);
+    rule_ids.extend(ports::CHECK_IDS.iter().map(|id| id.to_string()));
+    if probing {
+        rule_ids.extend(probe::CHECK_IDS.iter().map(|id| id.to_string()));
+    }
+    if sampling {
+        rule_ids.extend(usage::CHECK_IDS.iter().map(|id| id.to_string()));
+    }
+    if drifting {
+        rule_ids.extend(drift::CHECK_IDS.iter().map(|id| id.to_string()));
+    }
+    let only_set = parse_id_set(&options.only);
+    let exclude_set = parse_id_set(&options.exclude);
 
     validate_ids(&rule_ids, &only_set, "--only")?;
     validate_ids(&rule_ids, &exclude_set, "--exclude")?;
+    let exclude_set = apply_deprecations(&engine, &only_set, exclude_set);
 
-    Ok(containers
-        .into_iter()
-        .map(|container| {
-            let findings = run_detectors_on_container(
-                &container,
-                &engine,
-                &only_set,
-                &exclude_set,
-            );
-            ContainerResult { container, findings }
+    let rules_evaluated = rule_ids
+        .iter()
+        .filter(|id| passes_id_filters(id, &only_set, &exclude_set))
+        .count();
+
+    // List → inspect: each container with the label of its host, in multi-host scans
+    let state_set = parse_state_set(&options.state);
+    let container_patterns = parse_container_patterns(&options.container);
+    let max_parallel = options.max_parallel.max(1);
+    let offline = match (&options.input, &options.quadlet) {
+        (Some(path), _) => Some(load_inspect_file(path)?),
+        (None, Some(path)) => Some(quadlet::load_units(path)?),
+        (None, None) => None,
+    };
+    let docker = match live {
+        true => Some(connect_docker(options.docker_host.as_deref()).map_err(ValerisError::DockerConnection)?),
+        false => None,
+    };
+    let containers: BoxStream<'static, (Option<String>, ContainerInspectResponse)> =
+        match (offline, &options.hosts, &docker) {
+            (Some(offline), _, _) => {
+                let containers: Vec<_> = offline
+                    .into_iter()
+                    .filter(|c| {
+                        matches_inspect_state(c, state_set.as_ref())
+                            && matches_inspect_patterns(c, container_patterns.as_ref())
+                    })
+                    .map(|c| (None, c))
+                    .collect();
+                stream::iter(containers).boxed()
+            }
+            (None, Some(hosts), _) => {
+                let containers =
+                    get_fleet_containers(hosts, state_set.as_ref(), container_patterns.as_ref(), max_parallel)
+                        .await
+                        .map_err(ValerisError::DockerConnection)?;
+                stream::iter(containers).boxed()
+            }
+            (None, None, Some(docker)) => {
+                let ids = list_container_ids(docker, state_set.as_ref(), container_patterns.as_ref())
+                    .await
+                    .map_err(ValerisError::DockerConnection)?;
+                inspect_containers(docker.clone(), ids, max_parallel)
+                    .map(|c| (None, c))
+                    .boxed()
+            }
+            (None, None, None) => stream::empty().boxed(),
+        };
+    let since = options.since;
+    let containers = containers.filter(move |(_, c)| {
+        futures::future::ready(since.is_none_or(|cutoff| changed_since(c, cutoff)))
+    });
+    let daemon = match live {
+        true => daemon_info(options.docker_host.as_deref()).await,
+        false => None,
+    };
+
+    // → live checks: probes, stats, and diffs against the daemon
+    let checks = LiveChecks {
+        docker: docker.filter(|_| probing || sampling || drifting),
+        probe: probing,
+        usage: sampling,
+        drift: drifting,
+    };
+    let checked = containers
+        .map(move |(host, container)| {
+            let checks = checks.clone();
+            async move {
+                let findings = checks.run(&container).await;
+                (host, container, findings)
+            }
         })
-        .collect())
+        .buffered(max_parallel);
+
+    // → evaluate: rule evaluation is CPU-bound (JSON serialization, JSONPath,
+    // regex), so it runs on blocking threads; `buffered` keeps the listing order
+    let evaluator = Arc::new(Evaluator {
+        engine,
+        ports: port_policy,
+        only: only_set,
+        exclude: exclude_set,
+        explain: options.explain,
+        redact_secrets: options.redact_secrets,
+        observer: options.observer.clone(),
+    });
+    let results = checked
+        .map(move |(host, container, checked)| {
+            let evaluator = evaluator.clone();
+            async move {
+                match tokio::task::spawn_blocking(move || evaluator.evaluate(host, container, checked)).await {
+                    Ok(result) => result,
+                    Err(e) => std::panic::resume_unwind(e.into_panic()),
+                }
+            }
+        })
+        .buffered(max_parallel)
+        .boxed();
+
+    Ok(RuntimeScanStream {
+        rules_evaluated,
+        daemon,
+        results,
+        observer: options.observer.clone(),
+        started,
+        scanned: 0,
+        counts: SeverityCounts::default(),
+    })
+}
+
+/// Opt-in checks that query the Docker daemon for each container.
+#[derive(Clone)]
+struct LiveChecks {
+    /// Connection to the scanned daemon; `None` when no check is enabled
+    docker: Option<Docker>,
+    probe: bool,
+    usage: bool,
+    drift: bool,
+}
+
+impl LiveChecks {
+    /// Runs the enabled checks on `container`, returning their findings.
+    ///
+    /// Containers labeled [`IGNORE_LABEL`]`=true` are not probed or queried.
+    async fn run(&self, container: &ContainerInspectResponse) -> Vec<Finding> {
+        let Some(docker) = &self.docker else {
+            return Vec::new();
+        };
+        if OptOut::of(container).ignored {
+            return Vec::new();
+        }
+        let mut findings = Vec::new();
+        if self.probe {
+            findings.extend(probe::probe_container(docker, container).await);
+        }
+        if self.usage {
+            findings.extend(usage::check_container(docker, container).await);
+        }
+        if self.drift {
+            findings.extend(drift::check_container(docker, container).await);
+        }
+        findings
+    }
+}
+
+/// Rules and filters shared by the evaluation tasks of a scan.
+struct Evaluator {
+    engine: YamlRuleEngine,
+    ports: PortPolicy,
+    only: Option<HashSet<String>>,
+    exclude: Option<HashSet<String>>,
+    explain: bool,
+    redact_secrets: bool,
+    observer: Option<Arc<dyn ScanObserver>>,
 }
 
+impl Evaluator {
+    /// Evaluates the rules and port checks on `container` and adds the
+    /// findings of its live checks, applying the ID filters and the
+    /// container's opt-out labels to both.
+    fn evaluate(
+        &self,
+        host: Option<String>,
+        container: ContainerInspectResponse,
+        checked: Vec<Finding>,
+    ) -> ContainerResult {
+        let observer = self.observer.as_deref();
+        if let Some(observer) = observer {
+            observer.on_container_start(&container);
+        }
+        let mut findings = run_detectors_on_container(
+            &container,
+            &self.engine,
+            &self.ports,
+            &self.only,
+            &self.exclude,
+        );
+        // Redaction learns the secret values from the matched fields
+        if self.redact_secrets {
+            redact::redact_findings(&mut findings);
+        }
+        if !self.explain {
+            findings.iter_mut().for_each(|f| f.debug.clear());
+        }
+        let opt_out = OptOut::of(&container);
+        findings.extend(checked.into_iter().filter(|f| {
+            let id = f.kind.to_lowercase();
+            passes_id_filters(&id, &self.only, &self.exclude) && opt_out.allows(&id)
+        }));
+        if let Some(observer) = observer {
+            for finding in &findings {
+                observer.on_finding(ScanTarget::Container(&container), finding);
+            }
+        }
+        ContainerResult { container, findings, host }
+    }
+}
 
+/// Skips deprecated rules unless `only` selects them, warning when it does,
+/// and extends `exclude` with the replacements of the deprecated IDs it
+/// lists.
+///
+/// # Returns
+///
+/// The exclude set to filter findings with (lowercase IDs)
+fn apply_deprecations(
+    engine: &YamlRuleEngine,
+    only: &Option<HashSet<String>>,
+    exclude: Option<HashSet<String>>,
+) -> Option<HashSet<String>> {
+    let mut exclude = exclude.unwrap_or_default();
+    for rule in engine.rules().iter().filter(|rule| rule.deprecated) {
+        let id = rule.id.to_lowercase();
+        let replacement = rule.replaced_by.as_deref().map(str::to_lowercase);
+        // An exclusion written against the old ID still applies after a rename
+        if let Some(new) = replacement.clone().filter(|_| exclude.contains(&id)) {
+            exclude.insert(new);
+        }
+        if only.as_ref().is_some_and(|set| set.contains(&id)) {
+            match &replacement {
+                Some(new) => tracing::warn!("Rule '{}' is deprecated; use '{}' instead", rule.id, new),
+                None => tracing::warn!("Rule '{}' is deprecated", rule.id),
+            }
+        } else {
+            exclude.insert(id);
+        }
+    }
+    Some(exclude).filter(|set| !set.is_empty())
+}
 
 /// Extracts all rule IDs from the engine and normalizes them to lowercase.
 ///
@@ -95,24 +722,73 @@ fn collect_rule_ids(engine: &YamlRuleEngine) -> HashSet<String> {
         .collect()
 }
 
-/// Runs YAML-based detectors on a container and filters results.
+/// Container label that opts the container out of runtime rules (`true`)
+pub const IGNORE_LABEL: &str = "io.valeris.ignore";
+
+/// Container label listing rule IDs to skip for the container (comma-separated)
+pub const EXCLUDE_RULES_LABEL: &str = "io.valeris.exclude-rules";
+
+/// Opt-out labels of a container ([`IGNORE_LABEL`], [`EXCLUDE_RULES_LABEL`]).
+#[derive(Debug, Default)]
+struct OptOut {
+    /// Labeled [`IGNORE_LABEL`]`=true`: nothing is checked or reported
+    ignored: bool,
+    /// Lowercase rule IDs listed in [`EXCLUDE_RULES_LABEL`]
+    excluded: HashSet<String>,
+}
+
+impl OptOut {
+    /// Reads the opt-out labels of `container`.
+    fn of(container: &ContainerInspectResponse) -> Self {
+        let labels = container.config.as_ref().and_then(|c| c.labels.as_ref());
+        let label = |key: &str| labels.and_then(|l| l.get(key)).map(|v| v.trim());
+        Self {
+            ignored: label(IGNORE_LABEL).is_some_and(|v| v.eq_ignore_ascii_case("true")),
+            excluded: label(EXCLUDE_RULES_LABEL)
+                .into_iter()
+                .flat_map(|v| v.split(','))
+                .map(|id| id.trim().to_lowercase())
+                .filter(|id| !id.is_empty())
+                .collect(),
+        }
+    }
+
+    /// Whether a finding with the lowercase rule ID `id` is reported
+    fn allows(&self, id: &str) -> bool {
+        !self.ignored && !self.excluded.contains(id)
+    }
+}
+
+/// Runs YAML-based detectors and the built-in port checks on a container
+/// and filters results.
+///
+/// Containers labeled [`IGNORE_LABEL`]`=true` yield no findings, and rules
+/// listed in [`EXCLUDE_RULES_LABEL`] are skipped for that container.
 ///
 /// # Arguments
 ///
 /// * `container` - Container inspection response from Docker API
 /// * `engine` - YAML rule engine with loaded detection rules
+/// * `ports` - Port policy of the port checks and the `exposed_ports` rule
 /// * `only` - Optional set of rule IDs to exclusively run (whitelist)
 /// * `exclude` - Optional set of rule IDs to skip (blacklist)
 ///
 /// # Returns
 ///
-/// Vector of findings matching the filter criteria
+/// Vector of findings matching the filter criteria, each targeting `container`
 fn run_detectors_on_container(
     container: &ContainerInspectResponse,
     engine: &YamlRuleEngine,
+    ports: &PortPolicy,
     only: &Option<HashSet<String>>,
     exclude: &Option<HashSet<String>>,
 ) -> Vec<Finding> {
+    let opt_out = OptOut::of(container);
+    if opt_out.ignored {
+        tracing::debug!("Skipping container {:?}: labeled {}", container.name, IGNORE_LABEL);
+        return Vec::new();
+    }
+
     let json = match to_value(container) {
         Ok(val) => val,
         Err(e) => {
@@ -121,23 +797,33 @@ fn run_detectors_on_container(
         }
     };
 
-    let findings = engine.scan_value(&json);
+    let mut findings = engine.scan_container_json(&json);
+    ports.apply_exposure(container, &mut findings);
+    findings.extend(ports::check(container, ports));
 
     // Apply filters in one pass for efficiency
     findings
         .into_iter()
         .filter(|f| {
             let id = f.kind.to_lowercase();
+            passes_id_filters(&id, only, exclude) && opt_out.allows(&id)
+        })
+        .collect()
+}
 
-            // Check whitelist (only) filter
-            let passes_only = only.as_ref().is_none_or(|set| set.contains(&id));
+/// Checks a lowercase rule ID against the only/exclude filters.
+fn passes_id_filters(
+    id: &str,
+    only: &Option<HashSet<String>>,
+    exclude: &Option<HashSet<String>>,
+) -> bool {
+    // Check whitelist (only) filter
+    let passes_only = only.as_ref().is_none_or(|set| set.contains(id));
 
-            // Check blacklist (exclude) filter
-            let passes_exclude = exclude.as_ref().is_none_or(|set| !set.contains(&id));
+    // Check blacklist (exclude) filter
+    let passes_exclude = exclude.as_ref().is_none_or(|set| !set.contains(id));
 
-            passes_only && passes_exclude
-        })
-        .collect()
+    passes_only && passes_exclude
 }
 
 
@@ -200,19 +886,47 @@ fn validate_ids(available: &HashSet<String>, provided: &Option<HashSet<String>>,
         let unknown: Vec<_> = ids.difference(available).cloned().collect();
         if !unknown.is_empty() {
             let noun = if unknown.len() == 1 { "detector" } else { "detectors" };
-            bail!("Unknown {noun} in {flag}: {}", unknown.join(", "));
+            return Err(ValerisError::InvalidFilter(format!(
+                "Unknown {noun} in {flag}: {}",
+                unknown.join(", ")
+            )));
         }
     }
     Ok(())
 }
 
+/// Asks the daemon for its version; failures are logged and yield `None`.
+pub(crate) async fn daemon_info(docker_host: Option<&str>) -> Option<DaemonInfo> {
+    let version = match connect_docker(docker_host) {
+        Ok(docker) => docker.version().await,
+        Err(e) => Err(e),
+    };
+    match version {
+        Ok(v) => Some(DaemonInfo {
+            version: v.version,
+            api_version: v.api_version,
+            os: v.os,
+            arch: v.arch,
+        }),
+        Err(e) => {
+            tracing::debug!("Failed to read Docker daemon version: {}", e);
+            None
+        }
+    }
+}
 
 /// Fetches and inspects Docker containers, optionally filtered by state and name/ID patterns.
 ///
+/// Inspections run concurrently (up to `max_parallel` at a time) and preserve
+/// the listing order. A container that fails to inspect (e.g. removed between
+/// listing and inspection) is logged and skipped rather than aborting the scan.
+///
 /// # Arguments
 ///
+/// * `docker_host` - Docker endpoint, or `None` for the local socket
 /// * `state_filter` - Optional set of container states to include (e.g., "running", "exited")
 /// * `container_patterns` - Optional vector of name/ID patterns to match
+/// * `max_parallel` - Maximum number of concurrent inspections
 ///
 /// # Returns
 ///
@@ -223,87 +937,253 @@ fn validate_ids(available: &HashSet<String>, provided: &Option<HashSet<String>>,
 /// Returns an error if:
 /// * Unable to connect to Docker socket
 /// * Container listing fails
-/// * Container inspection fails for any container
 async fn get_containers(
+    docker_host: Option<&str>,
     state_filter: Option<&HashSet<String>>,
     container_patterns: Option<&Vec<String>>,
-) -> Result<Vec<ContainerInspectResponse>> {
-    let docker = Docker::connect_with_socket_defaults()
-        .context("Failed to connect to Docker socket")?;
+    max_parallel: usize,
+) -> Result<Vec<ContainerInspectResponse>, bollard::errors::Error> {
+    let docker = connect_docker(docker_host)?;
+    let ids = list_container_ids(&docker, state_filter, container_patterns).await?;
+    Ok(inspect_containers(docker, ids, max_parallel).collect().await)
+}
 
+/// Lists the IDs of the containers that match the state and name/ID filters.
+///
+/// # Errors
+///
+/// Returns an error if the container listing fails
+async fn list_container_ids(
+    docker: &Docker,
+    state_filter: Option<&HashSet<String>>,
+    container_patterns: Option<&Vec<String>>,
+) -> Result<Vec<String>, bollard::errors::Error> {
     let containers = docker
         .list_containers(Some(ListContainersOptions::<String> {
             all: true,
             ..Default::default()
         }))
-        .await
-        .context("Failed to list Docker containers")?;
-
-    let mut result = Vec::new();
+        .await?;
 
-    for container in containers {
-        // Filter by state
-        if let Some(filter) = state_filter {
-            if let Some(state) = container.state.as_deref() {
-                if !filter.contains(&state.to_lowercase()) {
-                    continue;
-                }
-            }
-        }
+    Ok(containers
+        .into_iter()
+        .filter(|c| matches_state(c, state_filter) && matches_patterns(c, container_patterns))
+        .filter_map(|c| c.id)
+        .collect())
+}
 
-        // Filter by container name/ID pattern
-        if let Some(patterns) = container_patterns {
-            let mut matched = false;
-
-            // Check container ID
-            if let Some(id) = container.id.as_deref() {
-                let id_lower = id.to_lowercase();
-                for pattern in patterns {
-                    if id_lower.starts_with(pattern) || id_lower.contains(pattern) {
-                        matched = true;
-                        break;
+/// Inspects containers with up to `max_parallel` requests in flight, yielding
+/// them in the order of `ids`. A container that fails to inspect (e.g. one
+/// removed since it was listed) is logged and skipped.
+fn inspect_containers(
+    docker: Docker,
+    ids: Vec<String>,
+    max_parallel: usize,
+) -> impl futures::Stream<Item = ContainerInspectResponse> {
+    stream::iter(ids)
+        .map(move |id| {
+            let docker = docker.clone();
+            async move {
+                match docker.inspect_container(&id, None::<InspectContainerOptions>).await {
+                    Ok(inspect) => Some(inspect),
+                    Err(e) => {
+                        tracing::warn!("Failed to inspect container {}: {}", id, e);
+                        None
                     }
                 }
             }
+        })
+        .buffered(max_parallel.max(1))
+        .filter_map(futures::future::ready)
+}
 
-            // Check container names
-            if !matched {
-                if let Some(names) = &container.names {
-                    for name in names {
-                        let name_lower = name.trim_start_matches('/').to_lowercase();
-                        for pattern in patterns {
-                            if name_lower.contains(pattern) {
-                                matched = true;
-                                break;
-                            }
-                        }
-                        if matched {
-                            break;
-                        }
-                    }
-                }
-            }
 
-            if !matched {
-                continue;
+/// Lists and inspects the containers of every host concurrently, pairing
+/// each with the host's label.
+///
+/// Hosts that cannot be reached are skipped with a warning, so one
+/// unreachable machine does not block the report for the rest of the fleet.
+///
+/// # Errors
+///
+/// Returns the first host's error if no host could be scanned.
+async fn get_fleet_containers(
+    hosts: &[HostEntry],
+    state_filter: Option<&HashSet<String>>,
+    container_patterns: Option<&Vec<String>>,
+    max_parallel: usize,
+) -> Result<Vec<(Option<String>, ContainerInspectResponse)>, bollard::errors::Error> {
+    let timeout = Duration::from_secs(DockerConfig::default().connection_timeout_secs);
+    let scans = hosts.iter().map(|host| async move {
+        // Kept alive until the host's containers are inspected
+        let tunnel = match host.url.starts_with("ssh://") {
+            true => Some(SshTunnel::open(&host.url, timeout).await?),
+            false => None,
+        };
+        let url = tunnel.as_ref().map_or_else(|| host.url.clone(), SshTunnel::docker_host);
+        get_containers(Some(&url), state_filter, container_patterns, max_parallel).await
+    });
+    let outcomes = futures::future::join_all(scans).await;
+
+    let mut containers = Vec::new();
+    let mut first_error = None;
+    let mut reached = 0;
+    for (host, outcome) in hosts.iter().zip(outcomes) {
+        match outcome {
+            Ok(found) => {
+                reached += 1;
+                let label = host.label();
+                containers.extend(found.into_iter().map(|c| (Some(label.clone()), c)));
+            }
+            Err(e) => {
+                tracing::warn!("Skipping host {} ({}): {}", host.label(), host.url, e);
+                first_error.get_or_insert(e);
             }
         }
+    }
+
+    match (reached, first_error) {
+        (0, Some(e)) => Err(e),
+        _ => Ok(containers),
+    }
+}
+
+/// Reads containers from a file of `docker inspect` output.
+///
+/// Accepts a single inspect object or the array printed by `docker inspect`.
+///
+/// # Arguments
+///
+/// * `path` - Path to the JSON file
+///
+/// # Errors
+///
+/// * [`ValerisError::InputRead`] if the file cannot be read
+/// * [`ValerisError::InputParse`] if it is not inspect JSON
+pub fn load_inspect_file(path: &Path) -> Result<Vec<ContainerInspectResponse>> {
+    let raw = fs::read_to_string(path).map_err(|source| ValerisError::InputRead {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let parse_error = |message: String| ValerisError::InputParse {
+        path: path.to_path_buf(),
+        message,
+    };
+
+    let items = match serde_json::from_str(&raw).map_err(|e| parse_error(e.to_string()))? {
+        Value::Array(items) => items,
+        object @ Value::Object(_) => vec![object],
+        _ => {
+            return Err(parse_error(
+                "expected an inspect object or an array of them".to_string(),
+            ))
+        }
+    };
 
-        if let Some(id) = container.id.as_deref() {
-            let inspect = docker
-                .inspect_container(id, None::<InspectContainerOptions>)
-                .await
-                .with_context(|| format!("Failed to inspect container {}", id))?;
-            result.push(inspect);
+    items
+        .into_iter()
+        .enumerate()
+        .map(|(i, item)| {
+            serde_json::from_value(item).map_err(|e| parse_error(format!("entry {}: {}", i, e)))
+        })
+        .collect()
+}
+
+/// Connects to the Docker daemon at `host`, or the local socket if `None`.
+///
+/// Supports `unix://` sockets, `npipe://` named pipes (Windows), and
+/// unencrypted `tcp://` / `http://` endpoints.
+pub(crate) fn connect_docker(host: Option<&str>) -> Result<Docker, bollard::errors::Error> {
+    let Some(host) = host else {
+        return Docker::connect_with_socket_defaults();
+    };
+    let timeout = DockerConfig::default().connection_timeout_secs;
+
+    match host {
+        #[cfg(unix)]
+        h if h.starts_with("unix://") => {
+            Docker::connect_with_unix(h, timeout, bollard::API_DEFAULT_VERSION)
+        }
+        #[cfg(windows)]
+        h if h.starts_with("npipe://") => {
+            Docker::connect_with_named_pipe(h, timeout, bollard::API_DEFAULT_VERSION)
+        }
+        h if h.starts_with("tcp://") || h.starts_with("http://") => {
+            Docker::connect_with_http(h, timeout, bollard::API_DEFAULT_VERSION)
+        }
+        _ => Err(bollard::errors::Error::UnsupportedURISchemeError {
+            uri: host.to_string(),
+        }),
+    }
+}
+
+/// Checks a listed container against the optional state filter.
+fn matches_state(container: &ContainerSummary, state_filter: Option<&HashSet<String>>) -> bool {
+    state_matches(container.state.as_deref(), state_filter)
+}
+
+/// Checks a listed container's ID and names against the optional name/ID patterns.
+fn matches_patterns(container: &ContainerSummary, container_patterns: Option<&Vec<String>>) -> bool {
+    let names: Vec<&str> = container.names.iter().flatten().map(String::as_str).collect();
+    id_or_name_matches(container.id.as_deref(), &names, container_patterns)
+}
+
+/// Checks an inspected container (from `--input`) against the optional state filter.
+fn matches_inspect_state(
+    container: &ContainerInspectResponse,
+    state_filter: Option<&HashSet<String>>,
+) -> bool {
+    let state = container
+        .state
+        .as_ref()
+        .and_then(|s| s.status)
+        .map(|status| status.to_string());
+    state_matches(state.as_deref(), state_filter)
+}
+
+/// Checks an inspected container's ID and name against the optional name/ID patterns.
+fn matches_inspect_patterns(
+    container: &ContainerInspectResponse,
+    container_patterns: Option<&Vec<String>>,
+) -> bool {
+    let names: Vec<&str> = container.name.as_deref().into_iter().collect();
+    id_or_name_matches(container.id.as_deref(), &names, container_patterns)
+}
+
+fn state_matches(state: Option<&str>, state_filter: Option<&HashSet<String>>) -> bool {
+    let Some(filter) = state_filter else {
+        return true;
+    };
+    match state {
+        Some(state) => filter.contains(&state.to_lowercase()),
+        None => true,
+    }
+}
+
+fn id_or_name_matches(id: Option<&str>, names: &[&str], container_patterns: Option<&Vec<String>>) -> bool {
+    let Some(patterns) = container_patterns else {
+        return true;
+    };
+
+    // Check container ID
+    if let Some(id) = id {
+        let id_lower = id.to_lowercase();
+        if patterns.iter().any(|p| id_lower.starts_with(p) || id_lower.contains(p)) {
+            return true;
         }
     }
 
-    Ok(result)
+    // Check container names
+    names.iter().any(|name| {
+        let name_lower = name.trim_start_matches('/').to_lowercase();
+        patterns.iter().any(|p| name_lower.contains(p))
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::docker::model::{FindingTarget, RiskLevel};
 
     #[test]
     fn parse_id_set_normalizes_and_deduplicates() {
@@ -323,6 +1203,242 @@ mod tests {
         assert_eq!(set.len(), 2);
     }
 
+    #[test]
+    fn run_detectors_on_container_sets_target() {
+        let engine = YamlRuleEngine::from_dir(std::path::Path::new("rules/runtime")).unwrap();
+        let container: ContainerInspectResponse = serde_json::from_str(
+            r#"{"Id": "abc123", "Name": "/web", "HostConfig": {"Privileged": true}}"#,
+        )
+        .unwrap();
+
+        let findings = run_detectors_on_container(&container, &engine, &PortPolicy::default(), &None, &None);
+        let privileged = findings.iter().find(|f| f.kind == "privileged_mode").unwrap();
+
+        assert_eq!(
+            privileged.target,
+            Some(FindingTarget::Container {
+                id: "abc123".to_string(),
+                name: "web".to_string(),
+            })
+        );
+        assert!(privileged.rule_name.is_some());
+    }
+
+    #[test]
+    fn run_detectors_on_container_honors_opt_out_labels() {
+        let engine = YamlRuleEngine::from_dir(std::path::Path::new("rules/runtime")).unwrap();
+        let labeled = |labels: &str| -> ContainerInspectResponse {
+            serde_json::from_str(&format!(
+                r#"{{"Id": "abc123", "Name": "/web", "HostConfig": {{"Privileged": true}},
+                    "Config": {{"Labels": {{{}}}}}}}"#,
+                labels
+            ))
+            .unwrap()
+        };
+        let kinds = |container: &ContainerInspectResponse| -> Vec<String> {
+            run_detectors_on_container(container, &engine, &PortPolicy::default(), &None, &None)
+                .into_iter()
+                .map(|f| f.kind)
+                .collect()
+        };
+
+        let privileged = vec!["privileged_mode".to_string()];
+        assert_eq!(kinds(&labeled("")), privileged);
+        assert!(kinds(&labeled(r#""io.valeris.ignore": "true""#)).is_empty());
+        assert!(kinds(&labeled(r#""io.valeris.exclude-rules": "pids_limit, Privileged_Mode""#)).is_empty());
+        assert_eq!(kinds(&labeled(r#""io.valeris.exclude-rules": "pids_limit""#)), privileged);
+    }
+
+    #[test]
+    fn live_check_findings_honor_opt_out_labels() {
+        let dir = tempfile::tempdir().unwrap();
+        let evaluator = Evaluator {
+            engine: YamlRuleEngine::from_dir(dir.path()).unwrap(),
+            ports: PortPolicy::default(),
+            only: None,
+            exclude: None,
+            explain: false,
+            redact_secrets: false,
+            observer: None,
+        };
+        let labeled = |labels: &str| -> ContainerInspectResponse {
+            serde_json::from_str(&format!(r#"{{"Id": "abc123", "Config": {{"Labels": {{{}}}}}}}"#, labels)).unwrap()
+        };
+        let kinds = |container: ContainerInspectResponse| -> Vec<String> {
+            let checked = vec![
+                Finding::new("Image_Drift", "Files changed since the image was built", RiskLevel::Medium),
+                Finding::new("memory_usage", "Memory near its limit", RiskLevel::Low),
+            ];
+            evaluator.evaluate(None, container, checked).findings.into_iter().map(|f| f.kind).collect()
+        };
+
+        assert_eq!(kinds(labeled("")).len(), 2);
+        assert_eq!(kinds(labeled(r#""io.valeris.exclude-rules": "image_drift""#)), vec!["memory_usage"]);
+        assert!(kinds(labeled(r#""io.valeris.ignore": "true""#)).is_empty());
+    }
+
+    #[tokio::test]
+    async fn live_checks_skip_ignored_containers() {
+        // Never reachable: an ignored container must not be probed at all
+        let docker = Docker::connect_with_http("http://127.0.0.1:9", 1, bollard::API_DEFAULT_VERSION).unwrap();
+        let checks = LiveChecks {
+            docker: Some(docker),
+            probe: true,
+            usage: true,
+            drift: true,
+        };
+        let container: ContainerInspectResponse = serde_json::from_str(
+            r#"{"Id": "abc123", "State": {"Running": true}, "Config": {"Labels": {"io.valeris.ignore": "true"}}}"#,
+        )
+        .unwrap();
+
+        assert!(checks.run(&container).await.is_empty());
+    }
+
+    #[test]
+    fn deprecated_rules_run_only_when_selected() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("docker")).unwrap();
+        std::fs::write(
+            dir.path().join("docker/old.yaml"),
+            r#"
+id: old_privileged
+name: "Old"
+target: docker_runtime
+severity: HIGH
+deprecated: true
+replaced_by: privileged_mode
+match:
+  jsonpath: "$.HostConfig.Privileged"
+  equals: "true"
+message: "privileged"
+"#,
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("docker/new.yaml"),
+            r#"
+id: privileged_mode
+name: "New"
+target: docker_runtime
+severity: HIGH
+match:
+  jsonpath: "$.HostConfig.Privileged"
+  equals: "true"
+message: "privileged"
+"#,
+        )
+        .unwrap();
+        let engine = YamlRuleEngine::from_dir(dir.path()).unwrap();
+        assert_eq!(engine.renames()["old_privileged"], "privileged_mode");
+
+        let exclude = apply_deprecations(&engine, &None, None).unwrap();
+        assert_eq!(exclude, HashSet::from(["old_privileged".to_string()]));
+
+        let only = parse_id_set(&Some(vec!["old_privileged".to_string()]));
+        assert_eq!(apply_deprecations(&engine, &only, None), None);
+
+        let renamed = apply_deprecations(&engine, &None, parse_id_set(&Some(vec!["OLD_PRIVILEGED".to_string()])));
+        assert!(renamed.unwrap().contains("privileged_mode"));
+    }
+
+    #[tokio::test]
+    async fn scan_stream_yields_containers_in_listing_order() {
+        #[derive(Default)]
+        struct Completions(std::sync::Mutex<Vec<usize>>);
+        impl ScanObserver for Completions {
+            fn on_complete(&self, summary: &ScanSummary) {
+                self.0.lock().unwrap().push(summary.targets_scanned);
+            }
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("docker")).unwrap();
+        std::fs::write(
+            dir.path().join("docker/privileged.yaml"),
+            r#"
+id: privileged_mode
+name: "Privileged"
+target: docker_runtime
+severity: HIGH
+match:
+  jsonpath: "$.HostConfig.Privileged"
+  equals: "true"
+message: "privileged"
+"#,
+        )
+        .unwrap();
+        let input = dir.path().join("inspect.json");
+        std::fs::write(
+            &input,
+            r#"[
+                {"Id": "a1", "Name": "/web", "HostConfig": {"Privileged": true}},
+                {"Id": "b2", "Name": "/db", "HostConfig": {"Privileged": false}},
+                {"Id": "c3", "Name": "/cache", "HostConfig": {"Privileged": true}}
+            ]"#,
+        )
+        .unwrap();
+        let observer = Arc::new(Completions::default());
+        let options = RuntimeScanOptions::new(dir.path())
+            .with_input(&input)
+            .with_max_parallel(2)
+            .with_observer(observer.clone());
+
+        let mut scan = scan_docker_stream(&options).await.unwrap();
+        assert!(scan.daemon.is_none());
+        let mut seen = Vec::new();
+        while let Some(result) = scan.next().await {
+            seen.push((result.container.id.unwrap(), result.findings.len()));
+        }
+        assert!(scan.next().await.is_none());
+
+        let expected = [("a1", 1), ("b2", 0), ("c3", 1)].map(|(id, n)| (id.to_string(), n));
+        assert_eq!(seen, expected);
+        assert_eq!(*observer.0.lock().unwrap(), vec![3]);
+    }
+
+    #[test]
+    fn load_inspect_file_accepts_object_and_array() {
+        let dir = tempfile::tempdir().unwrap();
+        let object = dir.path().join("object.json");
+        let array = dir.path().join("array.json");
+        std::fs::write(&object, r#"{"Id": "abc123", "Name": "/web"}"#).unwrap();
+        std::fs::write(&array, r#"[{"Id": "abc123"}, {"Id": "def456"}]"#).unwrap();
+
+        assert_eq!(load_inspect_file(&object).unwrap().len(), 1);
+        assert_eq!(load_inspect_file(&array).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn load_inspect_file_rejects_non_inspect_json() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bad.json");
+        std::fs::write(&path, "42").unwrap();
+
+        let err = load_inspect_file(&path).unwrap_err();
+        assert!(matches!(err, ValerisError::InputParse { .. }));
+        assert!(matches!(
+            load_inspect_file(&dir.path().join("missing.json")).unwrap_err(),
+            ValerisError::InputRead { .. }
+        ));
+    }
+
+    #[test]
+    fn inspect_filters_match_state_and_name() {
+        let container: ContainerInspectResponse = serde_json::from_str(
+            r#"{"Id": "abc123", "Name": "/web", "State": {"Status": "running"}}"#,
+        )
+        .unwrap();
+        let running = parse_state_set(&Some(vec!["running".to_string()]));
+        let exited = parse_state_set(&Some(vec!["exited".to_string()]));
+
+        assert!(matches_inspect_state(&container, running.as_ref()));
+        assert!(!matches_inspect_state(&container, exited.as_ref()));
+        assert!(matches_inspect_patterns(&container, Some(&vec!["web".to_string()])));
+        assert!(matches_inspect_patterns(&container, Some(&vec!["abc".to_string()])));
+        assert!(!matches_inspect_patterns(&container, Some(&vec!["db".to_string()])));
+    }
+
     #[test]
     fn validate_ids_errors_on_unknown() {
         let available: HashSet<String> = ["a", "b"].iter().map(|s| s.to_string()).collect();
@@ -347,4 +1463,30 @@ mod tests {
         let patterns = parse_container_patterns(&input);
         assert!(patterns.is_none());
     }
+
+    fn summary(id: &str, name: &str, state: &str) -> ContainerSummary {
+        ContainerSummary {
+            id: Some(id.to_string()),
+            names: Some(vec![format!("/{name}")]),
+            state: Some(state.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn matches_state_filters_by_lowercase_state() {
+        let filter: HashSet<String> = ["running".to_string()].into_iter().collect();
+        assert!(matches_state(&summary("abc", "web", "running"), Some(&filter)));
+        assert!(!matches_state(&summary("abc", "web", "exited"), Some(&filter)));
+        assert!(matches_state(&summary("abc", "web", "exited"), None));
+    }
+
+    #[test]
+    fn matches_patterns_checks_id_and_names() {
+        let patterns = vec!["nginx".to_string(), "abc1".to_string()];
+        assert!(matches_patterns(&summary("abc123", "web", "running"), Some(&patterns)));
+        assert!(matches_patterns(&summary("fff", "my-nginx-1", "running"), Some(&patterns)));
+        assert!(!matches_patterns(&summary("fff", "redis", "running"), Some(&patterns)));
+        assert!(matches_patterns(&summary("fff", "redis", "running"), None));
+    }
 }
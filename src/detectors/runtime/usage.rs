@@ -0,0 +1,221 @@
+//! Resource limits compared with actual usage.
+//!
+//! `valeris scan --usage` reads one sample from the Docker stats API for
+//! each running container and flags memory limits that are far out of line
+//! with what the container really uses:
+//!
+//! * no limit at all while the container holds 8 GiB or more resident, so it
+//!   can starve the host and nothing stops it from growing further
+//! * a limit 100 times (or more) above the resident memory, which protects
+//!   nothing and hides the container's real footprint
+//!
+//! Resident memory is computed like `docker stats` does: usage minus the
+//! inactive page cache, which the kernel reclaims before hitting the limit.
+
+use bollard::{
+    container::{MemoryStats, MemoryStatsStats, StatsOptions},
+    models::ContainerInspectResponse,
+    Docker,
+};
+use futures::stream::StreamExt;
+use std::time::Duration;
+
+use crate::docker::model::{Finding, FindingTarget, RiskLevel};
+
+/// Finding kinds reported from usage, usable with `--only`/`--exclude`
+pub const CHECK_IDS: [&str; 2] = ["usage_memory_unbounded", "usage_memory_limit_oversized"];
+
+/// Resident memory at which a container without a limit is reported (8 GiB)
+pub const UNBOUNDED_RESIDENT_BYTES: u64 = 8 << 30;
+
+/// Ratio of limit to resident memory at which a limit is reported as oversized
+pub const OVERSIZED_RATIO: u64 = 100;
+
+/// Smallest limit reported as oversized (1 GiB), so small sidecars with
+/// modest limits and near-idle usage are not flagged
+pub const OVERSIZED_MIN_LIMIT_BYTES: u64 = 1 << 30;
+
+/// Maximum time to wait for a container's stats
+const STATS_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Resources a container was using when sampled.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Usage {
+    /// Resident memory in bytes, excluding reclaimable page cache
+    pub memory_bytes: Option<u64>,
+}
+
+/// Samples a running container once, returning its findings.
+///
+/// Containers that are not running, or whose stats cannot be read, get no
+/// findings; failures are logged rather than aborting the scan.
+pub(crate) async fn check_container(docker: &Docker, container: &ContainerInspectResponse) -> Vec<Finding> {
+    let running = container
+        .state
+        .as_ref()
+        .and_then(|s| s.running)
+        .unwrap_or(false);
+    let Some(id) = container.id.as_deref().filter(|_| running) else {
+        return Vec::new();
+    };
+    match sample(docker, id).await {
+        Some(usage) => compare(container, &usage),
+        None => Vec::new(),
+    }
+}
+
+/// Reads one stats sample of container `id`.
+async fn sample(docker: &Docker, id: &str) -> Option<Usage> {
+    let options = StatsOptions {
+        stream: false,
+        one_shot: true,
+    };
+    let mut stats = docker.stats(id, Some(options));
+    match tokio::time::timeout(STATS_TIMEOUT, stats.next()).await {
+        Ok(Some(Ok(stats))) => Some(Usage {
+            memory_bytes: resident_memory(&stats.memory_stats),
+        }),
+        Ok(Some(Err(e))) => {
+            tracing::warn!("Failed to read stats of container {}: {}", id, e);
+            None
+        }
+        Ok(None) => None,
+        Err(_) => {
+            tracing::warn!("Timed out reading stats of container {}", id);
+            None
+        }
+    }
+}
+
+/// Memory usage without the inactive page cache (cgroup v1 or v2).
+fn resident_memory(stats: &MemoryStats) -> Option<u64> {
+    let usage = stats.usage?;
+    let inactive = match stats.stats {
+        Some(MemoryStatsStats::V1(v1)) => v1.total_inactive_file,
+        Some(MemoryStatsStats::V2(v2)) => v2.inactive_file,
+        None => 0,
+    };
+    Some(usage.saturating_sub(inactive))
+}
+
+/// Compares a container's memory limit with its sampled usage.
+///
+/// # Returns
+///
+/// A finding if the container has no limit and at least
+/// [`UNBOUNDED_RESIDENT_BYTES`] resident, or a limit of at least
+/// [`OVERSIZED_MIN_LIMIT_BYTES`] that is [`OVERSIZED_RATIO`] times its usage
+/// or more, targeting `container`
+pub fn compare(container: &ContainerInspectResponse, usage: &Usage) -> Vec<Finding> {
+    let Some(resident) = usage.memory_bytes else {
+        return Vec::new();
+    };
+    let limit = container
+        .host_config
+        .as_ref()
+        .and_then(|h| h.memory)
+        .filter(|limit| *limit > 0)
+        .map(|limit| limit as u64);
+
+    let finding = match limit {
+        None if resident >= UNBOUNDED_RESIDENT_BYTES => usage_finding(
+            "usage_memory_unbounded",
+            "Large memory footprint without a limit",
+            format!("No memory limit set while {} is resident", format_bytes(resident)),
+            RiskLevel::High,
+            "Set a memory limit with --memory sized from the observed usage plus headroom, so the container cannot exhaust host memory",
+        ),
+        Some(limit) if limit >= OVERSIZED_MIN_LIMIT_BYTES && limit / resident.max(1) >= OVERSIZED_RATIO => {
+            usage_finding(
+                "usage_memory_limit_oversized",
+                "Memory limit far above usage",
+                format!(
+                    "Memory limit of {} is {}x the {} resident",
+                    format_bytes(limit),
+                    limit / resident.max(1),
+                    format_bytes(resident)
+                ),
+                RiskLevel::Low,
+                "Lower the --memory limit to the observed usage plus headroom, so a leak or runaway process is stopped early",
+            )
+        }
+        _ => return Vec::new(),
+    };
+    vec![Finding {
+        target: Some(FindingTarget::container(container)),
+        ..finding
+    }]
+}
+
+fn usage_finding(kind: &str, name: &str, description: String, risk: RiskLevel, fix: &str) -> Finding {
+    Finding {
+        rule_name: Some(name.to_string()),
+        remediation: Some(fix.to_string()),
+        tags: vec!["usage".to_string(), "resources".to_string()],
+        category: Some("runtime".to_string()),
+        ..Finding::new(kind, description, risk)
+    }
+}
+
+/// Formats a byte count with a binary unit (`1.5 GiB`).
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    match unit {
+        0 => format!("{} B", bytes),
+        _ => format!("{:.1} {}", value, UNITS[unit]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bollard::models::HostConfig;
+
+    fn container(memory: i64) -> ContainerInspectResponse {
+        ContainerInspectResponse {
+            id: Some("abc123".to_string()),
+            name: Some("/web".to_string()),
+            host_config: Some(HostConfig {
+                memory: Some(memory),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn kinds(findings: &[Finding]) -> Vec<&str> {
+        findings.iter().map(|f| f.kind.as_str()).collect()
+    }
+
+    fn resident(bytes: u64) -> Usage {
+        Usage { memory_bytes: Some(bytes) }
+    }
+
+    #[test]
+    fn reports_large_footprint_without_limit() {
+        let findings = compare(&container(0), &resident(9 << 30));
+        assert_eq!(kinds(&findings), ["usage_memory_unbounded"]);
+        assert_eq!(findings[0].description, "No memory limit set while 9.0 GiB is resident");
+        assert!(findings[0].target.is_some());
+
+        assert!(compare(&container(0), &resident(2 << 30)).is_empty());
+    }
+
+    #[test]
+    fn reports_limits_far_above_usage() {
+        let findings = compare(&container(16 << 30), &resident(100 << 20));
+        assert_eq!(kinds(&findings), ["usage_memory_limit_oversized"]);
+        assert_eq!(findings[0].description, "Memory limit of 16.0 GiB is 163x the 100.0 MiB resident");
+
+        // Within the ratio, or too small a limit to matter
+        assert!(compare(&container(4 << 30), &resident(512 << 20)).is_empty());
+        assert!(compare(&container(512 << 20), &resident(1 << 20)).is_empty());
+        assert!(compare(&container(16 << 30), &Usage::default()).is_empty());
+    }
+}
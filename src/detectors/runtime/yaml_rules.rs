@@ -1,18 +1,18 @@
-use std::{collections::HashSet, fs, path::Path};
+use std::{collections::{BTreeMap, HashMap, HashSet}, fs, path::Path, sync::{Arc, OnceLock}, time::Instant};
 
-use anyhow::{Context, Result};
 use itertools::Itertools;
 use jsonpath_lib as jsonpath;
 use regex::Regex;
 use serde::Deserialize;
 use serde_json::Value;
 
-use crate::docker::model::{Finding, RiskLevel};
+use crate::bench::{RuleKind, RuleProfile};
+use crate::docker::model::{Finding, FindingTarget, MatchedField, RiskLevel};
+use crate::error::ValerisError;
+use crate::i18n::MessageCatalog;
 
 type Bucket     = Vec<String>;          // Value for just one part
 type Buckets    = Vec<Bucket>;          // All parts
-#[allow(dead_code)]
-type Combos<'a> = Vec<&'a String>;      // One combination -> Future phase
 
 // ──────────────────────────────── Rules ────────────────────────────────
 #[derive(Debug, Deserialize)]
@@ -21,15 +21,28 @@ pub struct YamlRule {
     pub name: Option<String>,
     pub target: Option<String>,
     pub severity: Option<String>,
-    #[allow(dead_code)]
     pub description: Option<String>,
-    #[allow(dead_code)]
     #[serde(default)] pub references: Vec<String>,
+    #[serde(default)] pub tags: Vec<String>,
+    pub category: Option<String>,
+    /// Controls the rule maps to, keyed by framework (e.g. `nist-800-190`)
+    #[serde(default)] pub compliance: BTreeMap<String, Vec<String>>,
     #[serde(rename = "match")] pub matcher: RuleMatcher,
+    /// Conditions, in `match` syntax, under which the rule does not fire;
+    /// any one of them matching suppresses every finding of the rule
+    #[serde(default)] pub suppress_if: Vec<RuleMatcher>,
+    /// Finding description; see [`render_message`] for the `{{...}}` variables
     pub message: String,
-    #[allow(dead_code)]
+    /// Values the message can reference as `{{param.<name>}}`
+    #[serde(default)] pub params: BTreeMap<String, Value>,
     pub fix: Option<String>,
     #[serde(default)] pub include_match_in_description: bool,
+    /// Example configurations that trigger the rule (documentation only)
+    #[serde(default)] pub examples: Vec<String>,
+    /// Deprecated rules only run when selected with `--only`
+    #[serde(default)] pub deprecated: bool,
+    /// ID of the rule that supersedes this one
+    pub replaced_by: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -46,9 +59,116 @@ pub struct MatchPart {
     pub jsonpath: String,
 }
 
+/// One value a rule matched, before it becomes a finding.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct RuleMatch {
+    /// String the matcher tested; parts joined with the rule's separator
+    combined: String,
+    /// Matched values, parts joined with `, `
+    value: String,
+    /// JSONPath expressions that produced the values, joined with `, `
+    path: String,
+    /// Each JSONPath expression with the value it produced
+    fields: Vec<MatchedField>,
+}
+
+// ───────────────────────────── Index ────────────────────────────────────
+/// Maps top-level JSON keys to the rules that can only match under them.
+///
+/// A rule whose JSONPath expressions all start with `$.Key` cannot match a
+/// document where `Key` is missing or null, so those rules are skipped
+/// without evaluating any JSONPath.
+#[derive(Debug, Default)]
+struct RuleIndex {
+    /// Top-level key -> indices of rules rooted under that key
+    by_prefix: HashMap<String, Vec<usize>>,
+    /// Rules whose paths cannot be reduced to a single top-level key
+    always: Vec<usize>,
+    /// Total number of indexed rules
+    total: usize,
+}
+
+impl RuleIndex {
+    fn build(rules: &[YamlRule]) -> Self {
+        let mut index = Self {
+            total: rules.len(),
+            ..Self::default()
+        };
+        for (i, rule) in rules.iter().enumerate() {
+            match rule_prefixes(rule) {
+                Some(prefixes) => {
+                    for prefix in prefixes {
+                        index.by_prefix.entry(prefix).or_default().push(i);
+                    }
+                }
+                None => index.always.push(i),
+            }
+        }
+        index
+    }
+
+    /// Returns the indices of rules that may match `value`, in load order.
+    fn candidates(&self, value: &Value) -> Vec<usize> {
+        let Some(object) = value.as_object() else {
+            return (0..self.total).collect();
+        };
+
+        let mut candidates: Vec<usize> = object
+            .iter()
+            .filter(|(_, v)| !v.is_null())
+            .filter_map(|(key, _)| self.by_prefix.get(key))
+            .flatten()
+            .chain(&self.always)
+            .copied()
+            .collect();
+        candidates.sort_unstable();
+        candidates.dedup();
+        candidates
+    }
+}
+
+/// Collects the top-level keys a rule's JSONPath expressions are rooted at.
+///
+/// Returns `None` if any expression does not start with a plain key
+/// (e.g. `$..Foo` or `$[*]`), meaning the rule must always be evaluated.
+fn rule_prefixes(rule: &YamlRule) -> Option<HashSet<String>> {
+    let paths: Vec<&str> = match (&rule.matcher.parts, &rule.matcher.jsonpath) {
+        (Some(parts), _) => parts.iter().map(|p| p.jsonpath.as_str()).collect(),
+        (None, Some(expr)) => vec![expr.as_str()],
+        (None, None) => return None,
+    };
+    if paths.is_empty() {
+        return None;
+    }
+    paths.into_iter().map(top_level_key).collect()
+}
+
+/// Extracts the first key of a JSONPath such as `$.HostConfig.Privileged`
+/// or `$['HostConfig']`.
+fn top_level_key(expr: &str) -> Option<String> {
+    let rest = expr.trim().strip_prefix('$')?;
+
+    if let Some(dotted) = rest.strip_prefix('.') {
+        let key: String = dotted
+            .chars()
+            .take_while(|c| *c != '.' && *c != '[')
+            .collect();
+        if key.is_empty() || key == "*" {
+            return None;
+        }
+        return Some(key);
+    }
+
+    let quoted = rest.strip_prefix("['").or_else(|| rest.strip_prefix("[\""))?;
+    let end = quoted.find(['\'', '"'])?;
+    Some(quoted[..end].to_string()).filter(|k| !k.is_empty())
+}
+
 // ───────────────────────────── Engine ───────────────────────────────────
 pub struct YamlRuleEngine {
     rules: Vec<YamlRule>,
+    index: RuleIndex,
+    profile: Option<Arc<RuleProfile>>,
 }
 
 impl YamlRuleEngine {
@@ -57,9 +177,38 @@ impl YamlRuleEngine {
         &self.rules
     }
 
+    /// Maps the ID of each deprecated rule with a `replaced_by` to the ID of
+    /// its replacement.
+    pub fn renames(&self) -> HashMap<String, String> {
+        self.rules
+            .iter()
+            .filter(|rule| rule.deprecated)
+            .filter_map(|rule| Some((rule.id.clone(), rule.replaced_by.clone()?)))
+            .collect()
+    }
+
+    /// Replaces rule names, messages, and fixes with their translations in
+    /// `catalog`; rules it does not cover are left unchanged.
+    pub fn localize(&mut self, catalog: &MessageCatalog) {
+        for rule in &mut self.rules {
+            let Some(text) = catalog.get(&rule.id) else { continue };
+            if let Some(name) = &text.name {
+                rule.name = Some(name.clone());
+            }
+            if let Some(message) = &text.message {
+                rule.message = message.clone();
+            }
+            if let Some(remediation) = &text.remediation {
+                rule.fix = Some(remediation.clone());
+            }
+        }
+    }
+
     /// Loads YAML rules from a directory structure.
     ///
-    /// Expects rules to be in `{base}/docker/*.yaml` files.
+    /// Expects rules to be in `{base}/docker/*.yaml` files. A rule without a
+    /// `compliance` mapping gets the one of the bundled rule with the same ID
+    /// (see [`crate::bundled`]).
     ///
     /// # Arguments
     ///
@@ -67,34 +216,56 @@ impl YamlRuleEngine {
     ///
     /// # Returns
     ///
-    /// `Result<YamlRuleEngine>` with loaded rules, or error if directory read/parse fails
-    pub fn from_dir(base: &Path) -> Result<Self> {
+    /// `Result<YamlRuleEngine>` with loaded rules
+    ///
+    /// # Errors
+    ///
+    /// * [`ValerisError::RulesLoad`] if the directory or a rule file cannot be read
+    /// * [`ValerisError::RuleParse`] if a rule file is invalid
+    pub fn from_dir(base: &Path) -> Result<Self, ValerisError> {
     let dir = base.join("docker");
+    let load_error = |path: &Path, source: std::io::Error| ValerisError::RulesLoad {
+        path: path.to_path_buf(),
+        source: source.into(),
+    };
 
     let mut rules = Vec::new();
     if dir.exists() {
-        for entry in fs::read_dir(&dir)
-            .with_context(|| format!("Failed to read directory {}", dir.display()))? {
-            let path = entry
-                .with_context(|| format!("Failed to read directory entry in {}", dir.display()))?
-                .path();
+        for entry in fs::read_dir(&dir).map_err(|e| load_error(&dir, e))? {
+            let path = entry.map_err(|e| load_error(&dir, e))?.path();
             if path.extension().and_then(|e| e.to_str()) == Some("yaml") {
-                let contents = fs::read_to_string(&path)
-                    .with_context(|| format!("reading {}", path.display()))?;
-                let rule: YamlRule = serde_yml::from_str(&contents)
-                    .with_context(|| format!("parsing {}", path.display()))?;
+                let contents = fs::read_to_string(&path).map_err(|e| load_error(&path, e))?;
+                let mut rule: YamlRule = serde_yml::from_str(&contents)
+                    .map_err(|e| ValerisError::rule_parse(&path, e))?;
+                // Packs older than the compliance mappings: take the bundled one
+                if rule.compliance.is_empty() {
+                    if let Some(compliance) = crate::bundled::runtime_compliance(&rule.id) {
+                        rule.compliance = compliance.clone();
+                    }
+                }
                 rules.push(rule);
             }
         }
     }
     tracing::info!("Loaded {} YAML rules from {}", rules.len(), dir.display());
-    Ok(Self { rules })
+    let index = RuleIndex::build(&rules);
+    Ok(Self { rules, index, profile: None })
 }
 
+    /// Records the time and findings of every rule evaluation in `profile`
+    /// (`--profile-rules`).
+    pub fn with_profile(mut self, profile: Arc<RuleProfile>) -> Self {
+        self.profile = Some(profile);
+        self
+    }
+
 
 
     /// Scans a JSON value against all loaded rules.
     ///
+    /// Rules rooted under a top-level key that is absent from `value` are
+    /// skipped without evaluation.
+    ///
     /// # Arguments
     ///
     /// * `value` - JSON representation of the object to scan (e.g., Docker container inspect response)
@@ -103,27 +274,76 @@ impl YamlRuleEngine {
     ///
     /// Vector of findings from rules that matched
     pub fn scan_value(&self, value: &Value) -> Vec<Finding> {
-        self.rules
-            .iter()
-            .flat_map(|rule| self.scan_with_rule(rule, value))
+        self.index
+            .candidates(value)
+            .into_iter()
+            .flat_map(|i| self.profiled_scan_with_rule(&self.rules[i], value))
             .collect()
     }
 
-    // ------------ Apply a rule ---------------------------
-    fn scan_with_rule(&self, rule: &YamlRule, value: &Value) -> Vec<Finding> {
-        let sep = rule.matcher.separator.as_deref().unwrap_or(":");
+    /// Scans one container's `docker inspect` output against all loaded rules.
+    ///
+    /// `inspect` is a single inspect object, as returned by the Docker API or
+    /// as an element of the array printed by `docker inspect`, so output
+    /// collected elsewhere (another host, a forensic capture) can be scanned
+    /// without a Docker daemon. Unlike [`Self::scan_value`], every finding's
+    /// `target` is set from the object's `Id` and `Name`.
+    ///
+    /// # Arguments
+    ///
+    /// * `inspect` - Container inspect JSON object
+    ///
+    /// # Returns
+    ///
+    /// Vector of findings from rules that matched
+    pub fn scan_container_json(&self, inspect: &Value) -> Vec<Finding> {
+        let target = FindingTarget::Container {
+            id: inspect["Id"].as_str().unwrap_or_default().to_string(),
+            name: inspect["Name"]
+                .as_str()
+                .unwrap_or_default()
+                .trim_start_matches('/')
+                .to_string(),
+        };
 
-        // 1️Collect possible matches
-        let matches = if let Some(ref parts) = rule.matcher.parts {
-            self.matches_from_parts(parts, sep, &rule.matcher, value)
-        } else if let Some(ref expr) = rule.matcher.jsonpath {
-            self.matches_from_jsonpath(expr, &rule.matcher, value)
-        } else {
-            Vec::new()
+        self.scan_value(inspect)
+            .into_iter()
+            .map(|finding| Finding {
+                target: Some(target.clone()),
+                ..finding
+            })
+            .collect()
+    }
+
+    /// Scans a JSON value against a single rule, bypassing the rule index.
+    ///
+    /// Used to time rules individually (`valeris bench`).
+    pub fn scan_value_with_rule(&self, rule: &YamlRule, value: &Value) -> Vec<Finding> {
+        self.scan_with_rule(rule, value)
+    }
+
+    // ------------ Apply a rule ---------------------------
+    fn profiled_scan_with_rule(&self, rule: &YamlRule, value: &Value) -> Vec<Finding> {
+        let Some(profile) = &self.profile else {
+            return self.scan_with_rule(rule, value);
         };
+        let started = Instant::now();
+        let findings = self.scan_with_rule(rule, value);
+        profile.record(&rule.id, RuleKind::Runtime, started.elapsed(), findings.len());
+        findings
+    }
 
-        // 2️No matches found
-        if matches.is_empty() {
+    fn scan_with_rule(&self, rule: &YamlRule, value: &Value) -> Vec<Finding> {
+        // 1️Collect possible matches
+        let matches = self.collect_matches(&rule.matcher, value);
+
+        // 2️No matches found, or the rule is suppressed for this object
+        if matches.is_empty()
+            || rule
+                .suppress_if
+                .iter()
+                .any(|condition| !self.collect_matches(condition, value).is_empty())
+        {
             return Vec::new();
         }
 
@@ -131,14 +351,14 @@ impl YamlRuleEngine {
         let mut seen = HashSet::new();
         let unique = matches
             .into_iter()
-            .filter(|m| seen.insert(m.clone()))
+            .filter(|m| seen.insert(m.combined.clone()))
             .collect::<Vec<_>>();
 
         // Convert to findings
         let risk = risk_from_severity(rule.severity.as_deref());
         unique
             .into_iter()
-            .map(|mv| to_finding(rule, &mv, risk.clone()))
+            .map(|m| to_finding(rule, &m, value, risk.clone()))
             .collect()
     }
 
@@ -149,7 +369,8 @@ impl YamlRuleEngine {
         sep: &str,
         matcher: &RuleMatcher,
         value: &Value,
-    ) -> Vec<String> {
+    ) -> Vec<RuleMatch> {
+        let path = parts.iter().map(|p| p.jsonpath.as_str()).join(", ");
         let buckets: Buckets = parts
             .iter()
             .filter_map(|part| jsonpath::select(value, &part.jsonpath).ok())
@@ -172,24 +393,49 @@ impl YamlRuleEngine {
             .multi_cartesian_product()
             .filter_map(|combo| {
                 let combined = combo.iter().join(sep);
-                matcher_matches(&combined, matcher).then_some(combined)
+                matcher_matches(&combined, matcher).then(|| RuleMatch {
+                    value: combo.iter().join(", "),
+                    path: path.clone(),
+                    fields: parts
+                        .iter()
+                        .zip(&combo)
+                        .map(|(part, value)| MatchedField::new(&part.jsonpath, Some(value.to_string())))
+                        .collect(),
+                    combined,
+                })
             })
             .collect()
     }
 
+    fn collect_matches(&self, matcher: &RuleMatcher, value: &Value) -> Vec<RuleMatch> {
+        let sep = matcher.separator.as_deref().unwrap_or(":");
+        if let Some(ref parts) = matcher.parts {
+            self.matches_from_parts(parts, sep, matcher, value)
+        } else if let Some(ref expr) = matcher.jsonpath {
+            self.matches_from_jsonpath(expr, matcher, value)
+        } else {
+            Vec::new()
+        }
+    }
+
     fn matches_from_jsonpath(
         &self,
         expr: &str,
         matcher: &RuleMatcher,
         value: &Value,
-    ) -> Vec<String> {
+    ) -> Vec<RuleMatch> {
         jsonpath::select(value, expr)
             .ok()
             .into_iter()
             .flatten()
             .filter_map(|n| {
                 let s = n.to_string().trim_matches('"').to_string();
-                matcher_matches(&s, matcher).then_some(s)
+                matcher_matches(&s, matcher).then(|| RuleMatch {
+                    combined: s.clone(),
+                    fields: vec![MatchedField::new(expr, Some(s.clone()))],
+                    value: s,
+                    path: expr.to_string(),
+                })
             })
             .collect()
     }
@@ -216,21 +462,188 @@ fn risk_from_severity(s: Option<&str>) -> RiskLevel {
     match s.unwrap_or("MEDIUM").to_ascii_uppercase().as_str() {
         "INFORMATIVE" | "INFO" => RiskLevel::Informative,
         "LOW"       => RiskLevel::Low,
-        "HIGH" | "CRITICAL" => RiskLevel::High,
+        "HIGH"      => RiskLevel::High,
+        "CRITICAL"  => RiskLevel::Critical,
         _           => RiskLevel::Medium,
     }
 }
 
-fn to_finding(rule: &YamlRule, mv: &str, risk: RiskLevel) -> Finding {
-    let mut desc = rule.message.replace("{{match}}", mv);
+/// Fills the `{{...}}` variables of a rule message:
+///
+/// | Variable              | Value                                                     |
+/// |-----------------------|-----------------------------------------------------------|
+/// | `{{match}}`           | String the matcher tested (parts joined by `separator`)   |
+/// | `{{value}}`           | Matched value (parts joined by `, `)                      |
+/// | `{{path}}`            | JSONPath expression that matched (parts joined by `, `)   |
+/// | `{{container.name}}`  | Name of the scanned container, without the leading `/`    |
+/// | `{{container.image}}` | Image the scanned container was created from              |
+/// | `{{container.id}}`    | ID of the scanned container                               |
+/// | `{{param.<name>}}`    | Entry `<name>` of the rule's `params`                     |
+///
+/// Whitespace inside the braces is ignored. Unknown variables, and
+/// container variables when the document has no such field, are left as
+/// written.
+fn render_message(rule: &YamlRule, m: &RuleMatch, document: &Value) -> String {
+    static VARIABLE: OnceLock<Regex> = OnceLock::new();
+    let variable = VARIABLE.get_or_init(|| Regex::new(r"\{\{\s*([\w.-]+)\s*\}\}").expect("valid regex"));
+
+    variable
+        .replace_all(&rule.message, |caps: &regex::Captures| {
+            let resolved = match &caps[1] {
+                "match" => Some(m.combined.clone()),
+                "value" => Some(m.value.clone()),
+                "path" => Some(m.path.clone()),
+                "container.name" => document["Name"]
+                    .as_str()
+                    .map(|name| name.trim_start_matches('/').to_string()),
+                "container.image" => document["Config"]["Image"]
+                    .as_str()
+                    .or(document["Image"].as_str())
+                    .map(str::to_string),
+                "container.id" => document["Id"].as_str().map(str::to_string),
+                name => name
+                    .strip_prefix("param.")
+                    .and_then(|param| rule.params.get(param))
+                    .map(|value| match value {
+                        Value::String(s) => s.clone(),
+                        other => other.to_string(),
+                    }),
+            };
+            resolved.unwrap_or_else(|| caps[0].to_string())
+        })
+        .into_owned()
+}
+
+// ─────────────────────────── Context ──────────────────────────────
+/// One step of a simple JSONPath expression
+#[derive(Debug, PartialEq)]
+enum Step {
+    Key(String),
+    Index(usize),
+    /// `*` / `[*]`: every entry of an object or array
+    Any,
+}
+
+/// Splits a JSONPath made of keys, indices, and wildcards into steps.
+/// Filters, slices, and recursive descent are not supported.
+fn parse_steps(expr: &str) -> Option<Vec<Step>> {
+    let mut rest = expr.strip_prefix('$')?;
+    let mut steps = Vec::new();
+    while !rest.is_empty() {
+        if let Some(tail) = rest.strip_prefix('[') {
+            let end = tail.find(']')?;
+            let inner = tail[..end].trim();
+            steps.push(if inner == "*" {
+                Step::Any
+            } else if let Ok(index) = inner.parse() {
+                Step::Index(index)
+            } else {
+                let key = inner.strip_prefix('\'').and_then(|k| k.strip_suffix('\''))?;
+                Step::Key(key.to_string())
+            });
+            rest = &tail[end + 1..];
+        } else {
+            let tail = rest.strip_prefix('.').filter(|t| !t.starts_with('.'))?;
+            let end = tail.find(['.', '[']).unwrap_or(tail.len());
+            let key = &tail[..end];
+            steps.push(match key {
+                "*" => Step::Any,
+                "" => return None,
+                _ => Step::Key(key.to_string()),
+            });
+            rest = &tail[end..];
+        }
+    }
+    Some(steps)
+}
+
+/// Walks `steps` from `node` and collects, for every leaf equal to
+/// `matched`, its context: the innermost array element that is an object
+/// (a mount, a port binding), or else `{ key: leaf }`.
+fn collect_context(
+    node: &Value,
+    steps: &[Step],
+    matched: &str,
+    key: Option<&str>,
+    element: Option<&Value>,
+    out: &mut Vec<Value>,
+) {
+    let Some((step, rest)) = steps.split_first() else {
+        if node.to_string().trim_matches('"') == matched {
+            out.push(match (element, key) {
+                (Some(element), _) => element.clone(),
+                (None, Some(key)) => serde_json::json!({ key: node }),
+                (None, None) => node.clone(),
+            });
+        }
+        return;
+    };
+    match (step, node) {
+        (Step::Key(k), Value::Object(map)) => {
+            if let Some(child) = map.get(k) {
+                collect_context(child, rest, matched, Some(k), element, out);
+            }
+        }
+        (Step::Index(i), Value::Array(items)) => {
+            if let Some(child) = items.get(*i) {
+                let element = if child.is_object() { Some(child) } else { element };
+                collect_context(child, rest, matched, key, element, out);
+            }
+        }
+        (Step::Any, Value::Object(map)) => {
+            for (k, child) in map {
+                collect_context(child, rest, matched, Some(k), element, out);
+            }
+        }
+        (Step::Any, Value::Array(items)) => {
+            for child in items {
+                let element = if child.is_object() { Some(child) } else { element };
+                collect_context(child, rest, matched, key, element, out);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Returns the slice of `document` each matched field came from; an array
+/// when the fields come from different places, `None` when no expression
+/// can be resolved.
+fn context_of(document: &Value, fields: &[MatchedField]) -> Option<Value> {
+    let mut slices: Vec<Value> = Vec::new();
+    for field in fields {
+        let (Some(steps), Some(value)) = (parse_steps(&field.path), &field.value) else {
+            continue;
+        };
+        let mut found = Vec::new();
+        collect_context(document, &steps, value, None, None, &mut found);
+        for slice in found {
+            if !slices.contains(&slice) {
+                slices.push(slice);
+            }
+        }
+    }
+    match slices.len() {
+        0 => None,
+        1 => slices.pop(),
+        _ => Some(Value::Array(slices)),
+    }
+}
+
+fn to_finding(rule: &YamlRule, m: &RuleMatch, document: &Value, risk: RiskLevel) -> Finding {
+    let mut desc = render_message(rule, m, document);
     if rule.include_match_in_description {
-        desc = format!("{}: {}", desc, mv);
+        desc = format!("{}: {}", desc, m.combined);
     }
     Finding {
-        kind: rule.id.clone(),
-        description: desc,
-        risk,
-        line: None,
+        rule_name: rule.name.clone(),
+        remediation: rule.fix.as_deref().map(str::trim).map(str::to_string),
+        references: rule.references.clone(),
+        tags: rule.tags.clone(),
+        category: rule.category.clone(),
+        compliance: rule.compliance.clone(),
+        debug: m.fields.clone(),
+        context: context_of(document, &m.fields),
+        ..Finding::new(rule.id.clone(), desc, risk)
     }
 }
 
@@ -281,6 +694,9 @@ mod tests {
             severity: Some("info".into()),
             description: None,
             references: vec![],
+            tags: vec![],
+            category: None,
+            compliance: Default::default(),
             matcher: RuleMatcher {
                 parts: None,
                 separator: None,
@@ -288,14 +704,83 @@ mod tests {
                 regex: None,
                 jsonpath: None,
             },
+            suppress_if: vec![],
             message: "found {{match}}".into(),
+            params: BTreeMap::new(),
             fix: None,
             include_match_in_description: true,
+            examples: vec![],
+            deprecated: false,
+            replaced_by: None,
         };
 
-        let finding = to_finding(&rule, "VALUE", RiskLevel::Informative);
+        let m = RuleMatch {
+            combined: "VALUE".into(),
+            value: "VALUE".into(),
+            path: "$.Value".into(),
+            fields: vec![MatchedField::new("$.Value", Some("VALUE".into()))],
+        };
+        let finding = to_finding(&rule, &m, &Value::Null, RiskLevel::Informative);
         assert_eq!(finding.kind, "ID");
         assert!(finding.description.contains("VALUE"));
+        assert_eq!(finding.debug, m.fields);
+    }
+
+    #[test]
+    fn message_renders_container_value_path_and_param_variables() {
+        let mut rule = rule_with_path("mount", "$.Mounts[*].Source");
+        rule.message = "{{ container.name }} ({{container.image}}) mounts {{value}} via {{path}}; \
+                        limit {{param.limit}} for {{param.team}} {{unknown}}"
+            .into();
+        rule.params = BTreeMap::from([
+            ("limit".to_string(), serde_json::json!(3)),
+            ("team".to_string(), serde_json::json!("ops")),
+        ]);
+        rule.matcher.regex = Some("^/var".into());
+
+        let container = serde_json::json!({
+            "Name": "/web",
+            "Config": { "Image": "nginx:1.25" },
+            "Mounts": [{ "Source": "/var/run/docker.sock" }, { "Source": "/data" }],
+        });
+        let dir = tempfile::tempdir().unwrap();
+        let engine = YamlRuleEngine::from_dir(dir.path()).unwrap();
+        let findings = engine.scan_value_with_rule(&rule, &container);
+
+        assert_eq!(findings.len(), 1);
+        assert_eq!(
+            findings[0].description,
+            "web (nginx:1.25) mounts /var/run/docker.sock via $.Mounts[*].Source; \
+             limit 3 for ops {{unknown}}"
+        );
+    }
+
+    #[test]
+    fn localize_replaces_text_but_keeps_ids_and_templates() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("docker")).unwrap();
+        std::fs::create_dir_all(dir.path().join("i18n")).unwrap();
+        std::fs::write(
+            dir.path().join("docker/privileged.yaml"),
+            "id: privileged_mode\nmatch:\n  jsonpath: \"$.HostConfig.Privileged\"\n  equals: \"true\"\n\
+             message: \"Privileged\"\nfix: \"Drop --privileged\"\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("i18n/es.yaml"),
+            "privileged_mode:\n  message: \"{{container.name}} en modo privilegiado\"\n",
+        )
+        .unwrap();
+
+        let mut engine = YamlRuleEngine::from_dir(dir.path()).unwrap();
+        engine.localize(&MessageCatalog::load(dir.path(), "es").unwrap());
+        let rule = &engine.rules()[0];
+        assert_eq!(rule.id, "privileged_mode");
+        assert_eq!(rule.fix.as_deref(), Some("Drop --privileged"));
+
+        let container = serde_json::json!({ "Name": "/web", "HostConfig": { "Privileged": true } });
+        let findings = engine.scan_value_with_rule(rule, &container);
+        assert_eq!(findings[0].description, "web en modo privilegiado");
     }
 
     #[test]
@@ -304,4 +789,150 @@ mod tests {
         let engine = YamlRuleEngine::from_dir(dir.path()).unwrap();
         assert_eq!(engine.rules().len(), 0);
     }
+
+    #[test]
+    fn from_dir_fills_in_bundled_compliance_mappings() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir(dir.path().join("docker")).unwrap();
+        let rule = "match:\n  jsonpath: \"$.HostConfig.Privileged\"\n  equals: \"true\"\nmessage: found\n";
+        fs::write(dir.path().join("docker/a.yaml"), format!("id: privileged_mode\n{rule}")).unwrap();
+        fs::write(
+            dir.path().join("docker/b.yaml"),
+            format!("id: secrets_in_env\ncompliance:\n  custom: [\"1\"]\n{rule}"),
+        )
+        .unwrap();
+
+        let engine = YamlRuleEngine::from_dir(dir.path()).unwrap();
+        let compliance = |id: &str| {
+            let rule = engine.rules().iter().find(|r| r.id == id).unwrap();
+            rule.compliance.keys().cloned().collect::<Vec<_>>()
+        };
+
+        assert_eq!(compliance("privileged_mode"), ["nist-800-190"]);
+        // A rule's own mapping is kept as is
+        assert_eq!(compliance("secrets_in_env"), ["custom"]);
+    }
+
+    fn rule_with_path(id: &str, jsonpath: &str) -> YamlRule {
+        YamlRule {
+            id: id.into(),
+            name: None,
+            target: None,
+            severity: None,
+            description: None,
+            references: vec![],
+            tags: vec![],
+            category: None,
+            compliance: Default::default(),
+            matcher: RuleMatcher {
+                parts: None,
+                separator: None,
+                equals: None,
+                regex: None,
+                jsonpath: Some(jsonpath.into()),
+            },
+            suppress_if: vec![],
+            message: "found".into(),
+            params: BTreeMap::new(),
+            fix: None,
+            include_match_in_description: false,
+            examples: vec![],
+            deprecated: false,
+            replaced_by: None,
+        }
+    }
+
+    #[test]
+    fn context_is_the_object_or_field_that_matched() {
+        let dir = tempfile::tempdir().unwrap();
+        let engine = YamlRuleEngine::from_dir(dir.path()).unwrap();
+        let doc = serde_json::json!({
+            "HostConfig": { "Privileged": true },
+            "Mounts": [
+                { "Source": "/srv/data", "Destination": "/data" },
+                { "Source": "/var/run/docker.sock", "Destination": "/var/run/docker.sock" }
+            ]
+        });
+
+        let mut sock = rule_with_path("sock", "$.Mounts[*].Source");
+        sock.matcher.regex = Some("docker\\.sock".into());
+        let findings = engine.scan_value_with_rule(&sock, &doc);
+        assert_eq!(findings[0].context, Some(doc["Mounts"][1].clone()));
+
+        let privileged = rule_with_path("privileged", "$.HostConfig.Privileged");
+        let findings = engine.scan_value_with_rule(&privileged, &doc);
+        assert_eq!(findings[0].context, Some(serde_json::json!({ "Privileged": true })));
+
+        assert_eq!(parse_steps("$..Source"), None);
+    }
+
+    #[test]
+    fn matched_fields_record_the_path_and_value_that_matched() {
+        let dir = tempfile::tempdir().unwrap();
+        let engine = YamlRuleEngine::from_dir(dir.path()).unwrap();
+        let doc = serde_json::json!({ "HostConfig": { "NetworkMode": "host" } });
+
+        let mut rule = rule_with_path("host_network", "$.HostConfig.NetworkMode");
+        rule.matcher.equals = Some("host".into());
+        let findings = engine.scan_value_with_rule(&rule, &doc);
+
+        assert_eq!(
+            findings[0].debug,
+            vec![MatchedField::new("$.HostConfig.NetworkMode", Some("host".into()))]
+        );
+    }
+
+    #[test]
+    fn suppress_if_skips_rule_when_condition_matches() {
+        let mut rule = rule_with_path("no_memory_limit", "$.HostConfig.Memory");
+        rule.matcher.equals = Some("0".into());
+        rule.suppress_if = vec![RuleMatcher {
+            parts: None,
+            separator: None,
+            equals: Some("true".into()),
+            regex: None,
+            jsonpath: Some("$.Config.Labels['io.valeris.batch']".into()),
+        }];
+
+        let dir = tempfile::tempdir().unwrap();
+        let engine = YamlRuleEngine::from_dir(dir.path()).unwrap();
+
+        let batch = serde_json::json!({
+            "HostConfig": { "Memory": 0 },
+            "Config": { "Labels": { "io.valeris.batch": "true" } },
+        });
+        assert!(engine.scan_value_with_rule(&rule, &batch).is_empty());
+
+        let service = serde_json::json!({
+            "HostConfig": { "Memory": 0 },
+            "Config": { "Labels": { "io.valeris.batch": "false" } },
+        });
+        assert_eq!(engine.scan_value_with_rule(&rule, &service).len(), 1);
+    }
+
+    #[test]
+    fn top_level_key_handles_dotted_and_bracket_paths() {
+        assert_eq!(top_level_key("$.HostConfig.Privileged").as_deref(), Some("HostConfig"));
+        assert_eq!(top_level_key("$.Mounts[*].Source").as_deref(), Some("Mounts"));
+        assert_eq!(top_level_key("$['Config'].User").as_deref(), Some("Config"));
+        assert_eq!(top_level_key("$..Privileged"), None);
+        assert_eq!(top_level_key("$[*]"), None);
+        assert_eq!(top_level_key("$.*"), None);
+    }
+
+    #[test]
+    fn rule_index_skips_rules_for_missing_keys() {
+        let rules = vec![
+            rule_with_path("privileged", "$.HostConfig.Privileged"),
+            rule_with_path("user", "$.Config.User"),
+            rule_with_path("anywhere", "$..Privileged"),
+        ];
+        let index = RuleIndex::build(&rules);
+
+        let value = serde_json::json!({ "HostConfig": { "Privileged": true }, "Config": null });
+        assert_eq!(index.candidates(&value), vec![0, 2]);
+
+        let not_object = serde_json::json!([1, 2]);
+        assert_eq!(index.candidates(&not_object), vec![0, 1, 2]);
+    }
 }
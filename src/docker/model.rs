@@ -1,4 +1,8 @@
-use bollard::secret::ContainerInspectResponse;
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use bollard::secret::{ContainerInspectResponse, Network};
 use serde::{Deserialize, Serialize};
 
 
@@ -9,16 +13,183 @@ pub struct Finding {
     pub risk: RiskLevel,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub line: Option<usize>,
+    /// Columns and end line of the source the finding points at, in files
+    #[serde(default, flatten, skip_serializing_if = "Option::is_none")]
+    pub span: Option<SourceSpan>,
+    /// Human-readable name of the rule that produced the finding
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rule_name: Option<String>,
+    /// How to fix the issue
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remediation: Option<String>,
+    /// Links to documentation or benchmarks (e.g. CIS)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub references: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    /// Compliance controls the rule maps to, keyed by framework (e.g. `nist-800-190`)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub compliance: BTreeMap<String, Vec<String>>,
+    /// What was scanned
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<FindingTarget>,
+    /// First and last sighting, when a findings history is kept
+    #[serde(default, flatten, skip_serializing_if = "Option::is_none")]
+    pub seen: Option<FindingHistory>,
+    /// What the rule tested and the values it found. Rule engines always
+    /// record them; the container and Dockerfile scanners keep them only in
+    /// explain mode (`--explain`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub debug: Vec<MatchedField>,
+    /// Slice of the inspect JSON the finding is about (e.g. the mount object
+    /// or port binding), so it can be verified without Docker access
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context: Option<serde_json::Value>,
+}
+
+impl Finding {
+    /// Creates a finding with no line number, rule metadata, or target.
+    pub fn new(kind: impl Into<String>, description: impl Into<String>, risk: RiskLevel) -> Self {
+        Self {
+            kind: kind.into(),
+            description: description.into(),
+            risk,
+            line: None,
+            span: None,
+            rule_name: None,
+            remediation: None,
+            references: Vec::new(),
+            tags: Vec::new(),
+            category: None,
+            compliance: BTreeMap::new(),
+            target: None,
+            seen: None,
+            debug: Vec::new(),
+            context: None,
+        }
+    }
+
+    /// Order findings are reported in: most severe first, then by rule ID,
+    /// line, and description, so repeated scans produce identical reports.
+    pub fn report_order(&self, other: &Self) -> Ordering {
+        other
+            .risk
+            .cmp(&self.risk)
+            .then_with(|| self.kind.cmp(&other.kind))
+            .then_with(|| self.line.cmp(&other.line))
+            .then_with(|| self.description.cmp(&other.description))
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, PartialOrd, Ord)]
+/// Sorts findings into [`Finding::report_order`].
+pub fn sort_findings(findings: &mut [Finding]) {
+    findings.sort_by(Finding::report_order);
+}
+
+/// A JSONPath (runtime rules) or instruction field (Dockerfile rules) a
+/// rule matched on, with the value it found there.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Hash, Clone)]
+pub struct MatchedField {
+    pub path: String,
+    /// `None` when the rule matched because the field is missing
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+}
+
+impl MatchedField {
+    pub fn new(path: impl Into<String>, value: Option<String>) -> Self {
+        Self {
+            path: path.into(),
+            value,
+        }
+    }
+}
+
+/// Extent of a finding in a scanned file, beyond its start line.
+///
+/// Columns are 1-based character positions; `end_column` is the position
+/// after the last character, as in SARIF regions.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+pub struct SourceSpan {
+    pub column: usize,
+    pub end_line: usize,
+    pub end_column: usize,
+}
+
+/// When a finding was first and last reported, from the findings history.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+pub struct FindingHistory {
+    /// Start of the first scan that reported the finding, RFC 3339
+    pub first_seen: String,
+    /// Start of the latest scan that reported the finding, RFC 3339
+    pub last_seen: String,
+    /// Whether no earlier scan in the history reported the finding
+    pub is_new: bool,
+}
+
+/// The container or file a finding was reported for.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum FindingTarget {
+    /// A running or stopped container
+    Container { id: String, name: String },
+    /// A scanned file, such as a Dockerfile
+    File { path: PathBuf },
+    /// A Docker network
+    Network { id: String, name: String },
+}
+
+impl FindingTarget {
+    /// Describes an inspected container, with the leading `/` stripped from its name.
+    pub fn container(container: &ContainerInspectResponse) -> Self {
+        Self::Container {
+            id: container.id.clone().unwrap_or_default(),
+            name: container
+                .name
+                .as_deref()
+                .unwrap_or_default()
+                .trim_start_matches('/')
+                .to_string(),
+        }
+    }
+
+    /// Describes an inspected network.
+    pub fn network(network: &Network) -> Self {
+        Self::Network {
+            id: network.id.clone().unwrap_or_default(),
+            name: network.name.clone().unwrap_or_default(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum RiskLevel {
     Informative,
     Low,
     Medium,
     High,
+    Critical,
 }
+#[derive(Debug, Clone)]
 pub struct ContainerResult {
     pub container: ContainerInspectResponse,
     pub findings: Vec<Finding>,
+    /// Label of the Docker host the container runs on, in multi-host scans
+    pub host: Option<String>,
+}
+
+/// Findings for one scanned file (a Dockerfile or `.env` file).
+#[derive(Debug, Clone)]
+pub struct FileResult {
+    pub path: PathBuf,
+    pub findings: Vec<Finding>,
+}
+
+/// Findings for one Docker network.
+#[derive(Debug, Clone)]
+pub struct NetworkResult {
+    pub network: Network,
+    pub findings: Vec<Finding>,
 }
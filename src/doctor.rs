@@ -0,0 +1,389 @@
+//! Installation diagnostics (`valeris doctor`).
+//!
+//! Checks what a scan depends on — the Docker socket and daemon version,
+//! the rules directory, the configuration file, and the terminal — and
+//! reports each problem with a hint on how to fix it, for first-run
+//! troubleshooting.
+
+use std::io::IsTerminal;
+use std::path::Path;
+use std::time::Duration;
+
+use console::{style, Emoji};
+use walkdir::WalkDir;
+
+use crate::config::{ConfigFile, RulesConfig, CONFIG_FILE_ENV, RULES_DIR_ENV};
+use crate::detectors::dockerfile::yaml_rules::load_rules_from_dir;
+use crate::detectors::runtime::scanner::connect_docker;
+use crate::detectors::runtime::yaml_rules::YamlRuleEngine;
+use crate::output::metadata::RULES_VERSION_FILE;
+
+/// How long to wait for the Docker daemon to answer
+const DOCKER_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Terminal width below which tables wrap badly
+const MIN_TABLE_WIDTH: u16 = 80;
+
+/// Outcome of one check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckStatus {
+    Ok,
+    /// Works, but something may get in the way
+    Warn,
+    /// Scans depending on this will fail
+    Fail,
+}
+
+/// One diagnostic, with a hint when it did not pass.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Check {
+    pub name: &'static str,
+    pub status: CheckStatus,
+    pub detail: String,
+    /// What to do about a warning or failure
+    pub hint: Option<String>,
+}
+
+impl Check {
+    fn ok(name: &'static str, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Ok,
+            detail: detail.into(),
+            hint: None,
+        }
+    }
+
+    fn warn(name: &'static str, detail: impl Into<String>, hint: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Warn,
+            detail: detail.into(),
+            hint: Some(hint.into()),
+        }
+    }
+
+    fn fail(name: &'static str, detail: impl Into<String>, hint: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: CheckStatus::Fail,
+            detail: detail.into(),
+            hint: Some(hint.into()),
+        }
+    }
+}
+
+/// Every check of a `valeris doctor` run, in the order they ran.
+#[derive(Debug, Clone, Default)]
+pub struct DoctorReport {
+    pub checks: Vec<Check>,
+}
+
+impl DoctorReport {
+    /// Returns `true` if any check failed.
+    pub fn failed(&self) -> bool {
+        self.checks.iter().any(|c| c.status == CheckStatus::Fail)
+    }
+}
+
+/// Runs every check.
+///
+/// # Arguments
+///
+/// * `docker_host` - Docker endpoint, or `None` for the local socket
+/// * `rules` - Rules directory a scan would use
+pub async fn run_checks(docker_host: Option<&str>, rules: &RulesConfig) -> DoctorReport {
+    let mut checks = check_docker(docker_host).await;
+    checks.push(check_rules(rules));
+    checks.push(check_config());
+    checks.push(check_terminal());
+    DoctorReport { checks }
+}
+
+// ────────────────────────────────────────────────────────────────────
+// DOCKER
+// ────────────────────────────────────────────────────────────────────
+
+/// Checks that the daemon answers and reports a usable API version.
+async fn check_docker(docker_host: Option<&str>) -> Vec<Check> {
+    let endpoint = docker_host.unwrap_or("the default Docker socket").to_string();
+    let unreachable = |detail: String| {
+        vec![Check::fail(
+            "Docker socket",
+            detail,
+            "Start the Docker daemon. On Linux, add your user to the `docker` group if permission \
+             is denied; remote daemons are scanned with --hosts. Dockerfile and --input scans work \
+             without a daemon",
+        )]
+    };
+
+    let docker = match connect_docker(docker_host) {
+        Ok(docker) => docker,
+        Err(e) => return unreachable(format!("Cannot connect to {}: {}", endpoint, e)),
+    };
+    match tokio::time::timeout(DOCKER_TIMEOUT, docker.ping()).await {
+        Ok(Ok(_)) => {}
+        Ok(Err(e)) => return unreachable(format!("{} did not answer: {}", endpoint, e)),
+        Err(_) => {
+            return unreachable(format!("{} did not answer within {}s", endpoint, DOCKER_TIMEOUT.as_secs()))
+        }
+    }
+    let mut checks = vec![Check::ok("Docker socket", format!("Connected to {}", endpoint))];
+
+    let version = match tokio::time::timeout(DOCKER_TIMEOUT, docker.version()).await {
+        Ok(Ok(version)) => version,
+        Ok(Err(e)) => {
+            checks.push(Check::fail(
+                "Docker daemon",
+                format!("Failed to read the daemon version: {}", e),
+                "Upgrade Docker: the daemon may not support the API version Valeris requests",
+            ));
+            return checks;
+        }
+        Err(_) => {
+            checks.push(Check::fail(
+                "Docker daemon",
+                "The daemon did not report its version",
+                "Check the daemon logs; scans will time out the same way",
+            ));
+            return checks;
+        }
+    };
+    let detail = format!(
+        "Docker {} (API {}, {}/{})",
+        version.version.as_deref().unwrap_or("unknown"),
+        version.api_version.as_deref().unwrap_or("unknown"),
+        version.os.as_deref().unwrap_or("unknown"),
+        version.arch.as_deref().unwrap_or("unknown"),
+    );
+    let wanted = bollard::API_DEFAULT_VERSION;
+    let older = version
+        .api_version
+        .as_deref()
+        .and_then(parse_api_version)
+        .is_some_and(|api| api < (wanted.major_version, wanted.minor_version));
+    checks.push(if older {
+        Check::warn(
+            "Docker daemon",
+            detail,
+            format!(
+                "The daemon is older than API {}.{}; some inspect fields may be missing. Upgrade Docker if checks report nothing",
+                wanted.major_version, wanted.minor_version
+            ),
+        )
+    } else {
+        Check::ok("Docker daemon", detail)
+    });
+    checks
+}
+
+/// Parses a Docker API version (`1.43`).
+fn parse_api_version(version: &str) -> Option<(usize, usize)> {
+    let (major, minor) = version.split_once('.')?;
+    Some((major.parse().ok()?, minor.parse().ok()?))
+}
+
+// ────────────────────────────────────────────────────────────────────
+// RULES, CONFIGURATION, TERMINAL
+// ────────────────────────────────────────────────────────────────────
+
+/// Checks that the rules directory holds rules that load.
+fn check_rules(rules: &RulesConfig) -> Check {
+    const NAME: &str = "Rules directory";
+    let dir = &rules.base_dir;
+    let has_yaml = WalkDir::new(dir)
+        .into_iter()
+        .filter_map(Result::ok)
+        .any(|e| e.path().extension() == Some("yaml".as_ref()));
+    if !has_yaml {
+        let hint = if rules.auto_download {
+            format!(
+                "Run `valeris scan` once to download the default rule pack, or set {} to a rule bundle",
+                RULES_DIR_ENV
+            )
+        } else {
+            format!("Install a rule pack in {} or set {} to a rule bundle", dir.display(), RULES_DIR_ENV)
+        };
+        return Check::fail(NAME, format!("No rules in {}", dir.display()), hint);
+    }
+
+    let runtime = match YamlRuleEngine::from_dir(dir) {
+        Ok(engine) => engine.rules().len(),
+        Err(e) => return Check::fail(NAME, format!("{:#}", anyhow::Error::from(e)), "Fix or remove the rule file"),
+    };
+    if runtime == 0 {
+        return Check::warn(
+            NAME,
+            format!("No container rules in {}", dir.join("docker").display()),
+            format!("Container scans find nothing without rules; point {} at a rule bundle", RULES_DIR_ENV),
+        );
+    }
+    let dockerfile_dir = rules.dockerfile_dir();
+    let dockerfile = if dockerfile_dir.is_dir() {
+        match load_rules_from_dir(&dockerfile_dir) {
+            Ok(ruleset) => ruleset.rules.len(),
+            Err(e) => return Check::fail(NAME, format!("{:#}", anyhow::Error::from(e)), "Fix or remove the rule file"),
+        }
+    } else {
+        0
+    };
+    let version = rules_version(dir).unwrap_or_else(|| "unversioned".to_string());
+    Check::ok(
+        NAME,
+        format!(
+            "{} ({}): {} runtime and {} Dockerfile rules",
+            dir.display(),
+            version,
+            runtime,
+            dockerfile
+        ),
+    )
+}
+
+/// Version recorded by the rule pack download, if any
+fn rules_version(dir: &Path) -> Option<String> {
+    std::fs::read_to_string(dir.join(RULES_VERSION_FILE))
+        .ok()
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+/// Checks that the configuration file, if any, parses and has valid values.
+fn check_config() -> Check {
+    const NAME: &str = "Configuration file";
+    if let Ok(path) = std::env::var(CONFIG_FILE_ENV) {
+        if !Path::new(&path).exists() {
+            return Check::warn(
+                NAME,
+                format!("{}={} does not exist; defaults are used", CONFIG_FILE_ENV, path),
+                format!("Create the file or unset {}", CONFIG_FILE_ENV),
+            );
+        }
+    }
+    let Some(path) = ConfigFile::locate() else {
+        return Check::ok(NAME, "None found; defaults are used");
+    };
+    let config = match ConfigFile::load(&path) {
+        Ok(config) => config,
+        Err(e) => {
+            // TOML errors quote the offending line below the message
+            let cause = e.root_cause().to_string();
+            return Check::fail(
+                NAME,
+                format!("{}: {}", e, cause.lines().next().unwrap_or_default()),
+                "Fix the TOML syntax: every other command fails until the file parses",
+            )
+        }
+    };
+    match config.validate() {
+        Ok(()) => Check::ok(NAME, format!("{} is valid", path.display())),
+        Err(e) => Check::fail(
+            NAME,
+            format!("{}: {}", path.display(), e),
+            "Fix the value: every scan stops with this error",
+        ),
+    }
+}
+
+/// Reports how output will look in this terminal.
+fn check_terminal() -> Check {
+    const NAME: &str = "Terminal";
+    if !std::io::stdout().is_terminal() {
+        return Check::warn(
+            NAME,
+            "stdout is not a terminal",
+            "`valeris scan` writes JSON when piped; pass --format table for a table",
+        );
+    }
+    let term = console::Term::stdout();
+    let (_, width) = term.size();
+    let colors = if console::colors_enabled() { "colors" } else { "no colors" };
+    let unicode = if term.features().wants_emoji() { "emoji" } else { "no emoji" };
+    let detail = format!("{} columns, {}, {}", width, colors, unicode);
+    if width < MIN_TABLE_WIDTH {
+        return Check::warn(
+            NAME,
+            detail,
+            format!("Tables wrap below {} columns; widen the terminal or use --format json", MIN_TABLE_WIDTH),
+        );
+    }
+    Check::ok(NAME, detail)
+}
+
+/// Prints a doctor report, one line per check with hints under problems.
+pub fn print_doctor_report(report: &DoctorReport) {
+    println!("Valeris doctor");
+    println!("{}", "━".repeat(60));
+    for check in &report.checks {
+        let mark = match check.status {
+            CheckStatus::Ok => style(Emoji("✅", "ok")).green(),
+            CheckStatus::Warn => style(Emoji("⚠️ ", "warn")).yellow(),
+            CheckStatus::Fail => style(Emoji("❌", "FAIL")).red(),
+        };
+        println!("{} {:<20} {}", mark, check.name, check.detail);
+        if let Some(hint) = &check.hint {
+            println!("   → {}", hint);
+        }
+    }
+    let failures = report.checks.iter().filter(|c| c.status == CheckStatus::Fail).count();
+    let warnings = report.checks.iter().filter(|c| c.status == CheckStatus::Warn).count();
+    println!();
+    match (failures, warnings) {
+        (0, 0) => println!("Everything looks good."),
+        (0, w) => println!("{} warning(s); scans should still work.", w),
+        (f, w) => println!("{} problem(s) and {} warning(s) found.", f, w),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn rules_config(dir: &Path) -> RulesConfig {
+        RulesConfig {
+            base_dir: dir.to_path_buf(),
+            auto_download: false,
+        }
+    }
+
+    #[test]
+    fn empty_rules_directory_fails_with_a_hint() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let check = check_rules(&rules_config(dir.path()));
+
+        assert_eq!(check.status, CheckStatus::Fail);
+        assert!(check.hint.unwrap().contains(RULES_DIR_ENV));
+    }
+
+    #[test]
+    fn rules_directory_reports_counts_and_version() {
+        let check = check_rules(&rules_config(Path::new("rules/runtime")));
+
+        assert_eq!(check.status, CheckStatus::Ok, "{}", check.detail);
+        assert!(check.detail.contains("(unversioned)"), "{}", check.detail);
+        assert!(!check.detail.contains(" 0 runtime"), "{}", check.detail);
+
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join(RULES_VERSION_FILE), "v2.0.0\n").unwrap();
+        assert_eq!(rules_version(dir.path()).as_deref(), Some("v2.0.0"));
+    }
+
+    #[test]
+    fn parses_api_versions() {
+        assert_eq!(parse_api_version("1.43"), Some((1, 43)));
+        assert!(parse_api_version("1.9") < parse_api_version("1.43"));
+        assert_eq!(parse_api_version("latest"), None);
+    }
+
+    #[test]
+    fn report_fails_only_on_failed_checks() {
+        let mut report = DoctorReport {
+            checks: vec![Check::ok("a", "fine"), Check::warn("b", "meh", "hint")],
+        };
+        assert!(!report.failed());
+        report.checks.push(Check::fail("c", "broken", "hint"));
+        assert!(report.failed());
+    }
+}
@@ -0,0 +1,283 @@
+//! Typed errors for the Valeris library API.
+//!
+//! Public entry points return [`ValerisError`] so embedders and the CLI can
+//! tell user errors (bad filters, invalid rules or config) apart from
+//! environment errors (Docker unreachable, unreadable files) and pick exit
+//! codes accordingly.
+//!
+//! [`ValerisError::code`] names each kind of failure with a stable,
+//! machine-readable code; [`ErrorReport`] is what `--error-format json`
+//! writes to stderr, so CI wrappers can tell a scanner failure from
+//! findings that failed the gate.
+
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::report::{EXIT_ENVIRONMENT, EXIT_USAGE};
+
+/// Result type for the Valeris library API.
+pub type Result<T, E = ValerisError> = std::result::Result<T, E>;
+
+/// Errors returned by the Valeris library API.
+#[derive(Debug, Error)]
+pub enum ValerisError {
+    /// The Docker daemon could not be reached or returned an error
+    #[error("Failed to connect to Docker daemon or list containers")]
+    DockerConnection(#[source] bollard::errors::Error),
+
+    /// A rules directory or rule file could not be read (or downloaded)
+    #[error("Failed to load rules from {}", .path.display())]
+    RulesLoad {
+        path: PathBuf,
+        #[source]
+        source: anyhow::Error,
+    },
+
+    /// A rule file is not valid YAML or does not match the rule schema
+    #[error("Invalid rule file {}{}", .file.display(), .line.map(|l| format!(" (line {l})")).unwrap_or_default())]
+    RuleParse {
+        file: PathBuf,
+        line: Option<usize>,
+        #[source]
+        source: serde_yml::Error,
+    },
+
+    /// A Dockerfile could not be read
+    #[error("Failed to read Dockerfile {}", .path.display())]
+    DockerfileRead {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    /// A Dockerfile could not be parsed
+    #[error("Error parsing Dockerfile {}: {message}", .path.display())]
+    DockerfileParse { path: PathBuf, message: String },
+
+    /// An input file (`docker inspect` output or a scan report) could not be read
+    #[error("Failed to read input file {}", .path.display())]
+    InputRead {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    /// An input file is not valid `docker inspect` output or scan report
+    #[error("Invalid input file {}: {message}", .path.display())]
+    InputParse { path: PathBuf, message: String },
+
+    /// A report could not be written
+    #[error("Failed to export report")]
+    Export(#[source] anyhow::Error),
+
+    /// A GitHub API request failed
+    #[error("GitHub API request failed")]
+    GitHub(#[source] anyhow::Error),
+
+    /// The findings history store could not be read or written
+    #[error("Failed to access findings history")]
+    History(#[source] anyhow::Error),
+
+    /// A filter argument (`--only`, `--exclude`, `--since`, ...) is invalid
+    #[error("{0}")]
+    InvalidFilter(String),
+
+    /// An `--output` destination is not a valid object storage URL
+    #[error("{0}")]
+    InvalidOutput(String),
+
+    /// The configuration file contains invalid values
+    #[error("Invalid configuration: {0}")]
+    Config(String),
+
+    /// A command needs network access, which `--offline` disables
+    #[error("{0} needs network access, which --offline disables")]
+    Offline(String),
+
+    /// The command line could not be parsed; holds clap's message, usage
+    /// line included
+    #[error("{0}")]
+    Usage(String),
+
+    /// Any other failure
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+impl ValerisError {
+    /// Builds a [`ValerisError::RuleParse`] from a YAML error, keeping its line number.
+    pub fn rule_parse(file: impl Into<PathBuf>, source: serde_yml::Error) -> Self {
+        Self::RuleParse {
+            file: file.into(),
+            line: source.location().map(|l| l.line()),
+            source,
+        }
+    }
+
+    /// Returns `true` if the error was caused by user input (arguments,
+    /// rules, configuration, or the scanned Dockerfile) rather than the
+    /// environment.
+    pub fn is_user_error(&self) -> bool {
+        matches!(
+            self,
+            Self::RuleParse { .. }
+                | Self::DockerfileParse { .. }
+                | Self::InputParse { .. }
+                | Self::InvalidFilter(_)
+                | Self::InvalidOutput(_)
+                | Self::Config(_)
+                | Self::Offline(_)
+                | Self::Usage(_)
+        )
+    }
+
+    /// Stable, machine-readable code of this kind of error.
+    ///
+    /// Codes never change between releases; new kinds of errors get new
+    /// codes.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::DockerConnection(_) => "docker_connection",
+            Self::RulesLoad { .. } => "rules_load",
+            Self::RuleParse { .. } => "rule_parse",
+            Self::DockerfileRead { .. } => "dockerfile_read",
+            Self::DockerfileParse { .. } => "dockerfile_parse",
+            Self::InputRead { .. } => "input_read",
+            Self::InputParse { .. } => "input_parse",
+            Self::Export(_) => "export",
+            Self::GitHub(_) => "github",
+            Self::History(_) => "history",
+            Self::InvalidFilter(_) => "invalid_filter",
+            Self::InvalidOutput(_) => "invalid_output",
+            Self::Config(_) => "config",
+            Self::Offline(_) => "offline",
+            Self::Usage(_) => "usage",
+            Self::Other(_) => "other",
+        }
+    }
+
+    /// File the error is about, if any.
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            Self::RulesLoad { path, .. }
+            | Self::DockerfileRead { path, .. }
+            | Self::DockerfileParse { path, .. }
+            | Self::InputRead { path, .. }
+            | Self::InputParse { path, .. } => Some(path),
+            Self::RuleParse { file, .. } => Some(file),
+            _ => None,
+        }
+    }
+
+    /// Builds the structured form of this error written by
+    /// `--error-format json`.
+    pub fn report(&self) -> ErrorReport {
+        let mut causes = Vec::new();
+        let mut source = std::error::Error::source(self);
+        while let Some(err) = source {
+            causes.push(err.to_string());
+            source = err.source();
+        }
+        ErrorReport {
+            code: self.code(),
+            message: self.to_string(),
+            causes,
+            kind: if self.is_user_error() { "usage" } else { "environment" },
+            exit_code: self.exit_code(),
+            path: self.path().map(|p| p.display().to_string()),
+            line: match self {
+                Self::RuleParse { line, .. } => *line,
+                _ => None,
+            },
+        }
+    }
+
+    /// Process exit code for this error: [`EXIT_USAGE`] for user errors,
+    /// [`EXIT_ENVIRONMENT`] otherwise.
+    pub fn exit_code(&self) -> i32 {
+        if self.is_user_error() {
+            EXIT_USAGE
+        } else {
+            EXIT_ENVIRONMENT
+        }
+    }
+}
+
+/// An operational error as written to stderr by `--error-format json`:
+/// `{"error": {"code": "docker_connection", ...}}` on one line.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct ErrorReport {
+    /// Stable error code (see [`ValerisError::code`])
+    pub code: &'static str,
+    /// Top-level message
+    pub message: String,
+    /// Underlying causes, outermost first
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub causes: Vec<String>,
+    /// `usage` for user errors, `environment` otherwise
+    pub kind: &'static str,
+    /// Exit code the process ends with
+    pub exit_code: i32,
+    /// File the error is about
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// Line of the file, for rule parse errors
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+}
+
+impl ErrorReport {
+    /// Renders the report as the single JSON line written to stderr.
+    pub fn to_json_line(&self) -> String {
+        serde_json::json!({ "error": self }).to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rule_parse_reports_file_and_line() {
+        let source = serde_yml::from_str::<Vec<String>>("- a\n- [b\n").unwrap_err();
+        let err = ValerisError::rule_parse("rules/bad.yaml", source);
+
+        assert!(err.to_string().starts_with("Invalid rule file rules/bad.yaml (line "));
+        assert_eq!(err.exit_code(), EXIT_USAGE);
+    }
+
+    #[test]
+    fn environment_errors_use_environment_exit_code() {
+        let err = ValerisError::Export(anyhow::anyhow!("disk full"));
+        assert!(!err.is_user_error());
+        assert_eq!(err.exit_code(), EXIT_ENVIRONMENT);
+    }
+
+    #[test]
+    fn report_carries_code_causes_and_path() {
+        let err = ValerisError::RulesLoad {
+            path: PathBuf::from("/opt/rules"),
+            source: anyhow::anyhow!("No such file or directory"),
+        };
+        let report = err.report();
+
+        assert_eq!(report.code, "rules_load");
+        assert_eq!(report.kind, "environment");
+        assert_eq!(report.exit_code, EXIT_ENVIRONMENT);
+        assert_eq!(report.causes, vec!["No such file or directory".to_string()]);
+
+        let json: serde_json::Value = serde_json::from_str(&report.to_json_line()).unwrap();
+        assert_eq!(json["error"]["path"], "/opt/rules");
+        assert_eq!(json["error"]["message"], "Failed to load rules from /opt/rules");
+        assert!(json["error"].get("line").is_none());
+    }
+
+    #[test]
+    fn config_errors_are_usage_errors() {
+        let report = ValerisError::Config("bad weight".into()).report();
+        assert_eq!((report.code, report.kind, report.exit_code), ("config", "usage", EXIT_USAGE));
+        assert!(report.causes.is_empty());
+    }
+}
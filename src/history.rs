@@ -0,0 +1,605 @@
+//! Local findings history.
+//!
+//! With `--store <PATH>`, every scan appends one [`ScanRecord`] to a local
+//! history file: when it ran, which targets it covered, and a fingerprint
+//! per finding. Fingerprints identify "the same finding" across scans (rule,
+//! target name, line and description; the name rather than the container ID,
+//! so re-created containers keep their history), which is what diffs, trends
+//! and first-seen dates are built on.
+//!
+//! The store is an append-only JSON Lines file, one scan per line, rather
+//! than a SQLite database, so it needs no database server or native library
+//! and can be inspected with standard tools.
+
+use std::collections::{BTreeSet, HashMap};
+use std::fs::{self, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use anyhow::{anyhow, Context};
+#[cfg(feature = "cli")]
+use comfy_table::{presets::UTF8_FULL, Cell, ContentArrangement, Table};
+use bollard::secret::ContainerInspectResponse;
+use ring::digest;
+use serde::{Deserialize, Serialize};
+
+use crate::docker::model::{ContainerResult, Finding, FindingHistory, RiskLevel};
+use crate::error::{Result, ValerisError};
+use crate::output::summary::{SeverityCounts, TargetKind};
+
+/// Default history file, next to the downloaded rules in the local data directory.
+pub fn default_store_path() -> PathBuf {
+    dirs::data_local_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("valeris")
+        .join("history.jsonl")
+}
+
+/// Stable identifier for a finding across scans: the rule, the target's
+/// name (or Dockerfile path), and the line and description, hashed.
+///
+/// A rule can fire several times on one target (one finding per secret, per
+/// Dockerfile line, ...); the line and description keep those findings
+/// apart. Merged reports deduplicate findings by the same key.
+pub fn fingerprint(rule: &str, target: &str, line: Option<usize>, description: &str) -> String {
+    let line = line.map(|l| l.to_string()).unwrap_or_default();
+    let key = format!("{}\0{}\0{}\0{}", rule, target, line, description);
+    let hash = digest::digest(&digest::SHA256, key.as_bytes());
+    hash.as_ref()[..8].iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Name a container's findings are fingerprinted under: its name, or its ID
+/// if it has none.
+pub(crate) fn container_target(container: &ContainerInspectResponse) -> &str {
+    container
+        .name
+        .as_deref()
+        .or(container.id.as_deref())
+        .unwrap_or_default()
+        .trim_start_matches('/')
+}
+
+/// Scans selected for a history report.
+#[derive(Debug, Clone, Default)]
+pub struct HistoryFilter {
+    /// Keep only targets whose name contains one of these patterns
+    /// (case-insensitive)
+    pub targets: Option<Vec<String>>,
+    /// Keep only scans started at or after this time
+    pub since: Option<SystemTime>,
+}
+
+/// Findings over time for the scans matching a [`HistoryFilter`].
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct HistoryReport {
+    /// One entry per scan, oldest first
+    pub points: Vec<TrendPoint>,
+    /// Findings in the latest scan that the first scan did not have
+    pub new_findings: Vec<FindingRecord>,
+    /// Findings of the first scan that are gone from the latest scan (of
+    /// targets the latest scan covered)
+    pub resolved_findings: Vec<FindingRecord>,
+    /// Rules firing in the latest scan that did not fire in the first scan
+    pub introduced_rules: Vec<String>,
+}
+
+/// Finding counts of one scan.
+#[derive(Debug, Clone, Serialize)]
+pub struct TrendPoint {
+    pub timestamp: String,
+    pub targets: usize,
+    pub findings: SeverityCounts,
+}
+
+/// One scan in the history store.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScanRecord {
+    /// Scan start time, RFC 3339
+    pub timestamp: String,
+    pub target_kind: TargetKind,
+    /// Names of every target scanned, including those without findings
+    pub targets: Vec<String>,
+    pub findings: Vec<FindingRecord>,
+}
+
+/// A finding as recorded in the history store.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FindingRecord {
+    pub fingerprint: String,
+    pub rule: String,
+    /// Container name or Dockerfile path
+    pub target: String,
+    pub severity: RiskLevel,
+}
+
+impl ScanRecord {
+    /// Creates an empty record for a scan started at `started`.
+    pub fn new(target_kind: TargetKind, started: SystemTime) -> Self {
+        Self {
+            timestamp: humantime::format_rfc3339_seconds(started).to_string(),
+            target_kind,
+            targets: Vec::new(),
+            findings: Vec::new(),
+        }
+    }
+
+    /// Adds a scanned container and its findings.
+    pub fn add_container(&mut self, result: &ContainerResult) {
+        self.add_target(container_target(&result.container), &result.findings);
+    }
+
+    /// Adds a scanned Dockerfile and its findings.
+    pub fn add_dockerfile(&mut self, path: &Path, findings: &[Finding]) {
+        self.add_target(&path.display().to_string(), findings);
+    }
+
+    fn add_target(&mut self, target: &str, findings: &[Finding]) {
+        self.targets.push(target.to_string());
+        self.findings.extend(findings.iter().map(|f| FindingRecord {
+            fingerprint: fingerprint(&f.kind, target, f.line, &f.description),
+            rule: f.kind.clone(),
+            target: target.to_string(),
+            severity: f.risk.clone(),
+        }));
+    }
+
+    /// Scan start time, or `None` if the stored timestamp is malformed.
+    pub fn time(&self) -> Option<SystemTime> {
+        humantime::parse_rfc3339_weak(&self.timestamp).ok()
+    }
+
+    /// Restricts the record to targets matching `patterns`.
+    fn retain_targets(&mut self, patterns: &[String]) {
+        let patterns: Vec<String> = patterns.iter().map(|p| p.to_lowercase()).collect();
+        let matches = |target: &str| {
+            let target = target.to_lowercase();
+            patterns.iter().any(|p| target.contains(p.as_str()))
+        };
+        self.targets.retain(|t| matches(t));
+        self.findings.retain(|f| matches(&f.target));
+    }
+
+    fn fingerprints(&self) -> BTreeSet<&str> {
+        self.findings.iter().map(|f| f.fingerprint.as_str()).collect()
+    }
+}
+
+/// Builds a history report from recorded scans.
+///
+/// New and resolved findings compare the first and the latest matching
+/// scan; a finding only counts as resolved if its target was scanned again.
+pub fn history_report(scans: Vec<ScanRecord>, filter: &HistoryFilter) -> HistoryReport {
+    let scans: Vec<ScanRecord> = scans
+        .into_iter()
+        .filter(|scan| match (filter.since, scan.time()) {
+            (Some(since), Some(time)) => time >= since,
+            (Some(_), None) => false,
+            (None, _) => true,
+        })
+        .filter_map(|mut scan| {
+            if let Some(patterns) = &filter.targets {
+                scan.retain_targets(patterns);
+            }
+            (!scan.targets.is_empty()).then_some(scan)
+        })
+        .collect();
+
+    let points = scans
+        .iter()
+        .map(|scan| {
+            let mut findings = SeverityCounts::default();
+            scan.findings.iter().for_each(|f| findings.add_risk(&f.severity));
+            TrendPoint {
+                timestamp: scan.timestamp.clone(),
+                targets: scan.targets.len(),
+                findings,
+            }
+        })
+        .collect();
+
+    let (Some(first), Some(last)) = (scans.first(), scans.last()) else {
+        return HistoryReport::default();
+    };
+    let (before, after) = (first.fingerprints(), last.fingerprints());
+    let rescanned: BTreeSet<&str> = last.targets.iter().map(String::as_str).collect();
+    let first_rules: BTreeSet<&str> = first.findings.iter().map(|f| f.rule.as_str()).collect();
+
+    HistoryReport {
+        points,
+        new_findings: last
+            .findings
+            .iter()
+            .filter(|f| !before.contains(f.fingerprint.as_str()))
+            .cloned()
+            .collect(),
+        resolved_findings: first
+            .findings
+            .iter()
+            .filter(|f| rescanned.contains(f.target.as_str()) && !after.contains(f.fingerprint.as_str()))
+            .cloned()
+            .collect(),
+        introduced_rules: last
+            .findings
+            .iter()
+            .map(|f| f.rule.as_str())
+            .filter(|rule| !first_rules.contains(rule))
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .map(str::to_string)
+            .collect(),
+    }
+}
+
+/// Prints a history report as a trend table followed by the changes
+/// between the first and latest scan.
+#[cfg(feature = "cli")]
+pub fn print_history_report(report: &HistoryReport) {
+    println!("Valeris findings history");
+    println!("{}", "━".repeat(60));
+    if report.points.is_empty() {
+        println!("No recorded scans match.");
+        return;
+    }
+
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_header(vec!["Scan", "Targets", "Findings", "Critical", "High", "Medium", "Low", "Info"]);
+    for point in &report.points {
+        table.add_row(vec![
+            Cell::new(&point.timestamp),
+            Cell::new(point.targets),
+            Cell::new(point.findings.total),
+            Cell::new(point.findings.critical),
+            Cell::new(point.findings.high),
+            Cell::new(point.findings.medium),
+            Cell::new(point.findings.low),
+            Cell::new(point.findings.informative),
+        ]);
+    }
+    println!("{table}");
+
+    let since = &report.points[0].timestamp;
+    println!();
+    println!("New since {}: {}", since, report.new_findings.len());
+    for finding in &report.new_findings {
+        println!("  + {} on {} ({:?})", finding.rule, finding.target, finding.severity);
+    }
+    println!("Resolved since {}: {}", since, report.resolved_findings.len());
+    for finding in &report.resolved_findings {
+        println!("  - {} on {} ({:?})", finding.rule, finding.target, finding.severity);
+    }
+    if !report.introduced_rules.is_empty() {
+        println!("Newly firing rules: {}", report.introduced_rules.join(", "));
+    }
+}
+
+/// When each recorded finding was first seen, for annotating the findings of
+/// a new scan with [`FindingHistory`].
+#[derive(Debug, Clone, Default)]
+pub struct SeenIndex {
+    first_seen: HashMap<String, String>,
+    /// Deprecated IDs each rule replaced, so renames keep their history
+    previous_ids: HashMap<String, Vec<String>>,
+    timestamp: String,
+}
+
+impl SeenIndex {
+    /// Indexes recorded `scans` (oldest first) for a scan started at `started`.
+    pub fn new(scans: &[ScanRecord], started: SystemTime) -> Self {
+        let mut first_seen = HashMap::new();
+        for scan in scans {
+            for finding in &scan.findings {
+                first_seen
+                    .entry(finding.fingerprint.clone())
+                    .or_insert_with(|| scan.timestamp.clone());
+            }
+        }
+        Self {
+            first_seen,
+            previous_ids: HashMap::new(),
+            timestamp: humantime::format_rfc3339_seconds(started).to_string(),
+        }
+    }
+
+    /// Counts findings recorded under a deprecated rule ID as sightings of
+    /// its replacement, per `renames` (old ID to new ID).
+    pub fn with_renames(mut self, renames: &HashMap<String, String>) -> Self {
+        for (old, new) in renames {
+            self.previous_ids.entry(new.clone()).or_default().push(old.clone());
+        }
+        self
+    }
+
+    /// Sets `seen` on each finding of a scanned container.
+    pub fn annotate_container(&self, result: &mut ContainerResult) {
+        let target = container_target(&result.container).to_string();
+        self.annotate(&target, &mut result.findings);
+    }
+
+    /// Sets `seen` on each finding of a scanned Dockerfile.
+    pub fn annotate_dockerfile(&self, path: &Path, findings: &mut [Finding]) {
+        self.annotate(&path.display().to_string(), findings);
+    }
+
+    fn annotate(&self, target: &str, findings: &mut [Finding]) {
+        for finding in findings {
+            let previous = self.previous_ids.get(&finding.kind).into_iter().flatten();
+            let first_seen = std::iter::once(&finding.kind)
+                .chain(previous)
+                .filter_map(|id| self.first_seen.get(&fingerprint(id, target, finding.line, &finding.description)))
+                .min();
+            finding.seen = Some(FindingHistory {
+                first_seen: first_seen.unwrap_or(&self.timestamp).clone(),
+                last_seen: self.timestamp.clone(),
+                is_new: first_seen.is_none(),
+            });
+        }
+    }
+}
+
+/// An append-only findings history file.
+#[derive(Debug, Clone)]
+pub struct HistoryStore {
+    path: PathBuf,
+}
+
+impl HistoryStore {
+    /// Opens the history store at `path`; the file is created on first write.
+    pub fn open(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Path of the history file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Appends a scan to the store.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ValerisError::History`] if the file cannot be written
+    pub fn record(&self, scan: &ScanRecord) -> Result<()> {
+        self.append(scan)
+            .with_context(|| format!("Failed to write history store {}", self.path.display()))
+            .map_err(ValerisError::History)
+    }
+
+    fn append(&self, scan: &ScanRecord) -> anyhow::Result<()> {
+        if let Some(dir) = self.path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)?;
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        writeln!(file, "{}", serde_json::to_string(scan)?)?;
+        Ok(())
+    }
+
+    /// Reads every recorded scan, oldest first. A missing file is an empty history.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ValerisError::History`] if the file cannot be read or a
+    /// line is not a valid scan record
+    pub fn scans(&self) -> Result<Vec<ScanRecord>> {
+        self.read()
+            .with_context(|| format!("Failed to read history store {}", self.path.display()))
+            .map_err(ValerisError::History)
+    }
+
+    /// Indexes the recorded scans for annotating a scan started at `started`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ValerisError::History`] if the store cannot be read
+    pub fn seen_index(&self, started: SystemTime) -> Result<SeenIndex> {
+        Ok(SeenIndex::new(&self.scans()?, started))
+    }
+
+    fn read(&self) -> anyhow::Result<Vec<ScanRecord>> {
+        let file = match fs::File::open(&self.path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut scans = Vec::new();
+        for (index, line) in BufReader::new(file).lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let scan = serde_json::from_str(&line).map_err(|e| anyhow!("line {}: {}", index + 1, e))?;
+            scans.push(scan);
+        }
+        Ok(scans)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bollard::models::ContainerInspectResponse;
+    use std::time::Duration;
+
+    fn container(name: &str, rules: &[&str]) -> ContainerResult {
+        ContainerResult {
+            container: ContainerInspectResponse {
+                id: Some(format!("{name}-id")),
+                name: Some(format!("/{name}")),
+                ..Default::default()
+            },
+            findings: rules
+                .iter()
+                .map(|rule| Finding::new(*rule, "finding", RiskLevel::High))
+                .collect(),
+            host: None,
+        }
+    }
+
+    #[test]
+    fn fingerprint_depends_on_rule_target_line_and_description() {
+        let web = fingerprint("privileged_mode", "web", None, "finding");
+        assert_eq!(web, fingerprint("privileged_mode", "web", None, "finding"));
+        assert_ne!(web, fingerprint("privileged_mode", "db", None, "finding"));
+        assert_ne!(web, fingerprint("pids_limit", "web", None, "finding"));
+        assert_ne!(web, fingerprint("privileged_mode", "web", Some(3), "finding"));
+        assert_ne!(web, fingerprint("privileged_mode", "web", None, "other"));
+        assert_eq!(web.len(), 16);
+    }
+
+    #[test]
+    fn one_rule_firing_twice_on_a_target_is_two_findings() {
+        let mut web = container("web", &[]);
+        web.findings = vec![
+            Finding::new("secrets_in_env", "DB_PASSWORD=****", RiskLevel::High),
+            Finding::new("secrets_in_env", "JWT_SECRET=****", RiskLevel::High),
+        ];
+        let first = scan_at(0, &[web.clone()]);
+        web.findings.pop();
+        let second = scan_at(60, &[web.clone()]);
+
+        assert_eq!(first.fingerprints().len(), 2);
+        let report = history_report(vec![first.clone(), second], &HistoryFilter::default());
+        assert_eq!(report.resolved_findings.len(), 1);
+        assert_eq!(report.resolved_findings[0].fingerprint, first.findings[1].fingerprint);
+
+        web.findings.push(Finding::new("secrets_in_env", "API_KEY=****", RiskLevel::High));
+        SeenIndex::new(&[first], SystemTime::UNIX_EPOCH + Duration::from_secs(120)).annotate_container(&mut web);
+        let is_new: Vec<bool> = web.findings.iter().map(|f| f.seen.as_ref().unwrap().is_new).collect();
+        assert_eq!(is_new, [false, true]);
+    }
+
+    #[test]
+    fn records_targets_without_findings() {
+        let mut scan = ScanRecord::new(TargetKind::Containers, SystemTime::UNIX_EPOCH);
+        scan.add_container(&container("web", &["privileged_mode"]));
+        scan.add_container(&container("db", &[]));
+
+        assert_eq!(scan.timestamp, "1970-01-01T00:00:00Z");
+        assert_eq!(scan.targets, ["web", "db"]);
+        assert_eq!(scan.findings.len(), 1);
+        assert_eq!(scan.findings[0].target, "web");
+    }
+
+    #[test]
+    fn store_round_trips_scans_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = HistoryStore::open(dir.path().join("nested/history.jsonl"));
+        assert!(store.scans().unwrap().is_empty());
+
+        let mut first = ScanRecord::new(TargetKind::Containers, SystemTime::UNIX_EPOCH);
+        first.add_container(&container("web", &["privileged_mode"]));
+        let mut second = ScanRecord::new(
+            TargetKind::Dockerfiles,
+            SystemTime::UNIX_EPOCH + Duration::from_secs(60),
+        );
+        second.add_dockerfile(Path::new("Dockerfile"), &[]);
+        store.record(&first).unwrap();
+        store.record(&second).unwrap();
+
+        let scans = store.scans().unwrap();
+        assert_eq!(scans, [first, second]);
+        assert_eq!(scans[1].time(), Some(SystemTime::UNIX_EPOCH + Duration::from_secs(60)));
+    }
+
+    fn scan_at(secs: u64, containers: &[ContainerResult]) -> ScanRecord {
+        let mut scan = ScanRecord::new(
+            TargetKind::Containers,
+            SystemTime::UNIX_EPOCH + Duration::from_secs(secs),
+        );
+        containers.iter().for_each(|c| scan.add_container(c));
+        scan
+    }
+
+    #[test]
+    fn history_report_compares_first_and_latest_scan() {
+        let scans = vec![
+            scan_at(0, &[container("web", &["privileged_mode", "pids_limit"]), container("db", &["root_user"])]),
+            scan_at(60, &[container("web", &["pids_limit", "exposed_ports"])]),
+        ];
+
+        let report = history_report(scans, &HistoryFilter::default());
+
+        assert_eq!(report.points.len(), 2);
+        assert_eq!(report.points[0].findings.total, 3);
+        assert_eq!(report.points[1].targets, 1);
+        let rules = |findings: &[FindingRecord]| findings.iter().map(|f| f.rule.clone()).collect::<Vec<_>>();
+        assert_eq!(rules(&report.new_findings), ["exposed_ports"]);
+        // db was not rescanned, so its finding is not resolved
+        assert_eq!(rules(&report.resolved_findings), ["privileged_mode"]);
+        assert_eq!(report.introduced_rules, ["exposed_ports"]);
+    }
+
+    #[test]
+    fn history_report_filters_targets_and_time() {
+        let scans = vec![
+            scan_at(0, &[container("web", &["privileged_mode"])]),
+            scan_at(60, &[container("web", &[]), container("db", &["root_user"])]),
+            scan_at(120, &[container("db", &[])]),
+        ];
+        let filter = HistoryFilter {
+            targets: Some(vec!["WEB".to_string()]),
+            since: None,
+        };
+
+        let report = history_report(scans.clone(), &filter);
+        assert_eq!(report.points.len(), 2);
+        assert_eq!(report.resolved_findings.len(), 1);
+
+        let filter = HistoryFilter {
+            targets: None,
+            since: Some(SystemTime::UNIX_EPOCH + Duration::from_secs(60)),
+        };
+        let report = history_report(scans, &filter);
+        assert_eq!(report.points.len(), 2);
+        assert_eq!(report.points[0].timestamp, "1970-01-01T00:01:00Z");
+    }
+
+    #[test]
+    fn corrupt_lines_are_reported() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("history.jsonl");
+        fs::write(&path, "{}\n").unwrap();
+
+        let err = HistoryStore::open(&path).scans().unwrap_err();
+        assert!(matches!(err, ValerisError::History(_)));
+        assert!(format!("{:#}", anyhow::Error::from(err)).contains("line 1"));
+    }
+
+    #[test]
+    fn seen_index_keeps_first_sighting_and_flags_new_findings() {
+        let scans = vec![
+            scan_at(0, &[container("web", &["privileged_mode"])]),
+            scan_at(60, &[container("web", &["privileged_mode"])]),
+        ];
+        let index = SeenIndex::new(&scans, SystemTime::UNIX_EPOCH + Duration::from_secs(120));
+        let mut result = container("web", &["privileged_mode", "pids_limit"]);
+
+        index.annotate_container(&mut result);
+
+        let seen: Vec<_> = result.findings.iter().map(|f| f.seen.clone().unwrap()).collect();
+        assert_eq!(seen[0].first_seen, "1970-01-01T00:00:00Z");
+        assert_eq!(seen[0].last_seen, "1970-01-01T00:02:00Z");
+        assert!(!seen[0].is_new);
+        assert_eq!(seen[1].first_seen, "1970-01-01T00:02:00Z");
+        assert!(seen[1].is_new);
+    }
+
+    #[test]
+    fn seen_index_follows_renamed_rules() {
+        let scans = vec![scan_at(0, &[container("web", &["old_privileged"])])];
+        let renames = HashMap::from([("old_privileged".to_string(), "privileged_mode".to_string())]);
+        let index = SeenIndex::new(&scans, SystemTime::UNIX_EPOCH + Duration::from_secs(60))
+            .with_renames(&renames);
+        let mut result = container("web", &["privileged_mode"]);
+
+        index.annotate_container(&mut result);
+
+        let seen = result.findings[0].seen.clone().unwrap();
+        assert_eq!(seen.first_seen, "1970-01-01T00:00:00Z");
+        assert!(!seen.is_new);
+    }
+}
@@ -0,0 +1,346 @@
+//! Git hook integration.
+//!
+//! Backs `valeris hook install` and `valeris hook run`: installs a
+//! pre-commit or pre-push hook, and scans the Dockerfiles a commit stages
+//! (or a push contains) so misconfigurations are caught before they reach
+//! CI. The `.pre-commit-hooks.yaml` at the repository root exposes the same
+//! scan to the pre-commit framework.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Instant;
+
+use anyhow::{anyhow, Context};
+use clap::ValueEnum;
+
+use crate::bench::is_dockerfile;
+use crate::cli::{HookType, SeverityLevel};
+use crate::config::{ConfigFile, PathOverrides, RulesConfig, SeverityMap};
+use crate::detectors::dockerfile::fs::StdFileSystem;
+use crate::detectors::dockerfile::scanner::scan_dockerfile_content;
+use crate::detectors::dockerfile::yaml_rules::{load_rules_from_dir, localize};
+use crate::docker::model::{Finding, RiskLevel};
+use crate::error::{Result, ValerisError};
+use crate::i18n::MessageCatalog;
+use crate::output::printer::{print_scan_report, print_status_line, ScanContext};
+use crate::output::severity::SeverityScheme;
+use crate::output::summary::{ScanSummary, TargetKind};
+use crate::report::{ScanFindings, ScanReport};
+
+/// First comment line of every hook Valeris writes, used to recognize them
+pub const HOOK_MARKER: &str = "# Installed by valeris hook install";
+
+/// Repository-local configuration file read by `valeris hook run`
+pub const REPO_CONFIG_FILE: &str = ".valeris.toml";
+
+/// Threshold used when neither the command line nor the config sets one
+const DEFAULT_FAIL_ON: SeverityLevel = SeverityLevel::High;
+
+// ─────────────────────────────────────────────────────────────────
+// Installation
+// ─────────────────────────────────────────────────────────────────
+
+/// Renders the hook script for `hook_type`.
+pub fn hook_script(hook_type: &HookType, fail_on: Option<&SeverityLevel>) -> String {
+    let mut command = String::from("valeris hook run");
+    if *hook_type == HookType::PrePush {
+        command.push_str(" --all");
+    }
+    if let Some(level) = fail_on.and_then(|l| l.to_possible_value()) {
+        command.push_str(&format!(" --fail-on {}", level.get_name()));
+    }
+
+    let bypass = match hook_type {
+        HookType::PreCommit => "git commit --no-verify",
+        HookType::PrePush => "git push --no-verify",
+    };
+
+    format!(
+        "#!/bin/sh\n{}\n# Scans Dockerfiles with Valeris; bypass with `{}`.\nexec {}\n",
+        HOOK_MARKER, bypass, command
+    )
+}
+
+/// Installs a git hook in the current repository.
+///
+/// # Arguments
+///
+/// * `hook_type` - Hook to install
+/// * `fail_on` - Threshold baked into the hook; `None` defers to the config
+/// * `force` - Replace an existing hook that Valeris did not write
+///
+/// # Returns
+///
+/// Path of the installed hook
+///
+/// # Errors
+///
+/// * [`ValerisError::Other`] if a foreign hook exists and `force` is not
+///   set, git fails, or the hook cannot be written
+pub fn install(hook_type: &HookType, fail_on: Option<&SeverityLevel>, force: bool) -> Result<PathBuf> {
+    let path = PathBuf::from(git(&["rev-parse", "--git-path", &format!("hooks/{}", hook_type.file_name())])?);
+
+    if let Ok(existing) = fs::read_to_string(&path) {
+        if !existing.contains(HOOK_MARKER) && !force {
+            return Err(anyhow!("{} already exists; pass --force to replace it", path.display()).into());
+        }
+    }
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create hooks directory {}", dir.display()))?;
+    }
+    fs::write(&path, hook_script(hook_type, fail_on))
+        .with_context(|| format!("Failed to write hook {}", path.display()))?;
+    make_executable(&path)?;
+
+    Ok(path)
+}
+
+#[cfg(unix)]
+fn make_executable(path: &Path) -> anyhow::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    fs::set_permissions(path, fs::Permissions::from_mode(0o755))
+        .with_context(|| format!("Failed to make {} executable", path.display()))
+}
+
+#[cfg(not(unix))]
+fn make_executable(_path: &Path) -> anyhow::Result<()> {
+    Ok(())
+}
+
+// ─────────────────────────────────────────────────────────────────
+// Scanning
+// ─────────────────────────────────────────────────────────────────
+
+/// Where a hook scan reads Dockerfiles from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HookSource {
+    /// Staged content of Dockerfiles in the index
+    Staged,
+    /// Working tree content of every tracked Dockerfile
+    Tracked,
+    /// Working tree content of the given files
+    Files(Vec<PathBuf>),
+}
+
+/// Scans Dockerfiles for a git hook.
+///
+/// Settings come from `.valeris.toml` at the repository root when present,
+/// otherwise from the user configuration: `scan.fail_on` (unless `fail_on`
+/// is given, defaulting to high), `output.severity_map`, `[override]`
+/// tables (matched against paths relative to the repository root),
+/// `output.lang`, `rules.directory`, and `rules.auto_download`.
+///
+/// # Arguments
+///
+/// * `source` - Which Dockerfiles to scan
+/// * `fail_on` - Threshold overriding the configuration
+/// * `rules` - Dockerfile rules directory overriding the configuration
+/// * `offline` - Never download the rule pack, even if it is missing
+///
+/// # Returns
+///
+/// The combined report; its exit code is non-zero when the threshold was met
+///
+/// # Errors
+///
+/// * [`ValerisError::Config`] if the configuration is invalid
+/// * [`ValerisError::DockerfileRead`] / [`ValerisError::DockerfileParse`] if a
+///   Dockerfile cannot be read or parsed
+/// * [`ValerisError::Other`] if git fails
+pub async fn run(
+    source: HookSource,
+    fail_on: Option<SeverityLevel>,
+    rules: Option<PathBuf>,
+    offline: bool,
+) -> Result<ScanReport> {
+    let started = Instant::now();
+    let root = PathBuf::from(git(&["rev-parse", "--show-toplevel"])?);
+    let config = load_repo_config(&root)?;
+
+    let fail_on = match fail_on {
+        Some(level) => level,
+        None => config_fail_on(config.as_ref())?.unwrap_or(DEFAULT_FAIL_ON),
+    };
+    let severity_map = match &config {
+        Some(cfg) => cfg.severity_map()?,
+        None => SeverityMap::default(),
+    };
+    let path_overrides = match &config {
+        Some(cfg) => cfg.path_overrides()?,
+        None => PathOverrides::default(),
+    };
+    let severity_scheme = match &config {
+        Some(cfg) => cfg.severity_scheme()?,
+        None => SeverityScheme::default(),
+    };
+
+    let (staged, files) = match source {
+        HookSource::Staged => (
+            true,
+            git_paths(&root, &["diff", "--cached", "--name-only", "--diff-filter=ACMR", "-z"])?,
+        ),
+        HookSource::Tracked => (false, git_paths(&root, &["ls-files", "-z"])?),
+        HookSource::Files(files) => (false, files),
+    };
+    let dockerfiles: Vec<PathBuf> = files.into_iter().filter(|f| is_dockerfile(f)).collect();
+    if dockerfiles.is_empty() {
+        return Ok(ScanReport::default());
+    }
+
+    let rules_dir = match rules {
+        Some(dir) => dir,
+        None => rules_dir(config.as_ref(), offline).await?,
+    };
+    let mut ruleset = load_rules_from_dir(&rules_dir)?;
+    ruleset.rules.retain(|rule| !rule.deprecated());
+    if let Some(lang) = config.as_ref().and_then(|c| c.output.as_ref()).and_then(|o| o.lang.as_deref()) {
+        localize(&mut ruleset.rules, &MessageCatalog::load(&rules_dir, lang)?);
+    }
+
+    let threshold = risk(&fail_on);
+    let mut all_findings: Vec<Finding> = Vec::new();
+    for path in &dockerfiles {
+        let content = if staged {
+            git(&["-C", &root.to_string_lossy(), "show", &format!(":{}", path.display())])?
+        } else {
+            let full = root.join(path);
+            fs::read_to_string(&full).map_err(|source| ValerisError::DockerfileRead {
+                path: full.clone(),
+                source,
+            })?
+        };
+
+        let mut findings =
+            scan_dockerfile_content(&content, &root.join(path), &ruleset.rules, &StdFileSystem).await?;
+        path_overrides.apply(path, &severity_map, &mut findings);
+
+        if !findings.is_empty() {
+            print_scan_report(ScanContext::Dockerfile(path), &findings, severity_scheme);
+        }
+        all_findings.extend(findings);
+    }
+
+    let failed = all_findings.iter().any(|f| f.risk >= threshold);
+    let summary = ScanSummary::new(
+        TargetKind::Dockerfiles,
+        dockerfiles.len(),
+        ruleset.rules.len(),
+        &all_findings,
+        started.elapsed(),
+        failed,
+    );
+    print_status_line(&summary);
+
+    Ok(ScanReport::from_scan(summary, ScanFindings::None))
+}
+
+/// Loads `.valeris.toml` from the repository root, falling back to the
+/// user configuration.
+fn load_repo_config(root: &Path) -> Result<Option<ConfigFile>> {
+    let local = root.join(REPO_CONFIG_FILE);
+    if local.exists() {
+        return ConfigFile::load(&local)
+            .map(Some)
+            .map_err(|e| ValerisError::Config(format!("{:#}", e)));
+    }
+    Ok(ConfigFile::load_default().ok().flatten())
+}
+
+fn config_fail_on(config: Option<&ConfigFile>) -> Result<Option<SeverityLevel>> {
+    let Some(value) = config.and_then(|c| c.scan.as_ref()).and_then(|s| s.fail_on.as_deref()) else {
+        return Ok(None);
+    };
+    SeverityLevel::from_str(value, true)
+        .map(Some)
+        .map_err(|_| ValerisError::Config(format!("Unknown scan.fail_on severity '{}'", value)))
+}
+
+/// Dockerfile rules under the configured (or installed) rule bundle.
+async fn rules_dir(config: Option<&ConfigFile>, offline: bool) -> Result<PathBuf> {
+    if let Some(dir) = config.and_then(|c| c.rules.as_ref()).and_then(|r| r.directory.clone()) {
+        return Ok(dir.join("dockerfile"));
+    }
+
+    let mut rules = config.map(ConfigFile::rules_config).unwrap_or_default();
+    rules.auto_download &= !offline;
+    let base_dir = rules.base_dir.clone();
+    tokio::task::spawn_blocking(move || crate::rules::ensure_rules(&rules))
+        .await
+        .context("Failed to spawn rules download task")?
+        .map_err(|source| ValerisError::RulesLoad {
+            path: base_dir,
+            source,
+        })?;
+    Ok(RulesConfig::default().dockerfile_dir())
+}
+
+fn risk(level: &SeverityLevel) -> RiskLevel {
+    match level {
+        SeverityLevel::Informative => RiskLevel::Informative,
+        SeverityLevel::Low => RiskLevel::Low,
+        SeverityLevel::Medium => RiskLevel::Medium,
+        SeverityLevel::High => RiskLevel::High,
+        SeverityLevel::Critical => RiskLevel::Critical,
+    }
+}
+
+/// Runs git and returns its trimmed stdout.
+fn git(args: &[&str]) -> anyhow::Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .output()
+        .context("Failed to run git")?;
+    if !output.status.success() {
+        return Err(anyhow!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim_end_matches('\n').to_string())
+}
+
+/// Runs a NUL-separated git listing command in `root`.
+fn git_paths(root: &Path, args: &[&str]) -> anyhow::Result<Vec<PathBuf>> {
+    let root = root.to_string_lossy();
+    let mut full = vec!["-C", root.as_ref()];
+    full.extend_from_slice(args);
+    Ok(git(&full)?
+        .split('\0')
+        .filter(|p| !p.is_empty())
+        .map(PathBuf::from)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pre_commit_script_scans_staged_files() {
+        let script = hook_script(&HookType::PreCommit, None);
+        assert!(script.starts_with("#!/bin/sh\n"));
+        assert!(script.contains(HOOK_MARKER));
+        assert!(script.ends_with("exec valeris hook run\n"));
+    }
+
+    #[test]
+    fn pre_push_script_scans_tracked_files_with_threshold() {
+        let script = hook_script(&HookType::PrePush, Some(&SeverityLevel::Medium));
+        assert!(script.ends_with("exec valeris hook run --all --fail-on medium\n"));
+        assert!(script.contains("git push --no-verify"));
+    }
+
+    #[test]
+    fn config_fail_on_parses_case_insensitively() {
+        let config: ConfigFile = toml::from_str("[scan]\nfail_on = \"Medium\"\n").unwrap();
+        assert_eq!(config_fail_on(Some(&config)).unwrap(), Some(SeverityLevel::Medium));
+
+        let config: ConfigFile = toml::from_str("[scan]\nfail_on = \"severe\"\n").unwrap();
+        assert!(matches!(config_fail_on(Some(&config)), Err(ValerisError::Config(_))));
+    }
+}
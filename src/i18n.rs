@@ -0,0 +1,112 @@
+//! Localized rule messages.
+//!
+//! A rule bundle can ship message catalogs under `i18n/` in each rules
+//! directory, one YAML file per language (`i18n/es.yaml`), keyed by rule ID:
+//!
+//! ```yaml
+//! privileged_mode:
+//!   name: "Modo privilegiado"
+//!   message: "El contenedor {{container.name}} se ejecuta en modo privilegiado"
+//!   remediation: "Elimine --privileged y añada solo las capacidades necesarias"
+//! ```
+//!
+//! `--lang <LANG>` (or `[output] lang`) replaces each rule's name, message,
+//! and remediation with the catalog's before scanning, so message templates
+//! still apply. Rule IDs never change; rules missing from the catalog keep
+//! their English text.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::error::{Result, ValerisError};
+
+/// Directory of the message catalogs inside a rules directory
+pub const CATALOG_DIR: &str = "i18n";
+
+/// Translated text of one rule. Fields left out keep the rule's own text.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct RuleText {
+    pub name: Option<String>,
+    pub message: Option<String>,
+    pub remediation: Option<String>,
+}
+
+/// Translated rule texts for one language, keyed by rule ID.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(transparent)]
+pub struct MessageCatalog {
+    rules: HashMap<String, RuleText>,
+}
+
+impl MessageCatalog {
+    /// Loads the `lang` catalog of a rules directory.
+    ///
+    /// A missing catalog logs a warning and yields an empty one, so every
+    /// rule keeps its English text.
+    ///
+    /// # Errors
+    ///
+    /// * [`ValerisError::Config`] if `lang` is not a plain language tag (e.g. `es`, `pt-BR`)
+    /// * [`ValerisError::RulesLoad`] if the catalog cannot be read
+    /// * [`ValerisError::RuleParse`] if the catalog is not valid YAML
+    pub fn load(rules_dir: &Path, lang: &str) -> Result<Self> {
+        let valid = !lang.is_empty()
+            && lang.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !valid {
+            return Err(ValerisError::Config(format!("Invalid language '{}'", lang)));
+        }
+
+        let path = rules_dir.join(CATALOG_DIR).join(format!("{}.yaml", lang));
+        if !path.exists() {
+            tracing::warn!("No '{}' message catalog in {}; using rule defaults", lang, rules_dir.display());
+            return Ok(Self::default());
+        }
+        let raw = fs::read_to_string(&path).map_err(|source| ValerisError::RulesLoad {
+            path: path.clone(),
+            source: source.into(),
+        })?;
+        serde_yml::from_str(&raw).map_err(|e| ValerisError::rule_parse(&path, e))
+    }
+
+    /// Returns the translated text of rule `id`, if the catalog has one.
+    pub fn get(&self, id: &str) -> Option<&RuleText> {
+        self.rules.get(id)
+    }
+
+    /// Returns whether the catalog translates no rules.
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn loads_catalog_by_language() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir(dir.path().join(CATALOG_DIR)).unwrap();
+        fs::write(
+            dir.path().join(CATALOG_DIR).join("es.yaml"),
+            "privileged_mode:\n  message: \"Modo privilegiado\"\n",
+        )
+        .unwrap();
+
+        let catalog = MessageCatalog::load(dir.path(), "es").unwrap();
+        let text = catalog.get("privileged_mode").unwrap();
+        assert_eq!(text.message.as_deref(), Some("Modo privilegiado"));
+        assert!(text.remediation.is_none());
+        assert!(MessageCatalog::load(dir.path(), "fr").unwrap().is_empty());
+    }
+
+    #[test]
+    fn rejects_path_like_languages() {
+        let dir = tempfile::tempdir().unwrap();
+        let err = MessageCatalog::load(dir.path(), "../es").unwrap_err();
+        assert!(matches!(err, ValerisError::Config(_)));
+    }
+}
@@ -11,10 +11,23 @@ async fn main() -> anyhow::Result<()> {
         .with_writer(std::io::stderr)
         .init();
 
-    if let Err(err) = valeris::run_with_args(std::env::args()).await {
-        eprintln!("Error: {:#}", err);
-        std::process::exit(1);
-    }
+    let args: Vec<String> = std::env::args().collect();
+    let error_format = valeris::cli::ErrorFormat::from_args(&args);
 
-    Ok(())
+    match valeris::run(args).await {
+        Ok(report) if report.failed() => std::process::exit(report.exit_code),
+        Ok(_) => Ok(()),
+        Err(err) => {
+            let exit_code = err.exit_code();
+            match error_format {
+                valeris::cli::ErrorFormat::Json => eprintln!("{}", err.report().to_json_line()),
+                // Argument errors read as clap prints them, usage line included
+                valeris::cli::ErrorFormat::Text if matches!(err, valeris::error::ValerisError::Usage(_)) => {
+                    eprintln!("error: {}", err)
+                }
+                valeris::cli::ErrorFormat::Text => eprintln!("Error: {:#}", anyhow::Error::from(err)),
+            }
+            std::process::exit(exit_code);
+        }
+    }
 }
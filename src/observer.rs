@@ -0,0 +1,72 @@
+//! Progress and event callbacks for embedders.
+//!
+//! A [`ScanObserver`] attached to [`RuntimeScanOptions`] or
+//! [`DockerfileScanOptions`] is notified as a scan progresses, so GUI
+//! wrappers and long-running services can stream progress without parsing
+//! stdout.
+//!
+//! [`RuntimeScanOptions`]: crate::detectors::runtime::scanner::RuntimeScanOptions
+//! [`DockerfileScanOptions`]: crate::detectors::dockerfile::scanner::DockerfileScanOptions
+
+use std::fmt;
+use std::path::Path;
+
+use bollard::models::ContainerInspectResponse;
+
+use crate::docker::model::Finding;
+use crate::output::summary::ScanSummary;
+
+/// What a finding was reported for.
+#[derive(Debug, Clone, Copy)]
+pub enum ScanTarget<'a> {
+    /// A container inspected by the runtime scanner
+    Container(&'a ContainerInspectResponse),
+    /// A Dockerfile scanned by the Dockerfile scanner
+    Dockerfile(&'a Path),
+}
+
+/// Receives scan events. Every method has an empty default implementation,
+/// so observers only implement the events they care about.
+///
+/// The runtime scanner evaluates containers in parallel, so callbacks may be
+/// invoked concurrently from several threads and in any container order.
+///
+/// # Example
+///
+/// ```
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use valeris::docker::model::Finding;
+/// use valeris::observer::{ScanObserver, ScanTarget};
+///
+/// #[derive(Default)]
+/// struct FindingCounter(AtomicUsize);
+///
+/// impl ScanObserver for FindingCounter {
+///     fn on_finding(&self, _target: ScanTarget<'_>, _finding: &Finding) {
+///         self.0.fetch_add(1, Ordering::Relaxed);
+///     }
+/// }
+/// ```
+pub trait ScanObserver: Send + Sync {
+    /// Called before rules are evaluated against a container.
+    fn on_container_start(&self, _container: &ContainerInspectResponse) {}
+
+    /// Called for every finding.
+    ///
+    /// Runtime findings are reported as produced by the rules, before the
+    /// CLI's severity remapping and filtering; Dockerfile findings are
+    /// reported after the scan options' severity map and filters are applied.
+    fn on_finding(&self, _target: ScanTarget<'_>, _finding: &Finding) {}
+
+    /// Called once when the scan has finished.
+    ///
+    /// Runtime scan summaries count the rules' raw findings and never have
+    /// `fail_on_triggered` set, since fail-on is evaluated by the caller.
+    fn on_complete(&self, _summary: &ScanSummary) {}
+}
+
+impl fmt::Debug for dyn ScanObserver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ScanObserver")
+    }
+}
@@ -0,0 +1,265 @@
+//! Report anonymization (`--anonymize`).
+//!
+//! Replaces container names and IDs, image names, exported container
+//! labels, host labels, and file paths with salted SHA-256 pseudonyms before results are printed or
+//! exported, so reports can be shared without exposing internal naming.
+//! The salt is random per [`Anonymizer`], so a value always maps to the same
+//! pseudonym within one report but cannot be matched across reports.
+//!
+//! Finding descriptions, matched values, and context slices are rewritten
+//! too: every value already anonymized is replaced wherever it appears.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use ring::digest;
+use ring::rand::{SecureRandom, SystemRandom};
+
+use crate::docker::model::{ContainerResult, Finding, FindingTarget};
+use crate::output::exporters::EXPORTED_LABELS;
+use crate::output::metadata::ScanMetadata;
+
+/// Length of the hash in name-like pseudonyms (`container-1a2b3c4d`)
+const SHORT_HASH: usize = 8;
+
+/// Length of a short container ID, as printed by `docker ps`
+const SHORT_ID: usize = 12;
+
+/// Consistent pseudonyms for the sensitive values of one report.
+#[derive(Debug, Clone)]
+pub struct Anonymizer {
+    salt: [u8; 16],
+    /// Original value to pseudonym, for rewriting descriptions
+    replaced: HashMap<String, String>,
+}
+
+impl Default for Anonymizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Anonymizer {
+    /// Creates an anonymizer with a random salt.
+    pub fn new() -> Self {
+        let mut salt = [0u8; 16];
+        if SystemRandom::new().fill(&mut salt).is_err() {
+            let nanos = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_nanos();
+            salt = nanos.to_le_bytes();
+        }
+        Self {
+            salt,
+            replaced: HashMap::new(),
+        }
+    }
+
+    /// Anonymizes a scanned container (ID, name, image, exported labels,
+    /// host label, and mount sources) and its findings.
+    pub fn container(&mut self, result: &mut ContainerResult) {
+        let container = &mut result.container;
+        if let Some(id) = &mut container.id {
+            *id = self.id(id);
+        }
+        if let Some(name) = &mut container.name {
+            *name = format!("/{}", self.name("container", name.trim_start_matches('/')));
+        }
+        if let Some(image) = container.config.as_mut().and_then(|c| c.image.as_mut()) {
+            *image = self.name("image", image);
+        }
+        let labels = container.config.as_mut().and_then(|c| c.labels.as_mut());
+        for (key, value) in labels.into_iter().flatten() {
+            if EXPORTED_LABELS.contains(&key.as_str()) {
+                *value = self.name("label", value);
+            }
+        }
+        if let Some(image) = &mut container.image {
+            *image = match image.strip_prefix("sha256:") {
+                Some(digest) => format!("sha256:{}", self.id(digest)),
+                None => self.name("image", image),
+            };
+        }
+        for mount in container.mounts.iter_mut().flatten() {
+            if let Some(source) = &mut mount.source {
+                *source = self.path(Path::new(source)).display().to_string();
+            }
+        }
+        if let Some(host) = &mut result.host {
+            *host = self.name("host", host);
+        }
+        self.findings(&mut result.findings);
+    }
+
+    /// Anonymizes the host name and rule bundle path of a report's metadata.
+    pub fn metadata(&mut self, metadata: &mut ScanMetadata) {
+        if let Some(host) = &mut metadata.host {
+            *host = self.name("host", host);
+        }
+        if let Some(rules) = &mut metadata.rules {
+            rules.path = self.path(Path::new(&rules.path)).display().to_string();
+        }
+    }
+
+    /// Returns the pseudonym of a file path (`path-1a2b3c4d`).
+    pub fn path(&mut self, path: &Path) -> PathBuf {
+        PathBuf::from(self.name("path", &path.display().to_string()))
+    }
+
+    /// Anonymizes finding targets and every known value in descriptions.
+    pub fn findings(&mut self, findings: &mut [Finding]) {
+        for finding in findings.iter_mut() {
+            match &mut finding.target {
+                Some(FindingTarget::Container { id, name }) => {
+                    *id = self.id(id);
+                    *name = self.name("container", name);
+                }
+                Some(FindingTarget::File { path }) => *path = self.path(path),
+                Some(FindingTarget::Network { .. }) | None => {}
+            }
+        }
+
+        // Longest first, so a name is not partly replaced by a value it contains
+        let mut known: Vec<(&String, &String)> = self.replaced.iter().collect();
+        known.sort_by(|a, b| b.0.len().cmp(&a.0.len()).then_with(|| a.0.cmp(b.0)));
+        for finding in findings {
+            let values = finding.debug.iter_mut().filter_map(|field| field.value.as_mut());
+            for text in std::iter::once(&mut finding.description).chain(values) {
+                replace_known(text, &known);
+            }
+            if let Some(context) = &mut finding.context {
+                replace_in_json(context, &known);
+            }
+        }
+    }
+
+    /// Pseudonym of a name-like value: `<prefix>-<short hash>`.
+    fn name(&mut self, prefix: &str, value: &str) -> String {
+        if value.is_empty() {
+            return String::new();
+        }
+        let pseudonym = format!("{}-{}", prefix, &self.hash(value)[..SHORT_HASH]);
+        self.remember(value, &pseudonym)
+    }
+
+    /// Pseudonym of a hex ID: a hash of the same shape. The short form is
+    /// remembered too, since descriptions usually show 12 characters.
+    fn id(&mut self, value: &str) -> String {
+        if value.is_empty() {
+            return String::new();
+        }
+        let pseudonym = self.hash(value);
+        if value.len() > SHORT_ID && value.is_char_boundary(SHORT_ID) {
+            self.remember(&value[..SHORT_ID], &pseudonym[..SHORT_ID]);
+        }
+        self.remember(value, &pseudonym)
+    }
+
+    fn remember(&mut self, value: &str, pseudonym: &str) -> String {
+        self.replaced
+            .entry(value.to_string())
+            .or_insert_with(|| pseudonym.to_string())
+            .clone()
+    }
+
+    fn hash(&self, value: &str) -> String {
+        let mut ctx = digest::Context::new(&digest::SHA256);
+        ctx.update(&self.salt);
+        ctx.update(value.as_bytes());
+        ctx.finish().as_ref().iter().map(|b| format!("{:02x}", b)).collect()
+    }
+}
+
+fn replace_known(text: &mut String, known: &[(&String, &String)]) {
+    for (original, pseudonym) in known {
+        if text.contains(original.as_str()) {
+            *text = text.replace(original.as_str(), pseudonym);
+        }
+    }
+}
+
+/// Rewrites known values in every string of a JSON slice.
+fn replace_in_json(value: &mut serde_json::Value, known: &[(&String, &String)]) {
+    match value {
+        serde_json::Value::String(text) => replace_known(text, known),
+        serde_json::Value::Array(items) => items.iter_mut().for_each(|v| replace_in_json(v, known)),
+        serde_json::Value::Object(map) => map.values_mut().for_each(|v| replace_in_json(v, known)),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::docker::model::RiskLevel;
+
+    fn container() -> ContainerResult {
+        let container = serde_json::from_str(
+            r#"{
+                "Id": "4f2a9c1e7b3d5f6a8c0e2b4d6f8a0c2e4b6d8f0a2c4e6b8d0f2a4c6e8b0d2f4a",
+                "Name": "/billing-api",
+                "Config": {
+                    "Image": "registry.internal/acme/billing:2.1",
+                    "Labels": { "com.docker.compose.project": "acme", "tier": "backend" }
+                },
+                "Mounts": [{ "Source": "/srv/acme/billing", "Destination": "/data" }]
+            }"#,
+        )
+        .unwrap();
+        let mut finding = Finding::new(
+            "mount",
+            "billing-api (4f2a9c1e7b3d) mounts /srv/acme/billing",
+            RiskLevel::Medium,
+        );
+        finding.target = Some(FindingTarget::container(&container));
+        finding.context = Some(serde_json::json!({ "Source": "/srv/acme/billing" }));
+        ContainerResult {
+            container,
+            findings: vec![finding],
+            host: Some("prod-eu-1".into()),
+        }
+    }
+
+    #[test]
+    fn container_values_are_replaced_everywhere() {
+        let mut anonymizer = Anonymizer::new();
+        let mut result = container();
+        anonymizer.container(&mut result);
+
+        let name = result.container.name.clone().unwrap();
+        let id = result.container.id.clone().unwrap();
+        assert!(name.starts_with("/container-"));
+        assert_eq!(id.len(), 64);
+        assert!(result.host.as_deref().unwrap().starts_with("host-"));
+        let config = result.container.config.unwrap();
+        assert!(config.image.unwrap().starts_with("image-"));
+        let labels = config.labels.unwrap();
+        assert!(labels["com.docker.compose.project"].starts_with("label-"));
+        assert_eq!(labels["tier"], "backend");
+
+        let description = &result.findings[0].description;
+        for secret in ["billing", "4f2a9c1e7b3d", "/srv/acme"] {
+            assert!(!description.contains(secret), "{description}");
+        }
+        assert!(description.starts_with(name.trim_start_matches('/')));
+        assert!(description.contains(&id[..12]));
+        let source = result.findings[0].context.as_ref().unwrap()["Source"].as_str().unwrap();
+        assert!(source.starts_with("path-"), "{source}");
+        assert_eq!(
+            result.findings[0].target,
+            Some(FindingTarget::Container { id, name: name.trim_start_matches('/').to_string() })
+        );
+    }
+
+    #[test]
+    fn pseudonyms_are_consistent_within_a_report_only() {
+        let mut first = Anonymizer::new();
+        let mut second = Anonymizer::new();
+        let path = Path::new("services/billing/Dockerfile");
+
+        assert_eq!(first.path(path), first.path(path));
+        assert_ne!(first.path(path), second.path(path));
+    }
+}
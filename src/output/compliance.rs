@@ -0,0 +1,382 @@
+//! Compliance framework views.
+//!
+//! Rules declare the controls they map to in a `compliance` block keyed by
+//! framework, and findings carry the mapping into JSON and CSV reports:
+//!
+//! ```yaml
+//! compliance:
+//!   nist-800-190: ["4.4.3"]
+//! ```
+//!
+//! [`compliance_report`] regroups the findings of one or more JSON reports
+//! by control, the way auditors read them (`valeris report compliance`).
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+#[cfg(feature = "cli")]
+use comfy_table::{presets::UTF8_FULL, Cell, ContentArrangement, Table};
+use serde::{Deserialize, Serialize};
+
+use crate::docker::model::RiskLevel;
+use crate::error::{Result, ValerisError};
+
+/// NIST SP 800-190, Application Container Security Guide
+pub const NIST_800_190: &str = "nist-800-190";
+
+/// NIST SP 800-190 section 4 risks; each section lists the countermeasures
+/// for its risk.
+const NIST_800_190_SECTIONS: &[(&str, &str)] = &[
+    ("4.1.1", "Image vulnerabilities"),
+    ("4.1.2", "Image configuration defects"),
+    ("4.1.3", "Embedded malware"),
+    ("4.1.4", "Embedded clear text secrets"),
+    ("4.1.5", "Use of untrusted images"),
+    ("4.2.1", "Insecure connections to registries"),
+    ("4.2.2", "Stale images in registries"),
+    ("4.2.3", "Insufficient authentication and authorization restrictions"),
+    ("4.3.1", "Unbounded administrative access"),
+    ("4.3.2", "Unauthorized access"),
+    ("4.3.3", "Poorly separated inter-container network traffic"),
+    ("4.3.4", "Mixing of workload sensitivity levels"),
+    ("4.3.5", "Orchestrator node trust"),
+    ("4.4.1", "Vulnerabilities within the runtime software"),
+    ("4.4.2", "Unbounded network access from containers"),
+    ("4.4.3", "Insecure container runtime configurations"),
+    ("4.4.4", "App vulnerabilities"),
+    ("4.4.5", "Rogue containers"),
+    ("4.5.1", "Large attack surface"),
+    ("4.5.2", "Shared kernel"),
+    ("4.5.3", "Host OS component vulnerabilities"),
+    ("4.5.4", "Improper user access rights"),
+    ("4.5.5", "Host OS file system tampering"),
+];
+
+/// Display name of a framework, or the identifier itself if unknown.
+pub fn framework_name(framework: &str) -> &str {
+    match framework {
+        NIST_800_190 => "NIST SP 800-190",
+        other => other,
+    }
+}
+
+/// Title of a control, if the framework is known.
+pub fn control_title(framework: &str, control: &str) -> Option<&'static str> {
+    match framework {
+        NIST_800_190 => NIST_800_190_SECTIONS
+            .iter()
+            .find(|(id, _)| *id == control)
+            .map(|(_, title)| *title),
+        _ => None,
+    }
+}
+
+/// A finding read back from a report.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ReportedFinding {
+    pub rule: String,
+    pub severity: RiskLevel,
+    /// Container name or Dockerfile path
+    pub target: String,
+    pub description: String,
+    #[serde(skip)]
+    pub compliance: BTreeMap<String, Vec<String>>,
+}
+
+/// Findings of one control.
+#[derive(Debug, Clone, Serialize)]
+pub struct ControlFindings {
+    pub control: String,
+    pub title: Option<&'static str>,
+    pub findings: Vec<ReportedFinding>,
+}
+
+/// Findings grouped by the controls of one framework.
+#[derive(Debug, Clone, Serialize)]
+pub struct ComplianceReport {
+    pub framework: String,
+    /// Controls with findings, in section order
+    pub controls: Vec<ControlFindings>,
+    /// Findings whose rule has no control in the framework
+    pub unmapped: Vec<ReportedFinding>,
+}
+
+/// Groups findings by the controls of `framework`. A finding mapped to
+/// several controls is listed under each.
+pub fn compliance_report(framework: &str, findings: Vec<ReportedFinding>) -> ComplianceReport {
+    let mut controls: BTreeMap<(Vec<u32>, String), Vec<ReportedFinding>> = BTreeMap::new();
+    let mut unmapped = Vec::new();
+
+    for finding in findings {
+        match finding.compliance.get(framework).filter(|c| !c.is_empty()) {
+            Some(ids) => {
+                for id in ids {
+                    controls
+                        .entry((section_order(id), id.clone()))
+                        .or_default()
+                        .push(finding.clone());
+                }
+            }
+            None => unmapped.push(finding),
+        }
+    }
+
+    ComplianceReport {
+        framework: framework.to_string(),
+        controls: controls
+            .into_iter()
+            .map(|((_, control), findings)| ControlFindings {
+                title: control_title(framework, &control),
+                control,
+                findings,
+            })
+            .collect(),
+        unmapped,
+    }
+}
+
+/// Sorts `4.4.10` after `4.4.9`; non-numeric parts sort last.
+fn section_order(control: &str) -> Vec<u32> {
+    control
+        .split('.')
+        .map(|part| part.parse().unwrap_or(u32::MAX))
+        .collect()
+}
+
+// ─────────────────────────────────────────────────────────────────
+// Report Loading
+// ─────────────────────────────────────────────────────────────────
+
+/// A JSON report from `scan`, `docker-file`, `env-file`, or `network`.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum JsonReport {
+    Containers {
+        containers: Vec<ContainerEntry>,
+    },
+    /// Written by `valeris merge`, or by `valeris network`
+    Dockerfiles {
+        #[serde(alias = "env_files", alias = "networks")]
+        dockerfiles: Vec<DockerfileReport>,
+    },
+    Dockerfile(DockerfileReport),
+}
+
+#[derive(Deserialize)]
+struct DockerfileReport {
+    /// `env-file` reports have the same shape with `env_file_path`, and
+    /// network entries with `network_name`
+    #[serde(alias = "env_file_path", alias = "network_name")]
+    dockerfile_path: String,
+    findings: Vec<DockerfileEntry>,
+}
+
+#[derive(Deserialize)]
+struct ContainerEntry {
+    container_name: String,
+    findings: Vec<ContainerFindingEntry>,
+}
+
+#[derive(Deserialize)]
+struct ContainerFindingEntry {
+    kind: String,
+    description: String,
+    risk: RiskLevel,
+    #[serde(default)]
+    compliance: BTreeMap<String, Vec<String>>,
+}
+
+#[derive(Deserialize)]
+struct DockerfileEntry {
+    id: String,
+    severity: String,
+    description: String,
+    #[serde(default)]
+    compliance: BTreeMap<String, Vec<String>>,
+}
+
+/// Reads the findings of JSON reports written by `scan`, `docker-file`,
+/// `env-file`, `network`, or `merge`.
+///
+/// # Errors
+///
+/// * [`ValerisError::InputRead`] if a file cannot be read
+/// * [`ValerisError::InputParse`] if a file is not a Valeris JSON report
+pub fn load_report_findings(paths: &[PathBuf]) -> Result<Vec<ReportedFinding>> {
+    let mut findings = Vec::new();
+    for path in paths {
+        let raw = fs::read_to_string(path).map_err(|source| ValerisError::InputRead {
+            path: path.clone(),
+            source,
+        })?;
+        let report: JsonReport = serde_json::from_str(&raw).map_err(|e| ValerisError::InputParse {
+            path: path.clone(),
+            message: format!("not a Valeris JSON report ({})", e),
+        })?;
+
+        match report {
+            JsonReport::Containers { containers } => {
+                for container in containers {
+                    findings.extend(container.findings.into_iter().map(|f| ReportedFinding {
+                        rule: f.kind,
+                        severity: f.risk,
+                        target: container.container_name.clone(),
+                        description: f.description,
+                        compliance: f.compliance,
+                    }));
+                }
+            }
+            JsonReport::Dockerfiles { dockerfiles } => {
+                for report in dockerfiles {
+                    findings.extend(dockerfile_findings(report));
+                }
+            }
+            JsonReport::Dockerfile(report) => findings.extend(dockerfile_findings(report)),
+        }
+    }
+    Ok(findings)
+}
+
+fn dockerfile_findings(report: DockerfileReport) -> impl Iterator<Item = ReportedFinding> {
+    let target = report.dockerfile_path;
+    report.findings.into_iter().map(move |f| ReportedFinding {
+        rule: f.id,
+        severity: severity_from_report(&f.severity),
+        target: target.clone(),
+        description: f.description,
+        compliance: f.compliance,
+    })
+}
+
+/// Parses a Dockerfile report severity (`CRITICAL`, `HIGH`, `MEDIUM`, `LOW`, `INFO`).
+pub(crate) fn severity_from_report(severity: &str) -> RiskLevel {
+    match severity.to_ascii_uppercase().as_str() {
+        "CRITICAL" => RiskLevel::Critical,
+        "HIGH" => RiskLevel::High,
+        "MEDIUM" => RiskLevel::Medium,
+        "LOW" => RiskLevel::Low,
+        _ => RiskLevel::Informative,
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────
+// Printing
+// ─────────────────────────────────────────────────────────────────
+
+/// Prints a compliance report as one table per control.
+#[cfg(feature = "cli")]
+pub fn print_compliance_report(report: &ComplianceReport) {
+    let framework = framework_name(&report.framework);
+    println!("Valeris compliance report: {}", framework);
+    println!("{}", "━".repeat(60));
+    if report.controls.is_empty() {
+        println!("No findings map to {}.", framework);
+    }
+
+    for control in &report.controls {
+        println!();
+        match control.title {
+            Some(title) => println!("{} {} ({})", control.control, title, control.findings.len()),
+            None => println!("{} ({})", control.control, control.findings.len()),
+        }
+        let mut table = Table::new();
+        table
+            .load_preset(UTF8_FULL)
+            .set_content_arrangement(ContentArrangement::Dynamic)
+            .set_header(vec!["Severity", "Rule", "Target", "Description"]);
+        for finding in &control.findings {
+            table.add_row(vec![
+                Cell::new(format!("{:?}", finding.severity)),
+                Cell::new(&finding.rule),
+                Cell::new(&finding.target),
+                Cell::new(&finding.description),
+            ]);
+        }
+        println!("{table}");
+    }
+
+    if !report.unmapped.is_empty() {
+        println!();
+        println!("Not mapped to {}: {} finding(s)", framework, report.unmapped.len());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn finding(rule: &str, controls: &[&str]) -> ReportedFinding {
+        let mut compliance = BTreeMap::new();
+        if !controls.is_empty() {
+            compliance.insert(
+                NIST_800_190.to_string(),
+                controls.iter().map(|c| c.to_string()).collect(),
+            );
+        }
+        ReportedFinding {
+            rule: rule.to_string(),
+            severity: RiskLevel::High,
+            target: "web".to_string(),
+            description: "finding".to_string(),
+            compliance,
+        }
+    }
+
+    #[test]
+    fn groups_findings_by_control_in_section_order() {
+        let report = compliance_report(
+            NIST_800_190,
+            vec![
+                finding("privileged_mode", &["4.4.3", "4.5.5"]),
+                finding("secrets_in_env", &["4.1.4"]),
+                finding("no_healthcheck", &[]),
+            ],
+        );
+
+        let controls: Vec<_> = report.controls.iter().map(|c| c.control.as_str()).collect();
+        assert_eq!(controls, ["4.1.4", "4.4.3", "4.5.5"]);
+        assert_eq!(report.controls[0].title, Some("Embedded clear text secrets"));
+        assert_eq!(report.controls[2].findings[0].rule, "privileged_mode");
+        assert_eq!(report.unmapped.len(), 1);
+    }
+
+    #[test]
+    fn loads_container_and_dockerfile_reports() {
+        let dir = tempfile::tempdir().unwrap();
+        let containers = dir.path().join("scan.json");
+        fs::write(
+            &containers,
+            r#"{"containers":[{"container_id":"abc","container_name":"web","findings":[
+                {"kind":"privileged_mode","description":"Privileged","risk":"High",
+                 "compliance":{"nist-800-190":["4.4.3"]}}]}],"summary":{}}"#,
+        )
+        .unwrap();
+        let dockerfile = dir.path().join("df.json");
+        fs::write(
+            &dockerfile,
+            r#"{"dockerfile_path":"Dockerfile","findings":[
+                {"id":"DF006","severity":"CRITICAL","line":3,"description":"Secret in ENV"}]}"#,
+        )
+        .unwrap();
+
+        let findings = load_report_findings(&[containers, dockerfile]).unwrap();
+
+        assert_eq!(findings.len(), 2);
+        assert_eq!(findings[0].target, "web");
+        assert_eq!(findings[0].compliance[NIST_800_190], ["4.4.3"]);
+        assert_eq!(findings[1].target, "Dockerfile");
+        assert_eq!(findings[1].severity, RiskLevel::Critical);
+    }
+
+    #[test]
+    fn rejects_files_that_are_not_reports() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("other.json");
+        fs::write(&path, r#"{"hello":"world"}"#).unwrap();
+
+        let err = load_report_findings(&[path]).unwrap_err();
+
+        assert!(matches!(err, ValerisError::InputParse { .. }));
+    }
+}
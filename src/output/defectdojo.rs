@@ -0,0 +1,229 @@
+//! DefectDojo generic findings export.
+//!
+//! Writes reports in the JSON format accepted by DefectDojo's "Generic
+//! Findings Import" scan type, so Valeris results can be imported into an
+//! existing DefectDojo engagement:
+//!
+//! ```json
+//! { "findings": [ { "title": "...", "severity": "High", "description": "...",
+//!                   "unique_id_from_tool": "...", ... } ] }
+//! ```
+
+use std::io::Write;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+
+use crate::docker::model::{ContainerResult, Finding, NetworkResult, RiskLevel};
+use crate::output::exporters::Exporter;
+use crate::report::{ScanFindings, ScanReport};
+
+/// DefectDojo generic findings JSON, registered as `defectdojo`.
+pub struct DefectDojoExporter;
+
+#[derive(Serialize)]
+struct GenericReport<'a> {
+    findings: Vec<GenericFinding<'a>>,
+}
+
+/// One finding in DefectDojo's generic import format.
+#[derive(Serialize)]
+struct GenericFinding<'a> {
+    title: &'a str,
+    severity: &'static str,
+    description: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mitigation: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    references: Option<String>,
+    /// Stable per rule and target, so re-imports deduplicate
+    unique_id_from_tool: String,
+    vuln_id_from_tool: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    file_path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    line: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    component_name: Option<&'a str>,
+    static_finding: bool,
+    dynamic_finding: bool,
+    #[serde(skip_serializing_if = "<[String]>::is_empty")]
+    tags: &'a [String],
+}
+
+impl Exporter for DefectDojoExporter {
+    fn export(&self, report: &ScanReport, w: &mut dyn Write) -> Result<()> {
+        let findings = match &report.findings {
+            ScanFindings::None => return Ok(()),
+            ScanFindings::Containers(results) => results.iter().flat_map(container_findings).collect(),
+            ScanFindings::Dockerfile { path, findings } | ScanFindings::EnvFile { path, findings } => {
+                findings.iter().map(|f| file_finding(path, f)).collect()
+            }
+            ScanFindings::Networks(results) => results.iter().flat_map(network_findings).collect(),
+            ScanFindings::Combined { containers, dockerfiles, env_files } => containers
+                .iter()
+                .flat_map(container_findings)
+                .chain(
+                    dockerfiles
+                        .iter()
+                        .chain(env_files)
+                        .flat_map(|file| file.findings.iter().map(|f| file_finding(&file.path, f))),
+                )
+                .collect(),
+        };
+
+        let json = serde_json::to_string_pretty(&GenericReport { findings })
+            .context("Failed to serialize DefectDojo report")?;
+        writeln!(w, "{}", json).context("Failed to write DefectDojo report")
+    }
+}
+
+fn container_findings(result: &ContainerResult) -> impl Iterator<Item = GenericFinding<'_>> {
+    let id = result.container.id.as_deref().unwrap_or_default();
+    let name = result
+        .container
+        .name
+        .as_deref()
+        .unwrap_or_default()
+        .trim_start_matches('/');
+
+    result.findings.iter().map(move |finding| GenericFinding {
+        description: format!("{}\n\nContainer: {} ({})", finding.description, name, id),
+        unique_id_from_tool: format!("{}:{}", finding.kind, id),
+        component_name: Some(name),
+        dynamic_finding: true,
+        ..generic_finding(finding)
+    })
+}
+
+fn network_findings(result: &NetworkResult) -> impl Iterator<Item = GenericFinding<'_>> {
+    let id = result.network.id.as_deref().unwrap_or_default();
+    let name = result.network.name.as_deref().unwrap_or_default();
+
+    result.findings.iter().map(move |finding| GenericFinding {
+        description: format!("{}\n\nNetwork: {} ({})", finding.description, name, id),
+        unique_id_from_tool: format!("{}:{}", finding.kind, id),
+        component_name: Some(name),
+        dynamic_finding: true,
+        ..generic_finding(finding)
+    })
+}
+
+fn file_finding<'a>(path: &Path, finding: &'a Finding) -> GenericFinding<'a> {
+    let file_path = path.display().to_string();
+    GenericFinding {
+        unique_id_from_tool: format!(
+            "{}:{}:{}",
+            finding.kind,
+            file_path,
+            finding.line.map(|l| l.to_string()).unwrap_or_default()
+        ),
+        file_path: Some(file_path),
+        line: finding.line,
+        static_finding: true,
+        ..generic_finding(finding)
+    }
+}
+
+/// Fields shared by container and Dockerfile findings.
+fn generic_finding(finding: &Finding) -> GenericFinding<'_> {
+    GenericFinding {
+        title: finding.rule_name.as_deref().unwrap_or(&finding.kind),
+        severity: severity(&finding.risk),
+        description: finding.description.clone(),
+        mitigation: finding.remediation.as_deref(),
+        references: Some(finding.references.join("\n")).filter(|r| !r.is_empty()),
+        unique_id_from_tool: finding.kind.clone(),
+        vuln_id_from_tool: &finding.kind,
+        file_path: None,
+        line: None,
+        component_name: None,
+        static_finding: false,
+        dynamic_finding: false,
+        tags: &finding.tags,
+    }
+}
+
+/// DefectDojo severity names (`Info`, `Low`, `Medium`, `High`, `Critical`).
+fn severity(risk: &RiskLevel) -> &'static str {
+    match risk {
+        RiskLevel::Critical => "Critical",
+        RiskLevel::High => "High",
+        RiskLevel::Medium => "Medium",
+        RiskLevel::Low => "Low",
+        RiskLevel::Informative => "Info",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::summary::{ScanSummary, TargetKind};
+    use bollard::models::ContainerInspectResponse;
+    use serde_json::Value;
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    fn export(findings: ScanFindings) -> Value {
+        let summary = ScanSummary::new(TargetKind::Containers, 1, 1, &[], Duration::ZERO, false);
+        let mut out = Vec::new();
+        DefectDojoExporter
+            .export(&ScanReport::from_scan(summary, findings), &mut out)
+            .unwrap();
+        serde_json::from_slice(&out).unwrap()
+    }
+
+    #[test]
+    fn exports_container_findings_as_dynamic() {
+        let finding = Finding {
+            rule_name: Some("Privileged Mode".to_string()),
+            remediation: Some("Drop --privileged".to_string()),
+            references: vec!["https://example.com/cis".to_string()],
+            ..Finding::new("privileged_mode", "Container is privileged", RiskLevel::High)
+        };
+        let result = ContainerResult {
+            container: ContainerInspectResponse {
+                id: Some("abc123".to_string()),
+                name: Some("/web".to_string()),
+                ..Default::default()
+            },
+            findings: vec![finding],
+            host: None,
+        };
+
+        let report = export(ScanFindings::Containers(vec![result]));
+        let finding = &report["findings"][0];
+
+        assert_eq!(finding["title"], "Privileged Mode");
+        assert_eq!(finding["severity"], "High");
+        assert_eq!(finding["unique_id_from_tool"], "privileged_mode:abc123");
+        assert_eq!(finding["vuln_id_from_tool"], "privileged_mode");
+        assert_eq!(finding["component_name"], "web");
+        assert_eq!(finding["mitigation"], "Drop --privileged");
+        assert_eq!(finding["references"], "https://example.com/cis");
+        assert_eq!(finding["dynamic_finding"], true);
+        assert_eq!(finding["static_finding"], false);
+    }
+
+    #[test]
+    fn exports_dockerfile_findings_with_location() {
+        let finding = Finding {
+            line: Some(3),
+            ..Finding::new("DF001", "Base image uses latest", RiskLevel::Informative)
+        };
+
+        let report = export(ScanFindings::Dockerfile {
+            path: PathBuf::from("app/Dockerfile"),
+            findings: vec![finding],
+        });
+        let finding = &report["findings"][0];
+
+        assert_eq!(finding["title"], "DF001");
+        assert_eq!(finding["severity"], "Info");
+        assert_eq!(finding["file_path"], "app/Dockerfile");
+        assert_eq!(finding["line"], 3);
+        assert_eq!(finding["unique_id_from_tool"], "DF001:app/Dockerfile:3");
+        assert_eq!(finding["static_finding"], true);
+    }
+}
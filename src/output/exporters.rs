@@ -2,14 +2,41 @@
 //!
 //! This module provides a consistent interface for exporting scan results
 //! to various formats (JSON, CSV) across different scanner types.
+//!
+//! Formats are implemented as [`Exporter`]s and looked up by name in an
+//! [`ExporterRegistry`], so new formats can be added (including by library
+//! users) without touching the built-in ones.
 
-use crate::docker::model::{ContainerResult, Finding, RiskLevel};
+use crate::docker::model::{ContainerResult, FileResult, Finding, FindingHistory, MatchedField, NetworkResult, RiskLevel, SourceSpan};
 use crate::cli::OutputFormat;
-use anyhow::{Context, Result};
+use crate::output::defectdojo::DefectDojoExporter;
+use crate::output::metadata::ScanMetadata;
+use crate::output::severity::SeverityScheme;
+use crate::output::ocsf::OcsfExporter;
+use crate::output::sarif::SarifExporter;
+use crate::output::junit::JunitExporter;
+use crate::output::html::HtmlExporter;
+use crate::output::gitlab::GitlabExporter;
+#[cfg(feature = "cli")]
+use crate::output::printer::{PlainTableWriter, ScanContext};
+use crate::output::summary::{ScanSummary, SeverityCounts};
+use crate::report::{ScanFindings, ScanReport};
+use anyhow::{anyhow, Context, Result};
 use bollard::models::ContainerInspectResponse;
 use serde::Serialize;
+use std::collections::BTreeMap;
+use std::io::{BufWriter, Write};
 use std::path::Path;
 
+/// Version of the JSON report structure, written as `schema_version`.
+///
+/// Bumped when a field is removed or changes type; new optional fields keep
+/// the version.
+pub const REPORT_SCHEMA_VERSION: u32 = 1;
+
+/// JSON Schema of the report structure, printed by `scan --print-schema`
+pub const REPORT_SCHEMA: &str = include_str!("../../docs/schema/report.schema.json");
+
 /// Source of a scan - what was scanned.
 pub enum ScanSource<'a> {
     /// Runtime container scans
@@ -25,167 +52,873 @@ pub enum ScanSource<'a> {
 // Container Export Structures
 // ─────────────────────────────────────────────────────────────────
 
+/// Container labels copied into container exports, so findings can be
+/// traced to their Compose project, Kubernetes pod, or image build
+pub const EXPORTED_LABELS: &[&str] = &[
+    "com.docker.compose.project",
+    "com.docker.compose.service",
+    "io.kubernetes.pod.namespace",
+    "io.kubernetes.pod.name",
+    "io.kubernetes.container.name",
+    "org.opencontainers.image.source",
+    "org.opencontainers.image.revision",
+    "org.opencontainers.image.version",
+];
+
 #[derive(Serialize)]
 pub struct ExportableContainerFinding {
     pub kind: String,
     pub description: String,
     pub risk: RiskLevel,
+    /// `risk` in the configured severity scheme
+    pub severity: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rule_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remediation: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub references: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub compliance: BTreeMap<String, Vec<String>>,
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub seen: Option<FindingHistory>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub debug: Vec<MatchedField>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub context: Option<serde_json::Value>,
+}
+
+/// One CSV row per container finding; list fields are joined with `; `.
+/// Columns after `host` match the Dockerfile and network CSV exports.
+#[derive(Serialize)]
+struct CsvContainerFinding<'a> {
+    container_id: String,
+    container_name: String,
+    /// Image reference the container was created from (`nginx:1.25`)
+    image: Option<&'a str>,
+    /// Image ID: the SHA-256 digest of the image configuration
+    image_digest: Option<&'a str>,
+    state: Option<String>,
+    /// [`EXPORTED_LABELS`] as `key=value` pairs
+    labels: String,
+    host: Option<&'a str>,
+    severity: String,
+    id: &'a str,
+    line: Option<usize>,
+    description: &'a str,
+    rule_name: Option<&'a str>,
+    remediation: Option<&'a str>,
+    references: String,
+    tags: String,
+    category: Option<&'a str>,
+    compliance: String,
+    first_seen: Option<&'a str>,
+    last_seen: Option<&'a str>,
+    is_new: Option<bool>,
+}
+
+/// A finding with its target: one line of an NDJSON report, or one entry of a
+/// report template's `findings`.
+#[derive(Serialize)]
+pub(crate) struct FlatFinding<'a> {
+    /// `container`, `dockerfile`, `env_file`, or `network`
+    target_type: &'static str,
+    /// Container or network name, or file path
+    target: &'a str,
+    /// Container or network ID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    target_id: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    host: Option<&'a str>,
+    id: &'a str,
+    severity: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    line: Option<usize>,
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    span: Option<&'a SourceSpan>,
+    description: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rule_name: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    remediation: Option<&'a str>,
+    #[serde(skip_serializing_if = "<[String]>::is_empty")]
+    references: &'a [String],
+    #[serde(skip_serializing_if = "<[String]>::is_empty")]
+    tags: &'a [String],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    category: Option<&'a str>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    compliance: &'a BTreeMap<String, Vec<String>>,
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    seen: Option<&'a FindingHistory>,
+    #[serde(skip_serializing_if = "<[MatchedField]>::is_empty")]
+    debug: &'a [MatchedField],
 }
 
 #[derive(Serialize)]
 pub struct ExportableContainerResult {
     pub container_id: String,
     pub container_name: String,
+    /// Image reference the container was created from (`nginx:1.25`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+    /// Image ID: the SHA-256 digest of the image configuration
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image_digest: Option<String>,
+    /// Container state when it was scanned (`running`, `exited`, ...)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+    /// Values of the container's [`EXPORTED_LABELS`]
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub labels: BTreeMap<String, String>,
+    /// Docker host of the container, in multi-host scans
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub host: Option<String>,
     pub findings: Vec<ExportableContainerFinding>,
 }
 
+/// Incremental writer for container scan reports.
+///
+/// Each container is serialized and written as soon as it is finished, so
+/// memory stays flat no matter how many containers are scanned. Formats
+/// without a streaming writer collect the results and are exported through
+/// the [`ExporterRegistry`] on [`finish`](Self::finish). JSON reports
+/// have the shape `{ "schema_version": 1, "containers": [...], "metadata": {...}, "summary": {...} }`;
+/// the metadata and summary are written last because they travel with the
+/// summary, which is only known once every container is done.
+pub struct ContainerExportStream {
+    sink: StreamSink,
+    scheme: SeverityScheme,
+    written: usize,
+}
+
+enum StreamSink {
+    Json(BufWriter<Box<dyn Write>>),
+    /// One finding per line, flushed after every container
+    Ndjson(BufWriter<Box<dyn Write>>),
+    Csv(Box<csv::Writer<Box<dyn Write>>>),
+    /// Formats that need the whole report, exported from the registry on finish
+    Buffered {
+        name: &'static str,
+        writer: BufWriter<Box<dyn Write>>,
+        results: Vec<ContainerResult>,
+    },
+    /// Plain-text table report
+    #[cfg(feature = "cli")]
+    Table(PlainTableWriter<BufWriter<std::fs::File>>),
+    Discard,
+}
+
+impl ContainerExportStream {
+    /// Opens the report destination for the given format.
+    ///
+    /// # Arguments
+    ///
+    /// * `format` - Output format (table output without a file is discarded; the printer shows it)
+    /// * `output` - Optional output file path (stdout if `None`)
+    /// * `scheme` - How severities are named in the report
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the output file cannot be created
+    pub fn create(format: &OutputFormat, output: &Option<String>, scheme: SeverityScheme) -> Result<Self> {
+        let sink = match format {
+            #[cfg(feature = "cli")]
+            OutputFormat::Table => match output {
+                Some(path) => StreamSink::Table(PlainTableWriter::create(path, scheme)?),
+                None => StreamSink::Discard,
+            },
+            #[cfg(not(feature = "cli"))]
+            OutputFormat::Table => StreamSink::Discard,
+            OutputFormat::Json => {
+                let mut writer = BufWriter::new(open_writer(output)?);
+                write!(writer, "{{\n  \"schema_version\": {},\n  \"containers\": [", REPORT_SCHEMA_VERSION)
+                    .context("Failed to write JSON report")?;
+                StreamSink::Json(writer)
+            }
+            OutputFormat::Ndjson => StreamSink::Ndjson(BufWriter::new(open_writer(output)?)),
+            OutputFormat::Csv => {
+                StreamSink::Csv(Box::new(csv::Writer::from_writer(open_writer(output)?)))
+            }
+            other => StreamSink::Buffered {
+                name: format_name(other).context("Output format has no exporter")?,
+                writer: BufWriter::new(open_writer(output)?),
+                results: Vec::new(),
+            },
+        };
+        Ok(Self { sink, scheme, written: 0 })
+    }
+
+    /// Writes the findings of one container.
+    pub fn write_result(&mut self, result: &ContainerResult) -> Result<()> {
+        match &mut self.sink {
+            StreamSink::Json(writer) => {
+                let entry = serde_json::to_string_pretty(&container_to_json(result, self.scheme))
+                    .context("Failed to serialize container to JSON")?;
+                let separator = if self.written == 0 { "\n" } else { ",\n" };
+                write!(writer, "{}    {}", separator, indent(&entry, "    "))
+                    .context("Failed to write JSON report")?;
+            }
+            StreamSink::Ndjson(writer) => {
+                write_ndjson(writer, &ScannedTarget::container(result), self.scheme)?;
+                writer.flush().context("Failed to flush NDJSON report")?;
+            }
+            StreamSink::Csv(writer) => {
+                write_container_csv(writer, result, self.scheme)?;
+            }
+            StreamSink::Buffered { results, .. } => results.push(result.clone()),
+            #[cfg(feature = "cli")]
+            StreamSink::Table(writer) => {
+                let context = match &result.host {
+                    Some(host) => ScanContext::RemoteContainer { container: &result.container, host },
+                    None => ScanContext::Container(&result.container),
+                };
+                writer.write_report(context, &result.findings)?;
+            }
+            StreamSink::Discard => {}
+        }
+        self.written += 1;
+        Ok(())
+    }
+
+    /// Writes the trailing summary and flushes the report.
+    pub fn finish(self, summary: &ScanSummary) -> Result<()> {
+        match self.sink {
+            StreamSink::Json(mut writer) => {
+                let close = if self.written == 0 { "]" } else { "\n  ]" };
+                write!(writer, "{}", close).context("Failed to write JSON report")?;
+                if let Some(metadata) = &summary.metadata {
+                    let metadata = serde_json::to_string_pretty(metadata)
+                        .context("Failed to serialize scan metadata to JSON")?;
+                    write!(writer, ",\n  \"metadata\": {}", indent(&metadata, "  "))
+                        .context("Failed to write JSON report")?;
+                }
+                let summary = serde_json::to_string_pretty(summary)
+                    .context("Failed to serialize scan summary to JSON")?;
+                write!(writer, ",\n  \"summary\": {}\n}}\n", indent(&summary, "  "))
+                    .context("Failed to write JSON report")?;
+                writer.flush().context("Failed to flush JSON report")?;
+            }
+            StreamSink::Ndjson(mut writer) => {
+                writer.flush().context("Failed to flush NDJSON report")?;
+            }
+            StreamSink::Csv(mut writer) => {
+                writer.flush().context("Failed to flush CSV writer")?;
+            }
+            StreamSink::Buffered { name, mut writer, results } => {
+                let report = ScanReport::from_scan(summary.clone(), ScanFindings::Containers(results))
+                    .with_severity_scheme(self.scheme);
+                ExporterRegistry::default().export(name, &report, &mut writer)?;
+                writer.flush().context("Failed to flush report")?;
+            }
+            #[cfg(feature = "cli")]
+            StreamSink::Table(writer) => writer.finish(summary)?,
+            StreamSink::Discard => {}
+        }
+        Ok(())
+    }
+}
+
 // ─────────────────────────────────────────────────────────────────
 // Dockerfile Export Structures
 // ─────────────────────────────────────────────────────────────────
 
 #[derive(Serialize)]
 pub struct DockerfileScanResult {
+    pub schema_version: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<ScanMetadata>,
     pub dockerfile_path: String,
     pub total_findings: usize,
     pub critical_count: usize,
+    pub high_count: usize,
+    pub medium_count: usize,
+    pub low_count: usize,
+    pub info_count: usize,
+    pub findings: Vec<DockerfileFinding>,
+    pub summary: ScanSummary,
+}
+
+/// JSON report of a `.env` file scan; findings have the Dockerfile shape.
+#[derive(Serialize)]
+pub struct EnvFileScanResult {
+    pub schema_version: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<ScanMetadata>,
+    pub env_file_path: String,
+    pub total_findings: usize,
+    pub critical_count: usize,
+    pub high_count: usize,
+    pub medium_count: usize,
+    pub low_count: usize,
+    pub info_count: usize,
+    pub findings: Vec<DockerfileFinding>,
+    pub summary: ScanSummary,
+}
+
+/// JSON report of a Docker network scan; findings have the Dockerfile shape.
+#[derive(Serialize)]
+pub struct NetworkScanResult {
+    pub schema_version: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<ScanMetadata>,
+    pub networks: Vec<ExportableNetwork>,
+    pub summary: ScanSummary,
+}
+
+#[derive(Serialize)]
+pub struct ExportableNetwork {
+    pub network_id: String,
+    pub network_name: String,
+    pub driver: String,
+    pub internal: bool,
+    pub total_findings: usize,
+    pub critical_count: usize,
+    pub high_count: usize,
+    pub medium_count: usize,
+    pub low_count: usize,
+    pub info_count: usize,
+    pub findings: Vec<DockerfileFinding>,
+}
+
+/// A Dockerfile or `.env` file in a combined scan report; entries have the
+/// shape of the single-file reports, without their summary.
+#[derive(Serialize)]
+pub struct ExportableFile {
+    #[serde(flatten)]
+    pub path: ExportableFilePath,
+    pub total_findings: usize,
+    pub critical_count: usize,
+    pub high_count: usize,
     pub medium_count: usize,
     pub low_count: usize,
     pub info_count: usize,
     pub findings: Vec<DockerfileFinding>,
 }
 
+/// Path field of an [`ExportableFile`], named as in the single-file reports.
+#[derive(Serialize)]
+#[serde(untagged)]
+pub enum ExportableFilePath {
+    Dockerfile { dockerfile_path: String },
+    EnvFile { env_file_path: String },
+}
+
 #[derive(Serialize)]
 pub struct DockerfileFinding {
     pub id: String,
     pub severity: String,
     pub line: Option<usize>,
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub span: Option<SourceSpan>,
     pub description: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rule_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remediation: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub references: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub compliance: BTreeMap<String, Vec<String>>,
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub seen: Option<FindingHistory>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub debug: Vec<MatchedField>,
 }
 
 // ─────────────────────────────────────────────────────────────────
-// Unified Export API
+// Exporter Trait and Registry
 // ─────────────────────────────────────────────────────────────────
 
-/// Exports scan results in the specified format.
+/// A report format.
 ///
-/// # Arguments
+/// Implement this trait and add it to an [`ExporterRegistry`] to support a
+/// new output format.
 ///
-/// * `source` - The scan source (containers or Dockerfile)
-/// * `format` - Output format (JSON or CSV)
-/// * `output` - Optional output file path
+/// # Example
 ///
-/// # Returns
+/// ```
+/// use std::io::Write;
+/// use valeris::output::exporters::{Exporter, ExporterRegistry};
+/// use valeris::report::ScanReport;
 ///
-/// `Ok(())` on success, or an error if export failed
-pub fn export_scan_results(
-    source: ScanSource,
-    format: &OutputFormat,
-    output: &Option<String>,
-) -> Result<()> {
-    match format {
-        OutputFormat::Table => {
-            // Table format is handled by the printer module
-            Ok(())
+/// struct ExitCodeExporter;
+///
+/// impl Exporter for ExitCodeExporter {
+///     fn export(&self, report: &ScanReport, w: &mut dyn Write) -> anyhow::Result<()> {
+///         writeln!(w, "{}", report.exit_code)?;
+///         Ok(())
+///     }
+/// }
+///
+/// let mut registry = ExporterRegistry::default();
+/// registry.register("exit-code", ExitCodeExporter);
+///
+/// let mut out = Vec::new();
+/// registry.export("exit-code", &ScanReport::default(), &mut out)?;
+/// assert_eq!(out, b"0\n");
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub trait Exporter: Send + Sync {
+    /// Writes `report` to `w`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the report cannot be serialized or written
+    fn export(&self, report: &ScanReport, w: &mut dyn Write) -> Result<()>;
+}
+
+/// Exporters keyed by format name.
+///
+/// [`ExporterRegistry::default`] contains the built-in `json`, `ndjson`, `csv`,
+/// `defectdojo`, `ocsf`, `sarif`, `junit`, `html` and `gitlab` exporters;
+/// [`ExporterRegistry::new`] starts empty. Names are case-insensitive.
+pub struct ExporterRegistry {
+    exporters: BTreeMap<String, Box<dyn Exporter>>,
+}
+
+impl ExporterRegistry {
+    /// Creates a registry with no exporters.
+    pub fn new() -> Self {
+        Self {
+            exporters: BTreeMap::new(),
         }
-        OutputFormat::Json => export_json(source, output),
-        OutputFormat::Csv => export_csv(source, output),
+    }
+
+    /// Registers `exporter` under `name`, replacing any exporter already
+    /// registered with that name.
+    pub fn register(&mut self, name: &str, exporter: impl Exporter + 'static) {
+        self.exporters
+            .insert(name.to_ascii_lowercase(), Box::new(exporter));
+    }
+
+    /// Returns the exporter registered under `name`.
+    pub fn get(&self, name: &str) -> Option<&dyn Exporter> {
+        self.exporters
+            .get(&name.to_ascii_lowercase())
+            .map(|exporter| exporter.as_ref())
+    }
+
+    /// Returns the registered format names, sorted.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.exporters.keys().map(String::as_str)
+    }
+
+    /// Writes `report` with the exporter registered under `name`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no exporter is registered under `name`, or if
+    /// the exporter fails
+    pub fn export(&self, name: &str, report: &ScanReport, w: &mut dyn Write) -> Result<()> {
+        let exporter = self.get(name).ok_or_else(|| {
+            anyhow!(
+                "Unknown export format '{}' (available: {})",
+                name,
+                self.names().collect::<Vec<_>>().join(", ")
+            )
+        })?;
+        exporter.export(report, w)
     }
 }
 
-fn export_json(source: ScanSource, output: &Option<String>) -> Result<()> {
-    let json = match source {
-        ScanSource::Containers(results) => {
-            let data = containers_to_json(results);
-            serde_json::to_string_pretty(&data)
-                .context("Failed to serialize containers to JSON")?
-        }
-        ScanSource::Dockerfile { path, findings } => {
-            let data = dockerfile_to_json(path, findings);
-            serde_json::to_string_pretty(&data)
-                .context("Failed to serialize Dockerfile to JSON")?
+impl Default for ExporterRegistry {
+    fn default() -> Self {
+        let mut registry = Self::new();
+        registry.register("json", JsonExporter);
+        registry.register("ndjson", NdjsonExporter);
+        registry.register("csv", CsvExporter);
+        registry.register("defectdojo", DefectDojoExporter);
+        registry.register("ocsf", OcsfExporter);
+        registry.register("sarif", SarifExporter);
+        registry.register("junit", JunitExporter);
+        registry.register("html", HtmlExporter);
+        registry.register("gitlab", GitlabExporter);
+        registry
+    }
+}
+
+/// JSON reports: `{ "schema_version": 1, "containers": [...], "summary": {...} }` for runtime
+/// scans, a [`DockerfileScanResult`] for Dockerfile scans, an
+/// [`EnvFileScanResult`] for `.env` file scans and a [`NetworkScanResult`]
+/// for network scans.
+pub struct JsonExporter;
+
+impl Exporter for JsonExporter {
+    fn export(&self, report: &ScanReport, w: &mut dyn Write) -> Result<()> {
+        let scheme = report.severity_scheme;
+        let json = match &report.findings {
+            ScanFindings::None => return Ok(()),
+            ScanFindings::Containers(results) => serde_json::to_string_pretty(&ContainerReport {
+                schema_version: REPORT_SCHEMA_VERSION,
+                metadata: report_summary(report)?.metadata.as_ref(),
+                containers: results.iter().map(|r| container_to_json(r, scheme)).collect(),
+                dockerfiles: Vec::new(),
+                env_files: Vec::new(),
+                summary: report_summary(report)?,
+            }),
+            ScanFindings::Dockerfile { path, findings } => serde_json::to_string_pretty(
+                &dockerfile_to_json(path, findings, report_summary(report)?, scheme),
+            ),
+            ScanFindings::EnvFile { path, findings } => serde_json::to_string_pretty(
+                &env_file_to_json(path, findings, report_summary(report)?, scheme),
+            ),
+            ScanFindings::Networks(results) => serde_json::to_string_pretty(&NetworkScanResult {
+                schema_version: REPORT_SCHEMA_VERSION,
+                metadata: report_summary(report)?.metadata.clone(),
+                networks: results.iter().map(|r| network_to_json(r, scheme)).collect(),
+                summary: report_summary(report)?.clone(),
+            }),
+            ScanFindings::Combined { containers, dockerfiles, env_files } => serde_json::to_string_pretty(&ContainerReport {
+                schema_version: REPORT_SCHEMA_VERSION,
+                metadata: report_summary(report)?.metadata.as_ref(),
+                containers: containers.iter().map(|r| container_to_json(r, scheme)).collect(),
+                dockerfiles: dockerfiles
+                    .iter()
+                    .map(|f| file_to_json(ExportableFilePath::Dockerfile { dockerfile_path: f.path.display().to_string() }, f, scheme))
+                    .collect(),
+                env_files: env_files
+                    .iter()
+                    .map(|f| file_to_json(ExportableFilePath::EnvFile { env_file_path: f.path.display().to_string() }, f, scheme))
+                    .collect(),
+                summary: report_summary(report)?,
+            }),
         }
-    };
+        .context("Failed to serialize report to JSON")?;
+
+        writeln!(w, "{}", json).context("Failed to write JSON report")
+    }
+}
 
-    write_or_print(&json, output)?;
+/// Newline-delimited JSON with one finding per line, for `jq`, Loki, and log
+/// shippers.
+///
+/// Every line is a complete object naming its target (`target_type`,
+/// `target`, and `target_id` / `host` where they apply) followed by the
+/// finding fields of the JSON report. No summary line is written, so all
+/// lines have the same shape.
+pub struct NdjsonExporter;
 
-    if let Some(path) = output {
-        tracing::info!("JSON exported to {}", path);
+impl Exporter for NdjsonExporter {
+    fn export(&self, report: &ScanReport, w: &mut dyn Write) -> Result<()> {
+        for target in scanned_targets(&report.findings) {
+            write_ndjson(w, &target, report.severity_scheme)?;
+        }
+        Ok(())
     }
+}
 
-    Ok(())
+/// A scanned container, file, or network and its findings.
+pub(crate) struct ScannedTarget<'a> {
+    /// `container`, `dockerfile`, `env_file`, or `network`
+    pub target_type: &'static str,
+    /// Container or network name, or file path
+    pub target: String,
+    /// Container or network ID
+    pub target_id: Option<&'a str>,
+    pub host: Option<&'a str>,
+    pub findings: &'a [Finding],
 }
 
-fn export_csv(source: ScanSource, output: &Option<String>) -> Result<()> {
-    match source {
-        ScanSource::Containers(results) => {
-            export_containers_csv(results, output)?;
+impl<'a> ScannedTarget<'a> {
+    fn container(result: &'a ContainerResult) -> Self {
+        Self {
+            target_type: "container",
+            target: get_container_name(&result.container),
+            target_id: result.container.id.as_deref(),
+            host: result.host.as_deref(),
+            findings: &result.findings,
         }
-        ScanSource::Dockerfile { path, findings } => {
-            export_dockerfile_csv(path, findings, output)?;
+    }
+
+    fn file(target_type: &'static str, path: &Path, findings: &'a [Finding]) -> Self {
+        Self {
+            target_type,
+            target: path.display().to_string(),
+            target_id: None,
+            host: None,
+            findings,
+        }
+    }
+
+    /// The target's findings in the flat NDJSON shape, their severities
+    /// named by `scheme`
+    pub fn flat_findings(&self, scheme: SeverityScheme) -> impl Iterator<Item = FlatFinding<'_>> {
+        self.findings.iter().map(move |finding| FlatFinding {
+            target_type: self.target_type,
+            target: &self.target,
+            target_id: self.target_id,
+            host: self.host,
+            id: &finding.kind,
+            severity: severity_to_string(&finding.risk, scheme),
+            line: finding.line,
+            span: finding.span.as_ref(),
+            description: &finding.description,
+            rule_name: finding.rule_name.as_deref(),
+            remediation: finding.remediation.as_deref(),
+            references: &finding.references,
+            tags: &finding.tags,
+            category: finding.category.as_deref(),
+            compliance: &finding.compliance,
+            seen: finding.seen.as_ref(),
+            debug: &finding.debug,
+        })
+    }
+}
+
+/// Every scanned target of a report, containers first.
+pub(crate) fn scanned_targets(findings: &ScanFindings) -> Vec<ScannedTarget<'_>> {
+    match findings {
+        ScanFindings::None => Vec::new(),
+        ScanFindings::Containers(results) => results.iter().map(ScannedTarget::container).collect(),
+        ScanFindings::Dockerfile { path, findings } => vec![ScannedTarget::file("dockerfile", path, findings)],
+        ScanFindings::EnvFile { path, findings } => vec![ScannedTarget::file("env_file", path, findings)],
+        ScanFindings::Networks(results) => results
+            .iter()
+            .map(|result| ScannedTarget {
+                target_type: "network",
+                target: result.network.name.clone().unwrap_or_default(),
+                target_id: result.network.id.as_deref(),
+                host: None,
+                findings: &result.findings,
+            })
+            .collect(),
+        ScanFindings::Combined { containers, dockerfiles, env_files } => containers
+            .iter()
+            .map(ScannedTarget::container)
+            .chain(dockerfiles.iter().map(|f| ScannedTarget::file("dockerfile", &f.path, &f.findings)))
+            .chain(env_files.iter().map(|f| ScannedTarget::file("env_file", &f.path, &f.findings)))
+            .collect(),
+    }
+}
+
+/// CSV reports with one row per finding.
+pub struct CsvExporter;
+
+impl Exporter for CsvExporter {
+    fn export(&self, report: &ScanReport, w: &mut dyn Write) -> Result<()> {
+        let scheme = report.severity_scheme;
+        match &report.findings {
+            ScanFindings::None => Ok(()),
+            ScanFindings::Containers(results) => {
+                let mut writer = csv::Writer::from_writer(w);
+                for result in results {
+                    write_container_csv(&mut writer, result, scheme)?;
+                }
+                writer.flush().context("Failed to flush CSV writer")
+            }
+            ScanFindings::Dockerfile { path, findings } => {
+                export_targets_csv("dockerfile", &[(path.display().to_string(), findings)], scheme, w)
+            }
+            ScanFindings::EnvFile { path, findings } => {
+                export_targets_csv("env_file", &[(path.display().to_string(), findings)], scheme, w)
+            }
+            ScanFindings::Networks(results) => {
+                let targets: Vec<(String, &[Finding])> = results
+                    .iter()
+                    .map(|r| (r.network.name.clone().unwrap_or_default(), r.findings.as_slice()))
+                    .collect();
+                export_targets_csv("network", &targets, scheme, w)
+            }
+            ScanFindings::Combined { containers, dockerfiles, env_files } => {
+                export_combined_csv(containers, dockerfiles, env_files, scheme, w)
+            }
         }
     }
+}
+
+#[derive(Serialize)]
+struct ContainerReport<'a> {
+    schema_version: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    metadata: Option<&'a ScanMetadata>,
+    containers: Vec<ExportableContainerResult>,
+    /// Files of a combined scan
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    dockerfiles: Vec<ExportableFile>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    env_files: Vec<ExportableFile>,
+    summary: &'a ScanSummary,
+}
+
+fn report_summary(report: &ScanReport) -> Result<&ScanSummary> {
+    report
+        .summary
+        .as_ref()
+        .context("Report has no scan summary to export")
+}
+
+// ─────────────────────────────────────────────────────────────────
+// Unified Export API
+// ─────────────────────────────────────────────────────────────────
+
+/// Returns the registry name for a CLI output format, or `None` for table
+/// output (handled by the printer module).
+pub fn format_name(format: &OutputFormat) -> Option<&'static str> {
+    match format {
+        OutputFormat::Table => None,
+        OutputFormat::Json => Some("json"),
+        OutputFormat::Ndjson => Some("ndjson"),
+        OutputFormat::Csv => Some("csv"),
+        OutputFormat::Defectdojo => Some("defectdojo"),
+        OutputFormat::Ocsf => Some("ocsf"),
+        OutputFormat::Sarif => Some("sarif"),
+        OutputFormat::Junit => Some("junit"),
+        OutputFormat::Html => Some("html"),
+        OutputFormat::Gitlab => Some("gitlab"),
+    }
+}
+
+/// Exports a report with the built-in exporter for `format`.
+///
+/// # Arguments
+///
+/// * `report` - The report to export
+/// * `format` - Output format (table output is skipped; it is handled by the printer)
+/// * `output` - Optional output file path (stdout if `None`)
+///
+/// # Errors
+///
+/// Returns an error if the output file cannot be created or written
+pub fn export_report(report: &ScanReport, format: &OutputFormat, output: &Option<String>) -> Result<()> {
+    let Some(name) = format_name(format) else {
+        return Ok(());
+    };
+
+    let mut writer = BufWriter::new(open_writer(output)?);
+    ExporterRegistry::default().export(name, report, &mut writer)?;
+    writer.flush().context("Failed to flush report")?;
 
     if let Some(path) = output {
-        tracing::info!("CSV exported to {}", path);
+        tracing::info!("{} exported to {}", name.to_ascii_uppercase(), path);
     }
 
     Ok(())
 }
 
+/// Exports scan results in the specified format.
+///
+/// Builds a [`ScanReport`] from the borrowed results and passes it to
+/// [`export_report`].
+///
+/// # Arguments
+///
+/// * `source` - The scan source (containers or Dockerfile)
+/// * `format` - Output format (JSON or CSV)
+/// * `output` - Optional output file path
+/// * `summary` - Scan statistics embedded as metadata in JSON exports
+/// * `scheme` - How severities are named in the report
+///
+/// # Returns
+///
+/// `Ok(())` on success, or an error if export failed
+pub fn export_scan_results(
+    source: ScanSource,
+    format: &OutputFormat,
+    output: &Option<String>,
+    summary: &ScanSummary,
+    scheme: SeverityScheme,
+) -> Result<()> {
+    let findings = match source {
+        ScanSource::Containers(results) => ScanFindings::Containers(results.to_vec()),
+        ScanSource::Dockerfile { path, findings } => ScanFindings::Dockerfile {
+            path: path.to_path_buf(),
+            findings: findings.to_vec(),
+        },
+    };
+    let report = ScanReport::from_scan(summary.clone(), findings).with_severity_scheme(scheme);
+    export_report(&report, format, output)
+}
+
 // ─────────────────────────────────────────────────────────────────
 // Container-specific Export Logic
 // ─────────────────────────────────────────────────────────────────
 
-fn containers_to_json(results: &[ContainerResult]) -> Vec<ExportableContainerResult> {
-    results
+fn container_to_json(result: &ContainerResult, scheme: SeverityScheme) -> ExportableContainerResult {
+    let findings = result
+        .findings
         .iter()
-        .map(|r| {
-            let id = get_container_id(&r.container);
-            let name = get_container_name(&r.container);
-
-            let findings = r
-                .findings
-                .iter()
-                .map(|f| ExportableContainerFinding {
-                    kind: f.kind.clone(),
-                    description: f.description.clone(),
-                    risk: f.risk.clone(),
-                })
-                .collect();
-
-            ExportableContainerResult {
-                container_id: id,
-                container_name: name,
-                findings,
-            }
+        .map(|f| ExportableContainerFinding {
+            kind: f.kind.clone(),
+            description: f.description.clone(),
+            risk: f.risk.clone(),
+            severity: severity_to_string(&f.risk, scheme),
+            rule_name: f.rule_name.clone(),
+            remediation: f.remediation.clone(),
+            references: f.references.clone(),
+            tags: f.tags.clone(),
+            category: f.category.clone(),
+            compliance: f.compliance.clone(),
+            seen: f.seen.clone(),
+            debug: f.debug.clone(),
+            context: f.context.clone(),
         })
-        .collect()
-}
-
-fn export_containers_csv(results: &[ContainerResult], output: &Option<String>) -> Result<()> {
-    let writer: Box<dyn std::io::Write> = match output {
-        Some(path) => Box::new(
-            std::fs::File::create(path)
-                .with_context(|| format!("Failed to create CSV file {}", path))?,
-        ),
-        None => Box::new(std::io::stdout()),
-    };
+        .collect();
 
-    let mut wtr = csv::Writer::from_writer(writer);
+    ExportableContainerResult {
+        container_id: get_container_id(&result.container),
+        container_name: get_container_name(&result.container),
+        image: get_container_image(&result.container).map(str::to_string),
+        image_digest: result.container.image.clone(),
+        state: get_container_state(&result.container),
+        labels: get_exported_labels(&result.container),
+        host: result.host.clone(),
+        findings,
+    }
+}
 
-    // Flatten findings for CSV
-    for result in results {
-        for finding in &result.findings {
-            wtr.serialize(&ExportableContainerFinding {
-                kind: finding.kind.clone(),
-                description: finding.description.clone(),
-                risk: finding.risk.clone(),
+fn write_container_csv<W: Write>(
+    writer: &mut csv::Writer<W>,
+    result: &ContainerResult,
+    scheme: SeverityScheme,
+) -> Result<()> {
+    for finding in &result.findings {
+        writer
+            .serialize(CsvContainerFinding {
+                container_id: get_container_id(&result.container),
+                container_name: get_container_name(&result.container),
+                image: get_container_image(&result.container),
+                image_digest: result.container.image.as_deref(),
+                state: get_container_state(&result.container),
+                labels: get_exported_labels(&result.container)
+                    .iter()
+                    .map(|(key, value)| format!("{}={}", key, value))
+                    .collect::<Vec<_>>()
+                    .join("; "),
+                host: result.host.as_deref(),
+                severity: severity_to_string(&finding.risk, scheme),
+                id: &finding.kind,
+                line: finding.line,
+                description: &finding.description,
+                rule_name: finding.rule_name.as_deref(),
+                remediation: finding.remediation.as_deref(),
+                references: finding.references.join("; "),
+                tags: finding.tags.join("; "),
+                category: finding.category.as_deref(),
+                compliance: compliance_controls(&finding.compliance),
+                first_seen: finding.seen.as_ref().map(|s| s.first_seen.as_str()),
+                last_seen: finding.seen.as_ref().map(|s| s.last_seen.as_str()),
+                is_new: finding.seen.as_ref().map(|s| s.is_new),
             })
             .context("Failed to write CSV row")?;
-        }
     }
+    Ok(())
+}
 
-    wtr.flush().context("Failed to flush CSV writer")?;
+/// Writes one line per finding of a scanned target.
+fn write_ndjson(w: &mut dyn Write, target: &ScannedTarget, scheme: SeverityScheme) -> Result<()> {
+    for finding in target.flat_findings(scheme) {
+        let line = serde_json::to_string(&finding).context("Failed to serialize finding to JSON")?;
+        writeln!(w, "{}", line).context("Failed to write NDJSON report")?;
+    }
     Ok(())
 }
 
@@ -202,102 +935,685 @@ fn get_container_name(container: &ContainerInspectResponse) -> String {
         .to_string()
 }
 
+fn get_container_image(container: &ContainerInspectResponse) -> Option<&str> {
+    container.config.as_ref().and_then(|c| c.image.as_deref())
+}
+
+fn get_container_state(container: &ContainerInspectResponse) -> Option<String> {
+    container
+        .state
+        .as_ref()
+        .and_then(|s| s.status.as_ref())
+        .map(ToString::to_string)
+        .filter(|s| !s.is_empty())
+}
+
+fn get_exported_labels(container: &ContainerInspectResponse) -> BTreeMap<String, String> {
+    let labels = container.config.as_ref().and_then(|c| c.labels.as_ref());
+    labels
+        .into_iter()
+        .flatten()
+        .filter(|(key, _)| EXPORTED_LABELS.contains(&key.as_str()))
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect()
+}
+
 // ─────────────────────────────────────────────────────────────────
 // Dockerfile-specific Export Logic
 // ─────────────────────────────────────────────────────────────────
 
-fn dockerfile_to_json(path: &Path, findings: &[Finding]) -> DockerfileScanResult {
-    let (critical, medium, low, info) = count_by_severity(findings);
+fn dockerfile_to_json(path: &Path, findings: &[Finding], summary: &ScanSummary, scheme: SeverityScheme) -> DockerfileScanResult {
+    let counts = SeverityCounts::from_findings(findings);
+
+    DockerfileScanResult {
+        schema_version: REPORT_SCHEMA_VERSION,
+        metadata: summary.metadata.clone(),
+        dockerfile_path: path.display().to_string(),
+        total_findings: findings.len(),
+        critical_count: counts.critical,
+        high_count: counts.high,
+        medium_count: counts.medium,
+        low_count: counts.low,
+        info_count: counts.informative,
+        findings: file_findings_to_json(findings, scheme),
+        summary: summary.clone(),
+    }
+}
+
+fn env_file_to_json(path: &Path, findings: &[Finding], summary: &ScanSummary, scheme: SeverityScheme) -> EnvFileScanResult {
+    let counts = SeverityCounts::from_findings(findings);
+
+    EnvFileScanResult {
+        schema_version: REPORT_SCHEMA_VERSION,
+        metadata: summary.metadata.clone(),
+        env_file_path: path.display().to_string(),
+        total_findings: findings.len(),
+        critical_count: counts.critical,
+        high_count: counts.high,
+        medium_count: counts.medium,
+        low_count: counts.low,
+        info_count: counts.informative,
+        findings: file_findings_to_json(findings, scheme),
+        summary: summary.clone(),
+    }
+}
+
+fn file_to_json(path: ExportableFilePath, file: &FileResult, scheme: SeverityScheme) -> ExportableFile {
+    let counts = SeverityCounts::from_findings(&file.findings);
 
-    let exportable_findings = findings
+    ExportableFile {
+        path,
+        total_findings: file.findings.len(),
+        critical_count: counts.critical,
+        high_count: counts.high,
+        medium_count: counts.medium,
+        low_count: counts.low,
+        info_count: counts.informative,
+        findings: file_findings_to_json(&file.findings, scheme),
+    }
+}
+
+fn network_to_json(result: &NetworkResult, scheme: SeverityScheme) -> ExportableNetwork {
+    let counts = SeverityCounts::from_findings(&result.findings);
+    let network = &result.network;
+
+    ExportableNetwork {
+        network_id: network.id.clone().unwrap_or_default(),
+        network_name: network.name.clone().unwrap_or_default(),
+        driver: network.driver.clone().unwrap_or_default(),
+        internal: network.internal.unwrap_or(false),
+        total_findings: result.findings.len(),
+        critical_count: counts.critical,
+        high_count: counts.high,
+        medium_count: counts.medium,
+        low_count: counts.low,
+        info_count: counts.informative,
+        findings: file_findings_to_json(&result.findings, scheme),
+    }
+}
+
+fn file_findings_to_json(findings: &[Finding], scheme: SeverityScheme) -> Vec<DockerfileFinding> {
+    findings
         .iter()
         .map(|f| DockerfileFinding {
             id: f.kind.clone(),
-            severity: severity_to_string(&f.risk),
+            severity: severity_to_string(&f.risk, scheme),
             line: f.line,
+            span: f.span.clone(),
             description: f.description.clone(),
+            rule_name: f.rule_name.clone(),
+            remediation: f.remediation.clone(),
+            references: f.references.clone(),
+            tags: f.tags.clone(),
+            category: f.category.clone(),
+            compliance: f.compliance.clone(),
+            seen: f.seen.clone(),
+            debug: f.debug.clone(),
         })
-        .collect();
-
-    DockerfileScanResult {
-        dockerfile_path: path.display().to_string(),
-        total_findings: findings.len(),
-        critical_count: critical,
-        medium_count: medium,
-        low_count: low,
-        info_count: info,
-        findings: exportable_findings,
-    }
+        .collect()
 }
 
-fn export_dockerfile_csv(path: &Path, findings: &[Finding], output: &Option<String>) -> Result<()> {
-    let mut wtr = csv::Writer::from_writer(vec![]);
-
-    let dockerfile_path = path.display().to_string();
+/// Writes the findings of scanned files or networks, with each target's
+/// path or name in the `column` column.
+fn export_targets_csv(
+    column: &str,
+    targets: &[(String, &[Finding])],
+    scheme: SeverityScheme,
+    w: &mut dyn Write,
+) -> Result<()> {
+    let mut wtr = csv::Writer::from_writer(w);
 
     // Write header
-    wtr.write_record(["dockerfile", "severity", "id", "line", "description"])
+    wtr.write_record([
+        column,
+        "severity",
+        "id",
+        "line",
+        "description",
+        "rule_name",
+        "remediation",
+        "references",
+        "tags",
+        "category",
+        "compliance",
+        "first_seen",
+        "last_seen",
+        "is_new",
+    ])
         .context("Failed to write CSV header")?;
 
     // Write findings
-    for finding in findings {
+    for (target, finding) in targets
+        .iter()
+        .flat_map(|(target, findings)| findings.iter().map(move |f| (target, f)))
+    {
         wtr.write_record([
-            &dockerfile_path,
-            &severity_to_string(&finding.risk),
+            target,
+            &severity_to_string(&finding.risk, scheme),
             &finding.kind,
             &finding.line.map(|n| n.to_string()).unwrap_or_else(|| "".to_string()),
             &finding.description,
+            finding.rule_name.as_deref().unwrap_or_default(),
+            finding.remediation.as_deref().unwrap_or_default(),
+            &finding.references.join("; "),
+            &finding.tags.join("; "),
+            finding.category.as_deref().unwrap_or_default(),
+            &compliance_controls(&finding.compliance),
+            finding.seen.as_ref().map(|s| s.first_seen.as_str()).unwrap_or_default(),
+            finding.seen.as_ref().map(|s| s.last_seen.as_str()).unwrap_or_default(),
+            &finding.seen.as_ref().map(|s| s.is_new.to_string()).unwrap_or_default(),
         ])
         .context("Failed to write CSV row")?;
     }
 
-    let data = String::from_utf8(wtr.into_inner().context("Failed to get CSV buffer")?)
-        .context("Failed to convert CSV to UTF-8")?;
+    wtr.flush().context("Failed to flush CSV writer")
+}
+
+/// Writes a combined scan with one row per finding: `target_type` is
+/// `container`, `dockerfile`, or `env_file`, and `target` the container
+/// name or file path.
+fn export_combined_csv(
+    containers: &[ContainerResult],
+    dockerfiles: &[FileResult],
+    env_files: &[FileResult],
+    scheme: SeverityScheme,
+    w: &mut dyn Write,
+) -> Result<()> {
+    let mut wtr = csv::Writer::from_writer(w);
+    wtr.write_record([
+        "target_type",
+        "target",
+        "host",
+        "severity",
+        "id",
+        "line",
+        "description",
+        "rule_name",
+        "remediation",
+        "references",
+        "tags",
+        "category",
+        "compliance",
+        "first_seen",
+        "last_seen",
+        "is_new",
+    ])
+    .context("Failed to write CSV header")?;
 
-    write_or_print(&data, output)?;
-    Ok(())
+    let container_rows = containers.iter().flat_map(|result| {
+        let name = get_container_name(&result.container);
+        let host = result.host.clone().unwrap_or_default();
+        result.findings.iter().map(move |f| ("container", name.clone(), host.clone(), f))
+    });
+    let file_rows = [("dockerfile", dockerfiles), ("env_file", env_files)]
+        .into_iter()
+        .flat_map(|(kind, files)| files.iter().map(move |file| (kind, file)))
+        .flat_map(|(kind, file)| {
+            let path = file.path.display().to_string();
+            file.findings.iter().map(move |f| (kind, path.clone(), String::new(), f))
+        });
+
+    for (kind, target, host, finding) in container_rows.chain(file_rows) {
+        wtr.write_record([
+            kind,
+            &target,
+            &host,
+            &severity_to_string(&finding.risk, scheme),
+            &finding.kind,
+            &finding.line.map(|n| n.to_string()).unwrap_or_default(),
+            &finding.description,
+            finding.rule_name.as_deref().unwrap_or_default(),
+            finding.remediation.as_deref().unwrap_or_default(),
+            &finding.references.join("; "),
+            &finding.tags.join("; "),
+            finding.category.as_deref().unwrap_or_default(),
+            &compliance_controls(&finding.compliance),
+            finding.seen.as_ref().map(|s| s.first_seen.as_str()).unwrap_or_default(),
+            finding.seen.as_ref().map(|s| s.last_seen.as_str()).unwrap_or_default(),
+            &finding.seen.as_ref().map(|s| s.is_new.to_string()).unwrap_or_default(),
+        ])
+        .context("Failed to write CSV row")?;
+    }
+
+    wtr.flush().context("Failed to flush CSV writer")
 }
 
 // ─────────────────────────────────────────────────────────────────
 // Helpers
 // ─────────────────────────────────────────────────────────────────
 
-fn count_by_severity(findings: &[Finding]) -> (usize, usize, usize, usize) {
-    let mut critical = 0;
-    let mut medium = 0;
-    let mut low = 0;
-    let mut info = 0;
-
-    for finding in findings {
-        match finding.risk {
-            RiskLevel::High => critical += 1,
-            RiskLevel::Medium => medium += 1,
-            RiskLevel::Low => low += 1,
-            RiskLevel::Informative => info += 1,
+/// Compliance controls as `framework:control` pairs joined with `; `.
+fn compliance_controls(compliance: &BTreeMap<String, Vec<String>>) -> String {
+    compliance
+        .iter()
+        .flat_map(|(framework, controls)| controls.iter().map(move |c| format!("{}:{}", framework, c)))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+fn severity_to_string(risk: &RiskLevel, scheme: SeverityScheme) -> String {
+    scheme.label(risk).to_string()
+}
+
+fn open_writer(output: &Option<String>) -> Result<Box<dyn Write>> {
+    Ok(match output {
+        Some(path) => Box::new(
+            std::fs::File::create(path)
+                .with_context(|| format!("Failed to create output file {}", path))?,
+        ),
+        None => Box::new(std::io::stdout()),
+    })
+}
+
+/// Indents every line after the first, for nesting pretty-printed JSON.
+fn indent(text: &str, prefix: &str) -> String {
+    text.replace('\n', &format!("\n{}", prefix))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::summary::TargetKind;
+    use std::time::Duration;
+
+    fn result(id: &str, risks: &[RiskLevel]) -> ContainerResult {
+        ContainerResult {
+            container: ContainerInspectResponse {
+                id: Some(id.to_string()),
+                name: Some(format!("/{id}")),
+                ..Default::default()
+            },
+            findings: risks
+                .iter()
+                .map(|risk| Finding::new("privileged_mode", "Container is privileged", risk.clone()))
+                .collect(),
+            host: None,
         }
     }
 
-    (critical, medium, low, info)
-}
+    fn summary(results: &[ContainerResult]) -> ScanSummary {
+        ScanSummary::new(
+            TargetKind::Containers,
+            results.len(),
+            1,
+            results.iter().flat_map(|r| r.findings.iter()),
+            Duration::from_millis(5),
+            false,
+        )
+    }
 
-fn severity_to_string(risk: &RiskLevel) -> String {
-    match risk {
-        RiskLevel::High => "CRITICAL".to_string(),
-        RiskLevel::Medium => "MEDIUM".to_string(),
-        RiskLevel::Low => "LOW".to_string(),
-        RiskLevel::Informative => "INFO".to_string(),
+    fn stream_to_string(results: &[ContainerResult], format: &OutputFormat) -> String {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("report").display().to_string();
+        let mut stream = ContainerExportStream::create(format, &Some(path.clone()), SeverityScheme::default()).unwrap();
+        for result in results {
+            stream.write_result(result).unwrap();
+        }
+        stream.finish(&summary(results)).unwrap();
+        std::fs::read_to_string(path).unwrap()
     }
-}
 
-fn write_or_print(content: &str, output: &Option<String>) -> Result<()> {
-    match output {
-        Some(path) => {
-            std::fs::write(path, content)
-                .with_context(|| format!("Failed to write output to {}", path))?;
+    fn export_to_string(registry: &ExporterRegistry, name: &str, report: &ScanReport) -> String {
+        let mut out = Vec::new();
+        registry.export(name, report, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn streamed_json_is_a_valid_report() {
+        let results = vec![
+            result("web", &[RiskLevel::High]),
+            result("db", &[RiskLevel::Low, RiskLevel::Medium]),
+        ];
+
+        let report: serde_json::Value =
+            serde_json::from_str(&stream_to_string(&results, &OutputFormat::Json)).unwrap();
+
+        assert_eq!(report["containers"].as_array().unwrap().len(), 2);
+        assert_eq!(report["containers"][1]["container_name"], "db");
+        assert_eq!(report["summary"]["findings"]["total"], 3);
+    }
+
+    #[test]
+    fn exports_tag_containers_with_their_host() {
+        let mut remote = result("web", &[RiskLevel::High]);
+        remote.host = Some("web-1".to_string());
+        let results = vec![remote, result("db", &[RiskLevel::Low])];
+
+        let report: serde_json::Value =
+            serde_json::from_str(&stream_to_string(&results, &OutputFormat::Json)).unwrap();
+        assert_eq!(report["containers"][0]["host"], "web-1");
+        assert!(report["containers"][1].get("host").is_none());
+
+        let csv = stream_to_string(&results, &OutputFormat::Csv);
+        assert!(csv.lines().next().unwrap().contains("labels,host,"));
+        assert!(csv.lines().nth(1).unwrap().contains(",web,,,,,web-1,HIGH,"));
+        assert!(csv.lines().nth(2).unwrap().contains(",db,,,,,,LOW,"));
+    }
+
+    #[test]
+    fn streamed_json_writes_metadata_before_summary() {
+        let results = vec![result("web", &[RiskLevel::High])];
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("report.json").display().to_string();
+        let mut stream = ContainerExportStream::create(&OutputFormat::Json, &Some(path.clone()), SeverityScheme::default()).unwrap();
+        stream.write_result(&results[0]).unwrap();
+        let metadata = ScanMetadata::new(std::time::UNIX_EPOCH).with_filter("only", Some("privileged_mode"));
+        stream.finish(&summary(&results).with_metadata(metadata)).unwrap();
+
+        let raw = std::fs::read_to_string(path).unwrap();
+        assert!(raw.find("\"metadata\"").unwrap() < raw.find("\"summary\"").unwrap());
+        let report: serde_json::Value = serde_json::from_str(&raw).unwrap();
+        assert_eq!(report["metadata"]["scanned_at"], "1970-01-01T00:00:00Z");
+        assert_eq!(report["metadata"]["filters"]["only"], "privileged_mode");
+        assert!(report["summary"].get("metadata").is_none());
+    }
+
+    #[test]
+    fn streamed_json_handles_no_containers() {
+        let report: serde_json::Value =
+            serde_json::from_str(&stream_to_string(&[], &OutputFormat::Json)).unwrap();
+
+        assert_eq!(report["containers"], serde_json::json!([]));
+        assert_eq!(report["summary"]["targets_scanned"], 0);
+    }
+
+    #[test]
+    fn streamed_csv_writes_one_row_per_finding() {
+        let results = vec![
+            result("web", &[RiskLevel::High]),
+            result("db", &[RiskLevel::Low, RiskLevel::Medium]),
+        ];
+
+        let csv = stream_to_string(&results, &OutputFormat::Csv);
+
+        assert_eq!(csv.lines().count(), 4);
+        assert!(csv.starts_with("container_id,container_name,image,image_digest,state,labels,host,severity,id,line,description"));
+    }
+
+    #[test]
+    fn container_exports_include_image_state_and_labels() {
+        let mut results = vec![result("web", &[RiskLevel::High])];
+        results[0].container.image = Some("sha256:4f2a9c1e".to_string());
+        results[0].container.config = Some(bollard::models::ContainerConfig {
+            image: Some("nginx:1.25".to_string()),
+            labels: Some(std::collections::HashMap::from([
+                ("com.docker.compose.project".to_string(), "shop".to_string()),
+                ("com.docker.compose.config-hash".to_string(), "9b1f".to_string()),
+            ])),
+            ..Default::default()
+        });
+        results[0].container.state = Some(bollard::models::ContainerState {
+            status: Some(bollard::models::ContainerStateStatusEnum::RUNNING),
+            ..Default::default()
+        });
+
+        let report: serde_json::Value =
+            serde_json::from_str(&stream_to_string(&results, &OutputFormat::Json)).unwrap();
+        let container = &report["containers"][0];
+        assert_eq!(container["image"], "nginx:1.25");
+        assert_eq!(container["image_digest"], "sha256:4f2a9c1e");
+        assert_eq!(container["state"], "running");
+        assert_eq!(container["labels"], serde_json::json!({ "com.docker.compose.project": "shop" }));
+
+        let csv = stream_to_string(&results, &OutputFormat::Csv);
+        assert!(csv.lines().nth(1).unwrap().starts_with(
+            "web,web,nginx:1.25,sha256:4f2a9c1e,running,com.docker.compose.project=shop,,HIGH,privileged_mode,"
+        ));
+    }
+
+    #[test]
+    fn streamed_reports_include_finding_history() {
+        let mut results = vec![result("web", &[RiskLevel::High, RiskLevel::Low])];
+        results[0].findings[0].seen = Some(FindingHistory {
+            first_seen: "2024-05-01T00:00:00Z".to_string(),
+            last_seen: "2024-05-02T00:00:00Z".to_string(),
+            is_new: false,
+        });
+
+        let report: serde_json::Value =
+            serde_json::from_str(&stream_to_string(&results, &OutputFormat::Json)).unwrap();
+        let findings = &report["containers"][0]["findings"];
+        assert_eq!(findings[0]["first_seen"], "2024-05-01T00:00:00Z");
+        assert_eq!(findings[0]["is_new"], false);
+        assert!(findings[1].get("first_seen").is_none());
+
+        let csv = stream_to_string(&results, &OutputFormat::Csv);
+        assert!(csv.lines().next().unwrap().ends_with("first_seen,last_seen,is_new"));
+        assert!(csv.lines().nth(1).unwrap().ends_with("2024-05-01T00:00:00Z,2024-05-02T00:00:00Z,false"));
+    }
+
+    #[test]
+    fn streamed_ndjson_writes_one_object_per_finding() {
+        let mut results = vec![
+            result("web", &[RiskLevel::High]),
+            result("db", &[RiskLevel::Medium, RiskLevel::Low]),
+        ];
+        results[1].host = Some("prod".to_string());
+
+        let ndjson = stream_to_string(&results, &OutputFormat::Ndjson);
+        let lines: Vec<serde_json::Value> = ndjson.lines().map(|l| serde_json::from_str(l).unwrap()).collect();
+
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0]["target_type"], "container");
+        assert_eq!(lines[0]["target"], "web");
+        assert_eq!(lines[0]["target_id"], "web");
+        assert!(lines[0].get("host").is_none());
+        assert_eq!(lines[2]["host"], "prod");
+        assert_eq!(lines[2]["id"], "privileged_mode");
+        assert_eq!(lines[2]["severity"], "LOW");
+        let report = ScanReport::from_scan(summary(&results), ScanFindings::Containers(results.clone()));
+        assert_eq!(ndjson, export_to_string(&ExporterRegistry::default(), "ndjson", &report));
+    }
+
+    #[test]
+    fn streamed_defectdojo_report_is_exported_on_finish() {
+        let results = vec![
+            result("web", &[RiskLevel::High]),
+            result("db", &[RiskLevel::Low, RiskLevel::Medium]),
+        ];
+
+        let report: serde_json::Value =
+            serde_json::from_str(&stream_to_string(&results, &OutputFormat::Defectdojo)).unwrap();
+
+        assert_eq!(report["findings"].as_array().unwrap().len(), 3);
+        assert_eq!(report["findings"][1]["unique_id_from_tool"], "privileged_mode:db");
+    }
+
+    #[test]
+    fn json_exporter_matches_streamed_report() {
+        let results = vec![
+            result("web", &[RiskLevel::High]),
+            result("db", &[RiskLevel::Low]),
+        ];
+        let streamed = stream_to_string(&results, &OutputFormat::Json);
+        let report = ScanReport::from_scan(summary(&results), ScanFindings::Containers(results));
+
+        let exported = export_to_string(&ExporterRegistry::default(), "JSON", &report);
+
+        assert_eq!(exported, streamed);
+    }
+
+    #[test]
+    fn dockerfile_json_counts_critical_and_high_separately() {
+        let findings: Vec<Finding> = [RiskLevel::Critical, RiskLevel::High, RiskLevel::High, RiskLevel::Low]
+            .into_iter()
+            .map(|risk| Finding::new("DF002", "Runs as root", risk))
+            .collect();
+        let summary = ScanSummary::new(TargetKind::Dockerfiles, 1, 1, &findings, Duration::ZERO, false);
+        let report = ScanReport::from_scan(
+            summary,
+            ScanFindings::Dockerfile {
+                path: std::path::PathBuf::from("Dockerfile"),
+                findings,
+            },
+        );
+
+        let json: serde_json::Value =
+            serde_json::from_str(&export_to_string(&ExporterRegistry::default(), "json", &report)).unwrap();
+
+        assert_eq!(json["total_findings"], 4);
+        assert_eq!(json["critical_count"], 1);
+        assert_eq!(json["high_count"], 2);
+        assert_eq!(json["low_count"], 1);
+    }
+
+    #[test]
+    fn severities_are_named_by_the_report_scheme() {
+        let findings = vec![Finding::new("DF002", "Runs as root", RiskLevel::High)];
+        let summary = ScanSummary::new(TargetKind::Dockerfiles, 1, 1, &findings, Duration::ZERO, false);
+        let report = |scheme: SeverityScheme| {
+            ScanReport::from_scan(
+                summary.clone(),
+                ScanFindings::Dockerfile {
+                    path: std::path::PathBuf::from("Dockerfile"),
+                    findings: findings.clone(),
+                },
+            )
+            .with_severity_scheme(scheme)
+        };
+        let severity = |report: &ScanReport| {
+            let json: serde_json::Value =
+                serde_json::from_str(&export_to_string(&ExporterRegistry::default(), "json", report)).unwrap();
+            json["findings"][0]["severity"].clone()
+        };
+
+        // Reports with different schemes can be exported side by side
+        let (legacy, cli) = (report(SeverityScheme::Legacy), report(SeverityScheme::Cli));
+        assert_eq!(severity(&legacy), "CRITICAL");
+        assert_eq!(severity(&cli), "high");
+        assert_eq!(severity(&report(SeverityScheme::default())), "HIGH");
+        assert!(export_to_string(&ExporterRegistry::default(), "csv", &cli).contains(",high,"));
+    }
+
+    /// Resolves `$ref`s into the schema's `$defs`.
+    fn resolve<'a>(root: &'a serde_json::Value, schema: &'a serde_json::Value) -> &'a serde_json::Value {
+        match schema["$ref"].as_str().and_then(|r| r.strip_prefix("#/$defs/")) {
+            Some(name) => resolve(root, &root["$defs"][name]),
+            None => schema,
         }
-        None => {
-            println!("{}", content);
+    }
+
+    /// Property schemas of an object schema, including those of its `allOf` parts.
+    fn properties<'a>(
+        root: &'a serde_json::Value,
+        schema: &'a serde_json::Value,
+    ) -> serde_json::Map<String, serde_json::Value> {
+        let schema = resolve(root, schema);
+        let mut properties = schema["properties"].as_object().cloned().unwrap_or_default();
+        for part in schema["allOf"].as_array().into_iter().flatten() {
+            properties.extend(self::properties(root, part));
         }
+        properties
+    }
+
+    /// Asserts that every field of `value` is declared in `schema`.
+    fn assert_declared(root: &serde_json::Value, schema: &serde_json::Value, value: &serde_json::Value, at: &str) {
+        match value {
+            serde_json::Value::Object(fields) => {
+                let declared = properties(root, schema);
+                let additional = &resolve(root, schema)["additionalProperties"];
+                for (name, field) in fields {
+                    let field_schema = declared
+                        .get(name)
+                        .or(Some(additional).filter(|s| s.is_object()))
+                        .unwrap_or_else(|| panic!("{at}.{name} is not in the report schema"));
+                    assert_declared(root, field_schema, field, &format!("{at}.{name}"));
+                }
+            }
+            serde_json::Value::Array(items) => {
+                for item in items {
+                    assert_declared(root, &resolve(root, schema)["items"], item, &format!("{at}[]"));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    #[test]
+    fn reports_match_the_published_schema() {
+        let schema: serde_json::Value = serde_json::from_str(REPORT_SCHEMA).unwrap();
+        assert_eq!(schema["$defs"]["schemaVersion"]["const"], REPORT_SCHEMA_VERSION);
+
+        let mut results = vec![result("web", &[RiskLevel::High])];
+        let finding = &mut results[0].findings[0];
+        finding.rule_name = Some("Privileged Mode".to_string());
+        finding.remediation = Some("Drop --privileged".to_string());
+        finding.references = vec!["https://docs.docker.com".to_string()];
+        finding.tags = vec!["isolation".to_string()];
+        finding.category = Some("runtime".to_string());
+        finding.compliance.insert("nist-800-190".to_string(), vec!["4.4.3".to_string()]);
+        finding.line = Some(3);
+        finding.seen = Some(FindingHistory {
+            first_seen: "2024-05-01T00:00:00Z".to_string(),
+            last_seen: "2024-05-02T00:00:00Z".to_string(),
+            is_new: true,
+        });
+        results[0].host = Some("web-1".to_string());
+        let findings = results[0].findings.clone();
+        let summary = summary(&results).with_scores(vec![crate::output::score::TargetScore {
+            target: "web".to_string(),
+            score: crate::output::score::SecurityScore::new(80),
+        }])
+        .with_metadata(
+            ScanMetadata::new(std::time::UNIX_EPOCH)
+                .with_daemon(Some(crate::output::metadata::DaemonInfo {
+                    version: Some("27.0.1".to_string()),
+                    ..Default::default()
+                }))
+                .with_filter("only", Some("privileged_mode")),
+        );
+
+        let containers: serde_json::Value =
+            serde_json::from_str(&stream_to_string(&results, &OutputFormat::Json)).unwrap();
+        assert_eq!(containers["schema_version"], REPORT_SCHEMA_VERSION);
+        assert_declared(&schema, &schema["$defs"]["containerReport"], &containers, "containers");
+
+        let dockerfile = serde_json::to_value(dockerfile_to_json(Path::new("Dockerfile"), &findings, &summary, SeverityScheme::default())).unwrap();
+        assert_eq!(dockerfile["schema_version"], REPORT_SCHEMA_VERSION);
+        assert_declared(&schema, &schema["$defs"]["dockerfileReport"], &dockerfile, "dockerfile");
+
+        let env_file = serde_json::to_value(env_file_to_json(Path::new(".env"), &findings, &summary, SeverityScheme::default())).unwrap();
+        assert_declared(&schema, &schema["$defs"]["envFileReport"], &env_file, "env_file");
+
+        let networks = NetworkScanResult {
+            schema_version: REPORT_SCHEMA_VERSION,
+            metadata: summary.metadata.clone(),
+            networks: vec![network_to_json(&NetworkResult {
+                network: bollard::models::Network {
+                    id: Some("abc".to_string()),
+                    name: Some("backend".to_string()),
+                    ..Default::default()
+                },
+                findings,
+            }, SeverityScheme::default())],
+            summary,
+        };
+        let networks = serde_json::to_value(networks).unwrap();
+        assert_declared(&schema, &schema["$defs"]["networkReport"], &networks, "networks");
+    }
+
+    #[test]
+    fn registry_accepts_custom_exporters() {
+        struct CountExporter;
+
+        impl Exporter for CountExporter {
+            fn export(&self, report: &ScanReport, w: &mut dyn Write) -> Result<()> {
+                let total = report.summary.as_ref().map_or(0, |s| s.findings.total);
+                write!(w, "{}", total)?;
+                Ok(())
+            }
+        }
+
+        let results = vec![result("web", &[RiskLevel::High, RiskLevel::Low])];
+        let report = ScanReport::from_scan(summary(&results), ScanFindings::Containers(results));
+        let mut registry = ExporterRegistry::default();
+        registry.register("count", CountExporter);
+
+        assert_eq!(registry.names().collect::<Vec<_>>(), ["count", "csv", "defectdojo", "gitlab", "html", "json", "junit", "ndjson", "ocsf", "sarif"]);
+        assert_eq!(export_to_string(&registry, "count", &report), "2");
+
+        let err = registry.export("xml", &report, &mut Vec::new()).unwrap_err();
+        assert!(err.to_string().contains("available: count, csv, defectdojo, gitlab, html, json, junit, ndjson, ocsf, sarif"));
     }
-    Ok(())
 }
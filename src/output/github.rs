@@ -0,0 +1,570 @@
+//! GitHub pull request comments for Dockerfile scan reports.
+//!
+//! Turns JSON reports written by `valeris docker-file --format json` into
+//! review feedback on a pull request:
+//!
+//! * one summary comment, found again by a hidden marker and updated in
+//!   place on every run;
+//! * inline review comments on Dockerfile lines the pull request adds,
+//!   skipping comments already posted by a previous run.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{bail, Context, Result};
+use reqwest::{Method, RequestBuilder};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::error::ValerisError;
+
+/// Default GitHub REST API endpoint
+pub const DEFAULT_API_URL: &str = "https://api.github.com";
+
+/// Hidden marker identifying the summary comment
+pub const SUMMARY_MARKER: &str = "<!-- valeris-report -->";
+
+/// Findings listed in the summary comment before the rest are elided
+const MAX_SUMMARY_FINDINGS: usize = 50;
+
+/// Page size for paginated API requests (the GitHub maximum)
+const PER_PAGE: usize = 100;
+
+// ─────────────────────────────────────────────────────────────────
+// Report Input
+// ─────────────────────────────────────────────────────────────────
+
+/// A Dockerfile scan report as exported with `--format json`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct DockerfileReport {
+    pub dockerfile_path: String,
+    pub findings: Vec<ReportFinding>,
+}
+
+/// One finding from a [`DockerfileReport`].
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReportFinding {
+    pub id: String,
+    pub severity: String,
+    pub line: Option<usize>,
+    pub description: String,
+    #[serde(default)]
+    pub rule_name: Option<String>,
+    #[serde(default)]
+    pub remediation: Option<String>,
+}
+
+/// A single Dockerfile report, or several combined by `valeris merge`.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ReportFile {
+    Merged { dockerfiles: Vec<DockerfileReport> },
+    Single(DockerfileReport),
+}
+
+/// Reads Dockerfile JSON reports, including merged ones.
+///
+/// # Errors
+///
+/// * [`ValerisError::InputRead`] if a file cannot be read
+/// * [`ValerisError::InputParse`] if a file is not a Dockerfile JSON report
+pub fn load_reports(paths: &[PathBuf]) -> crate::error::Result<Vec<DockerfileReport>> {
+    let mut reports = Vec::new();
+    for path in paths {
+        let raw = fs::read_to_string(path).map_err(|source| ValerisError::InputRead {
+            path: path.clone(),
+            source,
+        })?;
+        let file = serde_json::from_str(&raw).map_err(|e| ValerisError::InputParse {
+            path: path.clone(),
+            message: e.to_string(),
+        })?;
+        match file {
+            ReportFile::Merged { dockerfiles } => reports.extend(dockerfiles),
+            ReportFile::Single(report) => reports.push(report),
+        }
+    }
+    Ok(reports)
+}
+
+// ─────────────────────────────────────────────────────────────────
+// Comment Rendering
+// ─────────────────────────────────────────────────────────────────
+
+/// An inline review comment on one line of a changed file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct InlineComment {
+    pub path: String,
+    pub line: usize,
+    pub body: String,
+}
+
+/// Renders the summary comment for all reports.
+pub fn summary_body(reports: &[DockerfileReport]) -> String {
+    let findings: Vec<(&str, &ReportFinding)> = reports
+        .iter()
+        .flat_map(|r| r.findings.iter().map(move |f| (r.dockerfile_path.as_str(), f)))
+        .collect();
+
+    let mut body = format!("{}\n## Valeris scan results\n\n", SUMMARY_MARKER);
+
+    if findings.is_empty() {
+        body.push_str(&format!(
+            "No security issues found in {} Dockerfile(s).\n",
+            reports.len()
+        ));
+        return body;
+    }
+
+    let mut by_severity: BTreeMap<usize, (&str, usize)> = BTreeMap::new();
+    for (_, finding) in &findings {
+        let severity = finding.severity.as_str();
+        by_severity.entry(severity_rank(severity)).or_insert((severity, 0)).1 += 1;
+    }
+    let counts: Vec<String> = by_severity
+        .values()
+        .map(|(severity, count)| format!("{} {}", count, severity.to_lowercase()))
+        .collect();
+    body.push_str(&format!(
+        "**{} finding(s)** in {} Dockerfile(s): {}\n\n",
+        findings.len(),
+        reports.len(),
+        counts.join(", ")
+    ));
+
+    body.push_str("| Severity | Rule | File | Description |\n");
+    body.push_str("|----------|------|------|-------------|\n");
+    for (path, finding) in findings.iter().take(MAX_SUMMARY_FINDINGS) {
+        let location = match finding.line {
+            Some(line) => format!("`{}:{}`", path, line),
+            None => format!("`{}`", path),
+        };
+        body.push_str(&format!(
+            "| {} | {} | {} | {} |\n",
+            finding.severity,
+            finding.id,
+            location,
+            escape_cell(&finding.description)
+        ));
+    }
+    if findings.len() > MAX_SUMMARY_FINDINGS {
+        body.push_str(&format!(
+            "\n…and {} more. See the full report in the CI artifacts.\n",
+            findings.len() - MAX_SUMMARY_FINDINGS
+        ));
+    }
+
+    body
+}
+
+/// Builds inline comments for findings on lines the pull request adds.
+///
+/// # Arguments
+///
+/// * `reports` - Dockerfile reports to comment on
+/// * `changed` - Added line numbers keyed by repository-relative file path
+///
+/// # Returns
+///
+/// One comment per finding whose Dockerfile is part of the pull request and
+/// whose line was added by it
+pub fn inline_comments(
+    reports: &[DockerfileReport],
+    changed: &BTreeMap<String, BTreeSet<usize>>,
+) -> Vec<InlineComment> {
+    let mut comments = Vec::new();
+
+    for report in reports {
+        let Some((path, lines)) = changed
+            .iter()
+            .find(|(file, _)| same_file(&report.dockerfile_path, file))
+        else {
+            continue;
+        };
+
+        for finding in &report.findings {
+            let Some(line) = finding.line.filter(|l| lines.contains(l)) else {
+                continue;
+            };
+            comments.push(InlineComment {
+                path: path.clone(),
+                line,
+                body: inline_body(finding),
+            });
+        }
+    }
+
+    comments
+}
+
+/// Returns the new-file line numbers added by a unified diff patch.
+pub fn added_lines(patch: &str) -> BTreeSet<usize> {
+    let mut added = BTreeSet::new();
+    let mut line = 0;
+
+    for text in patch.lines() {
+        if let Some(header) = text.strip_prefix("@@ ") {
+            // @@ -old,len +new,len @@
+            line = header
+                .split_whitespace()
+                .find_map(|part| part.strip_prefix('+'))
+                .and_then(|range| range.split(',').next())
+                .and_then(|start| start.parse().ok())
+                .unwrap_or(0);
+        } else if text.starts_with('+') {
+            added.insert(line);
+            line += 1;
+        } else if !text.starts_with('-') && !text.starts_with('\\') {
+            line += 1;
+        }
+    }
+
+    added
+}
+
+fn inline_body(finding: &ReportFinding) -> String {
+    let title = finding.rule_name.as_deref().unwrap_or(&finding.id);
+    let mut body = format!(
+        "<!-- valeris:{} -->\n**{} · {}: {}**\n\n{}\n",
+        finding.id, finding.severity, finding.id, title, finding.description
+    );
+    if let Some(fix) = &finding.remediation {
+        body.push_str(&format!("\n**Fix:** {}\n", fix));
+    }
+    body
+}
+
+/// The hidden `<!-- valeris:ID -->` line identifying an inline comment's rule.
+fn comment_marker(body: &str) -> Option<&str> {
+    body.lines().next().filter(|line| line.starts_with("<!-- valeris:"))
+}
+
+/// Whether a report path refers to a repository-relative pull request file.
+fn same_file(report_path: &str, pr_file: &str) -> bool {
+    let normalized = report_path.replace('\\', "/");
+    let normalized = normalized.trim_start_matches("./");
+    normalized == pr_file || normalized.ends_with(&format!("/{}", pr_file))
+}
+
+fn severity_rank(severity: &str) -> usize {
+    match severity.to_uppercase().as_str() {
+        "CRITICAL" => 0,
+        "HIGH" => 1,
+        "MEDIUM" => 2,
+        "LOW" => 3,
+        _ => 4,
+    }
+}
+
+fn escape_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
+
+// ─────────────────────────────────────────────────────────────────
+// GitHub API
+// ─────────────────────────────────────────────────────────────────
+
+/// What [`publish_pr_report`] posted.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PublishOutcome {
+    /// Whether an existing summary comment was updated rather than created
+    pub summary_updated: bool,
+    /// Inline comments posted by this run
+    pub inline_comments: usize,
+}
+
+/// Minimal GitHub REST client for one repository.
+pub struct GitHubClient {
+    http: reqwest::Client,
+    api_url: String,
+    repo: String,
+    token: String,
+}
+
+impl GitHubClient {
+    /// Creates a client for `repo` (`owner/name`).
+    ///
+    /// # Errors
+    ///
+    /// * [`ValerisError::InvalidFilter`] if `repo` is not `owner/name`
+    pub fn new(
+        api_url: impl Into<String>,
+        repo: impl Into<String>,
+        token: impl Into<String>,
+    ) -> crate::error::Result<Self> {
+        let repo = repo.into();
+        if repo.split('/').filter(|part| !part.is_empty()).count() != 2 {
+            return Err(ValerisError::InvalidFilter(format!(
+                "Invalid --repo '{}': expected owner/name",
+                repo
+            )));
+        }
+
+        Ok(Self {
+            http: reqwest::Client::new(),
+            api_url: api_url.into().trim_end_matches('/').to_string(),
+            repo,
+            token: token.into(),
+        })
+    }
+
+    fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        self.http
+            .request(method, format!("{}/repos/{}/{}", self.api_url, self.repo, path))
+            .bearer_auth(&self.token)
+            .header("Accept", "application/vnd.github+json")
+            .header("User-Agent", concat!("valeris/", env!("CARGO_PKG_VERSION")))
+    }
+
+    async fn send(&self, request: RequestBuilder) -> Result<Value> {
+        let response = request.send().await.context("Failed to reach the GitHub API")?;
+        let status = response.status();
+        let text = response.text().await.context("Failed to read GitHub API response")?;
+        if !status.is_success() {
+            bail!("GitHub API returned {}: {}", status, text);
+        }
+        serde_json::from_str(&text).context("Invalid GitHub API response")
+    }
+
+    async fn send_json(&self, method: Method, path: &str, body: &Value) -> Result<Value> {
+        let request = self
+            .request(method, path)
+            .header("Content-Type", "application/json")
+            .body(body.to_string());
+        self.send(request).await
+    }
+
+    /// Fetches every item of a paginated list endpoint.
+    async fn list(&self, path: &str) -> Result<Vec<Value>> {
+        let mut items = Vec::new();
+        for page in 1.. {
+            let request = self
+                .request(Method::GET, path)
+                .query(&[("per_page", PER_PAGE), ("page", page)]);
+            let Value::Array(batch) = self.send(request).await? else {
+                bail!("Expected a list from {}", path);
+            };
+            let done = batch.len() < PER_PAGE;
+            items.extend(batch);
+            if done {
+                break;
+            }
+        }
+        Ok(items)
+    }
+
+    /// Returns the head commit SHA of a pull request.
+    async fn head_sha(&self, pr: u64) -> Result<String> {
+        let pull = self.send(self.request(Method::GET, &format!("pulls/{}", pr))).await?;
+        pull["head"]["sha"]
+            .as_str()
+            .map(str::to_string)
+            .context("Pull request has no head commit")
+    }
+
+    /// Returns the lines added by a pull request, keyed by file path.
+    async fn added_lines(&self, pr: u64) -> Result<BTreeMap<String, BTreeSet<usize>>> {
+        let files = self.list(&format!("pulls/{}/files", pr)).await?;
+        Ok(files
+            .iter()
+            .filter_map(|file| {
+                let path = file["filename"].as_str()?;
+                let patch = file["patch"].as_str()?;
+                Some((path.to_string(), added_lines(patch)))
+            })
+            .collect())
+    }
+
+    /// Creates the summary comment, or updates it if a previous run posted one.
+    ///
+    /// Returns `true` if an existing comment was updated.
+    async fn upsert_summary(&self, pr: u64, body: &str) -> Result<bool> {
+        let comments = self.list(&format!("issues/{}/comments", pr)).await?;
+        let existing = comments.iter().find_map(|c| {
+            c["body"]
+                .as_str()
+                .filter(|b| b.starts_with(SUMMARY_MARKER))
+                .and(c["id"].as_u64())
+        });
+
+        let payload = serde_json::json!({ "body": body });
+        match existing {
+            Some(id) => {
+                self.send_json(Method::PATCH, &format!("issues/comments/{}", id), &payload)
+                    .await?;
+                Ok(true)
+            }
+            None => {
+                self.send_json(Method::POST, &format!("issues/{}/comments", pr), &payload)
+                    .await?;
+                Ok(false)
+            }
+        }
+    }
+
+    /// Returns `(path, line, marker)` for Valeris review comments already on
+    /// the pull request.
+    async fn review_comments(&self, pr: u64) -> Result<BTreeSet<(String, usize, String)>> {
+        let comments = self.list(&format!("pulls/{}/comments", pr)).await?;
+        Ok(comments
+            .iter()
+            .filter_map(|c| {
+                Some((
+                    c["path"].as_str()?.to_string(),
+                    usize::try_from(c["line"].as_u64()?).ok()?,
+                    comment_marker(c["body"].as_str()?)?.to_string(),
+                ))
+            })
+            .collect())
+    }
+
+    /// Posts a review with the given inline comments.
+    async fn create_review(&self, pr: u64, commit_id: &str, comments: &[InlineComment]) -> Result<()> {
+        let comments: Vec<Value> = comments
+            .iter()
+            .map(|c| {
+                serde_json::json!({
+                    "path": c.path,
+                    "line": c.line,
+                    "side": "RIGHT",
+                    "body": c.body,
+                })
+            })
+            .collect();
+        let payload = serde_json::json!({
+            "commit_id": commit_id,
+            "event": "COMMENT",
+            "comments": comments,
+        });
+        self.send_json(Method::POST, &format!("pulls/{}/reviews", pr), &payload)
+            .await?;
+        Ok(())
+    }
+}
+
+/// Posts Dockerfile findings to a pull request.
+///
+/// Creates or updates the summary comment, then posts one review with an
+/// inline comment for every finding on a line added by the pull request that
+/// has not been commented on before.
+///
+/// # Arguments
+///
+/// * `client` - Client for the pull request's repository
+/// * `pr` - Pull request number
+/// * `reports` - Dockerfile reports to publish
+///
+/// # Errors
+///
+/// * [`ValerisError::GitHub`] if any API request fails
+pub async fn publish_pr_report(
+    client: &GitHubClient,
+    pr: u64,
+    reports: &[DockerfileReport],
+) -> crate::error::Result<PublishOutcome> {
+    async {
+        let summary_updated = client.upsert_summary(pr, &summary_body(reports)).await?;
+
+        let changed = client.added_lines(pr).await?;
+        let existing = client.review_comments(pr).await?;
+        let comments: Vec<InlineComment> = inline_comments(reports, &changed)
+            .into_iter()
+            .filter(|c| {
+                let marker = comment_marker(&c.body).unwrap_or_default().to_string();
+                !existing.contains(&(c.path.clone(), c.line, marker))
+            })
+            .collect();
+
+        if !comments.is_empty() {
+            let commit_id = client.head_sha(pr).await?;
+            client.create_review(pr, &commit_id, &comments).await?;
+        }
+
+        Ok(PublishOutcome {
+            summary_updated,
+            inline_comments: comments.len(),
+        })
+    }
+    .await
+    .map_err(ValerisError::GitHub)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(path: &str, findings: Vec<ReportFinding>) -> DockerfileReport {
+        DockerfileReport {
+            dockerfile_path: path.to_string(),
+            findings,
+        }
+    }
+
+    fn finding(id: &str, severity: &str, line: Option<usize>) -> ReportFinding {
+        ReportFinding {
+            id: id.to_string(),
+            severity: severity.to_string(),
+            line,
+            description: format!("{} | issue", id),
+            rule_name: None,
+            remediation: Some("Fix it".to_string()),
+        }
+    }
+
+    #[test]
+    fn added_lines_tracks_new_file_numbers() {
+        let patch = "@@ -1,3 +1,4 @@\n FROM ubuntu\n-RUN a\n+RUN b\n+USER app\n COPY . /app\n@@ -10,2 +11,2 @@ CMD\n EXPOSE 80\n+CMD [\"run\"]";
+        assert_eq!(added_lines(patch), BTreeSet::from([2, 3, 12]));
+    }
+
+    #[test]
+    fn inline_comments_only_cover_added_lines_of_changed_files() {
+        let reports = vec![
+            report(
+                "./docker/Dockerfile",
+                vec![finding("DF001", "HIGH", Some(2)), finding("DF002", "LOW", Some(5))],
+            ),
+            report("other/Dockerfile", vec![finding("DF003", "LOW", Some(1))]),
+        ];
+        let changed = BTreeMap::from([("docker/Dockerfile".to_string(), BTreeSet::from([1, 2]))]);
+
+        let comments = inline_comments(&reports, &changed);
+
+        assert_eq!(comments.len(), 1);
+        assert_eq!(comments[0].path, "docker/Dockerfile");
+        assert_eq!(comments[0].line, 2);
+        assert_eq!(comment_marker(&comments[0].body), Some("<!-- valeris:DF001 -->"));
+        assert!(comments[0].body.contains("**Fix:** Fix it"));
+    }
+
+    #[test]
+    fn summary_body_counts_and_escapes_findings() {
+        let reports = vec![report(
+            "Dockerfile",
+            vec![finding("DF001", "HIGH", Some(1)), finding("DF002", "LOW", None)],
+        )];
+
+        let body = summary_body(&reports);
+
+        assert!(body.starts_with(SUMMARY_MARKER));
+        assert!(body.contains("**2 finding(s)** in 1 Dockerfile(s): 1 high, 1 low"));
+        assert!(body.contains("| HIGH | DF001 | `Dockerfile:1` | DF001 \\| issue |"));
+        assert!(body.contains("| LOW | DF002 | `Dockerfile` |"));
+    }
+
+    #[test]
+    fn summary_body_without_findings() {
+        let body = summary_body(&[report("Dockerfile", vec![])]);
+        assert!(body.contains("No security issues found in 1 Dockerfile(s)."));
+    }
+
+    #[test]
+    fn client_rejects_malformed_repo() {
+        assert!(GitHubClient::new(DEFAULT_API_URL, "owner/name", "t").is_ok());
+        assert!(matches!(
+            GitHubClient::new(DEFAULT_API_URL, "just-a-name", "t"),
+            Err(ValerisError::InvalidFilter(_))
+        ));
+    }
+}
@@ -0,0 +1,378 @@
+//! GitLab security report export.
+//!
+//! Writes findings in GitLab's [security report format](https://gitlab.com/gitlab-org/security-products/security-report-schemas),
+//! so they show up in the security dashboard and merge request widgets.
+//! Container scans produce a Container Scanning report that locates each
+//! finding by image and container; every other scan produces a SAST report
+//! that locates findings by file and line, or names the container or network
+//! in `location.class`.
+
+use std::io::Write;
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+
+use crate::docker::model::{ContainerResult, FileResult, Finding, NetworkResult, RiskLevel};
+use crate::output::attestation::sha256_hex;
+use crate::output::exporters::Exporter;
+use crate::report::{ScanFindings, ScanReport};
+
+/// Version of the security report schema the report conforms to
+pub const GITLAB_SCHEMA_VERSION: &str = "15.0.7";
+
+/// GitLab security report JSON, registered as `gitlab`.
+pub struct GitlabExporter;
+
+#[derive(Serialize)]
+struct SecurityReport<'a> {
+    version: &'static str,
+    scan: Scan,
+    vulnerabilities: Vec<Vulnerability<'a>>,
+}
+
+#[derive(Serialize)]
+struct Scan {
+    analyzer: Tool,
+    scanner: Tool,
+    #[serde(rename = "type")]
+    kind: &'static str,
+    start_time: String,
+    end_time: String,
+    status: &'static str,
+}
+
+#[derive(Serialize)]
+struct Tool {
+    id: &'static str,
+    name: &'static str,
+    version: &'static str,
+    url: &'static str,
+    vendor: Vendor,
+}
+
+#[derive(Serialize)]
+struct Vendor {
+    name: &'static str,
+}
+
+#[derive(Serialize)]
+struct Vulnerability<'a> {
+    /// Stable per rule and target, so GitLab tracks the finding across pipelines
+    id: String,
+    name: &'a str,
+    description: &'a str,
+    severity: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    solution: Option<&'a str>,
+    identifiers: [Identifier<'a>; 1],
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    links: Vec<Link<'a>>,
+    location: Location,
+}
+
+#[derive(Serialize)]
+struct Identifier<'a> {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    name: &'a str,
+    value: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    url: Option<&'a str>,
+}
+
+#[derive(Serialize)]
+struct Link<'a> {
+    url: &'a str,
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+enum Location {
+    /// SAST: a file and line, or a container or network named as the class
+    Source {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        file: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        start_line: Option<usize>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        end_line: Option<usize>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        class: Option<String>,
+    },
+    /// Container Scanning: the image and the container running it
+    Image {
+        image: String,
+        operating_system: String,
+        dependency: Dependency,
+    },
+}
+
+#[derive(Serialize)]
+struct Dependency {
+    package: Package,
+}
+
+#[derive(Serialize)]
+struct Package {
+    name: String,
+}
+
+impl Exporter for GitlabExporter {
+    fn export(&self, report: &ScanReport, w: &mut dyn Write) -> Result<()> {
+        let (kind, located): (_, Vec<(&Finding, Location)>) = match &report.findings {
+            ScanFindings::None => return Ok(()),
+            ScanFindings::Containers(results) => (
+                "container_scanning",
+                results.iter().flat_map(image_vulnerabilities).collect(),
+            ),
+            ScanFindings::Dockerfile { path, findings } | ScanFindings::EnvFile { path, findings } => {
+                ("sast", findings.iter().map(|finding| file_vulnerability(path, finding)).collect())
+            }
+            ScanFindings::Networks(results) => ("sast", results.iter().flat_map(network_vulnerabilities).collect()),
+            ScanFindings::Combined { containers, dockerfiles, env_files } => (
+                "sast",
+                containers
+                    .iter()
+                    .flat_map(container_vulnerabilities)
+                    .chain(file_vulnerabilities(dockerfiles))
+                    .chain(file_vulnerabilities(env_files))
+                    .collect(),
+            ),
+        };
+
+        let vulnerabilities = located
+            .into_iter()
+            .map(|(finding, location)| vulnerability(finding, location))
+            .collect();
+        let (start_time, end_time) = scan_times(report);
+        let report = SecurityReport {
+            version: GITLAB_SCHEMA_VERSION,
+            scan: Scan {
+                analyzer: tool(),
+                scanner: tool(),
+                kind,
+                start_time,
+                end_time,
+                status: "success",
+            },
+            vulnerabilities,
+        };
+        let json = serde_json::to_string_pretty(&report).context("Failed to serialize GitLab report")?;
+        writeln!(w, "{}", json).context("Failed to write GitLab report")
+    }
+}
+
+fn tool() -> Tool {
+    Tool {
+        id: "valeris",
+        name: "Valeris",
+        version: env!("CARGO_PKG_VERSION"),
+        url: "https://github.com/rsgbengi/valeris",
+        vendor: Vendor { name: "Valeris" },
+    }
+}
+
+/// Container Scanning locations: the container's image, with the container
+/// name standing in for the package
+fn image_vulnerabilities(result: &ContainerResult) -> impl Iterator<Item = (&Finding, Location)> {
+    let container = &result.container;
+    let image = container
+        .config
+        .as_ref()
+        .and_then(|config| config.image.clone())
+        .or_else(|| container.image.clone())
+        .unwrap_or_default();
+    let os = container.platform.clone().unwrap_or_else(|| "linux".to_string());
+    let name = qualified_name(result);
+    result.findings.iter().map(move |finding| {
+        let location = Location::Image {
+            image: image.clone(),
+            operating_system: os.clone(),
+            dependency: Dependency {
+                package: Package { name: name.clone() },
+            },
+        };
+        (finding, location)
+    })
+}
+
+fn container_vulnerabilities(result: &ContainerResult) -> impl Iterator<Item = (&Finding, Location)> {
+    let class = format!("container {}", qualified_name(result));
+    result.findings.iter().map(move |finding| (finding, class_location(&class)))
+}
+
+fn network_vulnerabilities(result: &NetworkResult) -> impl Iterator<Item = (&Finding, Location)> {
+    let class = format!("network {}", result.network.name.as_deref().unwrap_or_default());
+    result.findings.iter().map(move |finding| (finding, class_location(&class)))
+}
+
+fn file_vulnerabilities(files: &[FileResult]) -> impl Iterator<Item = (&Finding, Location)> {
+    files
+        .iter()
+        .flat_map(|file| file.findings.iter().map(|finding| file_vulnerability(&file.path, finding)))
+}
+
+fn file_vulnerability<'a>(path: &Path, finding: &'a Finding) -> (&'a Finding, Location) {
+    let path = path.strip_prefix(".").unwrap_or(path);
+    let location = Location::Source {
+        file: Some(path.to_string_lossy().replace('\\', "/")),
+        start_line: finding.line,
+        end_line: finding.span.as_ref().map(|span| span.end_line).or(finding.line),
+        class: None,
+    };
+    (finding, location)
+}
+
+fn class_location(class: &str) -> Location {
+    Location::Source {
+        file: None,
+        start_line: None,
+        end_line: None,
+        class: Some(class.to_string()),
+    }
+}
+
+/// Container name without the leading `/`, prefixed by its host in multi-host scans
+fn qualified_name(result: &ContainerResult) -> String {
+    let name = result
+        .container
+        .name
+        .as_deref()
+        .unwrap_or_default()
+        .trim_start_matches('/');
+    match &result.host {
+        Some(host) => format!("{}/{}", host, name),
+        None => name.to_string(),
+    }
+}
+
+fn vulnerability(finding: &Finding, location: Location) -> Vulnerability<'_> {
+    let target = match &location {
+        Location::Source { file: Some(file), .. } => file.clone(),
+        Location::Source { class, .. } => class.clone().unwrap_or_default(),
+        Location::Image { image, dependency, .. } => format!("{}:{}", image, dependency.package.name),
+    };
+    Vulnerability {
+        id: sha256_hex(format!("{}:{}", finding.kind, target).as_bytes()),
+        name: finding.rule_name.as_deref().unwrap_or(&finding.kind),
+        description: &finding.description,
+        severity: severity(&finding.risk),
+        solution: finding.remediation.as_deref(),
+        identifiers: [Identifier {
+            kind: "valeris_rule_id",
+            name: finding.rule_name.as_deref().unwrap_or(&finding.kind),
+            value: &finding.kind,
+            url: finding.references.first().map(String::as_str),
+        }],
+        links: finding.references.iter().map(|url| Link { url }).collect(),
+        location,
+    }
+}
+
+/// Start and end of the scan in GitLab's `YYYY-MM-DDTHH:MM:SS` format. The
+/// start comes from the report metadata when present; otherwise the scan is
+/// taken to have ended now.
+fn scan_times(report: &ScanReport) -> (String, String) {
+    let summary = report.summary.as_ref();
+    let duration = Duration::from_millis(summary.map_or(0, |s| s.duration_ms));
+    let started = summary
+        .and_then(|s| s.metadata.as_ref())
+        .and_then(|m| humantime::parse_rfc3339(&m.scanned_at).ok())
+        .unwrap_or_else(|| SystemTime::now() - duration);
+    let format = |time: SystemTime| humantime::format_rfc3339_seconds(time).to_string().trim_end_matches('Z').to_string();
+    (format(started), format(started + duration))
+}
+
+/// GitLab severity names (`Info`, `Low`, `Medium`, `High`, `Critical`).
+fn severity(risk: &RiskLevel) -> &'static str {
+    match risk {
+        RiskLevel::Critical => "Critical",
+        RiskLevel::High => "High",
+        RiskLevel::Medium => "Medium",
+        RiskLevel::Low => "Low",
+        RiskLevel::Informative => "Info",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::metadata::ScanMetadata;
+    use crate::output::summary::{ScanSummary, TargetKind};
+    use bollard::models::{ContainerConfig, ContainerInspectResponse};
+    use serde_json::Value;
+    use std::path::PathBuf;
+
+    fn export(findings: ScanFindings) -> Value {
+        let summary = ScanSummary::new(TargetKind::Containers, 1, 1, &[], Duration::from_secs(90), false)
+            .with_metadata(ScanMetadata::new(SystemTime::UNIX_EPOCH));
+        let mut out = Vec::new();
+        GitlabExporter
+            .export(&ScanReport::from_scan(summary, findings), &mut out)
+            .unwrap();
+        serde_json::from_slice(&out).unwrap()
+    }
+
+    #[test]
+    fn dockerfile_findings_form_a_sast_report() {
+        let finding = Finding {
+            line: Some(3),
+            rule_name: Some("Latest Tag".to_string()),
+            remediation: Some("Pin the base image".to_string()),
+            references: vec!["https://example.com/pin".to_string()],
+            ..Finding::new("DF001", "Base image uses latest", RiskLevel::Medium)
+        };
+
+        let report = export(ScanFindings::Dockerfile {
+            path: PathBuf::from("./app/Dockerfile"),
+            findings: vec![finding],
+        });
+        let vulnerability = &report["vulnerabilities"][0];
+
+        assert_eq!(report["version"], GITLAB_SCHEMA_VERSION);
+        assert_eq!(report["scan"]["type"], "sast");
+        assert_eq!(report["scan"]["scanner"]["id"], "valeris");
+        assert_eq!(report["scan"]["start_time"], "1970-01-01T00:00:00");
+        assert_eq!(report["scan"]["end_time"], "1970-01-01T00:01:30");
+        assert_eq!(vulnerability["name"], "Latest Tag");
+        assert_eq!(vulnerability["severity"], "Medium");
+        assert_eq!(vulnerability["solution"], "Pin the base image");
+        assert_eq!(vulnerability["identifiers"][0]["value"], "DF001");
+        assert_eq!(vulnerability["identifiers"][0]["url"], "https://example.com/pin");
+        assert_eq!(vulnerability["links"][0]["url"], "https://example.com/pin");
+        assert_eq!(vulnerability["location"]["file"], "app/Dockerfile");
+        assert_eq!(vulnerability["location"]["start_line"], 3);
+        assert_eq!(vulnerability["id"].as_str().unwrap().len(), 64);
+    }
+
+    #[test]
+    fn container_findings_form_a_container_scanning_report() {
+        let container = |name: &str| ContainerResult {
+            container: ContainerInspectResponse {
+                name: Some(format!("/{name}")),
+                config: Some(ContainerConfig {
+                    image: Some("nginx:1.25".to_string()),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+            findings: vec![Finding::new("privileged_mode", "Container is privileged", RiskLevel::High)],
+            host: None,
+        };
+
+        let report = export(ScanFindings::Containers(vec![container("web"), container("api")]));
+        let vulnerabilities = report["vulnerabilities"].as_array().unwrap();
+
+        assert_eq!(report["scan"]["type"], "container_scanning");
+        assert_eq!(vulnerabilities.len(), 2);
+        assert_eq!(vulnerabilities[0]["severity"], "High");
+        assert_eq!(vulnerabilities[0]["name"], "privileged_mode");
+        let location = &vulnerabilities[1]["location"];
+        assert_eq!(location["image"], "nginx:1.25");
+        assert_eq!(location["operating_system"], "linux");
+        assert_eq!(location["dependency"]["package"]["name"], "api");
+        assert_ne!(vulnerabilities[0]["id"], vulnerabilities[1]["id"]);
+    }
+}
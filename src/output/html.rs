@@ -0,0 +1,357 @@
+//! HTML report export.
+//!
+//! Writes a single self-contained page (inline CSS and script, no external
+//! assets) with the scan's severity summary, one section per scanned
+//! container, Dockerfile, `.env` file, or network, and a findings table per
+//! section that sorts by any column when its header is clicked. The page can
+//! be attached to a pipeline run and opened in any browser.
+
+use std::fmt::Write as _;
+use std::io::Write;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+use crate::docker::model::{ContainerResult, FileResult, Finding, NetworkResult, RiskLevel};
+use crate::output::exporters::Exporter;
+use crate::output::severity::SeverityScheme;
+use crate::output::summary::ScanSummary;
+use crate::report::{ScanFindings, ScanReport};
+
+/// Self-contained HTML page, registered as `html`.
+pub struct HtmlExporter;
+
+const STYLE: &str = r#"
+body { font-family: system-ui, sans-serif; margin: 2rem; color: #1f2328; }
+h1 { margin-bottom: 0.25rem; }
+.meta { color: #59636e; margin-bottom: 1.5rem; }
+.cards { display: flex; gap: 1rem; margin-bottom: 2rem; }
+.card { border-radius: 6px; padding: 0.75rem 1.25rem; min-width: 6rem; color: #fff; }
+.card strong { display: block; font-size: 1.75rem; }
+.sev-critical { background: #8250df; }
+.sev-high { background: #cf222e; }
+.sev-medium { background: #bc4c00; }
+.sev-low { background: #9a6700; }
+.sev-informative { background: #59636e; }
+.gate { font-weight: bold; }
+.gate.failed { color: #cf222e; }
+section { margin-bottom: 2rem; }
+table { border-collapse: collapse; width: 100%; }
+th, td { border: 1px solid #d1d9e0; padding: 0.4rem 0.6rem; text-align: left; vertical-align: top; }
+th { background: #f6f8fa; cursor: pointer; user-select: none; }
+th::after { content: " \2195"; color: #818b98; }
+td.severity { color: #fff; font-weight: bold; white-space: nowrap; }
+.empty { color: #1a7f37; }
+"#;
+
+/// Sorts a table by the clicked column; severity cells sort by `data-rank`
+const SCRIPT: &str = r#"
+document.querySelectorAll("th").forEach((th) => th.addEventListener("click", () => {
+  const table = th.closest("table");
+  const body = table.tBodies[0];
+  const column = th.cellIndex;
+  const descending = th.dataset.order !== "desc";
+  table.querySelectorAll("th").forEach((other) => delete other.dataset.order);
+  th.dataset.order = descending ? "desc" : "asc";
+  const key = (row) => {
+    const cell = row.cells[column];
+    return cell.dataset.rank ?? cell.textContent.trim();
+  };
+  const rows = Array.from(body.rows).sort((a, b) => {
+    const order = key(a).localeCompare(key(b), undefined, { numeric: true });
+    return descending ? -order : order;
+  });
+  rows.forEach((row) => body.appendChild(row));
+}));
+"#;
+
+/// One scanned target, rendered as a section
+struct Target<'a> {
+    kind: &'static str,
+    name: String,
+    findings: &'a [Finding],
+}
+
+impl Exporter for HtmlExporter {
+    fn export(&self, report: &ScanReport, w: &mut dyn Write) -> Result<()> {
+        let targets: Vec<Target> = match &report.findings {
+            ScanFindings::None => return Ok(()),
+            ScanFindings::Containers(results) => results.iter().map(container_target).collect(),
+            ScanFindings::Dockerfile { path, findings } => vec![file_target("Dockerfile", path, findings)],
+            ScanFindings::EnvFile { path, findings } => vec![file_target(".env file", path, findings)],
+            ScanFindings::Networks(results) => results.iter().map(network_target).collect(),
+            ScanFindings::Combined { containers, dockerfiles, env_files } => containers
+                .iter()
+                .map(container_target)
+                .chain(file_targets("Dockerfile", dockerfiles))
+                .chain(file_targets(".env file", env_files))
+                .collect(),
+        };
+
+        let mut page = String::new();
+        page.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+        page.push_str("<title>Valeris scan report</title>\n");
+        let _ = writeln!(page, "<style>{}</style>\n</head>\n<body>", STYLE);
+        page.push_str("<h1>Valeris scan report</h1>\n");
+        if let Some(summary) = &report.summary {
+            write_summary(&mut page, summary, report.severity_scheme);
+        }
+        for target in &targets {
+            write_target(&mut page, target, report.summary.as_ref(), report.severity_scheme);
+        }
+        let _ = writeln!(page, "<script>{}</script>\n</body>\n</html>", SCRIPT);
+
+        w.write_all(page.as_bytes()).context("Failed to write HTML report")
+    }
+}
+
+fn container_target(result: &ContainerResult) -> Target<'_> {
+    let name = result
+        .container
+        .name
+        .as_deref()
+        .unwrap_or_default()
+        .trim_start_matches('/');
+    Target {
+        kind: "Container",
+        name: match &result.host {
+            Some(host) => format!("{}/{}", host, name),
+            None => name.to_string(),
+        },
+        findings: &result.findings,
+    }
+}
+
+fn network_target(result: &NetworkResult) -> Target<'_> {
+    Target {
+        kind: "Network",
+        name: result.network.name.clone().unwrap_or_default(),
+        findings: &result.findings,
+    }
+}
+
+fn file_targets<'a>(kind: &'static str, files: &'a [FileResult]) -> impl Iterator<Item = Target<'a>> {
+    files.iter().map(move |file| file_target(kind, &file.path, &file.findings))
+}
+
+fn file_target<'a>(kind: &'static str, path: &Path, findings: &'a [Finding]) -> Target<'a> {
+    Target {
+        kind,
+        name: path.display().to_string(),
+        findings,
+    }
+}
+
+/// Scan details, severity cards, and the fail gate outcome
+fn write_summary(page: &mut String, summary: &ScanSummary, scheme: SeverityScheme) {
+    let mut details = vec![
+        format!("{} {} scanned", summary.targets_scanned, summary.target_kind.label()),
+        format!("{} rules evaluated", summary.rules_evaluated),
+        format!("{:.2}s", summary.duration_ms as f64 / 1000.0),
+    ];
+    if let Some(metadata) = &summary.metadata {
+        details.insert(0, format!("Valeris {}", metadata.valeris_version));
+        details.insert(1, metadata.scanned_at.clone());
+        if let Some(host) = &metadata.host {
+            details.insert(2, format!("on {}", host));
+        }
+    }
+    let _ = writeln!(page, "<p class=\"meta\">{}</p>", escape(&details.join(" · ")));
+
+    let counts = &summary.findings;
+    page.push_str("<div class=\"cards\">\n");
+    for (risk, count) in [
+        (RiskLevel::Critical, counts.critical),
+        (RiskLevel::High, counts.high),
+        (RiskLevel::Medium, counts.medium),
+        (RiskLevel::Low, counts.low),
+        (RiskLevel::Informative, counts.informative),
+    ] {
+        let _ = writeln!(
+            page,
+            "<div class=\"card {}\"><strong>{}</strong>{}</div>",
+            class(&risk),
+            count,
+            scheme.label(&risk)
+        );
+    }
+    page.push_str("</div>\n");
+    let _ = writeln!(
+        page,
+        "<p class=\"gate{}\">Fail gate: {}</p>",
+        if summary.fail_on_triggered { " failed" } else { "" },
+        if summary.fail_on_triggered { "failed" } else { "passed" }
+    );
+}
+
+/// One section with the target's score and its findings table
+fn write_target(page: &mut String, target: &Target, summary: Option<&ScanSummary>, scheme: SeverityScheme) {
+    page.push_str("<section>\n");
+    let _ = writeln!(page, "<h2>{}: {}</h2>", target.kind, escape(&target.name));
+    let score = summary
+        .and_then(|s| s.scores.iter().find(|score| score.target == target.name))
+        .map(|s| s.score);
+    if let Some(score) = score {
+        let _ = writeln!(page, "<p>Score: {} ({})</p>", score.score, score.grade);
+    }
+    if target.findings.is_empty() {
+        page.push_str("<p class=\"empty\">No findings</p>\n</section>\n");
+        return;
+    }
+
+    page.push_str("<table>\n<thead><tr><th>Severity</th><th>Rule</th><th>Description</th>");
+    page.push_str("<th>Line</th><th>Remediation</th></tr></thead>\n<tbody>\n");
+    for finding in target.findings {
+        let mut rule = match &finding.rule_name {
+            Some(name) => format!("{}<br><code>{}</code>", escape(name), escape(&finding.kind)),
+            None => format!("<code>{}</code>", escape(&finding.kind)),
+        };
+        let labels: Vec<&String> = finding.category.iter().chain(&finding.tags).collect();
+        if !labels.is_empty() {
+            let labels: Vec<String> = labels.iter().map(|label| escape(label)).collect();
+            let _ = write!(rule, "<br><small>{}</small>", labels.join(", "));
+        }
+        let references: String = finding
+            .references
+            .iter()
+            .map(|r| format!("<br><a href=\"{0}\">{0}</a>", escape(r)))
+            .collect();
+        let _ = writeln!(
+            page,
+            "<tr><td class=\"severity {}\" data-rank=\"{}\">{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}{}</td></tr>",
+            class(&finding.risk),
+            rank(&finding.risk),
+            scheme.label(&finding.risk),
+            rule,
+            escape(&finding.description),
+            finding.line.map(|l| l.to_string()).unwrap_or_default(),
+            escape(finding.remediation.as_deref().unwrap_or_default()),
+            references
+        );
+    }
+    page.push_str("</tbody>\n</table>\n</section>\n");
+}
+
+fn class(risk: &RiskLevel) -> &'static str {
+    match risk {
+        RiskLevel::Critical => "sev-critical",
+        RiskLevel::High => "sev-high",
+        RiskLevel::Medium => "sev-medium",
+        RiskLevel::Low => "sev-low",
+        RiskLevel::Informative => "sev-informative",
+    }
+}
+
+/// Sort key of a severity cell, highest first when sorted descending
+fn rank(risk: &RiskLevel) -> u8 {
+    match risk {
+        RiskLevel::Critical => 4,
+        RiskLevel::High => 3,
+        RiskLevel::Medium => 2,
+        RiskLevel::Low => 1,
+        RiskLevel::Informative => 0,
+    }
+}
+
+/// Escapes text for HTML element content and quoted attributes
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::score::{SecurityScore, TargetScore};
+    use crate::output::summary::TargetKind;
+    use bollard::models::ContainerInspectResponse;
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    fn export(findings: ScanFindings, summary: ScanSummary) -> String {
+        let mut out = Vec::new();
+        HtmlExporter
+            .export(&ScanReport::from_scan(summary, findings), &mut out)
+            .unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn renders_summary_and_one_section_per_container() {
+        let finding = Finding {
+            rule_name: Some("Privileged Mode".to_string()),
+            remediation: Some("Drop --privileged".to_string()),
+            references: vec!["https://example.com/cis".to_string()],
+            category: Some("Runtime".to_string()),
+            tags: vec!["cis".to_string()],
+            ..Finding::new("privileged_mode", "Container is <privileged>", RiskLevel::High)
+        };
+        let container = |name: &str, findings: Vec<Finding>| ContainerResult {
+            container: ContainerInspectResponse {
+                name: Some(format!("/{name}")),
+                ..Default::default()
+            },
+            findings,
+            host: None,
+        };
+        let results = vec![container("web", vec![finding]), container("db", Vec::new())];
+        let summary = ScanSummary::new(
+            TargetKind::Containers,
+            2,
+            10,
+            results.iter().flat_map(|r| &r.findings),
+            Duration::from_millis(250),
+            true,
+        )
+        .with_scores(vec![TargetScore {
+            target: "web".to_string(),
+            score: SecurityScore::new(80),
+        }]);
+
+        let html = export(ScanFindings::Containers(results), summary);
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(!html.contains("<link") && !html.contains("src=\""));
+        assert!(html.contains("2 containers scanned · 10 rules evaluated · 0.25s"));
+        assert!(html.contains("<div class=\"card sev-high\"><strong>1</strong>HIGH</div>"));
+        assert!(html.contains("<p class=\"gate failed\">Fail gate: failed</p>"));
+        assert!(html.contains("<h2>Container: web</h2>\n<p>Score: 80 (B)</p>"));
+        assert!(html.contains(
+            "data-rank=\"3\">HIGH</td><td>Privileged Mode<br><code>privileged_mode</code><br><small>Runtime, cis</small>"
+        ));
+        assert!(html.contains("Container is &lt;privileged&gt;"));
+        assert!(html.contains("<a href=\"https://example.com/cis\">"));
+        assert!(html.contains("<h2>Container: db</h2>\n<p class=\"empty\">No findings</p>"));
+    }
+
+    #[test]
+    fn dockerfile_findings_show_their_line() {
+        let finding = Finding {
+            line: Some(4),
+            ..Finding::new("DF001", "Base image uses latest", RiskLevel::Low)
+        };
+        let summary = ScanSummary::new(TargetKind::Dockerfiles, 1, 5, [&finding], Duration::ZERO, false);
+
+        let html = export(
+            ScanFindings::Dockerfile {
+                path: PathBuf::from("app/Dockerfile"),
+                findings: vec![finding.clone()],
+            },
+            summary,
+        );
+
+        assert!(html.contains("<h2>Dockerfile: app/Dockerfile</h2>"));
+        assert!(html.contains("<td>Base image uses latest</td><td>4</td>"));
+        assert!(html.contains("Fail gate: passed"));
+    }
+}
@@ -0,0 +1,246 @@
+//! JUnit XML export.
+//!
+//! Writes findings as JUnit test results, so CI systems such as Jenkins and
+//! GitLab show them in their test report views. Each scanned container,
+//! Dockerfile, `.env` file, or network is a test suite, and each rule that
+//! produced findings on it is a failed test case; targets without findings
+//! get one passing test case.
+
+use std::fmt::Write as _;
+use std::io::Write;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use indexmap::IndexMap;
+
+use crate::docker::model::{ContainerResult, FileResult, Finding, NetworkResult};
+use crate::output::exporters::Exporter;
+use crate::output::severity::SeverityScheme;
+use crate::report::{ScanFindings, ScanReport};
+
+/// Name of the passing test case of a target without findings
+const CLEAN_CASE: &str = "no findings";
+
+/// JUnit XML test results, registered as `junit`.
+pub struct JunitExporter;
+
+/// One scanned target, rendered as a test suite
+struct Suite<'a> {
+    /// Kind of target, used in the suite name and test case class names
+    kind: &'static str,
+    name: String,
+    findings: &'a [Finding],
+}
+
+impl Exporter for JunitExporter {
+    fn export(&self, report: &ScanReport, w: &mut dyn Write) -> Result<()> {
+        let suites: Vec<Suite> = match &report.findings {
+            ScanFindings::None => return Ok(()),
+            ScanFindings::Containers(results) => results.iter().map(container_suite).collect(),
+            ScanFindings::Dockerfile { path, findings } => vec![file_suite("dockerfile", path, findings)],
+            ScanFindings::EnvFile { path, findings } => vec![file_suite("env_file", path, findings)],
+            ScanFindings::Networks(results) => results.iter().map(network_suite).collect(),
+            ScanFindings::Combined { containers, dockerfiles, env_files } => containers
+                .iter()
+                .map(container_suite)
+                .chain(file_suites("dockerfile", dockerfiles))
+                .chain(file_suites("env_file", env_files))
+                .collect(),
+        };
+
+        let mut body = String::new();
+        let (mut tests, mut failures) = (0, 0);
+        for suite in &suites {
+            let (suite_tests, suite_failures) = write_suite(&mut body, suite, report.severity_scheme);
+            tests += suite_tests;
+            failures += suite_failures;
+        }
+        let time = report.summary.as_ref().map_or(0, |s| s.duration_ms) as f64 / 1000.0;
+
+        writeln!(w, r#"<?xml version="1.0" encoding="UTF-8"?>"#)
+            .and_then(|_| {
+                writeln!(
+                    w,
+                    r#"<testsuites name="valeris" tests="{}" failures="{}" time="{:.3}">"#,
+                    tests, failures, time
+                )
+            })
+            .and_then(|_| write!(w, "{}", body))
+            .and_then(|_| writeln!(w, "</testsuites>"))
+            .context("Failed to write JUnit report")
+    }
+}
+
+fn container_suite(result: &ContainerResult) -> Suite<'_> {
+    let name = result
+        .container
+        .name
+        .as_deref()
+        .unwrap_or_default()
+        .trim_start_matches('/');
+    Suite {
+        kind: "container",
+        name: match &result.host {
+            Some(host) => format!("{}/{}", host, name),
+            None => name.to_string(),
+        },
+        findings: &result.findings,
+    }
+}
+
+fn network_suite(result: &NetworkResult) -> Suite<'_> {
+    Suite {
+        kind: "network",
+        name: result.network.name.clone().unwrap_or_default(),
+        findings: &result.findings,
+    }
+}
+
+fn file_suites<'a>(kind: &'static str, files: &'a [FileResult]) -> impl Iterator<Item = Suite<'a>> {
+    files.iter().map(move |file| file_suite(kind, &file.path, &file.findings))
+}
+
+fn file_suite<'a>(kind: &'static str, path: &Path, findings: &'a [Finding]) -> Suite<'a> {
+    Suite {
+        kind,
+        name: path.display().to_string(),
+        findings,
+    }
+}
+
+/// Appends `suite` to `out`, returning its test and failure counts.
+fn write_suite(out: &mut String, suite: &Suite, scheme: SeverityScheme) -> (usize, usize) {
+    // One test case per rule, in the order the rules first fired
+    let mut rules: IndexMap<&str, Vec<&Finding>> = IndexMap::new();
+    for finding in suite.findings {
+        rules.entry(finding.kind.as_str()).or_default().push(finding);
+    }
+    let classname = escape(&format!("valeris.{}.{}", suite.kind, suite.name));
+    let tests = rules.len().max(1);
+
+    let _ = writeln!(
+        out,
+        r#"  <testsuite name="{}" tests="{}" failures="{}">"#,
+        escape(&format!("{} {}", suite.kind, suite.name)),
+        tests,
+        rules.len()
+    );
+    if rules.is_empty() {
+        let _ = writeln!(out, r#"    <testcase classname="{}" name="{}"/>"#, classname, CLEAN_CASE);
+    }
+    for (rule, findings) in &rules {
+        let first = findings[0];
+        let details: Vec<String> = findings.iter().map(|finding| details(finding, scheme)).collect();
+        let _ = writeln!(out, r#"    <testcase classname="{}" name="{}">"#, classname, escape(rule));
+        let _ = writeln!(
+            out,
+            r#"      <failure type="{}" message="{}">{}</failure>"#,
+            scheme.label(&first.risk),
+            escape(first.rule_name.as_deref().unwrap_or(&first.description)),
+            escape(&details.join("\n\n"))
+        );
+        let _ = writeln!(out, "    </testcase>");
+    }
+    let _ = writeln!(out, "  </testsuite>");
+    (tests, rules.len())
+}
+
+/// Failure text of one finding: severity, description, line, and remediation
+fn details(finding: &Finding, scheme: SeverityScheme) -> String {
+    let mut text = format!("[{}] {}", scheme.label(&finding.risk), finding.description);
+    if let Some(line) = finding.line {
+        let _ = write!(text, " (line {})", line);
+    }
+    if let Some(remediation) = &finding.remediation {
+        let _ = write!(text, "\nFix: {}", remediation);
+    }
+    for reference in &finding.references {
+        let _ = write!(text, "\nRef: {}", reference);
+    }
+    text
+}
+
+/// Escapes text for XML attributes and element content, dropping characters
+/// XML 1.0 cannot represent
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' | '\n' | '\r' => escaped.push(c),
+            c if c < ' ' => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::docker::model::RiskLevel;
+    use crate::output::summary::{ScanSummary, TargetKind};
+    use bollard::models::ContainerInspectResponse;
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    fn export(findings: ScanFindings) -> String {
+        let summary = ScanSummary::new(TargetKind::Containers, 1, 1, &[], Duration::from_millis(1500), false);
+        let mut out = Vec::new();
+        JunitExporter
+            .export(&ScanReport::from_scan(summary, findings), &mut out)
+            .unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn groups_findings_into_one_case_per_rule_per_container() {
+        let container = |name: &str, findings: Vec<Finding>| ContainerResult {
+            container: ContainerInspectResponse {
+                name: Some(format!("/{name}")),
+                ..Default::default()
+            },
+            findings,
+            host: None,
+        };
+        let mount = |path: &str| Finding::new("sensitive_mount", format!("Mounts {path}"), RiskLevel::High);
+        let results = vec![
+            container("web", vec![mount("/etc"), mount("/var/run/docker.sock")]),
+            container("db", Vec::new()),
+        ];
+
+        let xml = export(ScanFindings::Containers(results));
+
+        assert!(xml.starts_with(r#"<?xml version="1.0" encoding="UTF-8"?>"#));
+        assert!(xml.contains(r#"<testsuites name="valeris" tests="2" failures="1" time="1.500">"#));
+        assert!(xml.contains(r#"<testsuite name="container web" tests="1" failures="1">"#));
+        assert!(xml.contains(r#"<testcase classname="valeris.container.web" name="sensitive_mount">"#));
+        assert!(xml.contains("Mounts /etc\n\n[HIGH] Mounts /var/run/docker.sock</failure>"));
+        assert!(xml.contains(r#"<testcase classname="valeris.container.db" name="no findings"/>"#));
+        assert_eq!(xml.matches("<testcase").count(), 2);
+    }
+
+    #[test]
+    fn dockerfile_failures_include_line_and_remediation_escaped() {
+        let finding = Finding {
+            line: Some(7),
+            rule_name: Some("Curl <pipe> shell".to_string()),
+            remediation: Some("Download & verify first".to_string()),
+            ..Finding::new("DF010", "curl | sh \"installer\"", RiskLevel::Medium)
+        };
+
+        let xml = export(ScanFindings::Dockerfile {
+            path: PathBuf::from("app/Dockerfile"),
+            findings: vec![finding],
+        });
+
+        assert!(xml.contains(r#"<testsuite name="dockerfile app/Dockerfile" tests="1" failures="1">"#));
+        assert!(xml.contains(r#"message="Curl &lt;pipe&gt; shell""#));
+        assert!(xml.contains("curl | sh &quot;installer&quot; (line 7)\nFix: Download &amp; verify first"));
+        assert_eq!(escape("a\u{1b}b"), "ab");
+    }
+}
@@ -0,0 +1,437 @@
+//! Merging JSON reports.
+//!
+//! `valeris merge` combines the JSON reports of separate scans (one per host,
+//! one per repository, ...) into a single report. Containers, Dockerfiles,
+//! `.env` files, and networks that appear in several reports are merged into one entry, their findings
+//! deduplicated by fingerprint (rule, target, line and description; see
+//! [`crate::history::fingerprint`]), and the summaries are aggregated:
+//!
+//! * `targets_scanned` and the severity counts are recomputed from the
+//!   merged entries
+//! * `rules_evaluated` is the largest of the inputs
+//! * `duration_ms` is the total of the inputs
+//! * `fail_on_triggered` is set if any input triggered it
+//! * `scores` keeps the lowest score reported for each target
+//!
+//! Finding fields are copied through unchanged, so rule metadata and
+//! history annotations survive the merge. Combined reports (`scan
+//! --dockerfile` or `--compose`) mix target kinds and cannot be merged.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::docker::model::RiskLevel;
+use crate::error::{Result, ValerisError};
+use crate::history::fingerprint;
+use crate::output::compliance::severity_from_report;
+use crate::output::exporters::REPORT_SCHEMA_VERSION;
+use crate::output::score::TargetScore;
+use crate::output::summary::{ScanSummary, SeverityCounts, TargetKind};
+
+/// Report produced by [`merge_reports`].
+#[derive(Debug, Clone, Serialize)]
+pub struct MergedReport {
+    pub schema_version: u32,
+    #[serde(flatten)]
+    pub targets: MergedTargets,
+    pub summary: ScanSummary,
+}
+
+/// Merged entries, in the shape of the input reports.
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum MergedTargets {
+    /// Container entries, as in `scan --format json`
+    Containers { containers: Vec<Value> },
+    /// One entry per Dockerfile, each as in `docker-file --format json`
+    /// without its `summary`
+    Dockerfiles { dockerfiles: Vec<Value> },
+    /// One entry per `.env` file, each as in `env-file --format json`
+    /// without its `summary`
+    EnvFiles { env_files: Vec<Value> },
+    /// Network entries, as in `network --format json`
+    Networks { networks: Vec<Value> },
+}
+
+/// Targets of one kind, merged by key in the order first seen.
+struct Merger {
+    kind: TargetKind,
+    entries: Vec<Value>,
+    index: HashMap<String, usize>,
+    seen: HashSet<(usize, String)>,
+    rules_evaluated: usize,
+    duration_ms: u64,
+    fail_on_triggered: bool,
+    scores: BTreeMap<String, TargetScore>,
+}
+
+impl Merger {
+    fn new(kind: TargetKind) -> Self {
+        Self {
+            kind,
+            entries: Vec::new(),
+            index: HashMap::new(),
+            seen: HashSet::new(),
+            rules_evaluated: 0,
+            duration_ms: 0,
+            fail_on_triggered: false,
+            scores: BTreeMap::new(),
+        }
+    }
+
+    /// Adds an entry, merging its findings into an earlier entry of the
+    /// same target.
+    fn add_entry(&mut self, target: String, mut entry: Map<String, Value>) {
+        let findings = match entry.remove("findings") {
+            Some(Value::Array(findings)) => findings,
+            _ => Vec::new(),
+        };
+        let slot = *self.index.entry(target.clone()).or_insert_with(|| {
+            entry.insert("findings".to_string(), Value::Array(Vec::new()));
+            self.entries.push(Value::Object(entry));
+            self.entries.len() - 1
+        });
+
+        for finding in findings {
+            let key = self.finding_key(&target, &finding);
+            if self.seen.insert((slot, key)) {
+                if let Some(Value::Array(merged)) = self.entries[slot].get_mut("findings") {
+                    merged.push(finding);
+                }
+            }
+        }
+    }
+
+    /// Findings are keyed by their history fingerprint.
+    fn finding_key(&self, target: &str, finding: &Value) -> String {
+        let rule_field = match self.kind {
+            // Combined reports are rejected by `read_report`
+            TargetKind::Containers | TargetKind::Combined => "kind",
+            TargetKind::Dockerfiles | TargetKind::EnvFiles | TargetKind::Networks => "id",
+        };
+        let field = |name: &str| finding.get(name).and_then(Value::as_str).unwrap_or_default();
+        let line = finding.get("line").and_then(Value::as_u64).map(|l| l as usize);
+        fingerprint(field(rule_field), target, line, field("description"))
+    }
+
+    fn add_summary(&mut self, summary: ScanSummary) {
+        self.rules_evaluated = self.rules_evaluated.max(summary.rules_evaluated);
+        self.duration_ms += summary.duration_ms;
+        self.fail_on_triggered |= summary.fail_on_triggered;
+        for score in summary.scores {
+            match self.scores.get(&score.target) {
+                Some(existing) if existing.score.score <= score.score.score => {}
+                _ => {
+                    self.scores.insert(score.target.clone(), score);
+                }
+            }
+        }
+    }
+
+    fn finish(mut self) -> MergedReport {
+        let mut counts = SeverityCounts::default();
+        for entry in &mut self.entries {
+            let mut entry_counts = SeverityCounts::default();
+            let findings = entry.get("findings").and_then(Value::as_array).into_iter().flatten();
+            for finding in findings {
+                entry_counts.add_risk(&finding_risk(self.kind, finding));
+            }
+            if self.kind != TargetKind::Containers {
+                set_file_counts(entry, &entry_counts);
+            }
+            counts.total += entry_counts.total;
+            counts.critical += entry_counts.critical;
+            counts.high += entry_counts.high;
+            counts.medium += entry_counts.medium;
+            counts.low += entry_counts.low;
+            counts.informative += entry_counts.informative;
+        }
+
+        let summary = ScanSummary::from_counts(
+            self.kind,
+            self.entries.len(),
+            self.rules_evaluated,
+            counts,
+            std::time::Duration::from_millis(self.duration_ms),
+            self.fail_on_triggered,
+        )
+        .with_scores(self.scores.into_values().collect());
+
+        MergedReport {
+            schema_version: REPORT_SCHEMA_VERSION,
+            targets: match self.kind {
+                TargetKind::Containers | TargetKind::Combined => MergedTargets::Containers { containers: self.entries },
+                TargetKind::Dockerfiles => MergedTargets::Dockerfiles { dockerfiles: self.entries },
+                TargetKind::EnvFiles => MergedTargets::EnvFiles { env_files: self.entries },
+                TargetKind::Networks => MergedTargets::Networks { networks: self.entries },
+            },
+            summary,
+        }
+    }
+}
+
+fn finding_risk(kind: TargetKind, finding: &Value) -> RiskLevel {
+    match kind {
+        TargetKind::Containers | TargetKind::Combined => finding
+            .get("risk")
+            .and_then(|risk| serde_json::from_value(risk.clone()).ok())
+            .unwrap_or(RiskLevel::Informative),
+        TargetKind::Dockerfiles | TargetKind::EnvFiles | TargetKind::Networks => {
+            severity_from_report(finding.get("severity").and_then(Value::as_str).unwrap_or_default())
+        }
+    }
+}
+
+/// Rewrites the per-file (or per-network) totals after deduplication.
+fn set_file_counts(entry: &mut Value, counts: &SeverityCounts) {
+    if let Value::Object(entry) = entry {
+        for (field, count) in [
+            ("total_findings", counts.total),
+            ("critical_count", counts.critical),
+            ("high_count", counts.high),
+            ("medium_count", counts.medium),
+            ("low_count", counts.low),
+            ("info_count", counts.informative),
+        ] {
+            entry.insert(field.to_string(), count.into());
+        }
+    }
+}
+
+/// Target a container entry is merged under: `host/name` in multi-host
+/// reports, otherwise its name.
+fn container_key(entry: &Map<String, Value>) -> String {
+    let name = entry
+        .get("container_name")
+        .and_then(Value::as_str)
+        .filter(|name| !name.is_empty())
+        .or_else(|| entry.get("container_id").and_then(Value::as_str))
+        .unwrap_or_default();
+    match entry.get("host").and_then(Value::as_str) {
+        Some(host) => format!("{}/{}", host, name),
+        None => name.to_string(),
+    }
+}
+
+fn file_key(entry: &Map<String, Value>, field: &str) -> String {
+    entry.get(field).and_then(Value::as_str).unwrap_or_default().to_string()
+}
+
+/// Merges JSON reports written by `scan`, `docker-file`, `env-file`,
+/// `network`, or `merge`.
+///
+/// # Arguments
+///
+/// * `paths` - Reports to merge; all must be of the same kind (no paths
+///   give an empty container report)
+///
+/// # Errors
+///
+/// * [`ValerisError::InputRead`] if a file cannot be read
+/// * [`ValerisError::InputParse`] if a file is not a Valeris JSON report,
+///   has a newer `schema_version` than this build supports, or its kind
+///   differs from the first report's
+pub fn merge_reports(paths: &[PathBuf]) -> Result<MergedReport> {
+    let mut merger: Option<Merger> = None;
+
+    for path in paths {
+        let parse_error = |message: String| ValerisError::InputParse {
+            path: path.clone(),
+            message,
+        };
+        let (kind, mut report) = read_report(path)?;
+        let merger = merger.get_or_insert_with(|| Merger::new(kind));
+        if merger.kind != kind {
+            return Err(parse_error(format!(
+                "cannot merge {} reports with {} reports",
+                kind.label(),
+                merger.kind.label()
+            )));
+        }
+
+        if let Some(summary) = report.remove("summary") {
+            let summary: ScanSummary = serde_json::from_value(summary)
+                .map_err(|e| parse_error(format!("invalid summary ({})", e)))?;
+            merger.add_summary(summary);
+        }
+        let entries = match ["containers", "dockerfiles", "env_files", "networks"].map(|key| report.remove(key)) {
+            [Some(Value::Array(entries)), _, _, _]
+            | [_, Some(Value::Array(entries)), _, _]
+            | [_, _, Some(Value::Array(entries)), _]
+            | [_, _, _, Some(Value::Array(entries))] => entries,
+            // A single `docker-file` or `env-file` report is itself the entry
+            _ => vec![Value::Object(report)],
+        };
+        for entry in entries {
+            let Value::Object(entry) = entry else {
+                return Err(parse_error("report entries must be objects".to_string()));
+            };
+            let target = match kind {
+                TargetKind::Containers | TargetKind::Combined => container_key(&entry),
+                TargetKind::Dockerfiles => file_key(&entry, "dockerfile_path"),
+                TargetKind::EnvFiles => file_key(&entry, "env_file_path"),
+                TargetKind::Networks => file_key(&entry, "network_id"),
+            };
+            merger.add_entry(target, entry);
+        }
+    }
+
+    Ok(merger
+        .unwrap_or_else(|| Merger::new(TargetKind::Containers))
+        .finish())
+}
+
+/// Reads a report and checks its schema version and kind.
+fn read_report(path: &Path) -> Result<(TargetKind, Map<String, Value>)> {
+    let parse_error = |message: String| ValerisError::InputParse {
+        path: path.to_path_buf(),
+        message,
+    };
+    let raw = fs::read_to_string(path).map_err(|source| ValerisError::InputRead {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let Value::Object(report) = serde_json::from_str(&raw)
+        .map_err(|e| parse_error(format!("not a Valeris JSON report ({})", e)))?
+    else {
+        return Err(parse_error("not a Valeris JSON report".to_string()));
+    };
+
+    // Reports written before `schema_version` was added are version 1
+    let version = match report.get("schema_version") {
+        None => 1,
+        Some(version) => version
+            .as_u64()
+            .ok_or_else(|| parse_error(format!("invalid schema_version {}", version)))?,
+    };
+    if version > u64::from(REPORT_SCHEMA_VERSION) {
+        return Err(parse_error(format!(
+            "schema_version {} is newer than this version of Valeris supports ({})",
+            version, REPORT_SCHEMA_VERSION
+        )));
+    }
+
+    let has_files = report.contains_key("dockerfiles") || report.contains_key("env_files");
+    let kind = if report.contains_key("containers") && has_files {
+        return Err(parse_error("combined scan reports (scan --dockerfile/--compose) cannot be merged".to_string()));
+    } else if report.contains_key("containers") {
+        TargetKind::Containers
+    } else if report.contains_key("dockerfiles") || report.contains_key("dockerfile_path") {
+        TargetKind::Dockerfiles
+    } else if report.contains_key("env_files") || report.contains_key("env_file_path") {
+        TargetKind::EnvFiles
+    } else if report.contains_key("networks") {
+        TargetKind::Networks
+    } else {
+        return Err(parse_error("not a Valeris JSON report".to_string()));
+    };
+    Ok((kind, report))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn write(dir: &Path, name: &str, report: Value) -> PathBuf {
+        let path = dir.join(name);
+        fs::write(&path, report.to_string()).unwrap();
+        path
+    }
+
+    fn summary(kind: &str, targets: usize, rules: usize, failed: bool, scores: Value) -> Value {
+        json!({
+            "target_kind": kind,
+            "targets_scanned": targets,
+            "rules_evaluated": rules,
+            "findings": {"total": 0, "high": 0, "medium": 0, "low": 0, "informative": 0},
+            "duration_ms": 100,
+            "fail_on_triggered": failed,
+            "scores": scores,
+        })
+    }
+
+    #[test]
+    fn merges_containers_and_deduplicates_findings() {
+        let dir = tempfile::tempdir().unwrap();
+        let privileged = json!({"kind": "privileged_mode", "description": "Privileged", "risk": "High"});
+        let pids = json!({"kind": "pids_limit", "description": "No PID limit", "risk": "Low"});
+        let first = write(dir.path(), "a.json", json!({
+            "containers": [
+                {"container_id": "1", "container_name": "web", "findings": [privileged]},
+                {"container_id": "2", "container_name": "db", "host": "db-1", "findings": []},
+            ],
+            "summary": summary("containers", 2, 20, false, json!([{"target": "web", "score": 80, "grade": "B"}])),
+        }));
+        let second = write(dir.path(), "b.json", json!({
+            "containers": [
+                {"container_id": "1", "container_name": "web", "findings": [privileged, pids]},
+                {"container_id": "3", "container_name": "db", "host": "db-2", "findings": [pids]},
+            ],
+            "summary": summary("containers", 2, 25, true, json!([{"target": "web", "score": 77, "grade": "C"}])),
+        }));
+
+        let merged = merge_reports(&[first, second]).unwrap();
+
+        let MergedTargets::Containers { containers } = &merged.targets else {
+            panic!("expected container report");
+        };
+        assert_eq!(containers.len(), 3);
+        assert_eq!(containers[0]["findings"].as_array().unwrap().len(), 2);
+        assert_eq!(containers[1]["host"], "db-1");
+        assert_eq!(merged.summary.targets_scanned, 3);
+        assert_eq!(merged.summary.rules_evaluated, 25);
+        assert_eq!(merged.summary.duration_ms, 200);
+        assert!(merged.summary.fail_on_triggered);
+        assert_eq!((merged.summary.findings.total, merged.summary.findings.high), (3, 1));
+        assert_eq!(merged.summary.scores.len(), 1);
+        assert_eq!(merged.summary.scores[0].score.score, 77);
+    }
+
+    #[test]
+    fn merges_dockerfile_reports_by_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let report = |findings: Value| json!({
+            "dockerfile_path": "api/Dockerfile",
+            "total_findings": 0,
+            "findings": findings,
+            "summary": summary("dockerfiles", 1, 10, false, json!([])),
+        });
+        let root = json!({"id": "root_user", "severity": "CRITICAL", "line": 3, "description": "Runs as root"});
+        let root_again = json!({"id": "root_user", "severity": "CRITICAL", "line": 9, "description": "Runs as root"});
+        let latest = json!({"id": "latest_tag", "severity": "HIGH", "line": 1, "description": "Uses latest"});
+        let first = write(dir.path(), "a.json", report(json!([root, latest])));
+        let second = write(dir.path(), "b.json", report(json!([root, root_again])));
+
+        let merged = merge_reports(&[first, second]).unwrap();
+
+        let MergedTargets::Dockerfiles { dockerfiles } = &merged.targets else {
+            panic!("expected Dockerfile report");
+        };
+        assert_eq!(dockerfiles.len(), 1);
+        assert_eq!(dockerfiles[0]["total_findings"], 3);
+        assert_eq!(dockerfiles[0]["critical_count"], 2);
+        assert_eq!(dockerfiles[0]["high_count"], 1);
+        assert!(dockerfiles[0].get("summary").is_none());
+        assert_eq!(merged.summary.findings.critical, 2);
+        assert_eq!(merged.summary.findings.high, 1);
+    }
+
+    #[test]
+    fn rejects_mixed_kinds_and_newer_schemas() {
+        let dir = tempfile::tempdir().unwrap();
+        let containers = write(dir.path(), "a.json", json!({"containers": []}));
+        let dockerfile = write(dir.path(), "b.json", json!({"dockerfile_path": "Dockerfile", "findings": []}));
+        let future = write(dir.path(), "c.json", json!({"schema_version": 99, "containers": []}));
+        let other = write(dir.path(), "d.json", json!({"results": []}));
+
+        for paths in [vec![containers.clone(), dockerfile], vec![containers.clone(), future], vec![other]] {
+            assert!(matches!(merge_reports(&paths), Err(ValerisError::InputParse { .. })));
+        }
+        assert_eq!(merge_reports(&[containers]).unwrap().schema_version, REPORT_SCHEMA_VERSION);
+    }
+}
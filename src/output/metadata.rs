@@ -0,0 +1,208 @@
+//! Provenance of a scan, written as the top-level `metadata` block of JSON
+//! reports.
+//!
+//! Records which Valeris version ran, when, on which host and Docker
+//! daemon, with which rule bundle (path, installed version, and SHA-256
+//! digest), and which filters narrowed the results, so a report can be
+//! audited without knowing the command line that produced it.
+//!
+//! `SOURCE_DATE_EPOCH` pins the recorded scan time (and zeroes the recorded
+//! duration, see [`crate::output::summary::ScanSummary`]) so that repeated
+//! scans of unchanged targets produce byte-identical reports.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::cli::SeverityLevel;
+use crate::output::attestation::rule_bundle_digest;
+use crate::output::syslog::local_hostname;
+
+/// File a downloaded rule bundle records its version in
+pub const RULES_VERSION_FILE: &str = ".valeris_version";
+
+/// Environment variable that pins report timestamps, in seconds since the
+/// Unix epoch (<https://reproducible-builds.org/specs/source-date-epoch/>)
+pub const SOURCE_DATE_EPOCH: &str = "SOURCE_DATE_EPOCH";
+
+/// Time set by `SOURCE_DATE_EPOCH`, if it holds a number of seconds.
+pub fn source_date_epoch() -> Option<SystemTime> {
+    let secs = std::env::var(SOURCE_DATE_EPOCH).ok()?.trim().parse().ok()?;
+    Some(UNIX_EPOCH + Duration::from_secs(secs))
+}
+
+/// Where, when, and how a scan ran.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ScanMetadata {
+    /// Version of Valeris that produced the report
+    pub valeris_version: String,
+    /// Scan start time (RFC 3339)
+    pub scanned_at: String,
+    /// Host Valeris ran on
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub host: Option<String>,
+    /// Rule bundle the scan evaluated; absent for built-in checks
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rules: Option<RuleBundleInfo>,
+    /// Docker daemon that was scanned
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub daemon: Option<DaemonInfo>,
+    /// Filters that narrowed the rules or results, keyed by option name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub filters: BTreeMap<String, String>,
+    /// Whether the scan ran with `--offline` (no network access)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub offline: bool,
+}
+
+/// Identity of a rule bundle.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RuleBundleInfo {
+    pub path: String,
+    /// Content of the bundle's version file, if it has one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// SHA-256 over every rule file (see [`rule_bundle_digest`])
+    pub sha256: String,
+}
+
+/// Version information reported by a Docker daemon.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct DaemonInfo {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_version: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub os: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub arch: Option<String>,
+}
+
+impl ScanMetadata {
+    /// Starts the metadata of a scan started at `started` on this host.
+    /// `SOURCE_DATE_EPOCH`, if set, is recorded instead of `started`.
+    pub fn new(started: SystemTime) -> Self {
+        let started = source_date_epoch().unwrap_or(started);
+        Self {
+            valeris_version: env!("CARGO_PKG_VERSION").to_string(),
+            scanned_at: humantime::format_rfc3339_seconds(started).to_string(),
+            host: local_hostname(),
+            rules: None,
+            daemon: None,
+            filters: BTreeMap::new(),
+            offline: false,
+        }
+    }
+
+    /// Records the rule bundle in `dir`. The version file is looked up in
+    /// `dir` and its parent, since installed bundles keep one per kind of
+    /// rules. A bundle that cannot be hashed is logged and left out.
+    pub fn with_rules(mut self, dir: &Path) -> Self {
+        match rule_bundle_digest(dir) {
+            Ok(sha256) => {
+                self.rules = Some(RuleBundleInfo {
+                    path: dir.display().to_string(),
+                    version: dir
+                        .ancestors()
+                        .take(2)
+                        .find_map(|d| fs::read_to_string(d.join(RULES_VERSION_FILE)).ok())
+                        .map(|v| v.trim().to_string())
+                        .filter(|v| !v.is_empty()),
+                    sha256,
+                });
+            }
+            Err(e) => tracing::warn!("Failed to hash rule bundle {}: {e:#}", dir.display()),
+        }
+        self
+    }
+
+    /// Records the scanned Docker daemon.
+    pub fn with_daemon(mut self, daemon: Option<DaemonInfo>) -> Self {
+        self.daemon = daemon;
+        self
+    }
+
+    /// Records whether the scan ran without network access.
+    pub fn with_offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
+    /// Records a filter if it was set; list values are joined with `,`.
+    pub fn with_filter<T: FilterValue + ?Sized>(mut self, name: &str, value: Option<&T>) -> Self {
+        if let Some(value) = value.map(FilterValue::render).filter(|v| !v.is_empty()) {
+            self.filters.insert(name.to_string(), value);
+        }
+        self
+    }
+}
+
+/// A filter option value as written in [`ScanMetadata::filters`].
+pub trait FilterValue {
+    fn render(&self) -> String;
+}
+
+impl FilterValue for str {
+    fn render(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl FilterValue for String {
+    fn render(&self) -> String {
+        self.clone()
+    }
+}
+
+impl FilterValue for SeverityLevel {
+    fn render(&self) -> String {
+        format!("{:?}", self).to_lowercase()
+    }
+}
+
+impl FilterValue for u32 {
+    fn render(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl<T: FilterValue> FilterValue for Vec<T> {
+    fn render(&self) -> String {
+        self.iter().map(FilterValue::render).collect::<Vec<_>>().join(",")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_rules_and_set_filters_only() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("privileged.yaml"), "id: privileged_mode\n").unwrap();
+        fs::write(dir.path().join(RULES_VERSION_FILE), "v1.4.0\n").unwrap();
+
+        let metadata = ScanMetadata::new(UNIX_EPOCH)
+            .with_rules(dir.path())
+            .with_filter("only", Some(&vec!["privileged_mode".to_string(), "root_user".to_string()]))
+            .with_filter("min_severity", Some(&SeverityLevel::Medium))
+            .with_filter::<Vec<String>>("exclude", None)
+            .with_filter("state", Some(&Vec::<String>::new()));
+
+        assert_eq!(metadata.scanned_at, "1970-01-01T00:00:00Z");
+        let rules = metadata.rules.unwrap();
+        assert_eq!(rules.version.as_deref(), Some("v1.4.0"));
+        assert_eq!(rules.sha256.len(), 64);
+        assert_eq!(
+            metadata.filters,
+            BTreeMap::from([
+                ("min_severity".to_string(), "medium".to_string()),
+                ("only".to_string(), "privileged_mode,root_user".to_string()),
+            ])
+        );
+    }
+}
@@ -3,5 +3,26 @@
 //! This module provides a consistent interface for displaying scan results
 //! across different scanner types (runtime containers, Dockerfiles, etc.).
 
+#[cfg(feature = "cli")]
 pub mod printer;
 pub mod exporters;
+pub mod anonymize;
+pub mod attestation;
+pub mod compliance;
+pub mod merge;
+pub mod redact;
+pub mod metadata;
+pub mod defectdojo;
+pub mod ocsf;
+pub mod sarif;
+pub mod junit;
+pub mod html;
+pub mod gitlab;
+pub mod template;
+pub mod syslog;
+pub mod storage;
+pub mod summary;
+pub mod rule_docs;
+pub mod score;
+pub mod severity;
+pub mod github;
@@ -0,0 +1,358 @@
+//! OCSF Security Finding export.
+//!
+//! Writes findings as [OCSF](https://schema.ocsf.io/) Security Finding events
+//! (class 2001), so pipelines standardized on OCSF (e.g. Amazon Security Lake)
+//! can ingest Valeris reports without a custom mapper. The report is a JSON
+//! array with one event per finding; the scanned container or Dockerfile is
+//! the event's resource.
+
+use std::io::Write;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+
+use crate::docker::model::{ContainerResult, FileResult, Finding, NetworkResult, RiskLevel};
+use crate::output::exporters::Exporter;
+use crate::output::metadata::source_date_epoch;
+use crate::report::{ScanFindings, ScanReport};
+
+/// OCSF schema version the events conform to
+pub const OCSF_VERSION: &str = "1.0.0";
+
+/// Security Finding class in the Findings category
+const CLASS_UID: u32 = 2001;
+const CATEGORY_UID: u32 = 2;
+/// Activity "Create": a finding reported for the first time
+const ACTIVITY_CREATE: u32 = 1;
+/// State "New"
+const STATE_NEW: u32 = 1;
+
+/// OCSF Security Finding events, registered as `ocsf`.
+pub struct OcsfExporter;
+
+#[derive(Serialize)]
+struct SecurityFinding<'a> {
+    activity_id: u32,
+    activity_name: &'static str,
+    category_uid: u32,
+    category_name: &'static str,
+    class_uid: u32,
+    class_name: &'static str,
+    type_uid: u32,
+    type_name: &'static str,
+    severity_id: u32,
+    severity: &'static str,
+    state_id: u32,
+    state: &'static str,
+    /// Milliseconds since the Unix epoch
+    time: u64,
+    message: &'a str,
+    metadata: Metadata,
+    finding: FindingDetails<'a>,
+    resources: Vec<Resource>,
+    unmapped: Unmapped<'a>,
+}
+
+#[derive(Serialize)]
+struct Metadata {
+    version: &'static str,
+    product: Product,
+}
+
+#[derive(Serialize)]
+struct Product {
+    name: &'static str,
+    vendor_name: &'static str,
+    version: &'static str,
+}
+
+#[derive(Serialize)]
+struct FindingDetails<'a> {
+    uid: String,
+    title: &'a str,
+    desc: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    types: Option<[&'a str; 1]>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    remediation: Option<Remediation<'a>>,
+    /// From the findings history, milliseconds since the Unix epoch
+    #[serde(skip_serializing_if = "Option::is_none")]
+    first_seen_time: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_seen_time: Option<u64>,
+}
+
+#[derive(Serialize)]
+struct Remediation<'a> {
+    desc: &'a str,
+    #[serde(skip_serializing_if = "<[String]>::is_empty")]
+    kb_articles: &'a [String],
+}
+
+#[derive(Serialize)]
+struct Resource {
+    uid: String,
+    name: String,
+    #[serde(rename = "type")]
+    kind: &'static str,
+}
+
+/// Valeris fields with no OCSF attribute
+#[derive(Serialize)]
+struct Unmapped<'a> {
+    rule_id: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    line: Option<usize>,
+    #[serde(skip_serializing_if = "<[String]>::is_empty")]
+    tags: &'a [String],
+}
+
+impl Exporter for OcsfExporter {
+    fn export(&self, report: &ScanReport, w: &mut dyn Write) -> Result<()> {
+        let time = now_ms();
+        let events: Vec<SecurityFinding> = match &report.findings {
+            ScanFindings::None => return Ok(()),
+            ScanFindings::Containers(results) => results
+                .iter()
+                .flat_map(|result| container_events(result, time))
+                .collect(),
+            ScanFindings::Dockerfile { path, findings } => findings
+                .iter()
+                .map(|finding| file_event(path, "Dockerfile", finding, time))
+                .collect(),
+            ScanFindings::EnvFile { path, findings } => findings
+                .iter()
+                .map(|finding| file_event(path, "EnvFile", finding, time))
+                .collect(),
+            ScanFindings::Networks(results) => results
+                .iter()
+                .flat_map(|result| network_events(result, time))
+                .collect(),
+            ScanFindings::Combined { containers, dockerfiles, env_files } => containers
+                .iter()
+                .flat_map(|result| container_events(result, time))
+                .chain(file_events(dockerfiles, "Dockerfile", time))
+                .chain(file_events(env_files, "EnvFile", time))
+                .collect(),
+        };
+
+        let json = serde_json::to_string_pretty(&events).context("Failed to serialize OCSF events")?;
+        writeln!(w, "{}", json).context("Failed to write OCSF report")
+    }
+}
+
+fn container_events(result: &ContainerResult, time: u64) -> impl Iterator<Item = SecurityFinding<'_>> {
+    let id = result.container.id.clone().unwrap_or_default();
+    let name = result
+        .container
+        .name
+        .as_deref()
+        .unwrap_or_default()
+        .trim_start_matches('/')
+        .to_string();
+
+    result.findings.iter().map(move |finding| {
+        let resource = Resource {
+            uid: id.clone(),
+            name: name.clone(),
+            kind: "Container",
+        };
+        event(finding, format!("{}:{}", finding.kind, id), resource, time)
+    })
+}
+
+fn network_events(result: &NetworkResult, time: u64) -> impl Iterator<Item = SecurityFinding<'_>> {
+    let id = result.network.id.clone().unwrap_or_default();
+    let name = result.network.name.clone().unwrap_or_default();
+
+    result.findings.iter().map(move |finding| {
+        let resource = Resource {
+            uid: id.clone(),
+            name: name.clone(),
+            kind: "Network",
+        };
+        event(finding, format!("{}:{}", finding.kind, id), resource, time)
+    })
+}
+
+fn file_events<'a>(files: &'a [FileResult], kind: &'static str, time: u64) -> impl Iterator<Item = SecurityFinding<'a>> {
+    files.iter().flat_map(move |file| {
+        file.findings
+            .iter()
+            .map(move |finding| file_event(&file.path, kind, finding, time))
+    })
+}
+
+fn file_event<'a>(path: &Path, kind: &'static str, finding: &'a Finding, time: u64) -> SecurityFinding<'a> {
+    let path = path.display().to_string();
+    let uid = format!(
+        "{}:{}:{}",
+        finding.kind,
+        path,
+        finding.line.map(|l| l.to_string()).unwrap_or_default()
+    );
+    let resource = Resource {
+        uid: path.clone(),
+        name: path,
+        kind,
+    };
+    event(finding, uid, resource, time)
+}
+
+fn event(finding: &Finding, uid: String, resource: Resource, time: u64) -> SecurityFinding<'_> {
+    let (severity_id, severity) = severity(&finding.risk);
+    SecurityFinding {
+        activity_id: ACTIVITY_CREATE,
+        activity_name: "Create",
+        category_uid: CATEGORY_UID,
+        category_name: "Findings",
+        class_uid: CLASS_UID,
+        class_name: "Security Finding",
+        type_uid: CLASS_UID * 100 + ACTIVITY_CREATE,
+        type_name: "Security Finding: Create",
+        severity_id,
+        severity,
+        state_id: STATE_NEW,
+        state: "New",
+        time,
+        message: &finding.description,
+        metadata: Metadata {
+            version: OCSF_VERSION,
+            product: Product {
+                name: "Valeris",
+                vendor_name: "rsgbengi",
+                version: env!("CARGO_PKG_VERSION"),
+            },
+        },
+        finding: FindingDetails {
+            uid,
+            title: finding.rule_name.as_deref().unwrap_or(&finding.kind),
+            desc: &finding.description,
+            types: finding.category.as_deref().map(|c| [c]),
+            remediation: finding.remediation.as_deref().map(|desc| Remediation {
+                desc,
+                kb_articles: &finding.references,
+            }),
+            first_seen_time: finding.seen.as_ref().and_then(|s| rfc3339_ms(&s.first_seen)),
+            last_seen_time: finding.seen.as_ref().and_then(|s| rfc3339_ms(&s.last_seen)),
+        },
+        resources: vec![resource],
+        unmapped: Unmapped {
+            rule_id: &finding.kind,
+            line: finding.line,
+            tags: &finding.tags,
+        },
+    }
+}
+
+/// OCSF `severity_id` and caption.
+fn severity(risk: &RiskLevel) -> (u32, &'static str) {
+    match risk {
+        RiskLevel::Critical => (5, "Critical"),
+        RiskLevel::Informative => (1, "Informational"),
+        RiskLevel::Low => (2, "Low"),
+        RiskLevel::Medium => (3, "Medium"),
+        RiskLevel::High => (4, "High"),
+    }
+}
+
+/// Event time: now, or `SOURCE_DATE_EPOCH` if set
+fn now_ms() -> u64 {
+    epoch_ms(source_date_epoch().unwrap_or_else(SystemTime::now))
+}
+
+fn rfc3339_ms(timestamp: &str) -> Option<u64> {
+    humantime::parse_rfc3339_weak(timestamp).ok().map(epoch_ms)
+}
+
+fn epoch_ms(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::docker::model::FindingHistory;
+    use crate::output::summary::{ScanSummary, TargetKind};
+    use bollard::models::ContainerInspectResponse;
+    use serde_json::Value;
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    fn export(findings: ScanFindings) -> Value {
+        let summary = ScanSummary::new(TargetKind::Containers, 1, 1, &[], Duration::ZERO, false);
+        let mut out = Vec::new();
+        OcsfExporter
+            .export(&ScanReport::from_scan(summary, findings), &mut out)
+            .unwrap();
+        serde_json::from_slice(&out).unwrap()
+    }
+
+    #[test]
+    fn container_findings_are_security_finding_events() {
+        let finding = Finding {
+            rule_name: Some("Privileged Mode".to_string()),
+            remediation: Some("Drop --privileged".to_string()),
+            references: vec!["https://example.com/cis".to_string()],
+            category: Some("Runtime".to_string()),
+            ..Finding::new("privileged_mode", "Container is privileged", RiskLevel::High)
+        };
+        let result = ContainerResult {
+            container: ContainerInspectResponse {
+                id: Some("abc123".to_string()),
+                name: Some("/web".to_string()),
+                ..Default::default()
+            },
+            findings: vec![finding],
+            host: None,
+        };
+
+        let events = export(ScanFindings::Containers(vec![result]));
+        let event = &events[0];
+
+        assert_eq!(event["class_uid"], 2001);
+        assert_eq!(event["category_uid"], 2);
+        assert_eq!(event["type_uid"], 200101);
+        assert_eq!(event["severity_id"], 4);
+        assert_eq!(event["metadata"]["version"], OCSF_VERSION);
+        assert_eq!(event["finding"]["uid"], "privileged_mode:abc123");
+        assert_eq!(event["finding"]["title"], "Privileged Mode");
+        assert_eq!(event["finding"]["types"][0], "Runtime");
+        assert_eq!(event["finding"]["remediation"]["kb_articles"][0], "https://example.com/cis");
+        assert_eq!(event["resources"][0]["name"], "web");
+        assert_eq!(event["resources"][0]["type"], "Container");
+        assert!(event["time"].as_u64().unwrap() > 0);
+    }
+
+    #[test]
+    fn dockerfile_findings_use_the_dockerfile_as_resource() {
+        let finding = Finding {
+            line: Some(3),
+            seen: Some(FindingHistory {
+                first_seen: "1970-01-01T00:00:01Z".to_string(),
+                last_seen: "1970-01-01T00:01:00Z".to_string(),
+                is_new: false,
+            }),
+            ..Finding::new("DF001", "Base image uses latest", RiskLevel::Informative)
+        };
+
+        let events = export(ScanFindings::Dockerfile {
+            path: PathBuf::from("app/Dockerfile"),
+            findings: vec![finding],
+        });
+        let event = &events[0];
+
+        assert_eq!(event["severity_id"], 1);
+        assert_eq!(event["severity"], "Informational");
+        assert_eq!(event["resources"][0]["uid"], "app/Dockerfile");
+        assert_eq!(event["resources"][0]["type"], "Dockerfile");
+        assert_eq!(event["unmapped"]["line"], 3);
+        assert!(event["finding"].get("remediation").is_none());
+        assert_eq!(event["finding"]["first_seen_time"], 1000);
+        assert_eq!(event["finding"]["last_seen_time"], 60000);
+    }
+}
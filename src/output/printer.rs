@@ -3,24 +3,71 @@
 //! This module provides a consistent, modular approach to displaying
 //! security findings across different scan types (containers, Dockerfiles, etc.).
 
-use crate::docker::model::{Finding, RiskLevel};
-use bollard::models::ContainerInspectResponse;
-use console::{style, Emoji};
+use crate::docker::model::{ContainerResult, Finding, RiskLevel};
+use crate::output::score::Grade;
+use crate::output::severity::SeverityScheme;
+use crate::output::summary::ScanSummary;
+use crate::report::ScanFindings;
+use bollard::models::{ContainerInspectResponse, Network};
+use console::{style, Emoji, StyledObject};
 use comfy_table::{Table, presets::UTF8_FULL, ContentArrangement, Cell, Color, Attribute};
+use anyhow::Context;
 use std::collections::BTreeMap;
-use std::path::PathBuf;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
 
 static DOCKER: Emoji<'_, '_> = Emoji("🐳 ", "[D] ");
 static MAGNIFIER: Emoji<'_, '_> = Emoji("🔍 ", "");
 static CHECK: Emoji<'_, '_> = Emoji("✅ ", "[OK] ");
 static WARN: Emoji<'_, '_> = Emoji("⚠️  ", "[!] ");
+static CHART: Emoji<'_, '_> = Emoji("📊 ", "");
 
 /// Context for a scan operation - what was scanned and metadata.
 pub enum ScanContext<'a> {
     /// A running Docker container
     Container(&'a ContainerInspectResponse),
+    /// A container on one of several scanned Docker hosts
+    RemoteContainer {
+        container: &'a ContainerInspectResponse,
+        host: &'a str,
+    },
     /// A Dockerfile on disk
     Dockerfile(&'a PathBuf),
+    /// A `.env` file on disk
+    EnvFile(&'a PathBuf),
+    /// A Docker network
+    Network(&'a Network),
+}
+
+/// Splits scan results into the per-target reports the printer shows, in
+/// report order: containers, networks, then files.
+pub fn report_sections(findings: &ScanFindings) -> Vec<(ScanContext<'_>, &[Finding])> {
+    match findings {
+        ScanFindings::None => Vec::new(),
+        ScanFindings::Containers(results) => results.iter().map(container_section).collect(),
+        ScanFindings::Dockerfile { path, findings } => vec![(ScanContext::Dockerfile(path), findings.as_slice())],
+        ScanFindings::EnvFile { path, findings } => vec![(ScanContext::EnvFile(path), findings.as_slice())],
+        ScanFindings::Networks(results) => results
+            .iter()
+            .map(|r| (ScanContext::Network(&r.network), r.findings.as_slice()))
+            .collect(),
+        ScanFindings::Combined { containers, dockerfiles, env_files } => containers
+            .iter()
+            .map(container_section)
+            .chain(dockerfiles.iter().map(|f| (ScanContext::Dockerfile(&f.path), f.findings.as_slice())))
+            .chain(env_files.iter().map(|f| (ScanContext::EnvFile(&f.path), f.findings.as_slice())))
+            .collect(),
+    }
+}
+
+/// Returns the print context and findings of one container's result.
+pub fn container_section(result: &ContainerResult) -> (ScanContext<'_>, &[Finding]) {
+    let context = match &result.host {
+        Some(host) => ScanContext::RemoteContainer { container: &result.container, host },
+        None => ScanContext::Container(&result.container),
+    };
+    (context, result.findings.as_slice())
 }
 
 /// Prints a comprehensive report for any scan type.
@@ -29,149 +76,204 @@ pub enum ScanContext<'a> {
 ///
 /// * `context` - The scan context (container or file)
 /// * `findings` - List of security/quality findings
-pub fn print_scan_report(context: ScanContext, findings: &[Finding]) {
-    print_header(&context);
+/// * `scheme` - How severities are named
+pub fn print_scan_report(context: ScanContext, findings: &[Finding], scheme: SeverityScheme) {
+    // Like `println!`, but a closed stdout (`| head`) is not a panic
+    let _ = write_scan_report(&mut io::stdout().lock(), context, findings, scheme);
+}
+
+/// Writes the report [`print_scan_report`] prints to `out`.
+///
+/// # Errors
+///
+/// Returns an error if `out` cannot be written
+pub fn write_scan_report(
+    out: &mut dyn Write,
+    context: ScanContext,
+    findings: &[Finding],
+    scheme: SeverityScheme,
+) -> io::Result<()> {
+    write_header(out, &context)?;
 
     if findings.is_empty() {
-        print_success_message();
-        return;
+        return write_success_message(out);
     }
 
-    print_summary(findings);
-    print_findings_table(&context, findings);
-    print_footer();
+    write_summary(out, findings, scheme)?;
+    write_findings_table(out, &context, findings, scheme)?;
+    write_footer(out)
 }
 
-/// Prints the report header based on scan context.
-fn print_header(context: &ScanContext) {
-    println!("\n{}", style("━".repeat(80)).dim());
+/// Writes the report header based on scan context.
+fn write_header(out: &mut dyn Write, context: &ScanContext) -> io::Result<()> {
+    writeln!(out, "\n{}", style("━".repeat(80)).dim())?;
 
     match context {
-        ScanContext::Container(container) => {
-            let name = container
-                .name
-                .as_deref()
-                .unwrap_or("<none>")
-                .trim_start_matches('/');
-            let image = container
-                .config
-                .as_ref()
-                .and_then(|cfg| cfg.image.as_deref())
-                .or(container.image.as_deref())
-                .unwrap_or("<unknown>");
-            let image_with_tag = if image.contains(':') {
-                image.to_string()
-            } else {
-                format!("{image}:latest")
-            };
-
-            let state_str = if let Some(state_obj) = &container.state {
-                match state_obj.status {
-                    Some(ref s) => format!("{:?}", s),
-                    None => "unknown".to_string(),
-                }
-            } else {
-                "unknown".to_string()
-            };
-
-            let status_style = match state_str.as_str() {
-                "RUNNING" => style("Running").green().bold().to_string(),
-                "EXITED" => style("Exited").red().bold().to_string(),
-                "PAUSED" => style("Paused").yellow().bold().to_string(),
-                "CREATED" => style("Created").blue().bold().to_string(),
-                _ => style(&state_str).dim().to_string(),
-            };
-
-            println!(
-                "{}{} {}",
-                DOCKER,
-                style("Container:").bold().cyan(),
-                style(name).bold().white()
-            );
-            println!("  {} {}", style("Image:").dim(), style(image_with_tag).white());
-            println!("  {} {}", style("Status:").dim(), status_style);
-        }
+        ScanContext::Container(container) => write_container_header(out, container, None)?,
+        ScanContext::RemoteContainer { container, host } => write_container_header(out, container, Some(host))?,
 
-        ScanContext::Dockerfile(path) => {
-            let file_name = path.file_name()
-                .and_then(|n| n.to_str())
-                .unwrap_or("Dockerfile");
-            let path_str = path.display().to_string();
-
-            println!(
-                "{}{} {}",
-                MAGNIFIER,
-                style("Scanning Dockerfile:").bold().cyan(),
-                style(file_name).bold().white()
-            );
-            println!("  {} {}", style("Path:").dim(), style(path_str).dim());
+        ScanContext::Dockerfile(path) => write_file_header(out, "Scanning Dockerfile:", path, "Dockerfile")?,
+        ScanContext::EnvFile(path) => write_file_header(out, "Scanning .env file:", path, ".env")?,
+        ScanContext::Network(network) => write_network_header(out, network)?,
+    }
+
+    writeln!(out, "{}", style("━".repeat(80)).dim())
+}
+
+/// Writes the header of a file report.
+fn write_file_header(out: &mut dyn Write, title: &str, path: &Path, default_name: &str) -> io::Result<()> {
+    let file_name = path.file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or(default_name);
+    let path_str = path.display().to_string();
+
+    writeln!(
+        out,
+        "{}{} {}",
+        MAGNIFIER,
+        style(title).bold().cyan(),
+        style(file_name).bold().white()
+    )?;
+    writeln!(out, "  {} {}", style("Path:").dim(), style(path_str).dim())?;
+    Ok(())
+}
+
+/// Writes the header of a container report.
+fn write_container_header(out: &mut dyn Write, container: &ContainerInspectResponse, host: Option<&str>) -> io::Result<()> {
+    let name = container
+        .name
+        .as_deref()
+        .unwrap_or("<none>")
+        .trim_start_matches('/');
+    let image = container
+        .config
+        .as_ref()
+        .and_then(|cfg| cfg.image.as_deref())
+        .or(container.image.as_deref())
+        .unwrap_or("<unknown>");
+    let image_with_tag = if image.contains(':') {
+        image.to_string()
+    } else {
+        format!("{image}:latest")
+    };
+
+    let state_str = if let Some(state_obj) = &container.state {
+        match state_obj.status {
+            Some(ref s) => format!("{:?}", s),
+            None => "unknown".to_string(),
         }
+    } else {
+        "unknown".to_string()
+    };
+
+    let status_style = match state_str.as_str() {
+        "RUNNING" => style("Running").green().bold().to_string(),
+        "EXITED" => style("Exited").red().bold().to_string(),
+        "PAUSED" => style("Paused").yellow().bold().to_string(),
+        "CREATED" => style("Created").blue().bold().to_string(),
+        _ => style(&state_str).dim().to_string(),
+    };
+
+    writeln!(
+        out,
+        "{}{} {}",
+        DOCKER,
+        style("Container:").bold().cyan(),
+        style(name).bold().white()
+    )?;
+    if let Some(host) = host {
+        writeln!(out, "  {} {}", style("Host:").dim(), style(host).white())?;
     }
+    writeln!(out, "  {} {}", style("Image:").dim(), style(image_with_tag).white())?;
+    writeln!(out, "  {} {}", style("Status:").dim(), status_style)?;
+    Ok(())
+}
 
-    println!("{}", style("━".repeat(80)).dim());
+/// Writes the header of a network report.
+fn write_network_header(out: &mut dyn Write, network: &Network) -> io::Result<()> {
+    let name = network.name.as_deref().unwrap_or("<none>");
+    let driver = network.driver.as_deref().unwrap_or("<unknown>");
+    let attached = network.containers.as_ref().map_or(0, |c| c.len());
+
+    writeln!(
+        out,
+        "{}{} {}",
+        DOCKER,
+        style("Network:").bold().cyan(),
+        style(name).bold().white()
+    )?;
+    writeln!(out, "  {} {}", style("Driver:").dim(), style(driver).white())?;
+    if network.internal.unwrap_or(false) {
+        writeln!(out, "  {} {}", style("Internal:").dim(), style("yes").green())?;
+    }
+    writeln!(out, "  {} {}", style("Containers:").dim(), style(attached).white())?;
+    Ok(())
 }
 
-/// Prints a success message when no issues are found.
-fn print_success_message() {
-    println!(
+/// Writes a success message when no issues are found.
+fn write_success_message(out: &mut dyn Write) -> io::Result<()> {
+    writeln!(
+        out,
         "\n  {}{}\n",
         CHECK,
         style("No security issues found!").green().bold()
-    );
-    println!("{}\n", style("━".repeat(80)).dim());
+    )?;
+    writeln!(out, "{}\n", style("━".repeat(80)).dim())?;
+    Ok(())
 }
 
-/// Prints a summary banner with issue counts by severity.
-fn print_summary(findings: &[Finding]) {
+/// Writes a summary banner with issue counts by severity.
+fn write_summary(out: &mut dyn Write, findings: &[Finding], scheme: SeverityScheme) -> io::Result<()> {
     let counts = count_findings_by_severity(findings);
     let total = findings.len();
 
-    print!("\n  {}", WARN);
-    print!("{} ", style(format!("{} issues found:", total)).bold().yellow());
+    write!(out, "\n  {}", WARN)?;
+    write!(out, "{} ", style(format!("{} issues found:", total)).bold().yellow())?;
 
-    let summary_parts = build_summary_parts(&counts);
-    println!("{}\n", summary_parts.join(", "));
+    let summary_parts = build_summary_parts(&counts, scheme);
+    writeln!(out, "{}\n", summary_parts.join(", "))?;
+    Ok(())
 }
 
 /// Counts findings grouped by severity level.
-fn count_findings_by_severity(findings: &[Finding]) -> BTreeMap<&'static str, usize> {
+fn count_findings_by_severity(findings: &[Finding]) -> BTreeMap<RiskLevel, usize> {
     let mut counts = BTreeMap::new();
 
     for finding in findings {
-        let severity = match finding.risk {
-            RiskLevel::High => "Critical",
-            RiskLevel::Medium => "Medium",
-            RiskLevel::Low => "Low",
-            RiskLevel::Informative => "Info",
-        };
-        *counts.entry(severity).or_insert(0) += 1;
+        *counts.entry(finding.risk.clone()).or_insert(0) += 1;
     }
 
     counts
 }
 
-/// Builds colored summary text parts for each severity level.
-fn build_summary_parts(counts: &BTreeMap<&'static str, usize>) -> Vec<String> {
-    let mut parts = Vec::new();
+/// Builds colored summary text parts for each severity level, highest first.
+fn build_summary_parts(counts: &BTreeMap<RiskLevel, usize>, scheme: SeverityScheme) -> Vec<String> {
+    counts
+        .iter()
+        .rev()
+        .map(|(risk, &n)| severity_count(n, risk, scheme).to_string())
+        .collect()
+}
 
-    if let Some(&n) = counts.get("Critical") {
-        parts.push(style(format!("{} critical", n)).red().bold().to_string());
-    }
-    if let Some(&n) = counts.get("Medium") {
-        parts.push(style(format!("{} medium", n)).yellow().to_string());
-    }
-    if let Some(&n) = counts.get("Low") {
-        parts.push(style(format!("{} low", n)).blue().to_string());
-    }
-    if let Some(&n) = counts.get("Info") {
-        parts.push(style(format!("{} info", n)).dim().to_string());
+/// Styles `"<n> <severity>"` in the color of the severity.
+fn severity_count(n: usize, risk: &RiskLevel, scheme: SeverityScheme) -> StyledObject<String> {
+    let text = style(format!("{} {}", n, scheme.label(risk).to_lowercase()));
+    match risk {
+        RiskLevel::Critical => text.magenta().bold(),
+        RiskLevel::High => text.red().bold(),
+        RiskLevel::Medium => text.yellow(),
+        RiskLevel::Low => text.blue(),
+        RiskLevel::Informative => text.dim(),
     }
-
-    parts
 }
 
-/// Prints a formatted table of all findings.
-fn print_findings_table(context: &ScanContext, findings: &[Finding]) {
+/// Writes a formatted table of all findings.
+fn write_findings_table(
+    out: &mut dyn Write,
+    context: &ScanContext,
+    findings: &[Finding],
+    scheme: SeverityScheme,
+) -> io::Result<()> {
     let mut table = Table::new();
 
     table
@@ -185,48 +287,83 @@ fn print_findings_table(context: &ScanContext, findings: &[Finding]) {
     ];
 
     // Add line column only for Dockerfile scans
-    if matches!(context, ScanContext::Dockerfile(_)) {
+    if matches!(context, ScanContext::Dockerfile(_) | ScanContext::EnvFile(_)) {
         header.push(Cell::new("Line").add_attribute(Attribute::Bold));
     }
 
     header.push(Cell::new("Description").add_attribute(Attribute::Bold));
     table.set_header(header);
 
-    // Add rows
+    // Add rows, most severe first
+    let mut findings: Vec<&Finding> = findings.iter().collect();
+    findings.sort_by(|a, b| a.report_order(b));
     for finding in findings {
-        add_finding_row(&mut table, context, finding);
+        add_finding_row(&mut table, context, finding, scheme);
     }
 
-    println!("{}\n", table);
+    writeln!(out, "{}\n", table)?;
+    Ok(())
 }
 
 /// Adds a single finding as a table row.
-fn add_finding_row(table: &mut Table, context: &ScanContext, finding: &Finding) {
-    let (severity_text, severity_color) = get_severity_display(&finding.risk);
+fn add_finding_row(table: &mut Table, context: &ScanContext, finding: &Finding, scheme: SeverityScheme) {
+    let (severity_text, severity_color) = get_severity_display(&finding.risk, scheme);
 
+    let rule = match finding.rule_name.as_deref().filter(|name| !name.is_empty()) {
+        Some(name) => format!("{}\n{}", finding.kind, name),
+        None => finding.kind.clone(),
+    };
     let mut cells = vec![
         Cell::new(severity_text).fg(severity_color).add_attribute(Attribute::Bold),
-        Cell::new(&finding.kind).fg(Color::Cyan),
+        Cell::new(rule).fg(Color::Cyan),
     ];
 
     // Add line number only for Dockerfile scans
-    if matches!(context, ScanContext::Dockerfile(_)) {
+    if matches!(context, ScanContext::Dockerfile(_) | ScanContext::EnvFile(_)) {
         let line_str = format_line_number(finding.line);
         cells.push(Cell::new(line_str));
     }
 
-    cells.push(Cell::new(&finding.description));
+    cells.push(Cell::new(describe_finding(finding)));
     table.add_row(cells);
 }
 
-/// Returns display text and color for a risk level.
-fn get_severity_display(risk: &RiskLevel) -> (&'static str, Color) {
-    match risk {
-        RiskLevel::High => ("CRITICAL", Color::Red),
-        RiskLevel::Medium => ("MEDIUM", Color::Yellow),
-        RiskLevel::Low => ("LOW", Color::Blue),
-        RiskLevel::Informative => ("INFO", Color::White),
+/// Builds the description cell: the finding text followed by the rule's
+/// remediation, references, category, and tags, when it has them.
+fn describe_finding(finding: &Finding) -> String {
+    let mut text = finding.description.clone();
+    if let Some(fix) = finding.remediation.as_deref().filter(|f| !f.is_empty()) {
+        text.push_str(&format!("\n{} {}", style("Fix:").green(), fix));
+    }
+    for reference in &finding.references {
+        text.push_str(&format!("\n{} {}", style("Ref:").dim(), style(reference).dim()));
+    }
+    if let Some(category) = &finding.category {
+        text.push_str(&format!("\n{} {}", style("Category:").dim(), category));
     }
+    if !finding.tags.is_empty() {
+        text.push_str(&format!("\n{} {}", style("Tags:").dim(), finding.tags.join(", ")));
+    }
+    for field in &finding.debug {
+        let value = match &field.value {
+            Some(value) => format!("= {}", value),
+            None => "(missing)".to_string(),
+        };
+        text.push_str(&format!("\n{} {} {}", style("Matched:").cyan(), field.path, value));
+    }
+    text
+}
+
+/// Returns display text and color for a risk level.
+fn get_severity_display(risk: &RiskLevel, scheme: SeverityScheme) -> (&'static str, Color) {
+    let color = match risk {
+        RiskLevel::Critical => Color::Magenta,
+        RiskLevel::High => Color::Red,
+        RiskLevel::Medium => Color::Yellow,
+        RiskLevel::Low => Color::Blue,
+        RiskLevel::Informative => Color::White,
+    };
+    (scheme.label(risk), color)
 }
 
 /// Formats a line number for display (or "—" if None).
@@ -237,7 +374,214 @@ fn format_line_number(line: Option<usize>) -> String {
     }
 }
 
-/// Prints the report footer.
-fn print_footer() {
-    println!("{}\n", style("━".repeat(80)).dim());
+/// Writes the report footer.
+fn write_footer(out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "{}\n", style("━".repeat(80)).dim())?;
+    Ok(())
+}
+
+/// Prints the final summary emitted after all per-target reports.
+///
+/// # Arguments
+///
+/// * `summary` - Statistics for the completed scan
+/// * `scheme` - How severities are named
+pub fn print_scan_summary(summary: &ScanSummary, scheme: SeverityScheme) {
+    let _ = write_scan_summary(&mut io::stdout().lock(), summary, scheme);
+}
+
+/// Writes the summary [`print_scan_summary`] prints to `out`.
+///
+/// # Errors
+///
+/// Returns an error if `out` cannot be written
+pub fn write_scan_summary(out: &mut dyn Write, summary: &ScanSummary, scheme: SeverityScheme) -> io::Result<()> {
+    writeln!(out, "{}", style("━".repeat(80)).dim())?;
+    writeln!(out, "{}{}", CHART, style("Scan Summary").bold().cyan())?;
+    writeln!(out, "{}", style("━".repeat(80)).dim())?;
+
+    writeln!(
+        out,
+        "  {} {} {}",
+        style("Scanned:").dim(),
+        summary.targets_scanned,
+        summary.target_kind.label()
+    )?;
+    writeln!(out, "  {} {}", style("Rules evaluated:").dim(), summary.rules_evaluated)?;
+
+    let counts = &summary.findings;
+    writeln!(
+        out,
+        "  {} {} ({}, {}, {}, {}, {})",
+        style("Findings:").dim(),
+        style(counts.total).bold(),
+        severity_count(counts.critical, &RiskLevel::Critical, scheme),
+        severity_count(counts.high, &RiskLevel::High, scheme),
+        severity_count(counts.medium, &RiskLevel::Medium, scheme),
+        severity_count(counts.low, &RiskLevel::Low, scheme),
+        severity_count(counts.informative, &RiskLevel::Informative, scheme)
+    )?;
+    writeln!(
+        out,
+        "  {} {:.2}s",
+        style("Duration:").dim(),
+        summary.duration_ms as f64 / 1000.0
+    )?;
+
+    let fail_status = if summary.fail_on_triggered {
+        style("triggered").red().bold().to_string()
+    } else {
+        style("not triggered").green().to_string()
+    };
+    writeln!(out, "  {} {}", style("Fail-on:").dim(), fail_status)?;
+
+    match summary.scores.as_slice() {
+        [] => {}
+        [only] => writeln!(
+            out,
+            "  {} {}/100 (grade {})",
+            style("Score:").dim(),
+            only.score.score,
+            style_grade(only.score.grade)
+        )?,
+        scores => {
+            // Worst first, so the services needing attention lead
+            let mut scores: Vec<_> = scores.iter().collect();
+            scores.sort_by_key(|s| (s.score.score, s.target.as_str()));
+            writeln!(out, "  {}", style("Scores:").dim())?;
+            for entry in scores {
+                writeln!(
+                    out,
+                    "    {} {:>3}  {}",
+                    style_grade(entry.score.grade),
+                    entry.score.score,
+                    entry.target
+                )?;
+            }
+        }
+    }
+
+    writeln!(out, "{}\n", style("━".repeat(80)).dim())?;
+    Ok(())
+}
+
+fn style_grade(grade: Grade) -> StyledObject<String> {
+    let styled = style(grade.to_string()).bold();
+    match grade {
+        Grade::A | Grade::B => styled.green(),
+        Grade::C | Grade::D => styled.yellow(),
+        Grade::F => styled.red(),
+    }
+}
+
+/// Table output for a report file (`--format table --output <FILE>`):
+/// the same layout as the console, always without colors.
+pub struct PlainTableWriter<W: Write> {
+    out: W,
+    scheme: SeverityScheme,
+}
+
+impl PlainTableWriter<BufWriter<File>> {
+    /// Creates the report file at `path`, naming severities with `scheme`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be created
+    pub fn create(path: impl AsRef<Path>, scheme: SeverityScheme) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let file = File::create(path)
+            .with_context(|| format!("Failed to create output file {}", path.display()))?;
+        Ok(Self::new(BufWriter::new(file), scheme))
+    }
+}
+
+impl<W: Write> PlainTableWriter<W> {
+    pub fn new(out: W, scheme: SeverityScheme) -> Self {
+        Self { out, scheme }
+    }
+
+    /// Writes the report of one scanned target.
+    pub fn write_report(&mut self, context: ScanContext, findings: &[Finding]) -> anyhow::Result<()> {
+        let mut buffer = Vec::new();
+        write_scan_report(&mut buffer, context, findings, self.scheme)?;
+        self.write_plain(&buffer)
+    }
+
+    /// Writes the final summary and flushes the file.
+    pub fn finish(mut self, summary: &ScanSummary) -> anyhow::Result<()> {
+        let mut buffer = Vec::new();
+        write_scan_summary(&mut buffer, summary, self.scheme)?;
+        self.write_plain(&buffer)?;
+        self.out.flush().context("Failed to flush table report")
+    }
+
+    /// Colors follow the console, so they are stripped after rendering
+    fn write_plain(&mut self, rendered: &[u8]) -> anyhow::Result<()> {
+        let text = console::strip_ansi_codes(&String::from_utf8_lossy(rendered)).into_owned();
+        self.out
+            .write_all(text.as_bytes())
+            .context("Failed to write table report")
+    }
+}
+
+/// Prints the one-line machine-parsable scan result to stderr.
+///
+/// Written regardless of output format so CI logs show the outcome even
+/// when the report itself is redirected to a file.
+pub fn print_status_line(summary: &ScanSummary) {
+    eprintln!("{}", summary.status_line());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::docker::model::MatchedField;
+
+    #[test]
+    fn describe_finding_appends_fix_and_references() {
+        console::set_colors_enabled(false);
+        let mut finding = Finding::new("privileged_mode", "Container is privileged", RiskLevel::High);
+        assert_eq!(describe_finding(&finding), "Container is privileged");
+
+        finding.remediation = Some("Drop --privileged".to_string());
+        finding.references = vec!["https://docs.docker.com/".to_string()];
+        assert_eq!(
+            describe_finding(&finding),
+            "Container is privileged\nFix: Drop --privileged\nRef: https://docs.docker.com/"
+        );
+
+        finding.category = Some("Runtime".to_string());
+        finding.tags = vec!["cis".to_string(), "escape".to_string()];
+        assert!(describe_finding(&finding).ends_with("\nCategory: Runtime\nTags: cis, escape"));
+    }
+
+    #[test]
+    fn describe_finding_appends_matched_fields() {
+        console::set_colors_enabled(false);
+        let mut finding = Finding::new("privileged_mode", "Container is privileged", RiskLevel::High);
+        finding.debug = vec![
+            MatchedField::new("$.HostConfig.Privileged", Some("true".to_string())),
+            MatchedField::new("healthcheck", None),
+        ];
+        assert_eq!(
+            describe_finding(&finding),
+            "Container is privileged\nMatched: $.HostConfig.Privileged = true\nMatched: healthcheck (missing)"
+        );
+    }
+
+    #[test]
+    fn plain_table_writer_strips_colors() {
+        let finding = Finding::new("privileged_mode", "Container is privileged", RiskLevel::High);
+        let path = PathBuf::from("Dockerfile");
+
+        let mut out = Vec::new();
+        let mut writer = PlainTableWriter::new(&mut out, SeverityScheme::default());
+        writer.write_plain(style("HIGH").red().force_styling(true).to_string().as_bytes()).unwrap();
+        writer.write_report(ScanContext::Dockerfile(&path), &[finding]).unwrap();
+
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.starts_with("HIGH\n"), "{text}");
+        assert!(!text.contains('\u{1b}'), "{text}");
+        assert!(text.contains("Container is privileged"));
+    }
 }
@@ -0,0 +1,195 @@
+//! Secret redaction (`--redact-secrets`).
+//!
+//! Findings of secret rules (tagged or categorized `secrets`, or one of the
+//! built-in [`SECRET_RULES`]) quote the variable they matched, e.g. `DB_PASSWORD=hunter2`. Redaction masks the
+//! value and keeps the key name, so the finding stays actionable without the
+//! report leaking the credential: `DB_PASSWORD=****`.
+//!
+//! Values are masked in descriptions, matched values ([`Finding::debug`]),
+//! and context slices. Every value a rule matched is masked
+//! wherever it appears, and any other `KEY=value` assignment in those texts
+//! is masked too.
+
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+use crate::docker::model::Finding;
+
+/// Text that replaces a secret value
+pub const MASK: &str = "****";
+
+/// Tag or category that marks a finding of a secret rule
+const SECRETS: &str = "secrets";
+
+/// IDs of the secret rules Valeris ships. Rule packs published before the
+/// `secrets` tag existed carry no metadata for them, so they are recognized
+/// by ID as well.
+pub const SECRET_RULES: &[&str] = &["secrets_in_env", "DF006"];
+
+/// Returns whether `finding` comes from a secret rule.
+pub fn is_secret(finding: &Finding) -> bool {
+    finding.category.as_deref() == Some(SECRETS)
+        || finding.tags.iter().any(|tag| tag == SECRETS)
+        || SECRET_RULES.iter().any(|id| finding.kind.eq_ignore_ascii_case(id))
+}
+
+/// Masks the secret values of every secret finding in `findings`.
+pub fn redact_findings(findings: &mut [Finding]) {
+    for finding in findings.iter_mut().filter(|finding| is_secret(finding)) {
+        redact(finding);
+    }
+}
+
+fn redact(finding: &mut Finding) {
+    // Values the rule matched, longest first, so a value is not partly
+    // masked by one it contains
+    let mut secrets: Vec<String> = finding
+        .debug
+        .iter()
+        .filter_map(|field| secret_value(&field.path, field.value.as_deref()?))
+        .filter(|secret| !secret.is_empty() && *secret != MASK)
+        .map(str::to_string)
+        .collect();
+    secrets.sort_by(|a, b| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+    secrets.dedup();
+
+    let values = finding.debug.iter_mut().filter_map(|field| field.value.as_mut());
+    for text in std::iter::once(&mut finding.description).chain(values) {
+        mask(text, &secrets);
+    }
+    if let Some(context) = &mut finding.context {
+        mask_json(context, &secrets);
+    }
+}
+
+/// The secret in a matched value: all of a value field (`env.value`), or
+/// the value of a `KEY=value` assignment.
+fn secret_value<'a>(path: &str, value: &'a str) -> Option<&'a str> {
+    if path.ends_with("value") {
+        return Some(value);
+    }
+    let (key, secret) = value.split_once('=')?;
+    let is_key = key.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.');
+    is_key.then_some(secret)
+}
+
+fn mask(text: &mut String, secrets: &[String]) {
+    for secret in secrets {
+        if text.contains(secret.as_str()) {
+            *text = text.replace(secret.as_str(), MASK);
+        }
+    }
+    if assignment_regex().is_match(text) {
+        *text = assignment_regex().replace_all(text, format!("${{1}}={}", MASK)).into_owned();
+    }
+}
+
+/// Masks secrets in every string of a JSON slice.
+fn mask_json(value: &mut serde_json::Value, secrets: &[String]) {
+    match value {
+        serde_json::Value::String(text) => mask(text, secrets),
+        serde_json::Value::Array(items) => items.iter_mut().for_each(|v| mask_json(v, secrets)),
+        serde_json::Value::Object(map) => map.values_mut().for_each(|v| mask_json(v, secrets)),
+        _ => {}
+    }
+}
+
+/// A `KEY=value` assignment; the value runs to the next whitespace
+fn assignment_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r"\b([A-Za-z_][A-Za-z0-9_.]*)=(\S+)").expect("valid assignment regex"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::docker::model::{MatchedField, RiskLevel};
+
+    fn env_finding(tags: Vec<String>) -> Finding {
+        Finding {
+            tags,
+            debug: vec![MatchedField::new("$.Config.Env[0]", Some("DB_PASSWORD=hunter2 now".into()))],
+            context: Some(serde_json::json!({ "Env": "DB_PASSWORD=hunter2 now" })),
+            ..Finding::new(
+                "secrets_in_env",
+                "Sensitive variable found in environment: DB_PASSWORD=hunter2 now",
+                RiskLevel::High,
+            )
+        }
+    }
+
+    #[test]
+    fn secret_values_are_masked_and_keys_kept() {
+        let mut findings = vec![env_finding(vec!["secrets".into()])];
+        redact_findings(&mut findings);
+
+        let finding = &findings[0];
+        assert_eq!(finding.description, "Sensitive variable found in environment: DB_PASSWORD=****");
+        assert_eq!(finding.debug[0].value.as_deref(), Some("DB_PASSWORD=****"));
+        assert_eq!(finding.context, Some(serde_json::json!({ "Env": "DB_PASSWORD=****" })));
+    }
+
+    #[test]
+    fn value_fields_and_loose_assignments_are_masked() {
+        let mut finding = Finding {
+            category: Some("secrets".into()),
+            debug: vec![
+                MatchedField::new("env.key", Some("API_KEY".into())),
+                MatchedField::new("env.value", Some("sk-123".into())),
+            ],
+            ..Finding::new("ENV", "Stage 0: API_KEY=sk-123 and TOKEN=abc", RiskLevel::High)
+        };
+        redact_findings(std::slice::from_mut(&mut finding));
+
+        assert_eq!(finding.description, "Stage 0: API_KEY=**** and TOKEN=****");
+        assert_eq!(finding.debug[0].value.as_deref(), Some("API_KEY"));
+        assert_eq!(finding.debug[1].value.as_deref(), Some(MASK));
+    }
+
+    #[test]
+    fn other_findings_are_left_alone() {
+        let mut findings = vec![Finding {
+            kind: "exposed_ports".into(),
+            ..env_finding(Vec::new())
+        }];
+        redact_findings(&mut findings);
+
+        assert!(findings[0].description.ends_with("DB_PASSWORD=hunter2 now"));
+    }
+
+    #[test]
+    fn built_in_secret_rules_are_masked_without_metadata() {
+        // `secrets_in_env` as published in the v0.1.0 rule pack: no category or tags
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("docker")).unwrap();
+        std::fs::write(
+            dir.path().join("docker/secrets_in_env.yaml"),
+            r#"id: secrets_in_env
+name: "Sensitive Environment Variables Checker"
+target: docker_runtime
+severity: HIGH
+description: Searches for passwords, tokens or other secrets in environment variables.
+match:
+  jsonpath: "$.Config.Env[*]"
+  regex: "PASSWORD|SECRET|TOKEN|API_KEY|ACCESS_KEY|PRIVATE_KEY|DB_PASSWORD|DB_PASS|DB_USER|AWS_ACCESS_KEY_ID|AWS_SECRET_ACCESS_KEY|GCP_KEY|JWT_SECRET"
+include_match_in_description: true
+message: "Sensitive variable found in environment"
+fix: |
+  Remove hardcoded secrets and use secure secret management.
+"#,
+        )
+        .unwrap();
+        let engine = crate::YamlRuleEngine::from_dir(dir.path()).unwrap();
+        let input = serde_json::json!({ "Config": { "Env": ["DB_PASSWORD=supersecret", "JWT_SECRET=xyz"] } });
+
+        let mut findings = engine.scan_value(&input);
+        assert_eq!(findings.len(), 2);
+        assert!(findings.iter().all(|f| f.tags.is_empty() && f.category.is_none()));
+        redact_findings(&mut findings);
+
+        assert!(findings[0].description.ends_with("DB_PASSWORD=****"), "{}", findings[0].description);
+        assert!(findings[1].description.ends_with("JWT_SECRET=****"), "{}", findings[1].description);
+    }
+}
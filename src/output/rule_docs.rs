@@ -0,0 +1,220 @@
+//! Rule documentation generation.
+//!
+//! Renders the metadata of every loaded rule (runtime and Dockerfile) into
+//! documentation pages, so published docs stay in lockstep with the rule
+//! bundle actually shipped to users.
+
+use crate::detectors::dockerfile::yaml_rules::Rule;
+use crate::detectors::runtime::yaml_rules::YamlRule;
+use crate::output::compliance::framework_name;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// Renders all rules as a single Markdown document.
+///
+/// Rules are sorted by ID within each section so the output is stable
+/// across runs and suitable for committing to a docs site.
+///
+/// # Arguments
+///
+/// * `runtime_rules` - Loaded runtime (container) rules
+/// * `dockerfile_rules` - Loaded Dockerfile rules
+///
+/// # Returns
+///
+/// The rendered Markdown document
+pub fn render_markdown(runtime_rules: &[YamlRule], dockerfile_rules: &[Rule]) -> String {
+    let mut out = String::new();
+
+    out.push_str("# Valeris Rule Reference\n\n");
+    out.push_str("> Generated by `valeris rules docs`. Do not edit by hand.\n\n");
+
+    if !runtime_rules.is_empty() {
+        let _ = writeln!(out, "## Runtime Rules ({})\n", runtime_rules.len());
+        let mut rules: Vec<&YamlRule> = runtime_rules.iter().collect();
+        rules.sort_by(|a, b| a.id.cmp(&b.id));
+        for rule in rules {
+            render_runtime_rule(&mut out, rule);
+        }
+    }
+
+    if !dockerfile_rules.is_empty() {
+        let _ = writeln!(out, "## Dockerfile Rules ({})\n", dockerfile_rules.len());
+        let mut rules: Vec<&Rule> = dockerfile_rules.iter().collect();
+        rules.sort_by(|a, b| a.id().cmp(b.id()));
+        for rule in rules {
+            render_dockerfile_rule(&mut out, rule);
+        }
+    }
+
+    out
+}
+
+/// Renders a single runtime rule section.
+fn render_runtime_rule(out: &mut String, rule: &YamlRule) {
+    render_heading(out, &rule.id, rule.name.as_deref());
+
+    out.push_str("| Field | Value |\n|-------|-------|\n");
+    let _ = writeln!(out, "| Severity | {} |", rule.severity.as_deref().unwrap_or("MEDIUM"));
+    if let Some(target) = &rule.target {
+        let _ = writeln!(out, "| Target | `{}` |", target);
+    }
+    if rule.deprecated {
+        render_deprecated(out, rule.replaced_by.as_deref());
+    }
+    render_compliance(out, &rule.compliance);
+    out.push('\n');
+
+    if let Some(description) = &rule.description {
+        let _ = writeln!(out, "{}\n", description.trim());
+    }
+
+    let _ = writeln!(out, "**Message:** {}\n", rule.message.trim());
+
+    if let Some(fix) = &rule.fix {
+        let _ = writeln!(out, "**Remediation:** {}\n", fix.trim());
+    }
+
+    render_list(out, "References", &rule.references);
+    render_examples(out, &rule.examples);
+}
+
+/// Renders a single Dockerfile rule section.
+fn render_dockerfile_rule(out: &mut String, rule: &Rule) {
+    render_heading(out, rule.id(), rule.name());
+
+    out.push_str("| Field | Value |\n|-------|-------|\n");
+    let _ = writeln!(out, "| Severity | {} |", rule.severity().as_str());
+    let _ = writeln!(out, "| Scope | {} |", rule.scope());
+    match rule {
+        Rule::Instruction { kind, .. } => {
+            let _ = writeln!(out, "| Instruction | `{}` |", kind);
+        }
+        Rule::Correlation { present, absent, .. } => {
+            for pattern in present {
+                let _ = writeln!(out, "| Requires | `{}` |", pattern.kind);
+            }
+            for pattern in absent {
+                let _ = writeln!(out, "| Forbids | `{}` |", pattern.kind);
+            }
+        }
+        _ => {}
+    }
+    if !rule.tags().is_empty() {
+        let _ = writeln!(out, "| Tags | {} |", rule.tags().join(", "));
+    }
+    if rule.deprecated() {
+        render_deprecated(out, rule.replaced_by());
+    }
+    render_compliance(out, rule.compliance());
+    out.push('\n');
+
+    let _ = writeln!(out, "**Message:** {}\n", rule.message().trim());
+    let _ = writeln!(out, "**Remediation:** {}\n", rule.remediation().trim());
+}
+
+/// Renders the `Deprecated` table row, naming the replacement if any.
+fn render_deprecated(out: &mut String, replaced_by: Option<&str>) {
+    match replaced_by {
+        Some(id) => {
+            let _ = writeln!(out, "| Deprecated | use `{}` |", id);
+        }
+        None => out.push_str("| Deprecated | yes |\n"),
+    }
+}
+
+/// Renders one `Compliance` table row per framework.
+fn render_compliance(out: &mut String, compliance: &BTreeMap<String, Vec<String>>) {
+    for (framework, controls) in compliance {
+        let _ = writeln!(out, "| Compliance | {} {} |", framework_name(framework), controls.join(", "));
+    }
+}
+
+fn render_heading(out: &mut String, id: &str, name: Option<&str>) {
+    match name {
+        Some(name) => {
+            let _ = writeln!(out, "### `{}` — {}\n", id, name);
+        }
+        None => {
+            let _ = writeln!(out, "### `{}`\n", id);
+        }
+    }
+}
+
+fn render_list(out: &mut String, title: &str, items: &[String]) {
+    if items.is_empty() {
+        return;
+    }
+    let _ = writeln!(out, "**{}:**\n", title);
+    for item in items {
+        let _ = writeln!(out, "- {}", item);
+    }
+    out.push('\n');
+}
+
+fn render_examples(out: &mut String, examples: &[String]) {
+    if examples.is_empty() {
+        return;
+    }
+    out.push_str("**Examples:**\n\n");
+    for example in examples {
+        let _ = writeln!(out, "```\n{}\n```\n", example.trim_end());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::detectors::runtime::yaml_rules::RuleMatcher;
+
+    fn runtime_rule(id: &str) -> YamlRule {
+        YamlRule {
+            id: id.to_string(),
+            name: Some("Privileged Mode".to_string()),
+            target: Some("docker_runtime".to_string()),
+            severity: Some("HIGH".to_string()),
+            description: Some("Detect privileged containers.".to_string()),
+            references: vec!["https://docs.docker.com/".to_string()],
+            tags: vec![],
+            category: None,
+            compliance: BTreeMap::from([("nist-800-190".to_string(), vec!["4.4.3".to_string()])]),
+            matcher: RuleMatcher {
+                parts: None,
+                separator: None,
+                equals: None,
+                regex: None,
+                jsonpath: None,
+            },
+            suppress_if: vec![],
+            message: "Container is privileged".to_string(),
+            params: BTreeMap::new(),
+            fix: Some("Drop --privileged".to_string()),
+            include_match_in_description: false,
+            examples: vec!["docker run --privileged nginx".to_string()],
+            deprecated: false,
+            replaced_by: None,
+        }
+    }
+
+    #[test]
+    fn render_markdown_includes_runtime_metadata() {
+        let doc = render_markdown(&[runtime_rule("privileged_mode")], &[]);
+
+        assert!(doc.contains("## Runtime Rules (1)"));
+        assert!(doc.contains("### `privileged_mode` — Privileged Mode"));
+        assert!(doc.contains("| Severity | HIGH |"));
+        assert!(doc.contains("| Compliance | NIST SP 800-190 4.4.3 |"));
+        assert!(doc.contains("**Remediation:** Drop --privileged"));
+        assert!(doc.contains("- https://docs.docker.com/"));
+        assert!(doc.contains("docker run --privileged nginx"));
+        assert!(!doc.contains("Dockerfile Rules"));
+    }
+
+    #[test]
+    fn render_markdown_sorts_rules_by_id() {
+        let doc = render_markdown(&[runtime_rule("b_rule"), runtime_rule("a_rule")], &[]);
+        let a = doc.find("`a_rule`").unwrap();
+        let b = doc.find("`b_rule`").unwrap();
+        assert!(a < b);
+    }
+}
@@ -0,0 +1,424 @@
+//! SARIF export.
+//!
+//! Writes findings as a [SARIF 2.1.0](https://docs.oasis-open.org/sarif/sarif/v2.1.0/)
+//! log, so they can be uploaded to GitHub Code Scanning and other SARIF
+//! consumers. The log has one run whose tool driver lists every rule that
+//! produced a finding; Dockerfile and `.env` findings point at their file and
+//! line, while container and network findings name the scanned object as a
+//! logical location and as a `container://` or `network://` artifact URI, for
+//! consumers that require a physical location.
+
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+
+use crate::docker::model::{ContainerResult, FileResult, Finding, NetworkResult, RiskLevel};
+use crate::output::exporters::Exporter;
+use crate::report::{ScanFindings, ScanReport};
+
+/// SARIF version the log conforms to
+pub const SARIF_VERSION: &str = "2.1.0";
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// SARIF 2.1.0 log, registered as `sarif`.
+pub struct SarifExporter;
+
+#[derive(Serialize)]
+struct Log<'a> {
+    #[serde(rename = "$schema")]
+    schema: &'static str,
+    version: &'static str,
+    runs: [Run<'a>; 1],
+}
+
+#[derive(Serialize)]
+struct Run<'a> {
+    tool: Tool<'a>,
+    results: Vec<SarifResult<'a>>,
+}
+
+#[derive(Serialize)]
+struct Tool<'a> {
+    driver: Driver<'a>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Driver<'a> {
+    name: &'static str,
+    version: &'static str,
+    information_uri: &'static str,
+    rules: Vec<Rule<'a>>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Rule<'a> {
+    id: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<&'a str>,
+    short_description: Message<'a>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    help: Option<Message<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    help_uri: Option<&'a str>,
+    default_configuration: Configuration,
+    properties: RuleProperties<'a>,
+}
+
+#[derive(Serialize)]
+struct Message<'a> {
+    text: &'a str,
+}
+
+#[derive(Serialize)]
+struct Configuration {
+    level: &'static str,
+}
+
+#[derive(Serialize)]
+struct RuleProperties<'a> {
+    /// CVSS-like score GitHub Code Scanning derives its severity from
+    #[serde(rename = "security-severity")]
+    security_severity: &'static str,
+    #[serde(skip_serializing_if = "<[String]>::is_empty")]
+    tags: &'a [String],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    category: Option<&'a str>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifResult<'a> {
+    rule_id: &'a str,
+    rule_index: usize,
+    level: &'static str,
+    message: Message<'a>,
+    locations: [Location; 1],
+    partial_fingerprints: BTreeMap<&'static str, String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Location {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    physical_location: Option<PhysicalLocation>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    logical_locations: Vec<LogicalLocation>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PhysicalLocation {
+    artifact_location: ArtifactLocation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    region: Option<Region>,
+}
+
+#[derive(Serialize)]
+struct ArtifactLocation {
+    uri: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Region {
+    start_line: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    start_column: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    end_line: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    end_column: Option<usize>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LogicalLocation {
+    name: String,
+    fully_qualified_name: String,
+    kind: &'static str,
+}
+
+impl Exporter for SarifExporter {
+    fn export(&self, report: &ScanReport, w: &mut dyn Write) -> Result<()> {
+        let located: Vec<(&Finding, Location)> = match &report.findings {
+            ScanFindings::None => return Ok(()),
+            ScanFindings::Containers(results) => results.iter().flat_map(container_results).collect(),
+            ScanFindings::Dockerfile { path, findings } => {
+                findings.iter().map(|finding| file_result(path, finding)).collect()
+            }
+            ScanFindings::EnvFile { path, findings } => {
+                findings.iter().map(|finding| file_result(path, finding)).collect()
+            }
+            ScanFindings::Networks(results) => results.iter().flat_map(network_results).collect(),
+            ScanFindings::Combined { containers, dockerfiles, env_files } => containers
+                .iter()
+                .flat_map(container_results)
+                .chain(file_results(dockerfiles))
+                .chain(file_results(env_files))
+                .collect(),
+        };
+
+        // One rule entry per rule ID, from the first finding it produced
+        let mut index = BTreeMap::new();
+        for (finding, _) in &located {
+            index.entry(finding.kind.as_str()).or_insert(*finding);
+        }
+        let rules: Vec<Rule> = index.values().map(|finding| rule(finding)).collect();
+        let position: BTreeMap<&str, usize> = index.keys().enumerate().map(|(i, id)| (*id, i)).collect();
+
+        let results = located
+            .into_iter()
+            .map(|(finding, location)| SarifResult {
+                rule_id: &finding.kind,
+                rule_index: position[finding.kind.as_str()],
+                level: level(&finding.risk),
+                message: Message {
+                    text: &finding.description,
+                },
+                partial_fingerprints: BTreeMap::from([("valerisFinding/v1", fingerprint(finding, &location))]),
+                locations: [location],
+            })
+            .collect();
+
+        let log = Log {
+            schema: SARIF_SCHEMA,
+            version: SARIF_VERSION,
+            runs: [Run {
+                tool: Tool {
+                    driver: Driver {
+                        name: "Valeris",
+                        version: env!("CARGO_PKG_VERSION"),
+                        information_uri: "https://github.com/rsgbengi/valeris",
+                        rules,
+                    },
+                },
+                results,
+            }],
+        };
+        let json = serde_json::to_string_pretty(&log).context("Failed to serialize SARIF log")?;
+        writeln!(w, "{}", json).context("Failed to write SARIF report")
+    }
+}
+
+fn container_results(result: &ContainerResult) -> impl Iterator<Item = (&Finding, Location)> {
+    let name = result
+        .container
+        .name
+        .as_deref()
+        .unwrap_or_default()
+        .trim_start_matches('/')
+        .to_string();
+    let qualified = match &result.host {
+        Some(host) => format!("{}/{}", host, name),
+        None => name.clone(),
+    };
+    result
+        .findings
+        .iter()
+        .map(move |finding| (finding, object(&name, &qualified, "container")))
+}
+
+fn network_results(result: &NetworkResult) -> impl Iterator<Item = (&Finding, Location)> {
+    let name = result.network.name.clone().unwrap_or_default();
+    result
+        .findings
+        .iter()
+        .map(move |finding| (finding, object(&name, &name, "network")))
+}
+
+fn file_results(files: &[FileResult]) -> impl Iterator<Item = (&Finding, Location)> {
+    files
+        .iter()
+        .flat_map(|file| file.findings.iter().map(|finding| file_result(&file.path, finding)))
+}
+
+fn file_result<'a>(path: &Path, finding: &'a Finding) -> (&'a Finding, Location) {
+    let location = Location {
+        physical_location: Some(PhysicalLocation {
+            artifact_location: ArtifactLocation { uri: uri(path) },
+            region: finding.line.map(|start_line| Region {
+                start_line,
+                start_column: finding.span.as_ref().map(|span| span.column),
+                end_line: finding.span.as_ref().map(|span| span.end_line),
+                end_column: finding.span.as_ref().map(|span| span.end_column),
+            }),
+        }),
+        logical_locations: Vec::new(),
+    };
+    (finding, location)
+}
+
+/// Location of a scanned object: `<kind>://<qualified>` as its artifact,
+/// and the object itself as a logical location.
+fn object(name: &str, qualified: &str, kind: &'static str) -> Location {
+    Location {
+        physical_location: Some(PhysicalLocation {
+            artifact_location: ArtifactLocation {
+                uri: format!("{}://{}", kind, qualified),
+            },
+            region: None,
+        }),
+        logical_locations: vec![LogicalLocation {
+            name: name.to_string(),
+            fully_qualified_name: qualified.to_string(),
+            kind,
+        }],
+    }
+}
+
+/// Relative URI of `path`, with forward slashes as SARIF requires
+fn uri(path: &Path) -> String {
+    let path = path.strip_prefix(".").unwrap_or(path);
+    path.to_string_lossy().replace('\\', "/")
+}
+
+fn rule(finding: &Finding) -> Rule<'_> {
+    Rule {
+        id: &finding.kind,
+        name: finding.rule_name.as_deref(),
+        short_description: Message {
+            text: finding.rule_name.as_deref().unwrap_or(&finding.description),
+        },
+        help: finding.remediation.as_deref().map(|text| Message { text }),
+        help_uri: finding.references.first().map(String::as_str),
+        default_configuration: Configuration {
+            level: level(&finding.risk),
+        },
+        properties: RuleProperties {
+            security_severity: security_severity(&finding.risk),
+            tags: &finding.tags,
+            category: finding.category.as_deref(),
+        },
+    }
+}
+
+/// Identifies a finding across scans: rule ID plus the scanned object, so
+/// consumers can track it when lines move
+fn fingerprint(finding: &Finding, location: &Location) -> String {
+    let target = match (location.logical_locations.first(), &location.physical_location) {
+        (Some(logical), _) => logical.fully_qualified_name.as_str(),
+        (None, Some(physical)) => physical.artifact_location.uri.as_str(),
+        (None, None) => "",
+    };
+    format!("{}:{}", finding.kind, target)
+}
+
+/// SARIF result level. Informative findings are notes, like low ones.
+fn level(risk: &RiskLevel) -> &'static str {
+    match risk {
+        RiskLevel::Critical | RiskLevel::High => "error",
+        RiskLevel::Medium => "warning",
+        RiskLevel::Low | RiskLevel::Informative => "note",
+    }
+}
+
+/// GitHub maps 9.0+ to critical, 7.0+ to high, 4.0+ to medium, and 0.1+ to low.
+fn security_severity(risk: &RiskLevel) -> &'static str {
+    match risk {
+        RiskLevel::Critical => "9.5",
+        RiskLevel::High => "8.0",
+        RiskLevel::Medium => "5.0",
+        RiskLevel::Low => "3.0",
+        RiskLevel::Informative => "0.0",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::docker::model::SourceSpan;
+    use crate::output::summary::{ScanSummary, TargetKind};
+    use bollard::models::ContainerInspectResponse;
+    use serde_json::Value;
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    fn export(findings: ScanFindings) -> Value {
+        let summary = ScanSummary::new(TargetKind::Containers, 1, 1, &[], Duration::ZERO, false);
+        let mut out = Vec::new();
+        SarifExporter
+            .export(&ScanReport::from_scan(summary, findings), &mut out)
+            .unwrap();
+        serde_json::from_slice(&out).unwrap()
+    }
+
+    #[test]
+    fn dockerfile_findings_have_file_and_line_locations() {
+        let finding = Finding {
+            line: Some(3),
+            span: Some(SourceSpan { column: 1, end_line: 4, end_column: 12 }),
+            rule_name: Some("Latest Tag".to_string()),
+            remediation: Some("Pin the base image".to_string()),
+            references: vec!["https://example.com/pin".to_string()],
+            tags: vec!["supply-chain".to_string()],
+            ..Finding::new("DF001", "Base image uses latest", RiskLevel::Medium)
+        };
+
+        let log = export(ScanFindings::Dockerfile {
+            path: PathBuf::from("./app/Dockerfile"),
+            findings: vec![finding],
+        });
+        let run = &log["runs"][0];
+        let rule = &run["tool"]["driver"]["rules"][0];
+        let result = &run["results"][0];
+
+        assert_eq!(log["version"], SARIF_VERSION);
+        assert_eq!(rule["id"], "DF001");
+        assert_eq!(rule["shortDescription"]["text"], "Latest Tag");
+        assert_eq!(rule["help"]["text"], "Pin the base image");
+        assert_eq!(rule["helpUri"], "https://example.com/pin");
+        assert_eq!(rule["defaultConfiguration"]["level"], "warning");
+        assert_eq!(rule["properties"]["security-severity"], "5.0");
+        assert_eq!(rule["properties"]["tags"][0], "supply-chain");
+        assert_eq!(result["ruleId"], "DF001");
+        assert_eq!(result["ruleIndex"], 0);
+        assert_eq!(result["level"], "warning");
+        assert_eq!(result["message"]["text"], "Base image uses latest");
+        let physical = &result["locations"][0]["physicalLocation"];
+        assert_eq!(physical["artifactLocation"]["uri"], "app/Dockerfile");
+        assert_eq!(physical["region"]["startLine"], 3);
+        assert_eq!(physical["region"]["startColumn"], 1);
+        assert_eq!(physical["region"]["endLine"], 4);
+        assert_eq!(physical["region"]["endColumn"], 12);
+    }
+
+    #[test]
+    fn container_findings_share_rules_and_name_the_container() {
+        let container = |name: &str| ContainerResult {
+            container: ContainerInspectResponse {
+                name: Some(format!("/{name}")),
+                ..Default::default()
+            },
+            findings: vec![
+                Finding::new("privileged_mode", "Container is privileged", RiskLevel::High),
+                Finding::new("memory_limit", "Memory limit not set", RiskLevel::Informative),
+            ],
+            host: Some("prod".to_string()),
+        };
+
+        let log = export(ScanFindings::Containers(vec![container("web"), container("db")]));
+        let run = &log["runs"][0];
+        let rules = run["tool"]["driver"]["rules"].as_array().unwrap();
+        let results = run["results"].as_array().unwrap();
+
+        assert_eq!(rules.len(), 2);
+        assert_eq!(rules[0]["id"], "memory_limit");
+        assert_eq!(results.len(), 4);
+        assert_eq!(results[0]["ruleIndex"], 1);
+        assert_eq!(results[0]["level"], "error");
+        assert_eq!(results[1]["level"], "note");
+        let logical = &results[2]["locations"][0]["logicalLocations"][0];
+        assert_eq!(logical["name"], "db");
+        assert_eq!(logical["fullyQualifiedName"], "prod/db");
+        let physical = &results[2]["locations"][0]["physicalLocation"];
+        assert_eq!(physical["artifactLocation"]["uri"], "container://prod/db");
+        assert!(physical.get("region").is_none());
+        assert_eq!(results[2]["partialFingerprints"]["valerisFinding/v1"], "privileged_mode:prod/db");
+    }
+}
@@ -0,0 +1,271 @@
+//! Per-target security scores.
+//!
+//! Each container or Dockerfile gets a score from 100 down to 0: every
+//! finding subtracts the weight of its severity, or of its tags when
+//! `[output.tag_weights]` weighs one of them. The score maps to a letter
+//! grade (A–F) so services can be compared at a glance. Weights come from
+//! `[output.score_weights]` in the configuration file.
+//!
+//! The summed weights of a target are also its risk points, which
+//! `--fail-score <POINTS>` compares against: several medium findings can
+//! fail a scan just like one high.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::parse_risk_level;
+use crate::docker::model::{Finding, RiskLevel};
+use crate::error::ValerisError;
+
+/// Score of a target without findings
+pub const MAX_SCORE: u32 = 100;
+
+/// Points subtracted per finding of each severity.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScoreWeights {
+    pub critical: u32,
+    pub high: u32,
+    pub medium: u32,
+    pub low: u32,
+    pub informative: u32,
+    /// Weights by rule tag, replacing the severity weight of tagged findings
+    pub tags: HashMap<String, u32>,
+}
+
+impl Default for ScoreWeights {
+    fn default() -> Self {
+        Self {
+            critical: 40,
+            high: 20,
+            medium: 10,
+            low: 3,
+            informative: 0,
+            tags: HashMap::new(),
+        }
+    }
+}
+
+impl ScoreWeights {
+    /// Builds weights from the raw `[output.score_weights]` table; severities
+    /// not listed keep their default weight.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ValerisError::Config`] if a key is not a known severity.
+    pub fn from_config(raw: &HashMap<String, u32>) -> Result<Self, ValerisError> {
+        let mut weights = Self::default();
+        for (name, weight) in raw {
+            let Some(level) = parse_risk_level(name) else {
+                return Err(ValerisError::Config(format!(
+                    "Unknown severity '{}' in [output.score_weights]",
+                    name
+                )));
+            };
+            *weights.weight_mut(&level) = *weight;
+        }
+        Ok(weights)
+    }
+
+    /// Weighs findings carrying these tags (`[output.tag_weights]`).
+    pub fn with_tag_weights(mut self, tags: HashMap<String, u32>) -> Self {
+        self.tags = tags;
+        self
+    }
+
+    /// Weight of one finding of `level`.
+    pub fn weight(&self, level: &RiskLevel) -> u32 {
+        match level {
+            RiskLevel::Critical => self.critical,
+            RiskLevel::High => self.high,
+            RiskLevel::Medium => self.medium,
+            RiskLevel::Low => self.low,
+            RiskLevel::Informative => self.informative,
+        }
+    }
+
+    fn weight_mut(&mut self, level: &RiskLevel) -> &mut u32 {
+        match level {
+            RiskLevel::Critical => &mut self.critical,
+            RiskLevel::High => &mut self.high,
+            RiskLevel::Medium => &mut self.medium,
+            RiskLevel::Low => &mut self.low,
+            RiskLevel::Informative => &mut self.informative,
+        }
+    }
+
+    /// Weight of one finding: the highest weight among its weighted tags,
+    /// or its severity's weight if none of its tags is weighted.
+    pub fn finding_weight(&self, finding: &Finding) -> u32 {
+        finding
+            .tags
+            .iter()
+            .filter_map(|tag| self.tags.get(tag))
+            .max()
+            .copied()
+            .unwrap_or_else(|| self.weight(&finding.risk))
+    }
+
+    /// Risk points of a target: the summed weights of its findings.
+    pub fn points<'a, I>(&self, findings: I) -> u32
+    where
+        I: IntoIterator<Item = &'a Finding>,
+    {
+        findings
+            .into_iter()
+            .map(|f| self.finding_weight(f))
+            .fold(0, u32::saturating_add)
+    }
+
+    /// Scores a target's findings.
+    pub fn score<'a, I>(&self, findings: I) -> SecurityScore
+    where
+        I: IntoIterator<Item = &'a Finding>,
+    {
+        SecurityScore::new(MAX_SCORE.saturating_sub(self.points(findings)))
+    }
+
+    /// Returns whether a target's risk points exceed `threshold`
+    /// (`--fail-score`); always `false` without a threshold.
+    pub fn exceeds<'a, I>(&self, findings: I, threshold: Option<u32>) -> bool
+    where
+        I: IntoIterator<Item = &'a Finding>,
+    {
+        threshold.is_some_and(|threshold| self.points(findings) > threshold)
+    }
+}
+
+/// Letter grade of a score.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Grade {
+    A,
+    B,
+    C,
+    D,
+    F,
+}
+
+impl Grade {
+    /// A from 90, B from 80, C from 70, D from 60, F below.
+    pub fn from_score(score: u32) -> Self {
+        match score {
+            90.. => Grade::A,
+            80..=89 => Grade::B,
+            70..=79 => Grade::C,
+            60..=69 => Grade::D,
+            _ => Grade::F,
+        }
+    }
+}
+
+impl fmt::Display for Grade {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// Score and grade of one target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SecurityScore {
+    pub score: u32,
+    pub grade: Grade,
+}
+
+impl SecurityScore {
+    pub fn new(score: u32) -> Self {
+        Self {
+            score,
+            grade: Grade::from_score(score),
+        }
+    }
+}
+
+/// Score of a named target, as listed in the scan summary.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TargetScore {
+    /// Container name or Dockerfile path
+    pub target: String,
+    #[serde(flatten)]
+    pub score: SecurityScore,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn findings(levels: &[RiskLevel]) -> Vec<Finding> {
+        levels
+            .iter()
+            .map(|level| Finding::new("rule", "finding", level.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn subtracts_weights_and_grades() {
+        let weights = ScoreWeights::default();
+
+        assert_eq!(weights.score(&[]), SecurityScore { score: 100, grade: Grade::A });
+        let score = weights.score(&findings(&[RiskLevel::High, RiskLevel::Low, RiskLevel::Informative]));
+        assert_eq!(score, SecurityScore { score: 77, grade: Grade::C });
+        let score = weights.score(&vec![Finding::new("rule", "finding", RiskLevel::High); 6]);
+        assert_eq!(score, SecurityScore { score: 0, grade: Grade::F });
+    }
+
+    #[test]
+    fn configured_weights_override_defaults() {
+        let raw = HashMap::from([
+            ("critical".to_string(), 60),
+            ("high".to_string(), 40),
+            ("info".to_string(), 1),
+        ]);
+
+        let weights = ScoreWeights::from_config(&raw).unwrap();
+
+        assert_eq!(weights.critical, 60);
+        assert_eq!(weights.high, 40);
+        assert_eq!(weights.informative, 1);
+        assert_eq!(weights.medium, 10);
+        let err = ScoreWeights::from_config(&HashMap::from([("urgent".to_string(), 5)])).unwrap_err();
+        assert!(matches!(err, ValerisError::Config(_)));
+    }
+
+    #[test]
+    fn tag_weights_replace_severity_weights() {
+        let weights = ScoreWeights::default().with_tag_weights(HashMap::from([
+            ("secrets".to_string(), 50),
+            ("best-practice".to_string(), 1),
+        ]));
+        let mut secret = Finding::new("secret", "finding", RiskLevel::Medium);
+        secret.tags = vec!["best-practice".to_string(), "secrets".to_string()];
+        let mut hygiene = Finding::new("hygiene", "finding", RiskLevel::Medium);
+        hygiene.tags = vec!["best-practice".to_string()];
+
+        assert_eq!(weights.finding_weight(&secret), 50);
+        assert_eq!(weights.points(&[secret, hygiene]), 51);
+    }
+
+    #[test]
+    fn many_mediums_exceed_like_one_high() {
+        let weights = ScoreWeights::default();
+        let mediums = findings(&[RiskLevel::Medium, RiskLevel::Medium, RiskLevel::Medium]);
+
+        assert!(weights.exceeds(&mediums, Some(25)));
+        assert!(weights.exceeds(&findings(&[RiskLevel::High, RiskLevel::Low]), Some(20)));
+        assert!(!weights.exceeds(&findings(&[RiskLevel::High]), Some(20)));
+        assert!(!weights.exceeds(&mediums, None));
+    }
+
+    #[test]
+    fn target_score_serializes_flat() {
+        let entry = TargetScore {
+            target: "web".to_string(),
+            score: SecurityScore::new(85),
+        };
+
+        assert_eq!(
+            serde_json::to_value(&entry).unwrap(),
+            serde_json::json!({"target": "web", "score": 85, "grade": "B"})
+        );
+    }
+}
@@ -0,0 +1,94 @@
+//! Severity labels shown in tables and written to reports.
+//!
+//! `[output] severity_scheme` picks how each [`RiskLevel`] is named. The
+//! default `standard` scheme uses the same words as `--severity`,
+//! `--min-severity`, and `--fail-on` (`CRITICAL`, `HIGH`, `MEDIUM`, `LOW`,
+//! `INFO`); `legacy` keeps the `CRITICAL` label earlier versions used for
+//! high findings, so it cannot tell high from critical; `cli` writes the
+//! exact lowercase values the CLI accepts.
+//!
+//! The scheme travels with the output: exporters read
+//! [`ScanReport::severity_scheme`](crate::report::ScanReport::severity_scheme),
+//! and the printer functions take it as an argument. Formats with their own
+//! fixed vocabulary (OCSF, DefectDojo, syslog) are not affected.
+
+use serde::{Deserialize, Serialize};
+
+use crate::docker::model::RiskLevel;
+use crate::error::ValerisError;
+
+/// How severities are named in output.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SeverityScheme {
+    /// `CRITICAL`, `HIGH`, `MEDIUM`, `LOW`, `INFO`
+    #[default]
+    Standard,
+    /// `CRITICAL` (critical and high), `MEDIUM`, `LOW`, `INFO`
+    Legacy,
+    /// `critical`, `high`, `medium`, `low`, `informative`
+    Cli,
+}
+
+impl SeverityScheme {
+    /// Parses a scheme name from `[output] severity_scheme`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ValerisError::Config`] if the name is not a known scheme.
+    pub fn from_config(name: &str) -> Result<Self, ValerisError> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "standard" => Ok(Self::Standard),
+            "legacy" => Ok(Self::Legacy),
+            "cli" => Ok(Self::Cli),
+            _ => Err(ValerisError::Config(format!(
+                "Unknown severity scheme '{}' (expected standard, legacy, or cli)",
+                name
+            ))),
+        }
+    }
+
+    /// Label of `risk` in this scheme.
+    pub fn label(self, risk: &RiskLevel) -> &'static str {
+        match (self, risk) {
+            (Self::Cli, RiskLevel::Critical) => "critical",
+            (_, RiskLevel::Critical) => "CRITICAL",
+            (Self::Standard, RiskLevel::High) => "HIGH",
+            (Self::Legacy, RiskLevel::High) => "CRITICAL",
+            (Self::Cli, RiskLevel::High) => "high",
+            (Self::Cli, RiskLevel::Medium) => "medium",
+            (Self::Cli, RiskLevel::Low) => "low",
+            (Self::Cli, RiskLevel::Informative) => "informative",
+            (_, RiskLevel::Medium) => "MEDIUM",
+            (_, RiskLevel::Low) => "LOW",
+            (_, RiskLevel::Informative) => "INFO",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::parse_risk_level;
+
+    #[test]
+    fn every_label_parses_back_to_its_level() {
+        for scheme in [SeverityScheme::Standard, SeverityScheme::Legacy, SeverityScheme::Cli] {
+            for risk in [RiskLevel::Critical, RiskLevel::High, RiskLevel::Medium, RiskLevel::Low, RiskLevel::Informative] {
+                if scheme == SeverityScheme::Legacy && risk == RiskLevel::High {
+                    continue;
+                }
+                assert_eq!(parse_risk_level(scheme.label(&risk)), Some(risk));
+            }
+        }
+        assert_eq!(SeverityScheme::Standard.label(&RiskLevel::High), "HIGH");
+        assert_eq!(SeverityScheme::Standard.label(&RiskLevel::Critical), "CRITICAL");
+        assert_eq!(SeverityScheme::Legacy.label(&RiskLevel::High), "CRITICAL");
+    }
+
+    #[test]
+    fn rejects_unknown_schemes() {
+        assert_eq!(SeverityScheme::from_config(" Legacy ").unwrap(), SeverityScheme::Legacy);
+        assert!(matches!(SeverityScheme::from_config("p1"), Err(ValerisError::Config(_))));
+    }
+}
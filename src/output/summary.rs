@@ -0,0 +1,260 @@
+//! Scan-level statistics shared by the printer and exporters.
+//!
+//! A [`ScanSummary`] is built once per invocation after all targets have
+//! been scanned, printed as a footer in table mode and embedded as a
+//! metadata block in JSON exports.
+
+use crate::docker::model::{Finding, RiskLevel};
+use crate::output::metadata::{source_date_epoch, ScanMetadata};
+use crate::output::score::TargetScore;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Kind of target that was scanned.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TargetKind {
+    /// Runtime Docker containers
+    Containers,
+    /// Dockerfiles on disk
+    Dockerfiles,
+    /// `.env` files on disk
+    #[serde(rename = "env_files")]
+    EnvFiles,
+    /// Docker networks
+    Networks,
+    /// Containers together with Dockerfiles and `.env` files
+    Combined,
+}
+
+impl TargetKind {
+    /// Returns the human-readable plural label for this target kind.
+    pub fn label(&self) -> &'static str {
+        match self {
+            TargetKind::Containers => "containers",
+            TargetKind::Dockerfiles => "Dockerfiles",
+            TargetKind::EnvFiles => ".env files",
+            TargetKind::Networks => "networks",
+            TargetKind::Combined => "targets",
+        }
+    }
+}
+
+/// Number of findings per severity level.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SeverityCounts {
+    pub total: usize,
+    /// Absent from reports written before the critical level existed
+    #[serde(default)]
+    pub critical: usize,
+    pub high: usize,
+    pub medium: usize,
+    pub low: usize,
+    pub informative: usize,
+}
+
+impl SeverityCounts {
+    /// Counts the given findings grouped by risk level.
+    pub fn from_findings<'a, I>(findings: I) -> Self
+    where
+        I: IntoIterator<Item = &'a Finding>,
+    {
+        let mut counts = Self::default();
+        counts.add(findings);
+        counts
+    }
+
+    /// Adds the given findings to the running counts.
+    pub fn add<'a, I>(&mut self, findings: I)
+    where
+        I: IntoIterator<Item = &'a Finding>,
+    {
+        for finding in findings {
+            self.add_risk(&finding.risk);
+        }
+    }
+
+    /// Counts one finding of the given risk level.
+    pub fn add_risk(&mut self, risk: &RiskLevel) {
+        self.total += 1;
+        match risk {
+            RiskLevel::Critical => self.critical += 1,
+            RiskLevel::High => self.high += 1,
+            RiskLevel::Medium => self.medium += 1,
+            RiskLevel::Low => self.low += 1,
+            RiskLevel::Informative => self.informative += 1,
+        }
+    }
+}
+
+/// Statistics describing a completed scan.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanSummary {
+    /// What kind of targets were scanned
+    pub target_kind: TargetKind,
+    /// Number of containers or files scanned
+    pub targets_scanned: usize,
+    /// Number of rules evaluated against each target
+    pub rules_evaluated: usize,
+    /// Findings grouped by severity (after filtering)
+    pub findings: SeverityCounts,
+    /// Wall-clock duration of the scan in milliseconds; 0 when
+    /// `SOURCE_DATE_EPOCH` is set, so reports are reproducible
+    pub duration_ms: u64,
+    /// Whether the `--fail-on` threshold was met
+    pub fail_on_triggered: bool,
+    /// Security score of each scanned target
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub scores: Vec<TargetScore>,
+    /// Provenance of the scan; JSON reports write it as a top-level
+    /// `metadata` block rather than inside the summary
+    #[serde(skip)]
+    pub metadata: Option<ScanMetadata>,
+}
+
+impl ScanSummary {
+    /// Builds a summary from the scanned findings.
+    ///
+    /// # Arguments
+    ///
+    /// * `target_kind` - Kind of target that was scanned
+    /// * `targets_scanned` - Number of containers or files scanned
+    /// * `rules_evaluated` - Number of rules active after only/exclude filtering
+    /// * `findings` - All findings reported across targets
+    /// * `duration` - Elapsed scan time
+    /// * `fail_on_triggered` - Whether the fail-on threshold was met
+    pub fn new<'a, I>(
+        target_kind: TargetKind,
+        targets_scanned: usize,
+        rules_evaluated: usize,
+        findings: I,
+        duration: Duration,
+        fail_on_triggered: bool,
+    ) -> Self
+    where
+        I: IntoIterator<Item = &'a Finding>,
+    {
+        Self::from_counts(
+            target_kind,
+            targets_scanned,
+            rules_evaluated,
+            SeverityCounts::from_findings(findings),
+            duration,
+            fail_on_triggered,
+        )
+    }
+
+    /// Builds a summary from severity counts accumulated while streaming results.
+    pub fn from_counts(
+        target_kind: TargetKind,
+        targets_scanned: usize,
+        rules_evaluated: usize,
+        findings: SeverityCounts,
+        duration: Duration,
+        fail_on_triggered: bool,
+    ) -> Self {
+        Self {
+            target_kind,
+            targets_scanned,
+            rules_evaluated,
+            findings,
+            duration_ms: match source_date_epoch() {
+                Some(_) => 0,
+                None => duration.as_millis() as u64,
+            },
+            fail_on_triggered,
+            scores: Vec::new(),
+            metadata: None,
+        }
+    }
+
+    /// Attaches the security score of each scanned target.
+    pub fn with_scores(mut self, scores: Vec<TargetScore>) -> Self {
+        self.scores = scores;
+        self
+    }
+
+    /// Attaches the provenance of the scan.
+    pub fn with_metadata(mut self, metadata: ScanMetadata) -> Self {
+        self.metadata = Some(metadata);
+        self
+    }
+
+    /// Formats the summary as a single `key=value` line for CI logs.
+    ///
+    /// Example: `valeris: scanned=12 findings=34 critical=1 high=2 medium=10 low=20 info=1 failed=true`
+    pub fn status_line(&self) -> String {
+        format!(
+            "valeris: scanned={} findings={} critical={} high={} medium={} low={} info={} failed={}",
+            self.targets_scanned,
+            self.findings.total,
+            self.findings.critical,
+            self.findings.high,
+            self.findings.medium,
+            self.findings.low,
+            self.findings.informative,
+            self.fail_on_triggered
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn finding(risk: RiskLevel) -> Finding {
+        Finding::new("test", "test", risk)
+    }
+
+    #[test]
+    fn severity_counts_group_by_risk() {
+        let findings = vec![
+            finding(RiskLevel::High),
+            finding(RiskLevel::High),
+            finding(RiskLevel::Low),
+            finding(RiskLevel::Informative),
+        ];
+
+        let counts = SeverityCounts::from_findings(&findings);
+
+        assert_eq!(counts.total, 4);
+        assert_eq!(counts.high, 2);
+        assert_eq!(counts.medium, 0);
+        assert_eq!(counts.low, 1);
+        assert_eq!(counts.informative, 1);
+    }
+
+    #[test]
+    fn summary_records_duration_in_millis() {
+        let summary = ScanSummary::new(
+            TargetKind::Containers,
+            3,
+            10,
+            &[],
+            Duration::from_millis(1500),
+            false,
+        );
+
+        assert_eq!(summary.duration_ms, 1500);
+        assert_eq!(summary.findings.total, 0);
+        assert_eq!(summary.targets_scanned, 3);
+    }
+
+    #[test]
+    fn status_line_is_machine_parsable() {
+        let findings = vec![finding(RiskLevel::Critical), finding(RiskLevel::High), finding(RiskLevel::Medium)];
+        let summary = ScanSummary::new(
+            TargetKind::Containers,
+            12,
+            30,
+            &findings,
+            Duration::from_millis(10),
+            true,
+        );
+
+        assert_eq!(
+            summary.status_line(),
+            "valeris: scanned=12 findings=3 critical=1 high=1 medium=1 low=0 info=0 failed=true"
+        );
+    }
+}
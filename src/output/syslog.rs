@@ -0,0 +1,362 @@
+//! Syslog output sink.
+//!
+//! Sends one [RFC 5424] message per finding to a syslog endpoint, for
+//! environments where all security tooling must report through syslog.
+//! Finding details travel as structured data, so collectors can index them
+//! without parsing the free-text message:
+//!
+//! ```text
+//! <34>1 2024-05-01T12:00:00.000Z host valeris 4242 finding [valeris@32473 rule="privileged_mode" severity="high" target="web"] Container is privileged
+//! ```
+//!
+//! Endpoints are given as `udp://host[:514]`, `tcp://host[:601]` (octet
+//! counting framing, RFC 6587) or `unix:///dev/log`.
+//!
+//! [RFC 5424]: https://www.rfc-editor.org/rfc/rfc5424
+
+use std::io::Write;
+use std::net::{TcpStream, UdpSocket};
+use std::path::Path;
+use std::time::SystemTime;
+
+use anyhow::Context;
+
+use crate::docker::model::{ContainerResult, Finding, RiskLevel};
+use crate::error::{Result, ValerisError};
+
+/// Structured data ID; 32473 is the enterprise number reserved for examples
+/// and private use (RFC 5612)
+pub const SD_ID: &str = "valeris@32473";
+
+/// Facility `auth` (security/authorization messages)
+const FACILITY: u8 = 4;
+
+const DEFAULT_UDP_PORT: u16 = 514;
+const DEFAULT_TCP_PORT: u16 = 601;
+
+/// A connected syslog endpoint.
+pub struct SyslogSink {
+    transport: Transport,
+    hostname: String,
+    pid: u32,
+}
+
+enum Transport {
+    Udp(UdpSocket),
+    Tcp(TcpStream),
+    #[cfg(unix)]
+    Unix(std::os::unix::net::UnixDatagram),
+}
+
+impl SyslogSink {
+    /// Connects to a syslog endpoint.
+    ///
+    /// # Arguments
+    ///
+    /// * `url` - `udp://host[:port]`, `tcp://host[:port]` or `unix:///path/to/socket`
+    ///
+    /// # Errors
+    ///
+    /// * [`ValerisError::InvalidFilter`] if the URL is malformed or uses an
+    ///   unsupported scheme
+    /// * [`ValerisError::Export`] if the endpoint cannot be reached
+    pub fn connect(url: &str) -> Result<Self> {
+        let (scheme, address) = url
+            .split_once("://")
+            .ok_or_else(|| invalid_url(url))?;
+
+        let transport = match scheme {
+            "udp" => {
+                let address = with_default_port(address, DEFAULT_UDP_PORT);
+                let socket = UdpSocket::bind("0.0.0.0:0")
+                    .and_then(|socket| socket.connect(&address).map(|_| socket))
+                    .with_context(|| format!("Failed to connect to syslog at {}", url))
+                    .map_err(ValerisError::Export)?;
+                Transport::Udp(socket)
+            }
+            "tcp" => {
+                let address = with_default_port(address, DEFAULT_TCP_PORT);
+                let stream = TcpStream::connect(&address)
+                    .with_context(|| format!("Failed to connect to syslog at {}", url))
+                    .map_err(ValerisError::Export)?;
+                Transport::Tcp(stream)
+            }
+            #[cfg(unix)]
+            "unix" => {
+                let socket = std::os::unix::net::UnixDatagram::unbound()
+                    .and_then(|socket| socket.connect(address).map(|_| socket))
+                    .with_context(|| format!("Failed to connect to syslog at {}", url))
+                    .map_err(ValerisError::Export)?;
+                Transport::Unix(socket)
+            }
+            _ => return Err(invalid_url(url)),
+        };
+
+        Ok(Self {
+            transport,
+            hostname: hostname(),
+            pid: std::process::id(),
+        })
+    }
+
+    /// Sends one message per finding of a scanned container.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ValerisError::Export`] if a message cannot be sent
+    pub fn send_container(&mut self, result: &ContainerResult) -> Result<()> {
+        let name = result
+            .container
+            .name
+            .as_deref()
+            .unwrap_or_default()
+            .trim_start_matches('/');
+        let id = result.container.id.as_deref().unwrap_or_default();
+        let target = [("target", name), ("container_id", id)];
+
+        for finding in &result.findings {
+            let message = self.format(finding, &target);
+            self.send(&message)?;
+        }
+        Ok(())
+    }
+
+    /// Sends one message per finding of a scanned Dockerfile.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ValerisError::Export`] if a message cannot be sent
+    pub fn send_dockerfile(&mut self, path: &Path, findings: &[Finding]) -> Result<()> {
+        let path = path.display().to_string();
+        let target = [("target", path.as_str())];
+
+        for finding in findings {
+            let message = self.format(finding, &target);
+            self.send(&message)?;
+        }
+        Ok(())
+    }
+
+    /// Renders an RFC 5424 message for `finding`.
+    fn format(&self, finding: &Finding, target: &[(&str, &str)]) -> String {
+        let line = finding.line.map(|l| l.to_string());
+        let mut params = vec![
+            ("rule", finding.kind.as_str()),
+            ("severity", severity_name(&finding.risk)),
+        ];
+        if let Some(rule_name) = &finding.rule_name {
+            params.push(("rule_name", rule_name));
+        }
+        if let Some(category) = &finding.category {
+            params.push(("category", category));
+        }
+        params.extend(finding.tags.iter().map(|tag| ("tag", tag.as_str())));
+        params.extend_from_slice(target);
+        if let Some(line) = &line {
+            params.push(("line", line));
+        }
+        if let Some(remediation) = finding.remediation.as_deref().filter(|r| !r.is_empty()) {
+            params.push(("remediation", remediation));
+        }
+        // RFC 5424 allows a parameter to repeat, one per reference
+        params.extend(finding.references.iter().map(|r| ("reference", r.as_str())));
+
+        let data: String = params
+            .iter()
+            .map(|(name, value)| format!(" {}=\"{}\"", name, escape_param(value)))
+            .collect();
+
+        format!(
+            "<{}>1 {} {} valeris {} finding [{}{}] {}",
+            FACILITY * 8 + severity_code(&finding.risk),
+            humantime::format_rfc3339_millis(SystemTime::now()),
+            self.hostname,
+            self.pid,
+            SD_ID,
+            data,
+            finding.description
+        )
+    }
+
+    fn send(&mut self, message: &str) -> Result<()> {
+        let sent = match &mut self.transport {
+            Transport::Udp(socket) => socket.send(message.as_bytes()).map(|_| ()),
+            Transport::Tcp(stream) => write!(stream, "{} {}", message.len(), message),
+            #[cfg(unix)]
+            Transport::Unix(socket) => socket.send(message.as_bytes()).map(|_| ()),
+        };
+        sent.context("Failed to send syslog message")
+            .map_err(ValerisError::Export)
+    }
+}
+
+fn invalid_url(url: &str) -> ValerisError {
+    ValerisError::InvalidFilter(format!(
+        "Invalid syslog endpoint '{}' (expected udp://host[:port], tcp://host[:port] or unix:///path)",
+        url
+    ))
+}
+
+fn with_default_port(address: &str, port: u16) -> String {
+    // A bracketed IPv6 address or `host:port`
+    let has_port = match address.rsplit_once(']') {
+        Some((_, rest)) => rest.starts_with(':'),
+        None => address.contains(':'),
+    };
+    if has_port {
+        address.to_string()
+    } else {
+        format!("{}:{}", address, port)
+    }
+}
+
+/// Syslog severity: critical is `critical`, high `error`, medium `warning`,
+/// low `notice`, informative `informational`.
+fn severity_code(risk: &RiskLevel) -> u8 {
+    match risk {
+        RiskLevel::Critical => 2,
+        RiskLevel::High => 3,
+        RiskLevel::Medium => 4,
+        RiskLevel::Low => 5,
+        RiskLevel::Informative => 6,
+    }
+}
+
+fn severity_name(risk: &RiskLevel) -> &'static str {
+    match risk {
+        RiskLevel::Critical => "critical",
+        RiskLevel::High => "high",
+        RiskLevel::Medium => "medium",
+        RiskLevel::Low => "low",
+        RiskLevel::Informative => "informative",
+    }
+}
+
+/// Escapes `"`, `\` and `]` in a structured data parameter value.
+fn escape_param(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '"' | '\\' | ']') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Host name for the HOSTNAME header field, or the nil value `-`.
+fn hostname() -> String {
+    local_hostname()
+        .filter(|name| !name.contains(' '))
+        .unwrap_or_else(|| "-".to_string())
+}
+
+/// Name of the local host, if it can be determined.
+pub(crate) fn local_hostname() -> Option<String> {
+    std::env::var("HOSTNAME")
+        .ok()
+        .or_else(|| std::fs::read_to_string("/proc/sys/kernel/hostname").ok())
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bollard::models::ContainerInspectResponse;
+
+    fn udp_pair() -> (UdpSocket, SyslogSink) {
+        let server = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let url = format!("udp://{}", server.local_addr().unwrap());
+        (server, SyslogSink::connect(&url).unwrap())
+    }
+
+    fn receive(server: &UdpSocket) -> String {
+        let mut buf = [0u8; 2048];
+        let len = server.recv(&mut buf).unwrap();
+        String::from_utf8_lossy(&buf[..len]).into_owned()
+    }
+
+    #[test]
+    fn sends_one_message_per_container_finding() {
+        let (server, mut sink) = udp_pair();
+        let result = ContainerResult {
+            container: ContainerInspectResponse {
+                id: Some("abc123".to_string()),
+                name: Some("/web".to_string()),
+                ..Default::default()
+            },
+            findings: vec![
+                Finding::new("privileged_mode", "Container is privileged", RiskLevel::High),
+                Finding::new("pids_limit", "No PIDs limit", RiskLevel::Low),
+            ],
+            host: None,
+        };
+
+        sink.send_container(&result).unwrap();
+
+        let first = receive(&server);
+        assert!(first.starts_with("<35>1 "), "{first}");
+        assert!(first.contains(
+            "[valeris@32473 rule=\"privileged_mode\" severity=\"high\" target=\"web\" container_id=\"abc123\"]"
+        ));
+        assert!(first.ends_with("] Container is privileged"));
+        assert!(receive(&server).starts_with("<37>1 "));
+    }
+
+    #[test]
+    fn dockerfile_messages_carry_path_and_line() {
+        let (server, mut sink) = udp_pair();
+        let finding = Finding {
+            line: Some(3),
+            ..Finding::new("DF001", "Base image uses latest", RiskLevel::Informative)
+        };
+
+        sink.send_dockerfile(Path::new("app/Dockerfile"), &[finding]).unwrap();
+
+        let message = receive(&server);
+        assert!(message.starts_with("<38>1 "));
+        assert!(message.contains("target=\"app/Dockerfile\" line=\"3\"]"));
+    }
+
+    #[test]
+    fn messages_carry_remediation_and_references() {
+        let (server, mut sink) = udp_pair();
+        let finding = Finding {
+            remediation: Some("Pin the base image".to_string()),
+            references: vec!["https://example.com/a".to_string(), "https://example.com/b".to_string()],
+            category: Some("Supply Chain".to_string()),
+            tags: vec!["pinning".to_string()],
+            ..Finding::new("DF001", "Base image uses latest", RiskLevel::Medium)
+        };
+
+        sink.send_dockerfile(Path::new("Dockerfile"), &[finding]).unwrap();
+
+        let message = receive(&server);
+        assert!(message.contains(" severity=\"medium\" category=\"Supply Chain\" tag=\"pinning\" target="));
+        assert!(message.contains(
+            " remediation=\"Pin the base image\" reference=\"https://example.com/a\" reference=\"https://example.com/b\"]"
+        ));
+    }
+
+    #[test]
+    fn escapes_structured_data_values() {
+        assert_eq!(escape_param(r#"a"b\c]d"#), r#"a\"b\\c\]d"#);
+    }
+
+    #[test]
+    fn rejects_unknown_schemes() {
+        assert!(matches!(
+            SyslogSink::connect("http://localhost"),
+            Err(ValerisError::InvalidFilter(_))
+        ));
+        assert!(matches!(SyslogSink::connect("localhost"), Err(ValerisError::InvalidFilter(_))));
+    }
+
+    #[test]
+    fn adds_default_port() {
+        assert_eq!(with_default_port("logs.example.com", 514), "logs.example.com:514");
+        assert_eq!(with_default_port("10.0.0.1:1514", 514), "10.0.0.1:1514");
+        assert_eq!(with_default_port("[::1]", 601), "[::1]:601");
+    }
+}
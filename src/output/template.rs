@@ -0,0 +1,203 @@
+//! Report templates.
+//!
+//! Renders a report with a user-supplied [Handlebars](https://handlebarsjs.com/)
+//! template, for branded or tool-specific layouts that none of the built-in
+//! formats cover. Templates see the scan metadata and summary, every scanned
+//! target with its findings, and a flat list of all findings in the NDJSON
+//! shape:
+//!
+//! ```handlebars
+//! # Scan of {{metadata.host}} ({{summary.findings.total}} findings)
+//! {{#each targets}}
+//! ## {{target_type}} {{target}}
+//! {{#each findings}}
+//! - [{{severity}}] {{id}}: {{description}}
+//! {{/each}}
+//! {{/each}}
+//! ```
+//!
+//! Values are HTML-escaped when the template file name ends in `.html` or
+//! `.htm` (optionally followed by `.hbs`), and written as they are otherwise.
+
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use handlebars::Handlebars;
+use serde::Serialize;
+
+use crate::error::ValerisError;
+use crate::output::exporters::{scanned_targets, Exporter, FlatFinding};
+use crate::output::metadata::ScanMetadata;
+use crate::output::summary::ScanSummary;
+use crate::report::ScanReport;
+
+/// Name the template is registered under
+const TEMPLATE_NAME: &str = "report";
+
+/// A report rendered with a Handlebars template.
+pub struct TemplateExporter {
+    handlebars: Handlebars<'static>,
+}
+
+/// Data a template is rendered with.
+#[derive(Serialize)]
+struct TemplateContext<'a> {
+    valeris_version: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    metadata: Option<&'a ScanMetadata>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    summary: Option<&'a ScanSummary>,
+    /// Whether the fail-on or fail-score gate was met
+    failed: bool,
+    targets: Vec<TemplateTarget<'a>>,
+    /// Findings of every target, in target order
+    findings: Vec<FlatFinding<'a>>,
+}
+
+#[derive(Serialize)]
+struct TemplateTarget<'a> {
+    target_type: &'static str,
+    target: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    target_id: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    host: Option<&'a str>,
+    findings: Vec<FlatFinding<'a>>,
+}
+
+impl TemplateExporter {
+    /// Compiles a template.
+    ///
+    /// # Arguments
+    ///
+    /// * `template` - Handlebars template source
+    /// * `escape_html` - Whether values are HTML-escaped
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ValerisError::InvalidFilter`] if the template does not parse
+    pub fn new(template: &str, escape_html: bool) -> crate::error::Result<Self> {
+        let mut handlebars = Handlebars::new();
+        if !escape_html {
+            handlebars.register_escape_fn(handlebars::no_escape);
+        }
+        handlebars
+            .register_template_string(TEMPLATE_NAME, template)
+            .map_err(|e| ValerisError::InvalidFilter(format!("Invalid report template: {e}")))?;
+        Ok(Self { handlebars })
+    }
+
+    /// Loads the template in `path`, escaping HTML if it is an `.html` or
+    /// `.htm` template.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ValerisError::InvalidFilter`] if the file cannot be read or
+    /// the template does not parse
+    pub fn from_file(path: &Path) -> crate::error::Result<Self> {
+        let template = fs::read_to_string(path).map_err(|e| {
+            ValerisError::InvalidFilter(format!("Failed to read report template {}: {e}", path.display()))
+        })?;
+        let name = path.file_name().unwrap_or_default().to_string_lossy().to_ascii_lowercase();
+        let name = name.strip_suffix(".hbs").unwrap_or(&name);
+        Self::new(&template, name.ends_with(".html") || name.ends_with(".htm")).map_err(|e| {
+            ValerisError::InvalidFilter(format!("{} ({})", e, path.display()))
+        })
+    }
+}
+
+impl Exporter for TemplateExporter {
+    fn export(&self, report: &ScanReport, w: &mut dyn Write) -> Result<()> {
+        let targets = scanned_targets(&report.findings);
+        let context = TemplateContext {
+            valeris_version: env!("CARGO_PKG_VERSION"),
+            metadata: report.summary.as_ref().and_then(|s| s.metadata.as_ref()),
+            summary: report.summary.as_ref(),
+            failed: report.failed(),
+            targets: targets
+                .iter()
+                .map(|target| TemplateTarget {
+                    target_type: target.target_type,
+                    target: &target.target,
+                    target_id: target.target_id,
+                    host: target.host,
+                    findings: target.flat_findings(report.severity_scheme).collect(),
+                })
+                .collect(),
+            findings: targets.iter().flat_map(|target| target.flat_findings(report.severity_scheme)).collect(),
+        };
+        let rendered = self
+            .handlebars
+            .render(TEMPLATE_NAME, &context)
+            .context("Failed to render report template")?;
+        w.write_all(rendered.as_bytes()).context("Failed to write templated report")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::docker::model::{Finding, RiskLevel};
+    use crate::output::summary::TargetKind;
+    use crate::report::ScanFindings;
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    fn render(exporter: &TemplateExporter) -> String {
+        let findings = vec![
+            Finding {
+                line: Some(1),
+                ..Finding::new("DF001", "Base image uses <latest>", RiskLevel::Medium)
+            },
+            Finding::new("DF002", "Runs as root", RiskLevel::High),
+        ];
+        let summary = ScanSummary::new(TargetKind::Dockerfiles, 1, 2, &findings, Duration::ZERO, true);
+        let report = ScanReport::from_scan(
+            summary,
+            ScanFindings::Dockerfile {
+                path: PathBuf::from("Dockerfile"),
+                findings,
+            },
+        );
+        let mut out = Vec::new();
+        exporter.export(&report, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn renders_targets_findings_and_summary() {
+        let exporter = TemplateExporter::new(
+            "{{summary.findings.total}} findings, failed={{failed}}\n\
+             {{#each targets}}{{target_type}} {{target}}:{{#each findings}} {{id}}@{{line}}{{/each}}{{/each}}\n\
+             {{#each findings}}[{{severity}}] {{description}}\n{{/each}}",
+            false,
+        )
+        .unwrap();
+
+        let out = render(&exporter);
+
+        assert!(out.starts_with("2 findings, failed=true\ndockerfile Dockerfile: DF002@ DF001@1\n"));
+        assert!(out.contains("] Base image uses <latest>\n"));
+    }
+
+    #[test]
+    fn html_templates_escape_values() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("report.html.hbs");
+        fs::write(&path, "{{#each findings}}<li>{{description}}</li>{{/each}}").unwrap();
+
+        let out = render(&TemplateExporter::from_file(&path).unwrap());
+
+        assert!(out.ends_with("<li>Base image uses &lt;latest&gt;</li>"));
+    }
+
+    #[test]
+    fn rejects_malformed_templates() {
+        assert!(matches!(
+            TemplateExporter::new("{{#each targets}}", false),
+            Err(ValerisError::InvalidFilter(_))
+        ));
+    }
+}
@@ -0,0 +1,204 @@
+//! Docker CLI plugin mode.
+//!
+//! Installed as `~/.docker/cli-plugins/docker-valeris`, Valeris runs as
+//! `docker valeris scan ...`. The Docker CLI first probes the plugin with
+//! `docker-cli-plugin-metadata`, then invokes it with its own arguments:
+//! Docker's global flags (`--context`, `--host`, ...), the plugin name, and
+//! the plugin's arguments. This module answers the probe, strips Docker's
+//! arguments, and resolves the Docker endpoint the CLI would use.
+
+use std::ffi::{OsStr, OsString};
+use std::path::Path;
+use std::process::Command;
+
+use serde_json::json;
+
+/// Executable name Docker looks for in its `cli-plugins` directory
+pub const PLUGIN_BINARY: &str = "docker-valeris";
+
+/// Subcommand name under `docker`
+pub const PLUGIN_NAME: &str = "valeris";
+
+/// Argument the Docker CLI passes to read the plugin metadata
+pub const METADATA_COMMAND: &str = "docker-cli-plugin-metadata";
+
+/// Environment variable the Docker CLI sets to its own executable path
+const ORIGINAL_CLI_ENV: &str = "DOCKER_CLI_PLUGIN_ORIGINAL_CLI_COMMAND";
+
+/// Docker global flags that take a value
+const VALUE_FLAGS: &[&str] = &[
+    "--config",
+    "--context",
+    "-c",
+    "--host",
+    "-H",
+    "--log-level",
+    "-l",
+    "--tlscacert",
+    "--tlscert",
+    "--tlskey",
+];
+
+/// A command line received as a Docker CLI plugin.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PluginInvocation {
+    /// Value of Docker's `--context` flag
+    pub context: Option<String>,
+    /// Value of Docker's `--host` flag
+    pub host: Option<String>,
+    /// Arguments for Valeris, starting with the binary name
+    pub args: Vec<OsString>,
+}
+
+/// Whether the process was started through the Docker CLI plugin executable.
+pub fn is_plugin(args: &[OsString]) -> bool {
+    args.first()
+        .and_then(|argv0| Path::new(argv0).file_stem())
+        .is_some_and(|stem| stem == PLUGIN_BINARY)
+}
+
+/// Plugin metadata returned for `docker-cli-plugin-metadata`.
+pub fn metadata() -> String {
+    json!({
+        "SchemaVersion": "0.1.0",
+        "Vendor": "rsgbengi",
+        "Version": env!("CARGO_PKG_VERSION"),
+        "ShortDescription": "Scan containers and Dockerfiles for security misconfigurations",
+        "URL": "https://github.com/rsgbengi/valeris",
+    })
+    .to_string()
+}
+
+/// Splits a plugin command line into Docker's flags and Valeris' arguments.
+///
+/// Everything before the `valeris` argument is treated as Docker global
+/// flags; `--context` and `--host` are kept, the rest are dropped. The
+/// returned arguments start with `docker valeris` as the binary name, so
+/// help and usage text match how the plugin was invoked.
+pub fn parse_args(args: Vec<OsString>) -> PluginInvocation {
+    let mut invocation = PluginInvocation::default();
+    let mut rest = args.into_iter().skip(1);
+
+    while let Some(arg) = rest.next() {
+        if arg == PLUGIN_NAME {
+            break;
+        }
+        let arg = arg.to_string_lossy().into_owned();
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) => (flag.to_string(), Some(value.to_string())),
+            None => (arg, None),
+        };
+        if !VALUE_FLAGS.contains(&flag.as_str()) {
+            continue;
+        }
+        let value = inline.or_else(|| rest.next().map(|v| v.to_string_lossy().into_owned()));
+        match flag.as_str() {
+            "--context" | "-c" => invocation.context = value,
+            "--host" | "-H" => invocation.host = value,
+            _ => {}
+        }
+    }
+
+    invocation.args = std::iter::once(OsString::from(format!("docker {}", PLUGIN_NAME)))
+        .chain(rest)
+        .collect();
+    invocation
+}
+
+impl PluginInvocation {
+    /// Resolves the Docker endpoint the Docker CLI would use.
+    ///
+    /// In order: `--host`, `DOCKER_HOST`, then the endpoint of `--context`,
+    /// `DOCKER_CONTEXT`, or the current context as reported by
+    /// `docker context inspect`. Returns `None` to fall back to the local
+    /// socket, e.g. when the context cannot be inspected.
+    pub fn docker_host(&self) -> Option<String> {
+        if let Some(host) = &self.host {
+            return Some(host.clone());
+        }
+        if let Ok(host) = std::env::var("DOCKER_HOST") {
+            return Some(host);
+        }
+
+        let context = self.context.clone().or_else(|| std::env::var("DOCKER_CONTEXT").ok());
+        let docker = std::env::var_os(ORIGINAL_CLI_ENV).unwrap_or_else(|| OsString::from("docker"));
+        context_host(&docker, context.as_deref())
+    }
+}
+
+/// Reads a context's Docker endpoint with `docker context inspect`.
+fn context_host(docker: &OsStr, context: Option<&str>) -> Option<String> {
+    let mut command = Command::new(docker);
+    command.args(["context", "inspect", "--format", "{{.Endpoints.docker.Host}}"]);
+    command.args(context);
+
+    let output = match command.output() {
+        Ok(output) if output.status.success() => output,
+        Ok(output) => {
+            tracing::debug!(
+                "docker context inspect failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+            return None;
+        }
+        Err(e) => {
+            tracing::debug!("Failed to run docker context inspect: {e}");
+            return None;
+        }
+    };
+
+    let host = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!host.is_empty()).then_some(host)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(values: &[&str]) -> Vec<OsString> {
+        values.iter().map(OsString::from).collect()
+    }
+
+    #[test]
+    fn detects_plugin_binary_name() {
+        assert!(is_plugin(&args(&["/home/u/.docker/cli-plugins/docker-valeris", "valeris"])));
+        assert!(!is_plugin(&args(&["/usr/bin/valeris", "scan"])));
+    }
+
+    #[test]
+    fn metadata_is_docker_plugin_schema() {
+        let metadata: serde_json::Value = serde_json::from_str(&metadata()).unwrap();
+        assert_eq!(metadata["SchemaVersion"], "0.1.0");
+        assert_eq!(metadata["Version"], env!("CARGO_PKG_VERSION"));
+    }
+
+    #[test]
+    fn parse_args_strips_docker_flags_and_plugin_name() {
+        let invocation = parse_args(args(&[
+            "docker-valeris",
+            "--context=remote",
+            "--debug",
+            "-H",
+            "tcp://10.0.0.1:2375",
+            "--log-level",
+            "info",
+            "valeris",
+            "scan",
+            "-c",
+            "web",
+        ]));
+
+        assert_eq!(invocation.context.as_deref(), Some("remote"));
+        assert_eq!(invocation.host.as_deref(), Some("tcp://10.0.0.1:2375"));
+        assert_eq!(invocation.args, args(&["docker valeris", "scan", "-c", "web"]));
+    }
+
+    #[test]
+    fn docker_host_prefers_host_flag() {
+        let invocation = PluginInvocation {
+            host: Some("unix:///tmp/docker.sock".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(invocation.docker_host().as_deref(), Some("unix:///tmp/docker.sock"));
+    }
+}
@@ -0,0 +1,377 @@
+//! Policy bundles.
+//!
+//! A rule bundle can ship a `policies.yaml` next to its rules describing
+//! named postures (`baseline`, `strict`, `cis-docker`, `nist-800-190`, ...).
+//! `--policy <NAME>` resolves one of them into the rule subset to run and the
+//! severity profile to apply, instead of hand-maintained `--only` lists:
+//!
+//! ```yaml
+//! version: 1
+//! policies:
+//!   baseline:
+//!     description: "High-impact isolation and secret checks"
+//!     rules: [privileged_mode, secrets_in_env]
+//!     min_severity: medium
+//!   nist-800-190:
+//!     compliance: nist-800-190
+//!     severity_map:
+//!       informative: low
+//! ```
+//!
+//! `rules` and `compliance` select rules (their union); a policy with
+//! neither runs every rule. Runtime policies live in the runtime rules
+//! directory (next to `docker/`), Dockerfile policies in the Dockerfile
+//! rules directory. A rules directory without a `policies.yaml` uses the
+//! policies bundled with the binary (see [`crate::bundled`]).
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::bundled;
+use crate::cli::SeverityLevel;
+use crate::config::{parse_risk_level, SeverityMap};
+use crate::detectors::dockerfile::yaml_rules::{load_rules_from_dir, renames};
+use crate::detectors::runtime::yaml_rules::YamlRuleEngine;
+use crate::docker::model::RiskLevel;
+use crate::error::{Result, ValerisError};
+
+/// File name of the policy definitions inside a rules directory
+pub const POLICY_FILE: &str = "policies.yaml";
+
+/// Policy definitions of one rules directory.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PolicyFile {
+    pub version: u32,
+    #[serde(default)]
+    pub policies: BTreeMap<String, Policy>,
+}
+
+/// A named rule subset and severity profile.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Policy {
+    pub description: Option<String>,
+    /// Rule IDs to run
+    #[serde(default)]
+    pub rules: Vec<String>,
+    /// Also runs every rule mapped to this compliance framework
+    pub compliance: Option<String>,
+    /// Minimum severity to keep, unless `--severity`/`--min-severity` is given
+    pub min_severity: Option<String>,
+    /// Severity remapping, applied over `[output.severity_map]`
+    #[serde(default)]
+    pub severity_map: HashMap<String, String>,
+}
+
+/// A policy resolved against the loaded rules.
+#[derive(Debug, Clone)]
+pub struct ResolvedPolicy {
+    pub name: String,
+    /// IDs of the loaded rules the policy selects
+    pub rules: Vec<String>,
+    pub min_severity: Option<SeverityLevel>,
+    pub severity_map: SeverityMap,
+}
+
+impl ResolvedPolicy {
+    /// Rule IDs to run, without those in `exclude` (case-insensitive).
+    pub fn only(&self, exclude: Option<&Vec<String>>) -> Vec<String> {
+        let excluded: HashSet<String> = exclude
+            .into_iter()
+            .flatten()
+            .map(|id| id.to_lowercase())
+            .collect();
+        self.rules
+            .iter()
+            .filter(|id| !excluded.contains(&id.to_lowercase()))
+            .cloned()
+            .collect()
+    }
+}
+
+/// Loads the policy definitions of a rules directory.
+///
+/// # Errors
+///
+/// * [`ValerisError::Config`] if the directory has no `policies.yaml`
+/// * [`ValerisError::RulesLoad`] if the file cannot be read
+/// * [`ValerisError::RuleParse`] if the file is not valid YAML
+pub fn load_policies(rules_dir: &Path) -> Result<PolicyFile> {
+    let path = rules_dir.join(POLICY_FILE);
+    if !path.exists() {
+        return Err(ValerisError::Config(format!(
+            "No policies defined in {} (expected {})",
+            rules_dir.display(),
+            path.display()
+        )));
+    }
+    let raw = fs::read_to_string(&path).map_err(|source| ValerisError::RulesLoad {
+        path: path.clone(),
+        source: source.into(),
+    })?;
+    serde_yml::from_str(&raw).map_err(|e| ValerisError::rule_parse(&path, e))
+}
+
+/// Loads the policy definitions of a rules directory, or parses `bundled`
+/// if the directory has none.
+fn load_policies_or_bundled(rules_dir: &Path, bundled: &str) -> Result<PolicyFile> {
+    if rules_dir.join(POLICY_FILE).exists() {
+        return load_policies(rules_dir);
+    }
+    tracing::debug!(
+        "No {} in {}, using the bundled policies",
+        POLICY_FILE,
+        rules_dir.display()
+    );
+    serde_yml::from_str(bundled).map_err(|e| ValerisError::rule_parse(Path::new(POLICY_FILE), e))
+}
+
+impl PolicyFile {
+    /// Looks up a policy by name.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ValerisError::Config`] listing the defined policies if
+    /// `name` is not one of them.
+    pub fn get(&self, name: &str) -> Result<&Policy> {
+        self.policies.get(name).ok_or_else(|| {
+            ValerisError::Config(format!(
+                "Unknown policy '{}' (available: {})",
+                name,
+                self.policies.keys().cloned().collect::<Vec<_>>().join(", ")
+            ))
+        })
+    }
+}
+
+impl Policy {
+    /// Returns a copy of the policy with deprecated rule IDs in `rules`
+    /// replaced by their successors, per `renames` (old ID to new ID).
+    pub fn with_renames(&self, renames: &HashMap<String, String>) -> Policy {
+        let mut policy = self.clone();
+        for id in &mut policy.rules {
+            if let Some(new) = renames.get(id.as_str()) {
+                *id = new.clone();
+            }
+        }
+        policy
+    }
+
+    /// Resolves the policy against the loaded rules, given as ID and
+    /// compliance mapping pairs.
+    ///
+    /// Listed rules missing from the bundle are skipped with a warning, so
+    /// deleting a rule file does not break the policies that name it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ValerisError::Config`] if a severity is unknown.
+    pub fn resolve<'a>(
+        &self,
+        name: &str,
+        rules: impl IntoIterator<Item = (&'a str, &'a BTreeMap<String, Vec<String>>)>,
+    ) -> Result<ResolvedPolicy> {
+        let listed: HashSet<String> = self.rules.iter().map(|id| id.to_lowercase()).collect();
+        let select_all = self.rules.is_empty() && self.compliance.is_none();
+
+        let mut found = HashSet::new();
+        let mut selected = Vec::new();
+        for (id, compliance) in rules {
+            let is_listed = listed.contains(&id.to_lowercase());
+            if is_listed {
+                found.insert(id.to_lowercase());
+            }
+            let is_mapped = self
+                .compliance
+                .as_ref()
+                .is_some_and(|framework| compliance.get(framework).is_some_and(|c| !c.is_empty()));
+            if select_all || is_listed || is_mapped {
+                selected.push(id.to_string());
+            }
+        }
+        for id in listed.difference(&found) {
+            tracing::warn!("Policy '{}' lists rule '{}', which is not loaded", name, id);
+        }
+
+        let min_severity = self
+            .min_severity
+            .as_deref()
+            .map(|level| {
+                parse_risk_level(level).map(severity_level).ok_or_else(|| {
+                    ValerisError::Config(format!(
+                        "Unknown min_severity '{}' in policy '{}'",
+                        level, name
+                    ))
+                })
+            })
+            .transpose()?;
+
+        Ok(ResolvedPolicy {
+            name: name.to_string(),
+            rules: selected,
+            min_severity,
+            severity_map: SeverityMap::from_table(
+                &self.severity_map,
+                &format!("severity_map of policy '{}'", name),
+            )?,
+        })
+    }
+}
+
+fn severity_level(risk: RiskLevel) -> SeverityLevel {
+    match risk {
+        RiskLevel::Informative => SeverityLevel::Informative,
+        RiskLevel::Low => SeverityLevel::Low,
+        RiskLevel::Medium => SeverityLevel::Medium,
+        RiskLevel::High => SeverityLevel::High,
+        RiskLevel::Critical => SeverityLevel::Critical,
+    }
+}
+
+/// Resolves policy `name` from the runtime rules in `rules_dir`, using
+/// [`bundled::RUNTIME_POLICIES`] if the directory has no `policies.yaml`.
+///
+/// # Errors
+///
+/// Fails if the policy file or rules cannot be loaded, or `name` is not
+/// defined (see [`load_policies`] and [`PolicyFile::get`]).
+pub fn runtime_policy(rules_dir: &Path, name: &str) -> Result<ResolvedPolicy> {
+    let policies = load_policies_or_bundled(rules_dir, bundled::RUNTIME_POLICIES)?;
+    let policy = policies.get(name)?;
+    let engine = YamlRuleEngine::from_dir(rules_dir)?;
+    policy.with_renames(&engine.renames()).resolve(
+        name,
+        engine
+            .rules()
+            .iter()
+            .filter(|rule| !rule.deprecated)
+            .map(|rule| (rule.id.as_str(), &rule.compliance)),
+    )
+}
+
+/// Resolves policy `name` from the Dockerfile rules in `rules_dir`, using
+/// [`bundled::DOCKERFILE_POLICIES`] if the directory has no `policies.yaml`.
+///
+/// # Errors
+///
+/// Fails if the policy file or rules cannot be loaded, or `name` is not
+/// defined (see [`load_policies`] and [`PolicyFile::get`]).
+pub fn dockerfile_policy(rules_dir: &Path, name: &str) -> Result<ResolvedPolicy> {
+    let policies = load_policies_or_bundled(rules_dir, bundled::DOCKERFILE_POLICIES)?;
+    let policy = policies.get(name)?;
+    let ruleset = load_rules_from_dir(rules_dir)?;
+    policy.with_renames(&renames(&ruleset.rules)).resolve(
+        name,
+        ruleset
+            .rules
+            .iter()
+            .filter(|rule| !rule.deprecated())
+            .map(|rule| (rule.id(), rule.compliance())),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mapped(controls: &[&str]) -> BTreeMap<String, Vec<String>> {
+        let mut compliance = BTreeMap::new();
+        if !controls.is_empty() {
+            compliance.insert(
+                "nist-800-190".to_string(),
+                controls.iter().map(|c| c.to_string()).collect(),
+            );
+        }
+        compliance
+    }
+
+    fn policy(yaml: &str) -> Policy {
+        serde_yml::from_str(yaml).unwrap()
+    }
+
+    #[test]
+    fn selects_listed_and_mapped_rules() {
+        let (privileged, secrets, healthcheck) = (mapped(&["4.4.3"]), mapped(&[]), mapped(&[]));
+        let rules = [
+            ("privileged_mode", &privileged),
+            ("secrets_in_env", &secrets),
+            ("healthcheck", &healthcheck),
+        ];
+
+        let resolved = policy("rules: [SECRETS_IN_ENV, removed_rule]\ncompliance: nist-800-190\nmin_severity: medium")
+            .resolve("baseline", rules)
+            .unwrap();
+
+        assert_eq!(resolved.rules, ["privileged_mode", "secrets_in_env"]);
+        assert_eq!(resolved.min_severity, Some(SeverityLevel::Medium));
+        assert_eq!(resolved.only(Some(&vec!["Privileged_Mode".to_string()])), ["secrets_in_env"]);
+    }
+
+    #[test]
+    fn empty_selection_runs_every_rule() {
+        let none = mapped(&[]);
+
+        let resolved = policy("severity_map:\n  low: medium")
+            .resolve("strict", [("healthcheck", &none), ("root_user", &none)])
+            .unwrap();
+
+        assert_eq!(resolved.rules, ["healthcheck", "root_user"]);
+        assert!(!resolved.severity_map.is_empty());
+    }
+
+    #[test]
+    fn rejects_unknown_severities() {
+        let err = policy("min_severity: urgent").resolve("strict", []).unwrap_err();
+
+        assert!(matches!(err, ValerisError::Config(msg) if msg.contains("urgent")));
+    }
+
+    #[test]
+    fn bundled_policies_resolve() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("rules");
+        for name in ["baseline", "strict", "cis-docker", "nist-800-190"] {
+            let runtime = runtime_policy(&root.join("runtime"), name).unwrap();
+            let dockerfile = dockerfile_policy(&root.join("dockerfile"), name).unwrap();
+            assert!(!runtime.rules.is_empty() && !dockerfile.rules.is_empty(), "{name}");
+        }
+
+        // Every rule a bundled policy lists exists in the bundle
+        for (dir, resolve) in [
+            ("runtime", runtime_policy as fn(&Path, &str) -> Result<ResolvedPolicy>),
+            ("dockerfile", dockerfile_policy),
+        ] {
+            for (name, policy) in load_policies(&root.join(dir)).unwrap().policies {
+                if policy.compliance.is_none() && !policy.rules.is_empty() {
+                    let resolved = resolve(&root.join(dir), &name).unwrap();
+                    assert_eq!(resolved.rules.len(), policy.rules.len(), "{dir} {name}");
+                }
+            }
+        }
+
+        let err = load_policies(&root.join("runtime")).unwrap().get("lenient").unwrap_err();
+        assert!(matches!(err, ValerisError::Config(msg) if msg.contains("baseline")));
+    }
+
+    #[test]
+    fn rules_dir_without_policies_uses_the_bundled_ones() {
+        // A pack with rules but no policies.yaml, like the v0.1.0 release
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir(dir.path().join("docker")).unwrap();
+        fs::write(
+            dir.path().join("docker/privileged_mode.yaml"),
+            "id: privileged_mode\nmatch:\n  jsonpath: $.HostConfig.Privileged\n  equals: true\nmessage: privileged\n",
+        )
+        .unwrap();
+
+        assert!(load_policies(dir.path()).is_err());
+        let resolved = runtime_policy(dir.path(), "baseline").unwrap();
+        assert_eq!(resolved.rules, ["privileged_mode"]);
+        assert_eq!(resolved.min_severity, Some(SeverityLevel::Medium));
+        // The rule has no compliance block; the bundled rule's mapping applies
+        let resolved = runtime_policy(dir.path(), "nist-800-190").unwrap();
+        assert_eq!(resolved.rules, ["privileged_mode"]);
+        assert!(dockerfile_policy(dir.path(), "baseline").is_ok());
+    }
+}
@@ -0,0 +1,155 @@
+//! Structured outcome of a Valeris invocation.
+//!
+//! [`crate::run`] returns a [`ScanReport`] instead of exiting the process,
+//! so Valeris can be embedded as a library; the binary maps
+//! [`ScanReport::exit_code`] to the process exit status.
+
+use std::path::PathBuf;
+
+use crate::docker::model::{sort_findings, ContainerResult, FileResult, Finding, NetworkResult};
+use crate::output::severity::SeverityScheme;
+use crate::output::summary::ScanSummary;
+
+/// Exit code when the scan completed and no fail-on threshold was met.
+pub const EXIT_SUCCESS: i32 = 0;
+
+/// Exit code when findings met the `--fail-on` threshold.
+pub const EXIT_FINDINGS: i32 = 1;
+
+/// Exit code for user errors: invalid arguments, rules, configuration, or Dockerfile.
+///
+/// Matches the code clap uses for invalid command-line usage.
+pub const EXIT_USAGE: i32 = 2;
+
+/// Exit code for environment errors: Docker unreachable, unreadable files, failed exports.
+pub const EXIT_ENVIRONMENT: i32 = 3;
+
+/// Findings produced by a scan command.
+#[derive(Debug, Default)]
+pub enum ScanFindings {
+    /// The command did not scan anything (e.g. `list-plugins`, `config`)
+    #[default]
+    None,
+    /// Runtime container scan results.
+    ///
+    /// Empty when results were streamed to an `--output` file, since they
+    /// are not kept in memory in that case.
+    Containers(Vec<ContainerResult>),
+    /// Dockerfile scan results
+    Dockerfile {
+        path: PathBuf,
+        findings: Vec<Finding>,
+    },
+    /// `.env` file scan results
+    EnvFile {
+        path: PathBuf,
+        findings: Vec<Finding>,
+    },
+    /// Docker network scan results
+    Networks(Vec<NetworkResult>),
+    /// Runtime scan combined with the Dockerfiles and `.env` files given
+    /// with `scan --dockerfile` or `--compose`
+    Combined {
+        containers: Vec<ContainerResult>,
+        dockerfiles: Vec<FileResult>,
+        env_files: Vec<FileResult>,
+    },
+}
+
+impl ScanFindings {
+    /// Sorts the findings of every target into [`Finding::report_order`].
+    pub fn sort(&mut self) {
+        match self {
+            Self::None => {}
+            Self::Containers(results) => results.iter_mut().for_each(|r| sort_findings(&mut r.findings)),
+            Self::Dockerfile { findings, .. } | Self::EnvFile { findings, .. } => sort_findings(findings),
+            Self::Networks(results) => results.iter_mut().for_each(|r| sort_findings(&mut r.findings)),
+            Self::Combined { containers, dockerfiles, env_files } => {
+                containers.iter_mut().for_each(|r| sort_findings(&mut r.findings));
+                dockerfiles
+                    .iter_mut()
+                    .chain(env_files.iter_mut())
+                    .for_each(|f| sort_findings(&mut f.findings));
+            }
+        }
+    }
+}
+
+/// Outcome of a Valeris command.
+#[derive(Debug, Default)]
+pub struct ScanReport {
+    /// Scan statistics (scan commands only)
+    pub summary: Option<ScanSummary>,
+    /// Findings (scan commands only)
+    pub findings: ScanFindings,
+    /// Process exit code the CLI should use
+    pub exit_code: i32,
+    /// How exporters name severities
+    pub severity_scheme: SeverityScheme,
+}
+
+impl ScanReport {
+    /// Builds the report for a completed scan, deriving the exit code from
+    /// whether the fail-on threshold was met. Each target's findings are
+    /// put in [`Finding::report_order`].
+    pub fn from_scan(summary: ScanSummary, mut findings: ScanFindings) -> Self {
+        findings.sort();
+        let exit_code = if summary.fail_on_triggered {
+            EXIT_FINDINGS
+        } else {
+            EXIT_SUCCESS
+        };
+        Self {
+            summary: Some(summary),
+            findings,
+            exit_code,
+            severity_scheme: SeverityScheme::default(),
+        }
+    }
+
+    /// Names severities in exported reports with `scheme`.
+    pub fn with_severity_scheme(mut self, scheme: SeverityScheme) -> Self {
+        self.severity_scheme = scheme;
+        self
+    }
+
+    /// Returns `true` if the fail-on threshold was met.
+    pub fn failed(&self) -> bool {
+        self.exit_code != EXIT_SUCCESS
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::docker::model::RiskLevel;
+
+    #[test]
+    fn findings_are_sorted_by_severity_rule_and_line() {
+        let finding = |id: &str, line: Option<usize>, risk: RiskLevel| Finding {
+            line,
+            ..Finding::new(id, "finding", risk)
+        };
+        let mut findings = ScanFindings::Dockerfile {
+            path: PathBuf::from("Dockerfile"),
+            findings: vec![
+                finding("DF002", Some(9), RiskLevel::Low),
+                finding("DF003", Some(4), RiskLevel::High),
+                finding("DF001", Some(7), RiskLevel::High),
+                finding("DF001", Some(2), RiskLevel::High),
+                finding("DF004", None, RiskLevel::Critical),
+            ],
+        };
+
+        findings.sort();
+
+        let ScanFindings::Dockerfile { findings, .. } = findings else {
+            unreachable!();
+        };
+        let order: Vec<(&str, Option<usize>)> = findings.iter().map(|f| (f.kind.as_str(), f.line)).collect();
+        assert_eq!(
+            order,
+            [("DF004", None), ("DF001", Some(2)), ("DF001", Some(7)), ("DF003", Some(4)), ("DF002", Some(9))]
+        );
+    }
+}
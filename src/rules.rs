@@ -1,23 +1,25 @@
 use std::fs;
 use std::path::{Path, PathBuf};
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use flate2::read::GzDecoder;
 use reqwest::blocking::get;
 use tar::Archive;
 use walkdir::WalkDir;
 
-use crate::config::{RulesConfig, DEFAULT_RULES_RELEASE_URL};
+use crate::config::{RulesConfig, DEFAULT_RULES_RELEASE_URL, RULES_DIR_ENV};
+use crate::output::metadata::RULES_VERSION_FILE;
 
-/// Returns the rules directory, using configuration defaults
-pub fn rules_dir() -> Result<PathBuf> {
-    let config = RulesConfig::default();
-    Ok(config.base_dir)
-}
-
-pub fn ensure_rules() -> Result<PathBuf> {
-    let dir = rules_dir()?;
-    let version_file = dir.join(".valeris_version");
+/// Returns the rules directory of `config`, downloading the default rule pack
+/// if it has no rules and `config.auto_download` is set.
+///
+/// # Errors
+///
+/// Returns an error if the directory has no rules and downloads are disabled,
+/// or if the download fails
+pub fn ensure_rules(config: &RulesConfig) -> Result<PathBuf> {
+    let dir = config.base_dir.clone();
+    let version_file = dir.join(RULES_VERSION_FILE);
 
     let have_rules = version_file.exists()
         || WalkDir::new(&dir)
@@ -29,6 +31,13 @@ pub fn ensure_rules() -> Result<PathBuf> {
         tracing::debug!("Rules already present in {}", dir.display());
         return Ok(dir);
     }
+    if !config.auto_download {
+        bail!(
+            "No rules found in {} and automatic download is disabled; install a rule pack there or set {}",
+            dir.display(),
+            RULES_DIR_ENV
+        );
+    }
 
     fs::create_dir_all(&dir)
         .with_context(|| format!("Failed to create rules directory {}", dir.display()))?;
@@ -64,7 +73,7 @@ mod tests {
     #[serial]
     fn rules_dir_uses_env_var() {
         std::env::set_var("VALERIS_RULES_DIR", "/tmp/valeris_test");
-        let dir = rules_dir().unwrap();
+        let dir = RulesConfig::default().base_dir;
         assert_eq!(dir, PathBuf::from("/tmp/valeris_test"));
         std::env::remove_var("VALERIS_RULES_DIR");
     }
@@ -78,8 +87,21 @@ mod tests {
         fs::create_dir_all(dir.join("docker")).unwrap();
         fs::write(dir.join(".valeris_version"), "installed").unwrap();
 
-        let res = ensure_rules().unwrap();
+        let res = ensure_rules(&RulesConfig::default()).unwrap();
         assert_eq!(res, dir);
         std::env::remove_var("VALERIS_RULES_DIR");
     }
+
+    #[test]
+    fn ensure_rules_fails_without_download_when_missing() {
+        let td = tempdir().unwrap();
+        let config = RulesConfig {
+            base_dir: td.path().join("detectors"),
+            auto_download: false,
+        };
+
+        let err = ensure_rules(&config).unwrap_err();
+        assert!(err.to_string().contains("automatic download is disabled"));
+        assert!(!config.base_dir.exists());
+    }
 }
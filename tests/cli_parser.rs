@@ -1,7 +1,11 @@
+#![cfg(feature = "cli")]
+
 #[cfg(test)]
 mod tests {
+    use std::path::PathBuf;
+
     use clap::Parser;
-    use valeris::cli::{Cli, Commands, OutputFormat, ScanTarget};
+    use valeris::cli::{Cli, Commands, ConfigCommand, ErrorFormat, OutputFormat, OutputTarget, ReportCommand, ScanTarget, SeverityLevel};
 
     const VALID_PLUGINS: &[&str] = &[
         "capabilities",
@@ -51,8 +55,8 @@ mod tests {
                 assert!(only_vec.contains(&"ports".to_string()));
                 assert!(only_vec.contains(&"secrets".to_string()));
                 assert!(exclude.is_none());
-                assert_eq!(format, OutputFormat::Json);
-                assert_eq!(output.unwrap(), "report.json");
+                assert_eq!(format, Some(OutputFormat::Json));
+                assert_eq!(output[0].path, "report.json");
             }
             _ => panic!("Expected Scan command"),
         }
@@ -73,8 +77,8 @@ mod tests {
                 assert_eq!(target, ScanTarget::Docker);
                 assert!(only.is_none());
                 assert!(exclude.is_none());
-                assert_eq!(format, OutputFormat::Json);
-                assert!(output.is_none());
+                assert_eq!(format, None); // table on a terminal, JSON when piped
+                assert!(output.is_empty());
             }
             _ => panic!("Expected Scan command"),
         }
@@ -102,6 +106,13 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parses_offline_before_or_after_the_command() {
+        assert!(!Cli::parse_from(["valeris", "list-plugins"]).offline);
+        assert!(Cli::parse_from(["valeris", "--offline", "list-plugins"]).offline);
+        assert!(Cli::parse_from(["valeris", "scan", "--offline"]).offline);
+    }
+
     #[test]
     fn fails_with_invalid_target() {
         let result = Cli::try_parse_from(["valeris", "scan", "--target", "invalidvalue"]);
@@ -138,12 +149,15 @@ mod tests {
         }
     }
     #[test]
-    fn fails_if_format_without_output() {
-        let result = Cli::try_parse_from(["valeris", "scan", "--format", "json"]);
-        assert!(
-            result.is_err(),
-            "Expected failure when using --format without --output"
-        );
+    fn parses_format_without_output() {
+        let cli = Cli::parse_from(["valeris", "scan", "--format", "json"]);
+        match cli.command {
+            Commands::Scan { output, format, .. } => {
+                assert!(output.is_empty());
+                assert_eq!(format, Some(OutputFormat::Json));
+            }
+            _ => panic!("Expected Scan command"),
+        }
     }
 
     #[test]
@@ -151,13 +165,74 @@ mod tests {
         let cli = Cli::parse_from(["valeris", "scan", "--output", "out.json"]);
         match cli.command {
             Commands::Scan { output, format, .. } => {
-                assert_eq!(output.unwrap(), "out.json");
-                assert_eq!(format, OutputFormat::Json); // default
+                assert_eq!(output[0].path, "out.json");
+                assert_eq!(format, None);
+                assert_eq!(output[0].format_or(&OutputFormat::Json), &OutputFormat::Json); // default
             }
             _ => panic!("Expected Scan command"),
         }
     }
 
+    #[test]
+    fn parses_format_ndjson() {
+        let cli = Cli::parse_from(["valeris", "scan", "--format", "ndjson", "--output", "findings.ndjson"]);
+        match cli.command {
+            Commands::Scan { format, .. } => assert_eq!(format, Some(OutputFormat::Ndjson)),
+            _ => panic!("Expected Scan command"),
+        }
+    }
+
+    #[test]
+    fn parses_repeated_outputs_with_formats() {
+        let cli = Cli::parse_from([
+            "valeris",
+            "scan",
+            "--output",
+            "report.json:json",
+            "--output",
+            "report.sarif:SARIF",
+            "-o",
+            "s3://reports/scan.csv",
+        ]);
+        match cli.command {
+            Commands::Scan { output, format, .. } => {
+                assert_eq!(output.len(), 3);
+                assert_eq!(output[1].path, "report.sarif");
+                assert_eq!(output[1].format, Some(OutputFormat::Sarif));
+                assert_eq!(output[2].path, "s3://reports/scan.csv");
+                assert_eq!(format, None);
+                assert_eq!(output[2].format, None);
+            }
+            _ => panic!("Expected Scan command"),
+        }
+    }
+
+    #[test]
+    fn parses_template_and_rejects_it_with_format() {
+        let cli = Cli::parse_from(["valeris", "scan", "--template", "audit.md.hbs", "--output", "audit.md"]);
+        match cli.command {
+            Commands::Scan { template, .. } => assert_eq!(template, Some(PathBuf::from("audit.md.hbs"))),
+            _ => panic!("Expected Scan command"),
+        }
+
+        let args = ["valeris", "scan", "--template", "t.hbs", "--format", "csv", "--output", "r.csv"];
+        assert!(Cli::try_parse_from(args).is_err());
+        assert!(Cli::try_parse_from(["valeris", "scan", "--template", "t.hbs"]).is_err());
+    }
+
+    #[test]
+    fn output_suffix_is_only_a_format_if_it_names_one() {
+        let target: OutputTarget = "reports/scan:2024.json".parse().unwrap();
+        assert_eq!(target.path, "reports/scan:2024.json");
+        assert_eq!(target.format, None);
+
+        let target: OutputTarget = "s3://reports/scan.html:html".parse().unwrap();
+        assert_eq!(target.path, "s3://reports/scan.html");
+        assert_eq!(target.format, Some(OutputFormat::Html));
+
+        assert!(":json".parse::<OutputTarget>().is_err());
+    }
+
     #[test]
     fn parses_format_csv_with_output() {
         let cli = Cli::parse_from([
@@ -170,13 +245,70 @@ mod tests {
         ]);
         match cli.command {
             Commands::Scan { output, format, .. } => {
-                assert_eq!(output.unwrap(), "report.csv");
-                assert_eq!(format, OutputFormat::Csv);
+                assert_eq!(output[0].path, "report.csv");
+                assert_eq!(format, Some(OutputFormat::Csv));
             }
             _ => panic!("Expected Scan command"),
         }
     }
 
+    #[test]
+    fn parses_format_defectdojo() {
+        let cli = Cli::parse_from([
+            "valeris",
+            "scan",
+            "--format",
+            "defectdojo",
+            "--output",
+            "defectdojo.json",
+        ]);
+        match cli.command {
+            Commands::Scan { format, .. } => assert_eq!(format, Some(OutputFormat::Defectdojo)),
+            _ => panic!("Expected Scan command"),
+        }
+    }
+
+    #[test]
+    fn parses_format_junit() {
+        let cli = Cli::parse_from(["valeris", "scan", "--format", "junit", "--output", "valeris.xml"]);
+        match cli.command {
+            Commands::Scan { format, .. } => assert_eq!(format, Some(OutputFormat::Junit)),
+            _ => panic!("Expected Scan command"),
+        }
+    }
+
+    #[test]
+    fn parses_format_gitlab() {
+        let cli = Cli::parse_from(["valeris", "docker-file", "--path", "Dockerfile", "--rules", "rules", "--format", "gitlab"]);
+        match cli.command {
+            Commands::DockerFile { format, .. } => assert_eq!(format, OutputFormat::Gitlab),
+            _ => panic!("Expected DockerFile command"),
+        }
+    }
+
+    #[test]
+    fn parses_format_html() {
+        let cli = Cli::parse_from(["valeris", "scan", "--format", "html", "--output", "report.html"]);
+        match cli.command {
+            Commands::Scan { format, .. } => assert_eq!(format, Some(OutputFormat::Html)),
+            _ => panic!("Expected Scan command"),
+        }
+    }
+
+    #[test]
+    fn parses_format_sarif() {
+        let cli = Cli::parse_from(["valeris", "scan", "--format", "sarif", "--output", "valeris.sarif"]);
+        match cli.command {
+            Commands::Scan { format, .. } => assert_eq!(format, Some(OutputFormat::Sarif)),
+            _ => panic!("Expected Scan command"),
+        }
+        let cli = Cli::parse_from(["valeris", "docker-file", "-p", "Dockerfile", "-r", "rules", "--format", "sarif"]);
+        match cli.command {
+            Commands::DockerFile { format, .. } => assert_eq!(format, OutputFormat::Sarif),
+            _ => panic!("Expected DockerFile command"),
+        }
+    }
+
     #[test]
     fn parses_state_option() {
         let cli = Cli::parse_from(["valeris", "scan", "--state", "running,exited"]);
@@ -219,8 +351,8 @@ mod tests {
                 assert!(only_vec.contains(&"capabilities".to_string()));
                 assert!(only_vec.contains(&"network".to_string()));
                 assert!(exclude.is_none());
-                assert_eq!(output.unwrap(), "output.csv");
-                assert_eq!(format, OutputFormat::Csv);
+                assert_eq!(output[0].path, "output.csv");
+                assert_eq!(format, Some(OutputFormat::Csv));
             }
             _ => panic!("Expected Scan command"),
         }
@@ -259,6 +391,28 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parses_since_filter() {
+        let cli = Cli::parse_from(["valeris", "scan", "--since", "2h"]);
+        match cli.command {
+            Commands::Scan { since, .. } => {
+                assert_eq!(since.as_deref(), Some("2h"));
+            }
+            _ => panic!("Expected Scan command"),
+        }
+    }
+
+    #[test]
+    fn parses_input_file() {
+        let cli = Cli::parse_from(["valeris", "scan", "-i", "inspect.json"]);
+        match cli.command {
+            Commands::Scan { input, .. } => {
+                assert_eq!(input, Some(PathBuf::from("inspect.json")));
+            }
+            _ => panic!("Expected Scan command"),
+        }
+    }
+
     #[test]
     fn parses_combined_filters() {
         let cli = Cli::parse_from([
@@ -332,6 +486,18 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parses_critical_severity() {
+        let cli = Cli::parse_from(["valeris", "scan", "--fail-on", "critical", "--severity", "critical,high"]);
+        match cli.command {
+            Commands::Scan { fail_on, severity, .. } => {
+                assert_eq!(fail_on, Some(SeverityLevel::Critical));
+                assert_eq!(severity, Some(vec![SeverityLevel::Critical, SeverityLevel::High]));
+            }
+            _ => panic!("Expected Scan command"),
+        }
+    }
+
     #[test]
     fn parses_quiet_with_fail_on() {
         let cli = Cli::parse_from(["valeris", "scan", "--quiet", "--fail-on", "medium"]);
@@ -344,12 +510,168 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parses_quiet_with_fail_score() {
+        let cli = Cli::parse_from(["valeris", "docker-file", "-p", "Dockerfile", "-r", "rules", "--quiet", "--fail-score", "50"]);
+        match cli.command {
+            Commands::DockerFile { quiet, fail_score, fail_on, .. } => {
+                assert!(quiet);
+                assert_eq!(fail_score, Some(50));
+                assert!(fail_on.is_none());
+            }
+            _ => panic!("Expected DockerFile command"),
+        }
+    }
+
     #[test]
     fn fails_quiet_without_fail_on() {
         let result = Cli::try_parse_from(["valeris", "scan", "--quiet"]);
         assert!(result.is_err());
     }
 
+    #[test]
+    fn fails_attest_without_key() {
+        let result = Cli::try_parse_from(["valeris", "scan", "--attest", "scan.intoto.json"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn fails_policy_with_only() {
+        let result = Cli::try_parse_from([
+            "valeris",
+            "scan",
+            "--policy",
+            "baseline",
+            "--only",
+            "privileged_mode",
+        ]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn parses_hosts_file() {
+        let cli = Cli::try_parse_from(["valeris", "scan", "--hosts", "hosts.toml"]).unwrap();
+        match cli.command {
+            Commands::Scan { hosts, .. } => assert_eq!(hosts, Some(PathBuf::from("hosts.toml"))),
+            _ => panic!("Expected Scan command"),
+        }
+    }
+
+    #[test]
+    fn parses_combined_scan_targets() {
+        let cli = Cli::try_parse_from([
+            "valeris", "scan", "--dockerfile", "Dockerfile", "--dockerfile", "api/Dockerfile",
+            "--compose", "docker-compose.yml",
+        ])
+        .unwrap();
+        match cli.command {
+            Commands::Scan { dockerfile, compose, dockerfile_rules, .. } => {
+                assert_eq!(dockerfile, vec![PathBuf::from("Dockerfile"), PathBuf::from("api/Dockerfile")]);
+                assert_eq!(compose, vec![PathBuf::from("docker-compose.yml")]);
+                assert_eq!(dockerfile_rules, None);
+            }
+            _ => panic!("Expected Scan command"),
+        }
+    }
+
+    #[test]
+    fn parses_lang() {
+        let cli = Cli::try_parse_from(["valeris", "scan", "--lang", "es"]).unwrap();
+        match cli.command {
+            Commands::Scan { lang, .. } => assert_eq!(lang.as_deref(), Some("es")),
+            _ => panic!("Expected Scan command"),
+        }
+    }
+
+    #[test]
+    fn parses_anonymize() {
+        let cli = Cli::parse_from(["valeris", "docker-file", "-p", "Dockerfile", "-r", "rules", "--anonymize"]);
+        match cli.command {
+            Commands::DockerFile { anonymize, .. } => assert!(anonymize),
+            _ => panic!("Expected DockerFile command"),
+        }
+    }
+
+    #[test]
+    fn parses_print_schema() {
+        let cli = Cli::parse_from(["valeris", "scan", "--print-schema"]);
+        match cli.command {
+            Commands::Scan { print_schema, .. } => assert!(print_schema),
+            _ => panic!("Expected Scan command"),
+        }
+    }
+
+    #[test]
+    fn fails_quadlet_with_input() {
+        let result = Cli::try_parse_from([
+            "valeris",
+            "scan",
+            "--quadlet",
+            "/etc/containers/systemd",
+            "--input",
+            "inspect.json",
+        ]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn parses_probe_and_rejects_it_offline() {
+        let cli = Cli::try_parse_from(["valeris", "scan", "--probe"]).unwrap();
+        match cli.command {
+            Commands::Scan { probe, .. } => assert!(probe),
+            _ => panic!("Expected Scan command"),
+        }
+
+        let result = Cli::try_parse_from(["valeris", "scan", "--probe", "--input", "inspect.json"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn parses_usage_and_rejects_it_offline() {
+        let cli = Cli::try_parse_from(["valeris", "scan", "--usage"]).unwrap();
+        match cli.command {
+            Commands::Scan { usage, .. } => assert!(usage),
+            _ => panic!("Expected Scan command"),
+        }
+
+        let result = Cli::try_parse_from(["valeris", "scan", "--usage", "--hosts", "hosts.toml"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn parses_profile_rules() {
+        let cli = Cli::parse_from(["valeris", "scan", "--profile-rules"]);
+        match cli.command {
+            Commands::Scan { profile_rules, .. } => assert!(profile_rules),
+            _ => panic!("Expected Scan command"),
+        }
+    }
+
+    #[test]
+    fn parses_drift_and_rejects_it_offline() {
+        let cli = Cli::try_parse_from(["valeris", "scan", "--drift"]).unwrap();
+        match cli.command {
+            Commands::Scan { drift, .. } => assert!(drift),
+            _ => panic!("Expected Scan command"),
+        }
+
+        let result = Cli::try_parse_from(["valeris", "scan", "--drift", "--input", "inspect.json"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn fails_hosts_with_input() {
+        let result = Cli::try_parse_from([
+            "valeris",
+            "scan",
+            "--hosts",
+            "hosts.toml",
+            "--input",
+            "inspect.json",
+        ]);
+        assert!(result.is_err());
+    }
+
     #[test]
     fn fails_severity_and_min_severity_together() {
         let result = Cli::try_parse_from([
@@ -362,4 +684,156 @@ mod tests {
         ]);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn parses_report_github_pr() {
+        let cli = Cli::parse_from([
+            "valeris",
+            "report",
+            "github-pr",
+            "--repo",
+            "org/name",
+            "--pr",
+            "123",
+            "--report",
+            "a.json,b.json",
+        ]);
+        match cli.command {
+            Commands::Report {
+                command: ReportCommand::GithubPr { repo, pr, report, api_url },
+            } => {
+                assert_eq!(repo, "org/name");
+                assert_eq!(pr, 123);
+                assert_eq!(report, [PathBuf::from("a.json"), PathBuf::from("b.json")]);
+                assert_eq!(api_url, "https://api.github.com");
+            }
+            _ => panic!("Expected Report command"),
+        }
+    }
+
+    #[test]
+    fn parses_report_compliance_with_default_framework() {
+        let cli = Cli::parse_from(["valeris", "report", "compliance", "-r", "scan.json"]);
+        match cli.command {
+            Commands::Report {
+                command: ReportCommand::Compliance { framework, report },
+            } => {
+                assert_eq!(framework, "nist-800-190");
+                assert_eq!(report, [PathBuf::from("scan.json")]);
+            }
+            _ => panic!("Expected Report command"),
+        }
+    }
+
+    #[test]
+    fn parses_history_filters() {
+        let cli = Cli::parse_from(["valeris", "history", "-c", "nginx,api", "--since", "30d"]);
+        match cli.command {
+            Commands::History {
+                store,
+                container,
+                since,
+            } => {
+                assert!(store.is_none());
+                assert_eq!(container.unwrap(), ["nginx", "api"]);
+                assert_eq!(since.as_deref(), Some("30d"));
+            }
+            _ => panic!("Expected History command"),
+        }
+    }
+
+    #[test]
+    fn parses_merge_reports_and_output() {
+        let cli = Cli::parse_from(["valeris", "merge", "a.json", "b.json", "-o", "combined.json"]);
+        match cli.command {
+            Commands::Merge { reports, output } => {
+                assert_eq!(reports, [PathBuf::from("a.json"), PathBuf::from("b.json")]);
+                assert_eq!(output.as_deref(), Some("combined.json"));
+            }
+            _ => panic!("Expected Merge command"),
+        }
+    }
+
+    #[test]
+    fn fails_merge_without_reports() {
+        assert!(Cli::try_parse_from(["valeris", "merge"]).is_err());
+    }
+
+    #[test]
+    fn parses_doctor() {
+        let cli = Cli::parse_from(["valeris", "doctor"]);
+        assert!(matches!(cli.command, Commands::Doctor {}));
+        assert!(Cli::try_parse_from(["valeris", "doctor", "--fix"]).is_err());
+    }
+
+    #[test]
+    fn parses_config_with_and_without_init() {
+        let cli = Cli::parse_from(["valeris", "config"]);
+        assert!(matches!(cli.command, Commands::Config { command: None }));
+
+        let cli = Cli::parse_from([
+            "valeris", "config", "init", "--path", "valeris.toml", "--exclude", "a,b", "--fail-on", "medium", "-f", "json",
+        ]);
+        match cli.command {
+            Commands::Config {
+                command:
+                    Some(ConfigCommand::Init {
+                        path,
+                        force,
+                        exclude,
+                        fail_on,
+                        format,
+                        min_severity,
+                        ..
+                    }),
+            } => {
+                assert_eq!(path, Some(PathBuf::from("valeris.toml")));
+                assert!(!force);
+                assert_eq!(exclude, Some(vec!["a".to_string(), "b".to_string()]));
+                assert_eq!(fail_on, Some(SeverityLevel::Medium));
+                assert_eq!(format, Some(OutputFormat::Json));
+                assert!(min_severity.is_none());
+            }
+            _ => panic!("Expected Config init"),
+        }
+    }
+
+    #[test]
+    fn parses_env_file_with_fail_on() {
+        let cli = Cli::parse_from(["valeris", "env-file", "-p", ".env", "--fail-on", "high", "--format", "json"]);
+        match cli.command {
+            Commands::EnvFile { path, fail_on, format, .. } => {
+                assert_eq!(path, PathBuf::from(".env"));
+                assert_eq!(fail_on, Some(SeverityLevel::High));
+                assert_eq!(format, OutputFormat::Json);
+            }
+            _ => panic!("Expected EnvFile command"),
+        }
+    }
+
+    #[test]
+    fn parses_network_with_input() {
+        let cli = Cli::parse_from(["valeris", "network", "-i", "networks.json", "--min-severity", "medium"]);
+        match cli.command {
+            Commands::Network { input, min_severity, format, .. } => {
+                assert_eq!(input, Some(PathBuf::from("networks.json")));
+                assert_eq!(min_severity, Some(SeverityLevel::Medium));
+                assert_eq!(format, OutputFormat::Table);
+            }
+            _ => panic!("Expected Network command"),
+        }
+    }
+
+    #[test]
+    fn parses_error_format_before_and_after_subcommand() {
+        let cli = Cli::parse_from(["valeris", "list-plugins"]);
+        assert_eq!(cli.error_format, ErrorFormat::Text);
+        let cli = Cli::parse_from(["valeris", "--error-format", "json", "list-plugins"]);
+        assert_eq!(cli.error_format, ErrorFormat::Json);
+        let cli = Cli::parse_from(["valeris", "list-plugins", "--error-format", "json"]);
+        assert_eq!(cli.error_format, ErrorFormat::Json);
+
+        assert_eq!(ErrorFormat::from_args(&["valeris", "scan", "--error-format=json"]), ErrorFormat::Json);
+        assert_eq!(ErrorFormat::from_args(&["valeris", "--", "--error-format=json"]), ErrorFormat::Text);
+    }
 }
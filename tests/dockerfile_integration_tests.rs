@@ -6,6 +6,7 @@
 use std::path::PathBuf;
 use valeris::detectors::dockerfile::scanner::scan_dockerfile;
 use valeris::cli::OutputFormat;
+use valeris::error::ValerisError;
 
 /// Helper function to get the test fixtures directory
 fn fixtures_dir() -> PathBuf {
@@ -130,8 +131,8 @@ fn test_scan_nonexistent_dockerfile() {
     // Should fail with file not found error
     assert!(result.is_err(), "Should fail when Dockerfile doesn't exist");
     assert!(
-        result.unwrap_err().to_string().contains("reading"),
-        "Error should mention reading the file"
+        matches!(result.unwrap_err(), ValerisError::DockerfileRead { .. }),
+        "Error should report that the file couldn't be read"
     );
 }
 
@@ -577,3 +578,261 @@ fn test_combined_filters() {
 
     assert!(result.is_ok(), "Combined filters should work");
 }
+
+#[test]
+fn test_json_export_includes_summary() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let test_dockerfile = temp_dir.path().join("Dockerfile");
+    let output_file = temp_dir.path().join("report.json");
+
+    std::fs::write(&test_dockerfile, "FROM ubuntu:latest\nUSER root\n").unwrap();
+
+    let result = scan_dockerfile(
+        test_dockerfile,
+        rules_dir(),
+        Some(vec!["DF001".to_string()]), // only
+        None, // exclude
+        None, // severity
+        None, // min_severity
+        Some(valeris::cli::SeverityLevel::Medium), // fail_on
+        false, // quiet
+        OutputFormat::Json,
+        Some(output_file.clone()),
+    );
+
+    assert!(result.unwrap(), "DF001 should trigger fail-on medium");
+
+    let report: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&output_file).unwrap()).unwrap();
+    let summary = &report["summary"];
+
+    assert_eq!(summary["target_kind"], "dockerfiles");
+    assert_eq!(summary["targets_scanned"], 1);
+    assert_eq!(summary["rules_evaluated"], 1);
+    assert_eq!(summary["findings"]["total"], 1);
+    assert_eq!(summary["fail_on_triggered"], true);
+}
+
+#[test]
+fn test_quiet_mode_still_writes_output_file() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let test_dockerfile = temp_dir.path().join("Dockerfile");
+    let output_file = temp_dir.path().join("report.json");
+
+    std::fs::write(&test_dockerfile, "FROM ubuntu:latest\n").unwrap();
+
+    let result = scan_dockerfile(
+        test_dockerfile,
+        rules_dir(),
+        None, // only
+        None, // exclude
+        None, // severity
+        None, // min_severity
+        Some(valeris::cli::SeverityLevel::High), // fail_on required for quiet
+        true, // quiet - console silenced
+        OutputFormat::Json,
+        Some(output_file.clone()),
+    );
+
+    assert!(result.is_ok(), "Quiet mode with output should work");
+    assert!(output_file.exists(), "Quiet mode should still write the report file");
+}
+
+#[test]
+fn test_scan_with_builder_options() {
+    use valeris::detectors::dockerfile::scanner::{scan_dockerfile_with_options, DockerfileScanOptions};
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    let test_dockerfile = temp_dir.path().join("Dockerfile");
+    let output_file = temp_dir.path().join("report.json");
+
+    std::fs::write(&test_dockerfile, "FROM ubuntu:latest\n").unwrap();
+
+    let options = DockerfileScanOptions::new(&test_dockerfile, rules_dir())
+        .with_only(vec!["DF001".to_string()])
+        .with_fail_on(valeris::cli::SeverityLevel::Medium)
+        .with_quiet()
+        .with_format(OutputFormat::Json)
+        .with_output(&output_file);
+
+    let should_fail = scan_dockerfile_with_options(&options).unwrap();
+
+    assert!(should_fail, "DF001 should trigger fail-on medium");
+    let report: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&output_file).unwrap()).unwrap();
+    assert_eq!(report["summary"]["findings"]["total"], 1);
+}
+
+#[test]
+fn test_anonymize_hides_dockerfile_path() {
+    use valeris::detectors::dockerfile::scanner::{scan_dockerfile_report, DockerfileScanOptions};
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    let test_dockerfile = temp_dir.path().join("Dockerfile");
+    let output_file = temp_dir.path().join("report.json");
+    std::fs::write(&test_dockerfile, "FROM ubuntu:latest\n").unwrap();
+
+    let options = DockerfileScanOptions::new(&test_dockerfile, rules_dir())
+        .with_only(vec!["DF001".to_string()])
+        .with_quiet()
+        .with_format(OutputFormat::Json)
+        .with_output(&output_file)
+        .with_anonymize();
+    let outcome = scan_dockerfile_report(&options).unwrap();
+
+    let report = std::fs::read_to_string(&output_file).unwrap();
+    let dir = temp_dir.path().display().to_string();
+    assert!(!report.contains(&dir), "report leaks the path: {report}");
+    assert!(report.contains("path-"));
+    assert_eq!(outcome.findings.len(), 1);
+}
+
+#[test]
+fn test_explain_adds_debug_block_to_json() {
+    use valeris::detectors::dockerfile::scanner::{scan_dockerfile_report, DockerfileScanOptions};
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    let test_dockerfile = temp_dir.path().join("Dockerfile");
+    std::fs::write(&test_dockerfile, "FROM ubuntu:latest\n").unwrap();
+
+    let scan = |explain: bool| {
+        let output_file = temp_dir.path().join(format!("report-{explain}.json"));
+        let mut options = DockerfileScanOptions::new(&test_dockerfile, rules_dir())
+            .with_only(vec!["DF001".to_string()])
+            .with_quiet()
+            .with_format(OutputFormat::Json)
+            .with_output(&output_file);
+        if explain {
+            options = options.with_explain();
+        }
+        scan_dockerfile_report(&options).unwrap();
+        let report: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&output_file).unwrap()).unwrap();
+        report["findings"][0]["debug"].clone()
+    };
+
+    assert!(scan(false).is_null());
+    let debug = scan(true);
+    assert!(debug[0]["path"].is_string(), "{debug}");
+}
+
+#[test]
+fn test_json_report_includes_scan_metadata() {
+    use valeris::detectors::dockerfile::scanner::{scan_dockerfile_report, DockerfileScanOptions};
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    let test_dockerfile = temp_dir.path().join("Dockerfile");
+    let output_file = temp_dir.path().join("report.json");
+    std::fs::write(&test_dockerfile, "FROM ubuntu:latest\n").unwrap();
+
+    let options = DockerfileScanOptions::new(&test_dockerfile, rules_dir())
+        .with_only(vec!["DF001".to_string()])
+        .with_quiet()
+        .with_format(OutputFormat::Json)
+        .with_output(&output_file);
+    scan_dockerfile_report(&options).unwrap();
+
+    let report: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&output_file).unwrap()).unwrap();
+    let metadata = &report["metadata"];
+    assert_eq!(metadata["valeris_version"], env!("CARGO_PKG_VERSION"));
+    assert!(metadata["scanned_at"].as_str().unwrap().ends_with('Z'));
+    assert_eq!(metadata["rules"]["sha256"].as_str().unwrap().len(), 64);
+    assert_eq!(metadata["filters"]["only"], "DF001");
+}
+
+#[test]
+fn test_scan_notifies_observer() {
+    use std::sync::{Arc, Mutex};
+    use valeris::detectors::dockerfile::scanner::{scan_dockerfile_report, DockerfileScanOptions};
+    use valeris::docker::model::Finding;
+    use valeris::observer::{ScanObserver, ScanTarget};
+    use valeris::output::summary::ScanSummary;
+
+    #[derive(Default)]
+    struct Recorder {
+        findings: Mutex<Vec<String>>,
+        completed: Mutex<Option<usize>>,
+    }
+
+    impl ScanObserver for Recorder {
+        fn on_finding(&self, target: ScanTarget<'_>, finding: &Finding) {
+            assert!(matches!(target, ScanTarget::Dockerfile(_)));
+            self.findings.lock().unwrap().push(finding.kind.clone());
+        }
+
+        fn on_complete(&self, summary: &ScanSummary) {
+            *self.completed.lock().unwrap() = Some(summary.findings.total);
+        }
+    }
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    let test_dockerfile = temp_dir.path().join("Dockerfile");
+    std::fs::write(&test_dockerfile, "FROM ubuntu:latest\n").unwrap();
+
+    let recorder = Arc::new(Recorder::default());
+    let options = DockerfileScanOptions::new(&test_dockerfile, rules_dir())
+        .with_only(vec!["DF001".to_string()])
+        .with_quiet()
+        .with_observer(recorder.clone());
+
+    let outcome = scan_dockerfile_report(&options).unwrap();
+
+    assert_eq!(*recorder.findings.lock().unwrap(), ["DF001"]);
+    assert_eq!(*recorder.completed.lock().unwrap(), Some(outcome.findings.len()));
+}
+
+#[test]
+fn test_findings_carry_rule_metadata_and_target() {
+    use valeris::detectors::dockerfile::scanner::{scan_dockerfile_report, DockerfileScanOptions};
+    use valeris::docker::model::FindingTarget;
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    let test_dockerfile = temp_dir.path().join("Dockerfile");
+    std::fs::write(&test_dockerfile, "FROM ubuntu:latest\n").unwrap();
+
+    let options = DockerfileScanOptions::new(&test_dockerfile, rules_dir())
+        .with_only(vec!["DF001".to_string()])
+        .with_quiet();
+    let outcome = scan_dockerfile_report(&options).unwrap();
+
+    let finding = &outcome.findings[0];
+    assert_eq!(finding.rule_name.as_deref(), Some("Disallow latest tag"));
+    assert!(finding.remediation.as_deref().unwrap().starts_with("Pin to a fixed version"));
+    assert_eq!(
+        finding.target,
+        Some(FindingTarget::File { path: test_dockerfile.clone() })
+    );
+}
+
+#[tokio::test]
+async fn test_scan_content_uses_injected_filesystem() {
+    use std::path::Path;
+    use valeris::detectors::dockerfile::fs::MemoryFileSystem;
+    use valeris::detectors::dockerfile::scanner::scan_dockerfile_content;
+    use valeris::detectors::dockerfile::yaml_rules::load_rules_from_dir;
+    use valeris::docker::model::FindingTarget;
+
+    let ruleset = load_rules_from_dir(&rules_dir()).unwrap();
+    let content = "FROM ubuntu:latest\nCOPY . /app\n";
+    let path = Path::new("project/Dockerfile");
+
+    let without_ignore = scan_dockerfile_content(content, path, &ruleset.rules, &MemoryFileSystem::new())
+        .await
+        .unwrap();
+    let missing = without_ignore
+        .iter()
+        .find(|f| f.description.contains(".dockerignore"))
+        .expect("missing .dockerignore finding");
+    assert_eq!(
+        missing.target,
+        Some(FindingTarget::File { path: path.to_path_buf() })
+    );
+    assert!(without_ignore.iter().any(|f| f.kind == "DF001"));
+
+    let fs = MemoryFileSystem::new().with_file("project/.dockerignore");
+    let with_ignore = scan_dockerfile_content(content, path, &ruleset.rules, &fs)
+        .await
+        .unwrap();
+    assert!(!with_ignore.iter().any(|f| f.description.contains(".dockerignore")));
+}
@@ -1,4 +1,8 @@
-use valeris::run_with_args;
+#![cfg(feature = "cli")]
+
+use valeris::error::ValerisError;
+use valeris::report::{ScanFindings, EXIT_USAGE};
+use valeris::{run, run_with_args};
 
 #[tokio::test]
 async fn run_scan_command_works() {
@@ -13,3 +17,68 @@ async fn run_list_plugins_works() {
     let result = run_with_args(args).await;
     assert!(result.is_ok());
 }
+
+#[tokio::test]
+async fn run_returns_dockerfile_report_without_exiting() {
+    let args = vec![
+        "valeris",
+        "docker-file",
+        "--path",
+        "tests/fixtures/dockerfile/insecure.Dockerfile",
+        "--rules",
+        "rules/dockerfile",
+        "--fail-on",
+        "low",
+        "--quiet",
+    ];
+    let report = run(args).await.unwrap();
+
+    assert!(report.failed());
+    assert_eq!(report.exit_code, 1);
+    assert!(report.summary.unwrap().findings.total > 0);
+    assert!(matches!(report.findings, ScanFindings::Dockerfile { ref findings, .. } if !findings.is_empty()));
+}
+
+#[tokio::test]
+async fn run_returns_streamed_container_findings() {
+    std::env::set_var("VALERIS_RULES_DIR", "rules/runtime");
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path().join("report.json");
+    let args = [
+        "valeris",
+        "scan",
+        "--input",
+        "tests/data/privileged_mode/input.json",
+        "--format",
+        "json",
+        "--output",
+        output.to_str().unwrap(),
+        "--quiet",
+        "--fail-on",
+        "critical",
+    ];
+    let report = run(args).await.unwrap();
+
+    let ScanFindings::Containers(results) = &report.findings else {
+        panic!("expected container findings, got {:?}", report.findings);
+    };
+    assert_eq!(results.len(), 1);
+    assert!(results[0].findings.iter().any(|f| f.kind == "privileged_mode"));
+    let written: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&output).unwrap()).unwrap();
+    assert_eq!(written["summary"]["findings"]["total"], report.summary.unwrap().findings.total);
+}
+
+#[tokio::test]
+async fn run_returns_argument_errors_without_exiting() {
+    let err = run(["valeris", "scan", "--min-severity", "urgent"]).await.unwrap_err();
+
+    assert!(matches!(err, ValerisError::Usage(ref message) if message.contains("urgent")));
+    assert_eq!(err.exit_code(), EXIT_USAGE);
+}
+
+#[tokio::test]
+async fn run_prints_version_without_exiting() {
+    let report = run(["valeris", "--version"]).await.unwrap();
+
+    assert!(!report.failed());
+}